@@ -4,10 +4,16 @@ use std::path::Path;
 use std::process;
 
 use anyhow::{Context, Result};
-use blog_client::{AuthResponse, BlogClient, BlogClientError, ListPostsResponse, Post, Transport};
+use blog_client::{
+    AuthResponse, BlogClient, BlogClientError, Comment, ContentFormat, DiffOp,
+    ListCommentsResponse, ListPostsResponse, PersonalToken, Post, Profile, ProfileUpdate, Revision,
+    RevisionDiff, RevisionSummary, SearchPostsResponse, Session, Tag, Transport,
+};
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 
 const TOKEN_FILE: &str = ".blog_token";
+const REFRESH_TOKEN_FILE: &str = ".blog_refresh_token";
 const DEFAULT_HTTP_SERVER: &str = "http://127.0.0.1:8080";
 const DEFAULT_GRPC_SERVER: &str = "http://127.0.0.1:50051";
 
@@ -22,10 +28,92 @@ struct Cli {
     #[arg(long, global = true)]
     server: Option<String>,
 
+    /// Персональный токен доступа вместо сохранённой сессии (`blog_pat_...`).
+    #[arg(long, global = true, env = "BLOG_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
     #[command(subcommand)]
     command: Command,
 }
 
+#[derive(Debug, Subcommand)]
+enum TokenCommand {
+    /// Выпуск токена; секрет выводится один раз.
+    Create {
+        #[arg(long)]
+        name: String,
+        /// Права через запятую: `posts:read`, `posts:write`, `comments:write`.
+        #[arg(long)]
+        scopes: String,
+        /// Срок жизни в днях (1–365); без флага токен бессрочный.
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+    },
+    /// Список активных токенов.
+    List,
+    /// Отзыв токена.
+    Revoke {
+        #[arg(long)]
+        id: i64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum SessionCommand {
+    /// Список активных сессий; текущая отмечена.
+    List,
+    /// Завершение сессии: её токены перестают приниматься.
+    Revoke {
+        #[arg(long)]
+        id: i64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum TrashCommand {
+    /// Свои удалённые посты, сначала удалённые последними.
+    List {
+        #[arg(long, default_value_t = 10)]
+        limit: u32,
+        #[arg(long, default_value_t = 0)]
+        offset: u32,
+    },
+    /// Восстановление поста из корзины с прежним статусом.
+    Restore {
+        #[arg(long)]
+        id: i64,
+    },
+    /// Окончательное удаление поста вместе с комментариями.
+    Purge {
+        #[arg(long)]
+        id: i64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ProfileCommand {
+    /// Публичный профиль пользователя.
+    Show {
+        #[arg(long)]
+        username: String,
+    },
+    /// Изменение своего профиля; пустое значение очищает поле.
+    Update {
+        /// До 64 символов.
+        #[arg(long)]
+        display_name: Option<String>,
+        /// До 500 символов.
+        #[arg(long)]
+        bio: Option<String>,
+        /// Ссылка http(s).
+        #[arg(long)]
+        website: Option<String>,
+        /// Ссылка http(s) на изображение.
+        #[arg(long)]
+        avatar_url: Option<String>,
+    },
+}
+
 #[derive(Debug, Subcommand)]
 enum Command {
     /// Регистрация пользователя.
@@ -43,6 +131,68 @@ enum Command {
         username: String,
         #[arg(long)]
         password: String,
+        /// Код из аутентификатора или код восстановления, если включён TOTP.
+        #[arg(long)]
+        code: Option<String>,
+    },
+    /// Выход: отзывает текущие токены на сервере и удаляет их локально.
+    Logout {
+        /// Завершить все сессии пользователя, а не только текущую.
+        #[arg(long)]
+        all: bool,
+    },
+    /// Смена пароля (требует токен); после неё все сессии завершаются.
+    ChangePassword {
+        #[arg(long)]
+        current_password: String,
+        #[arg(long)]
+        new_password: String,
+    },
+    /// Запрос письма с токеном для сброса забытого пароля.
+    ForgotPassword {
+        #[arg(long)]
+        email: String,
+    },
+    /// Новый пароль по токену из письма.
+    ResetPassword {
+        #[arg(long)]
+        token: String,
+        #[arg(long)]
+        new_password: String,
+    },
+    /// Подтверждение email по токену из письма.
+    VerifyEmail {
+        #[arg(long)]
+        token: String,
+    },
+    /// Повторная отправка письма подтверждения (требует токен).
+    ResendVerification,
+    /// Начало подключения TOTP: выводит секрет и URI для QR-кода (требует токен).
+    TotpSetup,
+    /// Подтверждение TOTP первым кодом; выводит коды восстановления (требует токен).
+    TotpConfirm {
+        #[arg(long)]
+        code: String,
+    },
+    /// Отключение TOTP по коду или коду восстановления (требует токен).
+    TotpDisable {
+        #[arg(long)]
+        code: String,
+    },
+    /// Сессии на других устройствах (требует вход по паролю).
+    Session {
+        #[command(subcommand)]
+        command: SessionCommand,
+    },
+    /// Персональные токены доступа для скриптов и CI (требует вход по паролю).
+    Token {
+        #[command(subcommand)]
+        command: TokenCommand,
+    },
+    /// Профили пользователей; изменение своего требует вход по паролю.
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommand,
     },
     /// Создание поста (требует токен).
     Create {
@@ -50,11 +200,27 @@ enum Command {
         title: String,
         #[arg(long)]
         content: String,
+        /// Формат содержимого: `plain` или `markdown`.
+        #[arg(long, default_value = "plain", value_parser = parse_content_format)]
+        format: ContentFormat,
+        /// Сохранить как черновик, не публикуя.
+        #[arg(long)]
+        draft: bool,
+        /// Теги через запятую, например `rust,web`.
+        #[arg(long)]
+        tags: Option<String>,
+        /// Отложенная публикация в RFC 3339, например `2026-03-20T09:00:00+03:00`;
+        /// пост сохраняется черновиком и публикуется сервером в указанное время.
+        #[arg(long, value_parser = parse_publish_at)]
+        publish_at: Option<DateTime<Utc>>,
     },
-    /// Получение поста по id.
+    /// Получение поста по id или slug.
+    ///
+    /// Прежний slug поста тоже находит его: после смены заголовка старые ссылки работают.
     Get {
-        #[arg(long)]
-        id: i64,
+        /// Число — id поста, иначе slug (`--slug privet-mir`).
+        #[arg(long = "id", visible_alias = "slug", value_name = "ID|SLUG", value_parser = parse_post_ref)]
+        post: PostRef,
     },
     /// Обновление поста (требует токен).
     ///
@@ -66,11 +232,54 @@ enum Command {
         title: String,
         #[arg(long)]
         content: Option<String>,
+        /// Новый формат содержимого: `plain` или `markdown`; без флага формат не меняется.
+        #[arg(long, value_parser = parse_content_format)]
+        format: Option<ContentFormat>,
+        /// Новые теги через запятую; без флага теги не меняются, `--tags ""` снимает все.
+        #[arg(long)]
+        tags: Option<String>,
+        /// Версия поста из вывода `get`; если пост успели изменить, правка отклоняется.
+        #[arg(long)]
+        expected_version: Option<i64>,
     },
-    /// Удаление поста (требует токен).
+    /// Перемещение поста в корзину (требует токен).
     Delete {
         #[arg(long)]
         id: i64,
+        /// Удалить, только если пост всё ещё этой версии.
+        #[arg(long)]
+        expected_version: Option<i64>,
+    },
+    /// Корзина: удалённые посты хранятся на сервере ограниченный срок (требует токен).
+    Trash {
+        #[command(subcommand)]
+        command: TrashCommand,
+    },
+    /// Публикация черновика (требует токен).
+    Publish {
+        #[arg(long)]
+        id: i64,
+    },
+    /// Возврат поста в черновики (требует токен).
+    Unpublish {
+        #[arg(long)]
+        id: i64,
+    },
+    /// Перенос поста в архив (требует токен).
+    Archive {
+        #[arg(long)]
+        id: i64,
+    },
+    /// Отложенная публикация черновика или её отмена (требует токен).
+    Schedule {
+        #[arg(long)]
+        id: i64,
+        /// Время публикации в RFC 3339.
+        #[arg(long, value_parser = parse_publish_at, required_unless_present = "cancel")]
+        publish_at: Option<DateTime<Utc>>,
+        /// Отменить отложенную публикацию; пост остаётся черновиком.
+        #[arg(long, conflicts_with = "publish_at")]
+        cancel: bool,
     },
     /// Список постов.
     List {
@@ -78,6 +287,78 @@ enum Command {
         limit: u32,
         #[arg(long, default_value_t = 0)]
         offset: u32,
+        /// Только посты, у которых есть все перечисленные теги (через запятую).
+        #[arg(long)]
+        tags: Option<String>,
+        /// Курсор следующей страницы из предыдущего вывода `list`.
+        #[arg(long, conflicts_with = "offset")]
+        cursor: Option<String>,
+        /// Только посты пользователя с этим логином.
+        #[arg(long)]
+        author: Option<String>,
+    },
+    /// Полнотекстовый поиск по постам.
+    Search {
+        /// Слова, "фразы", `or` и `-исключения`.
+        #[arg(long)]
+        query: String,
+        #[arg(long, default_value_t = 10)]
+        limit: u32,
+        #[arg(long, default_value_t = 0)]
+        offset: u32,
+    },
+    /// Список тегов с количеством опубликованных постов.
+    Tags,
+    /// Комментарии к посту в виде дерева.
+    Comments {
+        #[arg(long)]
+        post_id: i64,
+    },
+    /// Добавление комментария (требует токен).
+    Comment {
+        #[arg(long)]
+        post_id: i64,
+        #[arg(long)]
+        content: String,
+        /// Id комментария, на который отвечаем.
+        #[arg(long)]
+        reply_to: Option<i64>,
+    },
+    /// Удаление комментария вместе с ответами (требует токен).
+    DeleteComment {
+        #[arg(long)]
+        post_id: i64,
+        #[arg(long)]
+        id: i64,
+    },
+    /// История правок поста (требует токен автора).
+    Revisions {
+        #[arg(long)]
+        post_id: i64,
+    },
+    /// Прежняя версия поста (требует токен автора).
+    Revision {
+        #[arg(long)]
+        post_id: i64,
+        #[arg(long)]
+        revision: i32,
+    },
+    /// Построчный diff между ревизиями (требует токен автора).
+    Diff {
+        #[arg(long)]
+        post_id: i64,
+        #[arg(long)]
+        from: i32,
+        /// Без параметра сравнивается с текущей версией.
+        #[arg(long)]
+        to: Option<i32>,
+    },
+    /// Восстановление ревизии новой правкой (требует токен автора).
+    Restore {
+        #[arg(long)]
+        post_id: i64,
+        #[arg(long)]
+        revision: i32,
     },
 }
 
@@ -95,12 +376,33 @@ async fn run() -> Result<()> {
     let transport = resolve_transport(cli.grpc, cli.server);
     let mut client = BlogClient::new(transport);
 
-    if let Some(token) = load_token().context("не удалось прочитать .blog_token")?
+    // с токеном доступа сохранённая сессия не читается и не перезаписывается
+    if let Some(token) = cli.token.as_deref().and_then(parse_token_content) {
+        client.set_token(token);
+        return execute(&mut client, cli.command).await;
+    }
+
+    if let Some(token) = load_token(TOKEN_FILE).context("не удалось прочитать .blog_token")?
     {
         client.set_token(token);
     }
+    if let Some(token) =
+        load_token(REFRESH_TOKEN_FILE).context("не удалось прочитать .blog_refresh_token")?
+    {
+        client.set_refresh_token(token);
+    }
+
+    let result = execute(&mut client, cli.command).await;
+
+    // клиент мог получить новые токены при входе или ротировать их через refresh,
+    // даже если сама команда завершилась ошибкой
+    persist_tokens(&client).context("не удалось сохранить токен")?;
+
+    result
+}
 
-    match cli.command {
+async fn execute(client: &mut BlogClient, command: Command) -> Result<()> {
+    match command {
         Command::Register {
             username,
             email,
@@ -110,52 +412,409 @@ async fn run() -> Result<()> {
                 .register(&username, &email, &password)
                 .await
                 .map_err(map_client_error)?;
-            persist_token(&client).context("не удалось сохранить токен")?;
             print_auth("Регистрация успешна", &auth);
         }
-        Command::Login { username, password } => {
-            let auth = client
-                .login(&username, &password)
+        Command::Login {
+            username,
+            password,
+            code,
+        } => {
+            let auth = match (client.login(&username, &password).await, code) {
+                (Err(BlogClientError::MfaRequired), Some(code)) => client
+                    .complete_mfa_login(&code)
+                    .await
+                    .map_err(|err| match err {
+                        BlogClientError::Unauthorized => {
+                            anyhow::anyhow!("неверный или уже использованный код второго фактора")
+                        }
+                        err => map_client_error(err),
+                    }),
+                (result, _) => result.map_err(map_client_error),
+            }?;
+            print_auth("Вход выполнен", &auth);
+        }
+        Command::Logout { all } => {
+            if all {
+                client.logout_all().await
+            } else {
+                client.logout().await
+            }
+            .map_err(map_client_error)?;
+            println!("Выход выполнен");
+        }
+        Command::ChangePassword {
+            current_password,
+            new_password,
+        } => {
+            client
+                .change_password(&current_password, &new_password)
                 .await
                 .map_err(map_client_error)?;
-            persist_token(&client).context("не удалось сохранить токен")?;
-            print_auth("Вход выполнен", &auth);
+            println!("Пароль изменён, все сессии завершены: войдите с новым паролем");
         }
-        Command::Create { title, content } => {
-            let post = client
-                .create_post(&title, &content)
+        Command::ForgotPassword { email } => {
+            client
+                .request_password_reset(&email)
+                .await
+                .map_err(map_client_error)?;
+            println!("Если адрес зарегистрирован, на него отправлено письмо с токеном сброса");
+        }
+        Command::ResetPassword {
+            token,
+            new_password,
+        } => {
+            client
+                .reset_password(&token, &new_password)
+                .await
+                .map_err(map_client_error)?;
+            println!("Пароль изменён: войдите с новым паролем");
+        }
+        Command::VerifyEmail { token } => {
+            let user = client
+                .verify_email(&token)
+                .await
+                .map_err(map_client_error)?;
+            println!("Email {} подтверждён", user.email);
+        }
+        Command::ResendVerification => {
+            client
+                .resend_verification_email()
+                .await
+                .map_err(map_client_error)?;
+            println!("Письмо подтверждения отправлено повторно");
+        }
+        Command::TotpSetup => {
+            let enrollment = client
+                .start_totp_enrollment()
+                .await
+                .map_err(map_client_error)?;
+            println!("Добавьте учётную запись в приложение-аутентификатор");
+            println!("secret: {}", enrollment.secret);
+            println!("uri: {}", enrollment.otpauth_uri);
+            println!("Затем подтвердите: `blog-cli totp-confirm --code <код>`");
+        }
+        Command::TotpConfirm { code } => {
+            let recovery_codes = client
+                .confirm_totp_enrollment(&code)
                 .await
                 .map_err(map_client_error)?;
+            println!("TOTP включён. Коды восстановления (каждый действует один раз):");
+            for recovery_code in recovery_codes {
+                println!("  {recovery_code}");
+            }
+        }
+        Command::TotpDisable { code } => {
+            client.disable_totp(&code).await.map_err(map_client_error)?;
+            println!("TOTP отключён");
+        }
+        Command::Session { command } => execute_session(client, command).await?,
+        Command::Token { command } => execute_token(client, command).await?,
+        Command::Profile { command } => execute_profile(client, command).await?,
+        Command::Create {
+            title,
+            content,
+            format,
+            draft,
+            tags,
+            publish_at,
+        } => {
+            let tags = tags.as_deref().map(parse_tags).unwrap_or_default();
+            let post = if let Some(publish_at) = publish_at {
+                client
+                    .create_scheduled(&title, &content, format, &tags, publish_at)
+                    .await
+            } else if draft {
+                client.create_draft(&title, &content, format, &tags).await
+            } else {
+                client.create_post(&title, &content, format, &tags).await
+            }
+            .map_err(map_client_error)?;
             print_post("Пост создан", &post);
         }
-        Command::Get { id } => {
-            let post = client.get_post(id).await.map_err(map_client_error)?;
+        Command::Get { post } => {
+            let post = match post {
+                PostRef::Id(id) => client.get_post(id).await,
+                PostRef::Slug(slug) => client.get_post_by_slug(&slug).await,
+            }
+            .map_err(map_client_error)?;
             print_post("Пост", &post);
         }
-        Command::Update { id, title, content } => {
-            // Если пользователь не передал --content, сохраняем текущее содержимое поста.
-            let content = match content {
-                Some(content) => content,
-                None => client.get_post(id).await.map_err(map_client_error)?.content,
+        Command::Update {
+            id,
+            title,
+            content,
+            format,
+            tags,
+            expected_version,
+        } => {
+            // Если пользователь не передал --content, сохраняем текущее содержимое поста
+            // и правим именно прочитанную версию, чтобы не затереть чужие изменения.
+            let (content, expected_version) = match content {
+                Some(content) => (content, expected_version),
+                None => {
+                    let current = client.get_post(id).await.map_err(map_client_error)?;
+                    (current.content, expected_version.or(Some(current.version)))
+                }
             };
 
+            let tags = tags.as_deref().map(parse_tags);
             let post = client
-                .update_post(id, &title, &content)
+                .update_post(
+                    id,
+                    &title,
+                    &content,
+                    format,
+                    tags.as_deref(),
+                    expected_version,
+                )
                 .await
                 .map_err(map_client_error)?;
             print_post("Пост обновлён", &post);
         }
-        Command::Delete { id } => {
-            client.delete_post(id).await.map_err(map_client_error)?;
-            println!("Пост удалён: id={id}");
+        Command::Delete {
+            id,
+            expected_version,
+        } => {
+            client
+                .delete_post(id, expected_version)
+                .await
+                .map_err(map_client_error)?;
+            println!("Пост перемещён в корзину: id={id}");
+        }
+        Command::Trash { command } => execute_trash(client, command).await?,
+        Command::Publish { id } => {
+            let post = client.publish_post(id).await.map_err(map_client_error)?;
+            print_post("Пост опубликован", &post);
+        }
+        Command::Unpublish { id } => {
+            let post = client.unpublish_post(id).await.map_err(map_client_error)?;
+            print_post("Пост снят с публикации", &post);
+        }
+        Command::Archive { id } => {
+            let post = client.archive_post(id).await.map_err(map_client_error)?;
+            print_post("Пост перенесён в архив", &post);
+        }
+        Command::Schedule {
+            id,
+            publish_at,
+            cancel: _,
+        } => {
+            // без --publish-at clap пропускает команду только вместе с --cancel
+            let post = client
+                .schedule_post(id, publish_at)
+                .await
+                .map_err(map_client_error)?;
+            let title = if publish_at.is_some() {
+                "Публикация запланирована"
+            } else {
+                "Отложенная публикация отменена"
+            };
+            print_post(title, &post);
         }
-        Command::List { limit, offset } => {
+        Command::List {
+            limit,
+            offset,
+            tags,
+            cursor,
+            author,
+        } => {
+            let tags = tags.as_deref().map(parse_tags).unwrap_or_default();
+            let author = author.as_deref();
+            let list = match cursor.as_deref() {
+                Some(cursor) => {
+                    client
+                        .list_posts_by_cursor(&tags, author, Some(cursor), limit)
+                        .await
+                }
+                None => client.list_posts(&tags, author, limit, offset).await,
+            }
+            .map_err(map_client_error)?;
+            print_list(&list);
+        }
+        Command::Search {
+            query,
+            limit,
+            offset,
+        } => {
+            let results = client
+                .search_posts(&query, limit, offset)
+                .await
+                .map_err(map_client_error)?;
+            print_search_results(&results);
+        }
+        Command::Tags => {
+            let tags = client.list_tags().await.map_err(map_client_error)?;
+            print_tags(&tags);
+        }
+        Command::Comments { post_id } => {
+            let list = client
+                .list_comments(post_id)
+                .await
+                .map_err(map_client_error)?;
+            print_comments(&list);
+        }
+        Command::Comment {
+            post_id,
+            content,
+            reply_to,
+        } => {
+            let comment = client
+                .create_comment(post_id, &content, reply_to)
+                .await
+                .map_err(map_client_error)?;
+            println!(
+                "Комментарий добавлен: id={} (post_id={})",
+                comment.id, comment.post_id
+            );
+        }
+        Command::DeleteComment { post_id, id } => {
+            client
+                .delete_comment(post_id, id)
+                .await
+                .map_err(map_client_error)?;
+            println!("Комментарий удалён: id={id}");
+        }
+        Command::Revisions { post_id } => {
+            let revisions = client
+                .list_revisions(post_id)
+                .await
+                .map_err(map_client_error)?;
+            print_revisions(&revisions);
+        }
+        Command::Revision { post_id, revision } => {
+            let revision = client
+                .get_revision(post_id, revision)
+                .await
+                .map_err(map_client_error)?;
+            print_revision(&revision);
+        }
+        Command::Diff { post_id, from, to } => {
+            let diff = client
+                .diff_revisions(post_id, from, to)
+                .await
+                .map_err(map_client_error)?;
+            print_diff(&diff);
+        }
+        Command::Restore { post_id, revision } => {
+            let post = client
+                .restore_revision(post_id, revision)
+                .await
+                .map_err(map_client_error)?;
+            print_post(&format!("Ревизия {revision} восстановлена"), &post);
+        }
+    }
+
+    Ok(())
+}
+
+async fn execute_session(client: &mut BlogClient, command: SessionCommand) -> Result<()> {
+    match command {
+        SessionCommand::List => {
+            let sessions = client.list_sessions().await.map_err(map_client_error)?;
+            if sessions.is_empty() {
+                println!("Активных сессий нет");
+            }
+            for session in &sessions {
+                print_session(session);
+            }
+        }
+        SessionCommand::Revoke { id } => {
+            client.revoke_session(id).await.map_err(map_client_error)?;
+            println!("Сессия {id} завершена");
+        }
+    }
+
+    Ok(())
+}
+
+async fn execute_trash(client: &mut BlogClient, command: TrashCommand) -> Result<()> {
+    match command {
+        TrashCommand::List { limit, offset } => {
             let list = client
-                .list_posts(limit, offset)
+                .list_trash(limit, offset)
                 .await
                 .map_err(map_client_error)?;
             print_list(&list);
         }
+        TrashCommand::Restore { id } => {
+            let post = client.restore_post(id).await.map_err(map_client_error)?;
+            print_post("Пост восстановлен", &post);
+        }
+        TrashCommand::Purge { id } => {
+            client.purge_post(id).await.map_err(map_client_error)?;
+            println!("Пост удалён окончательно: id={id}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn execute_profile(client: &mut BlogClient, command: ProfileCommand) -> Result<()> {
+    match command {
+        ProfileCommand::Show { username } => {
+            let profile = client
+                .get_profile(&username)
+                .await
+                .map_err(map_client_error)?;
+            print_profile("Профиль", &profile);
+        }
+        ProfileCommand::Update {
+            display_name,
+            bio,
+            website,
+            avatar_url,
+        } => {
+            let update = ProfileUpdate {
+                display_name,
+                bio,
+                website,
+                avatar_url,
+            };
+            let profile = client
+                .update_profile(&update)
+                .await
+                .map_err(map_client_error)?;
+            print_profile("Профиль обновлён", &profile);
+        }
+    }
+
+    Ok(())
+}
+
+async fn execute_token(client: &mut BlogClient, command: TokenCommand) -> Result<()> {
+    match command {
+        TokenCommand::Create {
+            name,
+            scopes,
+            expires_in_days,
+        } => {
+            let created = client
+                .create_personal_token(&name, &parse_tags(&scopes), expires_in_days)
+                .await
+                .map_err(map_client_error)?;
+            print_personal_token("Токен создан", &created.token);
+            println!("secret: {}", created.secret);
+            println!("Сохраните secret сейчас: сервер больше его не покажет");
+        }
+        TokenCommand::List => {
+            let tokens = client
+                .list_personal_tokens()
+                .await
+                .map_err(map_client_error)?;
+            if tokens.is_empty() {
+                println!("Активных токенов нет");
+            }
+            for token in &tokens {
+                print_personal_token("Токен", token);
+            }
+        }
+        TokenCommand::Revoke { id } => {
+            client
+                .revoke_personal_token(id)
+                .await
+                .map_err(map_client_error)?;
+            println!("Токен {id} отозван");
+        }
     }
 
     Ok(())
@@ -185,6 +844,47 @@ fn normalize_server(server: String) -> String {
     format!("http://{server}")
 }
 
+fn parse_tags(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Пост, заданный id или slug.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PostRef {
+    Id(i64),
+    Slug(String),
+}
+
+/// Сервер не выдаёт чисто числовых slug, поэтому число — всегда id.
+fn parse_post_ref(raw: &str) -> Result<PostRef, String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err("ожидается id или slug поста".to_string());
+    }
+    Ok(raw
+        .parse::<i64>()
+        .map(PostRef::Id)
+        .unwrap_or_else(|_| PostRef::Slug(raw.to_string())))
+}
+
+fn parse_content_format(raw: &str) -> Result<ContentFormat, String> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "plain" => Ok(ContentFormat::Plain),
+        "markdown" | "md" => Ok(ContentFormat::Markdown),
+        _ => Err("ожидается plain или markdown".to_string()),
+    }
+}
+
+fn parse_publish_at(raw: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(raw.trim())
+        .map(|at| at.with_timezone(&Utc))
+        .map_err(|_| "ожидается время в RFC 3339, например 2026-03-20T09:00:00+03:00".to_string())
+}
+
 fn parse_token_content(raw: &str) -> Option<String> {
     let token = raw.trim().to_string();
     if token.is_empty() {
@@ -193,20 +893,27 @@ fn parse_token_content(raw: &str) -> Option<String> {
     Some(token)
 }
 
-fn load_token() -> io::Result<Option<String>> {
-    if !Path::new(TOKEN_FILE).exists() {
+fn load_token(path: &str) -> io::Result<Option<String>> {
+    if !Path::new(path).exists() {
         return Ok(None);
     }
 
-    let raw = fs::read_to_string(TOKEN_FILE)?;
+    let raw = fs::read_to_string(path)?;
     Ok(parse_token_content(&raw))
 }
 
-fn persist_token(client: &BlogClient) -> io::Result<()> {
-    if let Some(token) = client.get_token() {
-        fs::write(TOKEN_FILE, token)?;
+fn persist_tokens(client: &BlogClient) -> io::Result<()> {
+    persist_token_file(TOKEN_FILE, client.get_token())?;
+    persist_token_file(REFRESH_TOKEN_FILE, client.get_refresh_token())
+}
+
+fn persist_token_file(path: &str, token: Option<&str>) -> io::Result<()> {
+    match token {
+        Some(token) => fs::write(path, token),
+        // сессия сброшена (refresh-токен отклонён) — удаляем устаревший файл
+        None if Path::new(path).exists() => fs::remove_file(path),
+        None => Ok(()),
     }
-    Ok(())
 }
 
 fn map_client_error(err: BlogClientError) -> anyhow::Error {
@@ -215,8 +922,22 @@ fn map_client_error(err: BlogClientError) -> anyhow::Error {
             "требуется авторизация: выполните `blog-cli login ...` или `blog-cli register ...`"
                 .to_string()
         }
+        BlogClientError::Forbidden(message) => format!("доступ запрещён: {message}"),
+        BlogClientError::MfaRequired => {
+            "включена двухфакторная аутентификация: повторите вход с `--code <код>`".to_string()
+        }
         BlogClientError::NotFound => "ресурс не найден".to_string(),
         BlogClientError::InvalidRequest(message) => format!("некорректный запрос: {message}"),
+        BlogClientError::Conflict(message) => {
+            format!("пост успели изменить ({message}): получите его заново через `blog-cli get`")
+        }
+        BlogClientError::TooManyRequests {
+            message,
+            retry_after_secs: Some(secs),
+        } => format!("слишком много запросов ({message}): повторите через {secs} с"),
+        BlogClientError::TooManyRequests { message, .. } => {
+            format!("слишком много запросов: {message}")
+        }
         BlogClientError::Http(err) => format!("ошибка HTTP: {err}"),
         BlogClientError::GrpcStatus(status) => {
             format!(
@@ -233,21 +954,106 @@ fn map_client_error(err: BlogClientError) -> anyhow::Error {
 fn print_auth(title: &str, auth: &AuthResponse) {
     println!("{title}");
     println!("token: {}", auth.access_token);
+    println!("refresh_token: {}", auth.refresh_token);
     println!("user:");
     println!("  id: {}", auth.user.id);
     println!("  username: {}", auth.user.username);
     println!("  email: {}", auth.user.email);
+    println!("  role: {}", auth.user.role);
+    match auth.user.email_verified_at {
+        Some(verified_at) => println!("  email_verified_at: {verified_at}"),
+        None => println!("  email_verified_at: не подтверждён"),
+    }
     println!("  created_at: {}", auth.user.created_at);
 }
 
+fn print_session(session: &Session) {
+    if session.current {
+        println!("Сессия (текущая)");
+    } else {
+        println!("Сессия");
+    }
+    println!("id: {}", session.id);
+    println!(
+        "user_agent: {}",
+        session.user_agent.as_deref().unwrap_or("неизвестен")
+    );
+    println!(
+        "ip_address: {}",
+        session.ip_address.as_deref().unwrap_or("неизвестен")
+    );
+    println!("created_at: {}", session.created_at);
+    println!("last_seen_at: {}", session.last_seen_at);
+}
+
+fn print_profile(title: &str, profile: &Profile) {
+    println!("{title}");
+    println!("id: {}", profile.id);
+    println!("username: {}", profile.username);
+    for (name, value) in [
+        ("display_name", &profile.display_name),
+        ("bio", &profile.bio),
+        ("website", &profile.website),
+        ("avatar_url", &profile.avatar_url),
+    ] {
+        println!("{name}: {}", value.as_deref().unwrap_or("не указано"));
+    }
+    println!("created_at: {}", profile.created_at);
+}
+
+fn print_personal_token(title: &str, token: &PersonalToken) {
+    println!("{title}");
+    println!("id: {}", token.id);
+    println!("name: {}", token.name);
+    println!("scopes: {}", token.scopes.join(", "));
+    match token.expires_at {
+        Some(expires_at) => println!("expires_at: {expires_at}"),
+        None => println!("expires_at: бессрочный"),
+    }
+    match token.last_used_at {
+        Some(last_used_at) => println!("last_used_at: {last_used_at}"),
+        None => println!("last_used_at: не использовался"),
+    }
+    println!("created_at: {}", token.created_at);
+}
+
+/// `Имя (@логин)`; сервер без сведений об авторе даёт только id.
+fn post_author(post: &Post) -> String {
+    match &post.author {
+        Some(author) if author.display_name.is_some() => {
+            format!("{} (@{})", author.name(), author.username)
+        }
+        Some(author) => format!("@{}", author.username),
+        None => format!("id={}", post.author_id),
+    }
+}
+
 fn print_post(title: &str, post: &Post) {
     println!("{title}");
     println!("id: {}", post.id);
+    if !post.slug.is_empty() {
+        println!("slug: {}", post.slug);
+    }
     println!("title: {}", post.title);
     println!("content: {}", post.content);
-    println!("author_id: {}", post.author_id);
+    println!("format: {}", post.content_format);
+    println!("author: {}", post_author(post));
+    println!("status: {}", post.status);
+    if !post.tags.is_empty() {
+        println!("tags: {}", post.tags.join(", "));
+    }
+    if let Some(published_at) = post.published_at {
+        println!("published_at: {published_at}");
+    }
+    if let Some(publish_at) = post.publish_at {
+        println!("publish_at: {publish_at}");
+    }
+    if let Some(deleted_at) = post.deleted_at {
+        println!("deleted_at: {deleted_at}");
+    }
     println!("created_at: {}", post.created_at);
     println!("updated_at: {}", post.updated_at);
+    println!("version: {}", post.version);
 }
 
 fn print_list(list: &ListPostsResponse) {
@@ -261,12 +1067,106 @@ fn print_list(list: &ListPostsResponse) {
 
     for post in &list.posts {
         println!(
-            "- [{}] {} (author_id={})",
-            post.id, post.title, post.author_id
+            "- [{}] {} (author={}, status={})",
+            post.id,
+            post.title,
+            post_author(post),
+            post.status
+        );
+        if !post.tags.is_empty() {
+            println!("  tags: {}", post.tags.join(", "));
+        }
+        if let Some(deleted_at) = post.deleted_at {
+            println!("  deleted_at: {deleted_at}");
+        }
+    }
+
+    if let Some(next_cursor) = &list.next_cursor {
+        println!("Следующая страница: --cursor {next_cursor}");
+    }
+}
+
+fn print_search_results(results: &SearchPostsResponse) {
+    println!(
+        "Найдено: {} (limit={}, offset={}, total={})",
+        results.results.len(),
+        results.limit,
+        results.offset,
+        results.total
+    );
+
+    for hit in &results.results {
+        println!(
+            "- [{}] {} (author={}, rank={:.3})",
+            hit.post.id,
+            hit.post.title,
+            post_author(&hit.post),
+            hit.rank
+        );
+        println!("  {}", hit.snippet);
+    }
+}
+
+fn print_comments(list: &ListCommentsResponse) {
+    println!("Комментариев: {}", list.total);
+    for comment in &list.comments {
+        print_comment(comment, 0);
+    }
+}
+
+fn print_comment(comment: &Comment, depth: usize) {
+    let indent = "  ".repeat(depth);
+    println!(
+        "{indent}- [{}] author_id={}: {}",
+        comment.id, comment.author_id, comment.content
+    );
+    for reply in &comment.replies {
+        print_comment(reply, depth + 1);
+    }
+}
+
+fn print_tags(tags: &[Tag]) {
+    println!("Тегов: {}", tags.len());
+    for tag in tags {
+        println!("- {} ({})", tag.name, tag.post_count);
+    }
+}
+
+fn print_revisions(revisions: &[RevisionSummary]) {
+    println!("Ревизий: {}", revisions.len());
+    for revision in revisions {
+        println!(
+            "- r{} {} (editor_id={}, {})",
+            revision.revision, revision.title, revision.editor_id, revision.created_at
         );
     }
 }
 
+fn print_revision(revision: &Revision) {
+    println!(
+        "Ревизия r{} поста {} (editor_id={}, {})",
+        revision.revision, revision.post_id, revision.editor_id, revision.created_at
+    );
+    println!("title: {}", revision.title);
+    println!("content:\n{}", revision.content);
+}
+
+fn print_diff(diff: &RevisionDiff) {
+    let to = diff
+        .to_revision
+        .map_or_else(|| "текущая".to_string(), |revision| format!("r{revision}"));
+    println!("--- r{}: {}", diff.from_revision, diff.from_title);
+    println!("+++ {to}: {}", diff.to_title);
+    for line in &diff.lines {
+        let prefix = match line.op {
+            DiffOp::Equal => ' ',
+            DiffOp::Insert => '+',
+            DiffOp::Delete => '-',
+        };
+        println!("{prefix} {}", line.text);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,6 +1210,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_tags_splits_and_skips_blank_items() {
+        assert_eq!(parse_tags(" rust, web ,,"), vec!["rust", "web"]);
+        assert!(parse_tags("").is_empty());
+    }
+
+    #[test]
+    fn parse_post_ref_distinguishes_id_and_slug() {
+        assert_eq!(parse_post_ref(" 42 "), Ok(PostRef::Id(42)));
+        assert_eq!(
+            parse_post_ref("privet-mir"),
+            Ok(PostRef::Slug("privet-mir".to_string()))
+        );
+        assert!(parse_post_ref(" ").is_err());
+    }
+
+    #[test]
+    fn parse_content_format_accepts_known_formats() {
+        assert_eq!(parse_content_format("plain"), Ok(ContentFormat::Plain));
+        assert_eq!(
+            parse_content_format(" Markdown "),
+            Ok(ContentFormat::Markdown)
+        );
+        assert_eq!(parse_content_format("md"), Ok(ContentFormat::Markdown));
+        assert!(parse_content_format("html").is_err());
+    }
+
+    #[test]
+    fn parse_publish_at_converts_offset_to_utc() {
+        let at = parse_publish_at(" 2026-03-20T09:00:00+03:00 ").expect("must parse");
+        assert_eq!(at.to_rfc3339(), "2026-03-20T06:00:00+00:00");
+        assert!(parse_publish_at("2026-03-20 09:00").is_err());
+    }
+
     #[test]
     fn parse_token_content_trims_whitespace() {
         let token = parse_token_content("  abc.def.ghi  ");
@@ -20,6 +20,15 @@ pub enum BlogClientError {
     #[error("unauthorized")]
     Unauthorized,
 
+    /// Действие запрещено: не хватает прав или не подтверждён email.
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+
+    /// Пароль принят, но у пользователя включён TOTP: вход нужно завершить
+    /// кодом через `BlogClient::complete_mfa_login`.
+    #[error("second factor required")]
+    MfaRequired,
+
     /// Запрошенный ресурс не найден.
     #[error("not found")]
     NotFound,
@@ -27,6 +36,19 @@ pub enum BlogClientError {
     /// Некорректный запрос или бизнес-ошибка валидации.
     #[error("invalid request: {0}")]
     InvalidRequest(String),
+
+    /// Ресурс изменили после того, как клиент получил ожидаемую версию.
+    #[error("conflict: {0}")]
+    Conflict(String),
+
+    /// Сервер временно отклоняет запросы, например после серии неудачных входов.
+    #[error("too many requests: {message}")]
+    TooManyRequests {
+        /// Сообщение сервера.
+        message: String,
+        /// Через сколько секунд можно повторить, если сервер это сообщил.
+        retry_after_secs: Option<u64>,
+    },
 }
 
 /// Результат операций `blog-client`.
@@ -35,10 +57,18 @@ pub type BlogClientResult<T> = Result<T, BlogClientError>;
 impl BlogClientError {
     pub(crate) fn from_http_status(status: reqwest::StatusCode, message: Option<String>) -> Self {
         match status {
-            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
-                Self::Unauthorized
+            reqwest::StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            reqwest::StatusCode::FORBIDDEN => {
+                Self::Forbidden(message.unwrap_or_else(|| format!("http status {status}")))
             }
             reqwest::StatusCode::NOT_FOUND => Self::NotFound,
+            reqwest::StatusCode::PRECONDITION_FAILED => {
+                Self::Conflict(message.unwrap_or_else(|| format!("http status {status}")))
+            }
+            reqwest::StatusCode::TOO_MANY_REQUESTS => Self::TooManyRequests {
+                message: message.unwrap_or_else(|| format!("http status {status}")),
+                retry_after_secs: None,
+            },
             _ => {
                 let message = message.unwrap_or_else(|| format!("http status {status}"));
                 Self::InvalidRequest(message)
@@ -55,11 +85,21 @@ impl BlogClientError {
 
     pub(crate) fn from_grpc_status(status: tonic::Status) -> Self {
         match status.code() {
-            Code::Unauthenticated | Code::PermissionDenied => Self::Unauthorized,
+            Code::Unauthenticated => Self::Unauthorized,
+            Code::PermissionDenied => Self::Forbidden(status.message().to_string()),
             Code::NotFound => Self::NotFound,
-            Code::InvalidArgument | Code::AlreadyExists | Code::FailedPrecondition => {
+            Code::InvalidArgument | Code::AlreadyExists => {
                 Self::InvalidRequest(status.message().to_string())
             }
+            Code::FailedPrecondition => Self::Conflict(status.message().to_string()),
+            Code::ResourceExhausted => Self::TooManyRequests {
+                message: status.message().to_string(),
+                retry_after_secs: status
+                    .metadata()
+                    .get("retry-after")
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse().ok()),
+            },
             _ => Self::GrpcStatus(status),
         }
     }
@@ -3,7 +3,12 @@ use tonic::metadata::MetadataValue;
 use tonic::transport::{Channel, Endpoint};
 
 use crate::error::{BlogClientError, BlogClientResult};
-use crate::models::{AuthResponse, ListPostsResponse, Post, User};
+use crate::models::{
+    AuthResponse, Comment, ContentFormat, CreatedPersonalToken, DiffLine, DiffOp,
+    ListCommentsResponse, ListPostsResponse, LoginOutcome, PersonalToken, Post, PostAuthor,
+    PostStatus, Profile, ProfileUpdate, Revision, RevisionDiff, RevisionSummary, SearchHit,
+    SearchPostsResponse, Session, Tag, TotpEnrollment, User, UserRole,
+};
 
 pub mod pb {
     tonic::include_proto!("blog");
@@ -12,6 +17,7 @@ pub mod pb {
 #[derive(Debug)]
 struct AuthResponseDto {
     access_token: String,
+    refresh_token: String,
     user: UserDto,
 }
 
@@ -20,17 +26,29 @@ struct UserDto {
     id: i64,
     username: String,
     email: String,
+    role: UserRole,
+    email_verified_at: Option<chrono::DateTime<chrono::Utc>>,
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Debug)]
 struct PostDto {
     id: i64,
+    slug: String,
     title: String,
     content: String,
+    content_format: ContentFormat,
+    content_html: String,
     author_id: i64,
+    status: PostStatus,
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+    publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    tags: Vec<String>,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
+    version: i64,
+    author: Option<PostAuthor>,
 }
 
 #[derive(Debug)]
@@ -39,18 +57,28 @@ struct ListPostsResponseDto {
     limit: u32,
     offset: u32,
     total: u64,
+    next_cursor: Option<String>,
 }
 
 impl From<AuthResponseDto> for AuthResponse {
     fn from(value: AuthResponseDto) -> Self {
         Self {
             access_token: value.access_token,
-            user: User {
-                id: value.user.id,
-                username: value.user.username,
-                email: value.user.email,
-                created_at: value.user.created_at,
-            },
+            refresh_token: value.refresh_token,
+            user: value.user.into(),
+        }
+    }
+}
+
+impl From<UserDto> for User {
+    fn from(value: UserDto) -> Self {
+        Self {
+            id: value.id,
+            username: value.username,
+            email: value.email,
+            role: value.role,
+            email_verified_at: value.email_verified_at,
+            created_at: value.created_at,
         }
     }
 }
@@ -59,11 +87,21 @@ impl From<PostDto> for Post {
     fn from(value: PostDto) -> Self {
         Self {
             id: value.id,
+            slug: value.slug,
             title: value.title,
             content: value.content,
+            content_format: value.content_format,
+            content_html: value.content_html,
             author_id: value.author_id,
+            status: value.status,
+            published_at: value.published_at,
+            publish_at: value.publish_at,
+            deleted_at: value.deleted_at,
+            tags: value.tags,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            version: value.version,
+            author: value.author,
         }
     }
 }
@@ -75,6 +113,7 @@ impl From<ListPostsResponseDto> for ListPostsResponse {
             limit: value.limit,
             offset: value.offset,
             total: value.total,
+            next_cursor: value.next_cursor,
         }
     }
 }
@@ -96,130 +135,908 @@ impl GrpcClient {
     /// Регистрирует пользователя и возвращает JWT + данные пользователя.
     pub async fn register(
         &self,
-        username: &str,
-        email: &str,
-        password: &str,
-    ) -> BlogClientResult<AuthResponse> {
+        username: &str,
+        email: &str,
+        password: &str,
+    ) -> BlogClientResult<AuthResponse> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::RegisterRequest {
+            username: username.to_string(),
+            email: email.to_string(),
+            password: password.to_string(),
+        });
+        let response = client
+            .register(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+
+        let dto = Self::map_auth_response(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Выполняет авторизацию пользователя: возвращает JWT + данные пользователя
+    /// или промежуточный токен, если включён второй фактор.
+    pub(crate) async fn login(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> BlogClientResult<LoginOutcome> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::LoginRequest {
+            username: username.to_string(),
+            password: password.to_string(),
+        });
+        let response = client
+            .login(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+
+        if let Some(mfa) = response.mfa {
+            return Ok(LoginOutcome::MfaRequired {
+                mfa_token: mfa.mfa_token,
+            });
+        }
+        let dto = Self::map_auth_response(response)?;
+        Ok(LoginOutcome::Authenticated(dto.into()))
+    }
+
+    /// Завершает вход кодом второго фактора.
+    pub async fn complete_mfa_login(
+        &self,
+        mfa_token: &str,
+        code: &str,
+    ) -> BlogClientResult<AuthResponse> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::CompleteMfaLoginRequest {
+            mfa_token: mfa_token.to_string(),
+            code: code.to_string(),
+        });
+        let response = client
+            .complete_mfa_login(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+
+        let dto = Self::map_auth_response(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Обменивает refresh-токен на новую пару токенов.
+    pub async fn refresh(&self, refresh_token: &str) -> BlogClientResult<AuthResponse> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::RefreshRequest {
+            refresh_token: refresh_token.to_string(),
+        });
+        let response = client
+            .refresh(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+
+        let dto = Self::map_auth_response(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Отзывает access-токен и, если передан, refresh-токен текущего входа.
+    pub async fn logout(&self, token: &str, refresh_token: Option<&str>) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::LogoutRequest {
+            refresh_token: refresh_token.unwrap_or_default().to_string(),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        client
+            .logout(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Завершает все сессии пользователя.
+    pub async fn logout_all(&self, token: &str) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = Self::attach_bearer_token(tonic::Request::new(()), token)?;
+
+        client
+            .logout_all(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Меняет пароль; сервер завершает все сессии пользователя.
+    pub async fn change_password(
+        &self,
+        token: &str,
+        current_password: &str,
+        new_password: &str,
+    ) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::ChangePasswordRequest {
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        client
+            .change_password(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Запрашивает письмо с токеном сброса пароля.
+    pub async fn request_password_reset(&self, email: &str) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::RequestPasswordResetRequest {
+            email: email.to_string(),
+        });
+
+        client
+            .request_password_reset(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Задаёт новый пароль по токену из письма.
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::ResetPasswordRequest {
+            token: token.to_string(),
+            new_password: new_password.to_string(),
+        });
+
+        client
+            .reset_password(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Подтверждает email по токену из письма.
+    pub async fn verify_email(&self, token: &str) -> BlogClientResult<User> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::VerifyEmailRequest {
+            token: token.to_string(),
+        });
+
+        let response = client
+            .verify_email(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        Ok(Self::map_user(response)?.into())
+    }
+
+    /// Просит сервер повторно отправить письмо подтверждения.
+    pub async fn resend_verification_email(&self, token: &str) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = Self::attach_bearer_token(tonic::Request::new(()), token)?;
+
+        client
+            .resend_verification_email(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Начинает подключение TOTP и возвращает новый секрет.
+    pub async fn start_totp_enrollment(&self, token: &str) -> BlogClientResult<TotpEnrollment> {
+        let mut client = self.connect().await?;
+        let request = Self::attach_bearer_token(tonic::Request::new(()), token)?;
+
+        let response = client
+            .start_totp_enrollment(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        Ok(TotpEnrollment {
+            secret: response.secret,
+            otpauth_uri: response.otpauth_uri,
+        })
+    }
+
+    /// Подтверждает подключение TOTP кодом и возвращает коды восстановления.
+    pub async fn confirm_totp_enrollment(
+        &self,
+        token: &str,
+        code: &str,
+    ) -> BlogClientResult<Vec<String>> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::TotpCodeRequest {
+            code: code.to_string(),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .confirm_totp_enrollment(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        Ok(response.recovery_codes)
+    }
+
+    /// Отключает TOTP по коду из аутентификатора или коду восстановления.
+    pub async fn disable_totp(&self, token: &str, code: &str) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::TotpCodeRequest {
+            code: code.to_string(),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        client
+            .disable_totp(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Выпускает персональный токен доступа; секрет возвращается только здесь.
+    pub async fn create_personal_token(
+        &self,
+        token: &str,
+        name: &str,
+        scopes: &[String],
+        expires_in_days: Option<u32>,
+    ) -> BlogClientResult<CreatedPersonalToken> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::CreatePersonalTokenRequest {
+            name: name.to_string(),
+            scopes: scopes.to_vec(),
+            expires_in_days: expires_in_days.unwrap_or(0),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .create_personal_token(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        let personal_token = response.token.ok_or_else(|| {
+            BlogClientError::InvalidRequest("grpc response is missing token".to_string())
+        })?;
+        Ok(CreatedPersonalToken {
+            token: Self::map_personal_token(personal_token)?,
+            secret: response.secret,
+        })
+    }
+
+    /// Возвращает активные токены доступа пользователя.
+    pub async fn list_personal_tokens(&self, token: &str) -> BlogClientResult<Vec<PersonalToken>> {
+        let mut client = self.connect().await?;
+        let request = Self::attach_bearer_token(tonic::Request::new(()), token)?;
+
+        let response = client
+            .list_personal_tokens(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        response
+            .tokens
+            .into_iter()
+            .map(Self::map_personal_token)
+            .collect()
+    }
+
+    /// Отзывает токен доступа по идентификатору.
+    pub async fn revoke_personal_token(&self, token: &str, id: i64) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::RevokePersonalTokenRequest { id });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        client
+            .revoke_personal_token(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Возвращает активные сессии пользователя.
+    pub async fn list_sessions(&self, token: &str) -> BlogClientResult<Vec<Session>> {
+        let mut client = self.connect().await?;
+        let request = Self::attach_bearer_token(tonic::Request::new(()), token)?;
+
+        let response = client
+            .list_sessions(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        response
+            .sessions
+            .into_iter()
+            .map(Self::map_session)
+            .collect()
+    }
+
+    /// Завершает сессию по идентификатору.
+    pub async fn revoke_session(&self, token: &str, id: i64) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::RevokeSessionRequest { id });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        client
+            .revoke_session(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Возвращает публичный профиль пользователя.
+    pub async fn get_profile(&self, username: &str) -> BlogClientResult<Profile> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::GetProfileRequest {
+            username: username.to_string(),
+        });
+
+        let response = client
+            .get_profile(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        Self::map_profile(response)
+    }
+
+    /// Частично обновляет профиль текущего пользователя.
+    pub async fn update_profile(
+        &self,
+        token: &str,
+        update: &ProfileUpdate,
+    ) -> BlogClientResult<Profile> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::UpdateProfileRequest {
+            display_name: update.display_name.clone(),
+            bio: update.bio.clone(),
+            website: update.website.clone(),
+            avatar_url: update.avatar_url.clone(),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .update_profile(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        Self::map_profile(response)
+    }
+
+    /// Создаёт пост от имени авторизованного пользователя.
+    ///
+    /// Требует валидный JWT-токен.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_post(
+        &self,
+        token: &str,
+        title: &str,
+        content: &str,
+        format: ContentFormat,
+        status: PostStatus,
+        tags: &[String],
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> BlogClientResult<Post> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::CreatePostRequest {
+            title: title.to_string(),
+            content: content.to_string(),
+            status: Self::to_proto_status(status).into(),
+            tags: tags.to_vec(),
+            content_format: Self::to_proto_content_format(format).into(),
+            publish_at: publish_at.map(Self::to_proto_timestamp),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .create_post(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+
+        let dto = Self::map_post(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Получает пост по идентификатору.
+    ///
+    /// С токеном автора возвращает и его черновики.
+    pub async fn get_post(&self, token: Option<&str>, id: i64) -> BlogClientResult<Post> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::GetPostRequest { id });
+        let request = Self::attach_optional_bearer_token(request, token)?;
+
+        let response = client
+            .get_post(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        let dto = Self::map_post(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Получает пост по текущему или прежнему slug; в ответе slug всегда текущий.
+    pub async fn get_post_by_slug(
+        &self,
+        token: Option<&str>,
+        slug: &str,
+    ) -> BlogClientResult<Post> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::GetPostBySlugRequest {
+            slug: slug.to_string(),
+        });
+        let request = Self::attach_optional_bearer_token(request, token)?;
+
+        let response = client
+            .get_post_by_slug(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        let dto = Self::map_post(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Обновляет пост по идентификатору.
+    ///
+    /// С `expected_version` сервер отвечает `FAILED_PRECONDITION`, если пост
+    /// успели изменить; это `BlogClientError::Conflict`. Требует валидный JWT-токен.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_post(
+        &self,
+        token: &str,
+        id: i64,
+        title: &str,
+        content: &str,
+        format: Option<ContentFormat>,
+        tags: Option<&[String]>,
+        expected_version: Option<i64>,
+    ) -> BlogClientResult<Post> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::UpdatePostRequest {
+            id,
+            title: title.to_string(),
+            content: content.to_string(),
+            tags: tags.map(|tags| pb::TagList {
+                tags: tags.to_vec(),
+            }),
+            expected_version: expected_version.unwrap_or_default(),
+            content_format: format
+                .map(Self::to_proto_content_format)
+                .unwrap_or(pb::ContentFormat::Unspecified)
+                .into(),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .update_post(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        let dto = Self::map_post(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Перемещает пост в корзину.
+    ///
+    /// С `expected_version` удаляет, только если пост не менялся, иначе
+    /// `BlogClientError::Conflict`. Требует валидный JWT-токен.
+    pub async fn delete_post(
+        &self,
+        token: &str,
+        id: i64,
+        expected_version: Option<i64>,
+    ) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::DeletePostRequest {
+            id,
+            expected_version: expected_version.unwrap_or_default(),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        client
+            .delete_post(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Возвращает посты из корзины текущего пользователя, сначала удалённые последними.
+    ///
+    /// Требует валидный JWT-токен.
+    pub async fn list_trash(
+        &self,
+        token: &str,
+        limit: u32,
+        offset: u32,
+    ) -> BlogClientResult<ListPostsResponse> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::ListTrashRequest { limit, offset });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .list_trash(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        let dto = Self::map_list_posts_response(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Восстанавливает пост из корзины. Требует валидный JWT-токен.
+    pub async fn restore_post(&self, token: &str, id: i64) -> BlogClientResult<Post> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::RestorePostRequest { id });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .restore_post(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        let dto = Self::map_post(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Окончательно удаляет пост из корзины. Требует валидный JWT-токен.
+    pub async fn purge_post(&self, token: &str, id: i64) -> BlogClientResult<()> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::PurgePostRequest { id });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        client
+            .purge_post(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Возвращает список постов с пагинацией `limit/offset`.
+    ///
+    /// С токеном в выборку попадают и собственные черновики.
+    /// Непустой `tags` оставляет посты, у которых есть все перечисленные теги.
+    pub async fn list_posts(
+        &self,
+        token: Option<&str>,
+        tags: &[String],
+        author: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> BlogClientResult<ListPostsResponse> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::ListPostsRequest {
+            limit,
+            offset,
+            tags: tags.to_vec(),
+            cursor: String::new(),
+            author: author.unwrap_or_default().to_string(),
+        });
+        let request = Self::attach_optional_bearer_token(request, token)?;
+
+        let response = client
+            .list_posts(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        let dto = Self::map_list_posts_response(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Возвращает страницу постов после `cursor` (`next_cursor` предыдущей страницы).
+    pub async fn list_posts_by_cursor(
+        &self,
+        token: Option<&str>,
+        tags: &[String],
+        author: Option<&str>,
+        cursor: &str,
+        limit: u32,
+    ) -> BlogClientResult<ListPostsResponse> {
         let mut client = self.connect().await?;
-        let request = tonic::Request::new(pb::RegisterRequest {
-            username: username.to_string(),
-            email: email.to_string(),
-            password: password.to_string(),
+        let request = tonic::Request::new(pb::ListPostsRequest {
+            limit,
+            offset: 0,
+            tags: tags.to_vec(),
+            cursor: cursor.to_string(),
+            author: author.unwrap_or_default().to_string(),
         });
+        let request = Self::attach_optional_bearer_token(request, token)?;
+
         let response = client
-            .register(request)
+            .list_posts(request)
             .await
             .map_err(BlogClientError::from_grpc_status)?;
-
-        let dto = Self::map_auth_response(response.into_inner())?;
+        let dto = Self::map_list_posts_response(response.into_inner())?;
         Ok(dto.into())
     }
 
-    /// Выполняет авторизацию пользователя и возвращает JWT + данные пользователя.
-    pub async fn login(&self, username: &str, password: &str) -> BlogClientResult<AuthResponse> {
+    /// Полнотекстовый поиск постов; результаты упорядочены по релевантности.
+    pub async fn search_posts(
+        &self,
+        token: Option<&str>,
+        query: &str,
+        limit: u32,
+        offset: u32,
+    ) -> BlogClientResult<SearchPostsResponse> {
         let mut client = self.connect().await?;
-        let request = tonic::Request::new(pb::LoginRequest {
-            username: username.to_string(),
-            password: password.to_string(),
+        let request = tonic::Request::new(pb::SearchPostsRequest {
+            query: query.to_string(),
+            limit,
+            offset,
         });
+        let request = Self::attach_optional_bearer_token(request, token)?;
+
         let response = client
-            .login(request)
+            .search_posts(request)
             .await
             .map_err(BlogClientError::from_grpc_status)?;
+        Self::map_search_posts_response(response.into_inner())
+    }
 
-        let dto = Self::map_auth_response(response.into_inner())?;
-        Ok(dto.into())
+    /// Возвращает дерево комментариев поста.
+    ///
+    /// С токеном автора доступны комментарии к его черновикам.
+    pub async fn list_comments(
+        &self,
+        token: Option<&str>,
+        post_id: i64,
+    ) -> BlogClientResult<ListCommentsResponse> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::ListCommentsRequest { post_id });
+        let request = Self::attach_optional_bearer_token(request, token)?;
+
+        let response = client
+            .list_comments(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        let comments = response
+            .comments
+            .into_iter()
+            .map(Self::map_comment)
+            .collect::<BlogClientResult<Vec<_>>>()?;
+        Ok(ListCommentsResponse {
+            comments,
+            total: response.total,
+        })
     }
 
-    /// Создаёт пост от имени авторизованного пользователя.
+    /// Добавляет комментарий к посту или ответ на комментарий `parent_id`.
     ///
     /// Требует валидный JWT-токен.
-    pub async fn create_post(
+    pub async fn create_comment(
         &self,
         token: &str,
-        title: &str,
+        post_id: i64,
         content: &str,
-    ) -> BlogClientResult<Post> {
+        parent_id: Option<i64>,
+    ) -> BlogClientResult<Comment> {
         let mut client = self.connect().await?;
-        let request = tonic::Request::new(pb::CreatePostRequest {
-            title: title.to_string(),
+        let request = tonic::Request::new(pb::CreateCommentRequest {
+            post_id,
             content: content.to_string(),
+            parent_id: parent_id.unwrap_or_default(),
         });
         let request = Self::attach_bearer_token(request, token)?;
 
         let response = client
-            .create_post(request)
+            .create_comment(request)
             .await
             .map_err(BlogClientError::from_grpc_status)?;
+        Self::map_comment(response.into_inner())
+    }
 
-        let dto = Self::map_post(response.into_inner())?;
-        Ok(dto.into())
+    /// Возвращает историю правок поста от новых ревизий к старым.
+    ///
+    /// Требует валидный JWT-токен автора поста.
+    pub async fn list_revisions(
+        &self,
+        token: &str,
+        post_id: i64,
+    ) -> BlogClientResult<Vec<RevisionSummary>> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::ListRevisionsRequest { post_id });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .list_revisions(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        response
+            .into_inner()
+            .revisions
+            .into_iter()
+            .map(|summary| {
+                let created_at = summary.created_at.ok_or_else(|| {
+                    BlogClientError::InvalidRequest(
+                        "grpc revision is missing created_at".to_string(),
+                    )
+                })?;
+                Ok(RevisionSummary {
+                    revision: summary.revision,
+                    title: summary.title,
+                    editor_id: summary.editor_id,
+                    created_at: Self::map_timestamp(created_at, "revision.created_at")?,
+                })
+            })
+            .collect()
     }
 
-    /// Получает пост по идентификатору.
-    pub async fn get_post(&self, id: i64) -> BlogClientResult<Post> {
+    /// Возвращает прежнюю версию поста.
+    ///
+    /// Требует валидный JWT-токен автора поста.
+    pub async fn get_revision(
+        &self,
+        token: &str,
+        post_id: i64,
+        revision: i32,
+    ) -> BlogClientResult<Revision> {
         let mut client = self.connect().await?;
-        let request = tonic::Request::new(pb::GetPostRequest { id });
+        let request = tonic::Request::new(pb::GetRevisionRequest { post_id, revision });
+        let request = Self::attach_bearer_token(request, token)?;
 
         let response = client
-            .get_post(request)
+            .get_revision(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?
+            .into_inner();
+        let created_at = response.created_at.ok_or_else(|| {
+            BlogClientError::InvalidRequest("grpc revision is missing created_at".to_string())
+        })?;
+        Ok(Revision {
+            post_id: response.post_id,
+            revision: response.revision,
+            title: response.title,
+            content: response.content,
+            editor_id: response.editor_id,
+            created_at: Self::map_timestamp(created_at, "revision.created_at")?,
+        })
+    }
+
+    /// Построчный diff между ревизиями; `to = None` — с текущей версией.
+    ///
+    /// Требует валидный JWT-токен автора поста.
+    pub async fn diff_revisions(
+        &self,
+        token: &str,
+        post_id: i64,
+        from: i32,
+        to: Option<i32>,
+    ) -> BlogClientResult<RevisionDiff> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::DiffRevisionsRequest {
+            post_id,
+            from_revision: from,
+            to_revision: to.unwrap_or_default(),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .diff_revisions(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Self::map_revision_diff(response.into_inner())
+    }
+
+    /// Восстанавливает ревизию новой правкой поста.
+    ///
+    /// Требует валидный JWT-токен автора поста.
+    pub async fn restore_revision(
+        &self,
+        token: &str,
+        post_id: i64,
+        revision: i32,
+    ) -> BlogClientResult<Post> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::RestoreRevisionRequest { post_id, revision });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .restore_revision(request)
             .await
             .map_err(BlogClientError::from_grpc_status)?;
         let dto = Self::map_post(response.into_inner())?;
         Ok(dto.into())
     }
 
-    /// Обновляет пост по идентификатору.
+    /// Удаляет комментарий вместе с ответами.
     ///
-    /// Требует валидный JWT-токен.
-    pub async fn update_post(
+    /// Требует валидный JWT-токен автора комментария или автора поста.
+    pub async fn delete_comment(
         &self,
         token: &str,
-        id: i64,
-        title: &str,
-        content: &str,
-    ) -> BlogClientResult<Post> {
+        post_id: i64,
+        comment_id: i64,
+    ) -> BlogClientResult<()> {
         let mut client = self.connect().await?;
-        let request = tonic::Request::new(pb::UpdatePostRequest {
-            id,
-            title: title.to_string(),
-            content: content.to_string(),
+        let request = tonic::Request::new(pb::DeleteCommentRequest {
+            post_id,
+            comment_id,
         });
         let request = Self::attach_bearer_token(request, token)?;
 
+        client
+            .delete_comment(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(())
+    }
+
+    /// Возвращает теги с количеством опубликованных постов.
+    pub async fn list_tags(&self) -> BlogClientResult<Vec<Tag>> {
+        let mut client = self.connect().await?;
+
+        let response = client
+            .list_tags(tonic::Request::new(()))
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        Ok(response
+            .into_inner()
+            .tags
+            .into_iter()
+            .map(|tag| Tag {
+                name: tag.name,
+                post_count: tag.post_count,
+            })
+            .collect())
+    }
+
+    /// Публикует пост.
+    ///
+    /// Требует валидный JWT-токен.
+    pub async fn publish_post(&self, token: &str, id: i64) -> BlogClientResult<Post> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::PublishPostRequest { id });
+        let request = Self::attach_bearer_token(request, token)?;
+
         let response = client
-            .update_post(request)
+            .publish_post(request)
             .await
             .map_err(BlogClientError::from_grpc_status)?;
         let dto = Self::map_post(response.into_inner())?;
         Ok(dto.into())
     }
 
-    /// Удаляет пост по идентификатору.
+    /// Возвращает пост в черновики.
     ///
     /// Требует валидный JWT-токен.
-    pub async fn delete_post(&self, token: &str, id: i64) -> BlogClientResult<()> {
+    pub async fn unpublish_post(&self, token: &str, id: i64) -> BlogClientResult<Post> {
         let mut client = self.connect().await?;
-        let request = tonic::Request::new(pb::DeletePostRequest { id });
+        let request = tonic::Request::new(pb::UnpublishPostRequest { id });
         let request = Self::attach_bearer_token(request, token)?;
 
-        client
-            .delete_post(request)
+        let response = client
+            .unpublish_post(request)
             .await
             .map_err(BlogClientError::from_grpc_status)?;
-        Ok(())
+        let dto = Self::map_post(response.into_inner())?;
+        Ok(dto.into())
     }
 
-    /// Возвращает список постов с пагинацией `limit/offset`.
-    pub async fn list_posts(&self, limit: u32, offset: u32) -> BlogClientResult<ListPostsResponse> {
+    /// Переносит пост в архив.
+    ///
+    /// Требует валидный JWT-токен.
+    pub async fn archive_post(&self, token: &str, id: i64) -> BlogClientResult<Post> {
         let mut client = self.connect().await?;
-        let request = tonic::Request::new(pb::ListPostsRequest { limit, offset });
+        let request = tonic::Request::new(pb::ArchivePostRequest { id });
+        let request = Self::attach_bearer_token(request, token)?;
 
         let response = client
-            .list_posts(request)
+            .archive_post(request)
             .await
             .map_err(BlogClientError::from_grpc_status)?;
-        let dto = Self::map_list_posts_response(response.into_inner())?;
+        let dto = Self::map_post(response.into_inner())?;
+        Ok(dto.into())
+    }
+
+    /// Назначает или отменяет (`None`) отложенную публикацию черновика.
+    ///
+    /// Требует валидный JWT-токен.
+    pub async fn schedule_post(
+        &self,
+        token: &str,
+        id: i64,
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> BlogClientResult<Post> {
+        let mut client = self.connect().await?;
+        let request = tonic::Request::new(pb::SchedulePostRequest {
+            id,
+            publish_at: publish_at.map(Self::to_proto_timestamp),
+        });
+        let request = Self::attach_bearer_token(request, token)?;
+
+        let response = client
+            .schedule_post(request)
+            .await
+            .map_err(BlogClientError::from_grpc_status)?;
+        let dto = Self::map_post(response.into_inner())?;
         Ok(dto.into())
     }
 
@@ -237,6 +1054,8 @@ impl GrpcClient {
             .map_err(|err| {
                 BlogClientError::InvalidRequest(format!("invalid grpc endpoint: {err}"))
             })?
+            .user_agent(crate::USER_AGENT)
+            .map_err(|err| BlogClientError::InvalidRequest(format!("invalid user agent: {err}")))?
             .connect_timeout(Duration::from_secs(5))
             .timeout(Duration::from_secs(15))
             .connect()
@@ -253,6 +1072,7 @@ impl GrpcClient {
 
         Ok(AuthResponseDto {
             access_token: proto.access_token,
+            refresh_token: proto.refresh_token,
             user,
         })
     }
@@ -262,15 +1082,87 @@ impl GrpcClient {
             BlogClientError::InvalidRequest("grpc user is missing created_at".to_string())
         })?;
         let created_at = Self::map_timestamp(created_at, "user.created_at")?;
+        let email_verified_at = proto
+            .email_verified_at
+            .map(|ts| Self::map_timestamp(ts, "user.email_verified_at"))
+            .transpose()?;
 
         Ok(UserDto {
             id: proto.id,
             username: proto.username,
             email: proto.email,
+            role: Self::map_role(proto.role),
+            email_verified_at,
             created_at,
         })
     }
 
+    fn map_personal_token(proto: pb::PersonalToken) -> BlogClientResult<PersonalToken> {
+        let created_at = proto.created_at.ok_or_else(|| {
+            BlogClientError::InvalidRequest("grpc token is missing created_at".to_string())
+        })?;
+        let expires_at = proto
+            .expires_at
+            .map(|ts| Self::map_timestamp(ts, "token.expires_at"))
+            .transpose()?;
+        let last_used_at = proto
+            .last_used_at
+            .map(|ts| Self::map_timestamp(ts, "token.last_used_at"))
+            .transpose()?;
+
+        Ok(PersonalToken {
+            id: proto.id,
+            name: proto.name,
+            scopes: proto.scopes,
+            expires_at,
+            last_used_at,
+            created_at: Self::map_timestamp(created_at, "token.created_at")?,
+        })
+    }
+
+    fn map_session(proto: pb::Session) -> BlogClientResult<Session> {
+        let created_at = proto.created_at.ok_or_else(|| {
+            BlogClientError::InvalidRequest("grpc session is missing created_at".to_string())
+        })?;
+        let last_seen_at = proto.last_seen_at.ok_or_else(|| {
+            BlogClientError::InvalidRequest("grpc session is missing last_seen_at".to_string())
+        })?;
+
+        Ok(Session {
+            id: proto.id,
+            user_agent: Some(proto.user_agent).filter(|value| !value.is_empty()),
+            ip_address: Some(proto.ip_address).filter(|value| !value.is_empty()),
+            created_at: Self::map_timestamp(created_at, "session.created_at")?,
+            last_seen_at: Self::map_timestamp(last_seen_at, "session.last_seen_at")?,
+            current: proto.current,
+        })
+    }
+
+    fn map_profile(proto: pb::Profile) -> BlogClientResult<Profile> {
+        let created_at = proto.created_at.ok_or_else(|| {
+            BlogClientError::InvalidRequest("grpc profile is missing created_at".to_string())
+        })?;
+
+        Ok(Profile {
+            id: proto.id,
+            username: proto.username,
+            display_name: Some(proto.display_name).filter(|value| !value.is_empty()),
+            bio: Some(proto.bio).filter(|value| !value.is_empty()),
+            website: Some(proto.website).filter(|value| !value.is_empty()),
+            avatar_url: Some(proto.avatar_url).filter(|value| !value.is_empty()),
+            created_at: Self::map_timestamp(created_at, "profile.created_at")?,
+        })
+    }
+
+    /// Сервер без ролей присылает `USER_ROLE_UNSPECIFIED`; это обычный пользователь.
+    fn map_role(value: i32) -> UserRole {
+        match pb::UserRole::try_from(value) {
+            Ok(pb::UserRole::Moderator) => UserRole::Moderator,
+            Ok(pb::UserRole::Admin) => UserRole::Admin,
+            Ok(pb::UserRole::User) | Ok(pb::UserRole::Unspecified) | Err(_) => UserRole::User,
+        }
+    }
+
     fn map_post(proto: pb::Post) -> BlogClientResult<PostDto> {
         let created_at = proto.created_at.ok_or_else(|| {
             BlogClientError::InvalidRequest("grpc post is missing created_at".to_string())
@@ -278,17 +1170,105 @@ impl GrpcClient {
         let updated_at = proto.updated_at.ok_or_else(|| {
             BlogClientError::InvalidRequest("grpc post is missing updated_at".to_string())
         })?;
+        let status = Self::map_status(proto.status)?;
+        let published_at = proto
+            .published_at
+            .map(|ts| Self::map_timestamp(ts, "post.published_at"))
+            .transpose()?;
+        let publish_at = proto
+            .publish_at
+            .map(|ts| Self::map_timestamp(ts, "post.publish_at"))
+            .transpose()?;
+        let deleted_at = proto
+            .deleted_at
+            .map(|ts| Self::map_timestamp(ts, "post.deleted_at"))
+            .transpose()?;
 
         Ok(PostDto {
             id: proto.id,
+            slug: proto.slug,
             title: proto.title,
             content: proto.content,
+            content_format: Self::map_content_format(proto.content_format),
+            content_html: proto.content_html,
             author_id: proto.author_id,
+            status,
+            published_at,
+            publish_at,
+            deleted_at,
+            tags: proto.tags,
             created_at: Self::map_timestamp(created_at, "post.created_at")?,
             updated_at: Self::map_timestamp(updated_at, "post.updated_at")?,
+            version: proto.version,
+            author: proto.author.map(|author| PostAuthor {
+                id: author.id,
+                username: author.username,
+                display_name: Some(author.display_name).filter(|value| !value.is_empty()),
+            }),
+        })
+    }
+
+    fn map_comment(proto: pb::Comment) -> BlogClientResult<Comment> {
+        let created_at = proto.created_at.ok_or_else(|| {
+            BlogClientError::InvalidRequest("grpc comment is missing created_at".to_string())
+        })?;
+        let updated_at = proto.updated_at.ok_or_else(|| {
+            BlogClientError::InvalidRequest("grpc comment is missing updated_at".to_string())
+        })?;
+        let replies = proto
+            .replies
+            .into_iter()
+            .map(Self::map_comment)
+            .collect::<BlogClientResult<Vec<_>>>()?;
+
+        Ok(Comment {
+            id: proto.id,
+            post_id: proto.post_id,
+            author_id: proto.author_id,
+            parent_id: (proto.parent_id != 0).then_some(proto.parent_id),
+            content: proto.content,
+            created_at: Self::map_timestamp(created_at, "comment.created_at")?,
+            updated_at: Self::map_timestamp(updated_at, "comment.updated_at")?,
+            replies,
         })
     }
 
+    fn map_status(value: i32) -> BlogClientResult<PostStatus> {
+        match pb::PostStatus::try_from(value) {
+            Ok(pb::PostStatus::Draft) => Ok(PostStatus::Draft),
+            Ok(pb::PostStatus::Published) => Ok(PostStatus::Published),
+            Ok(pb::PostStatus::Archived) => Ok(PostStatus::Archived),
+            Ok(pb::PostStatus::Unspecified) | Err(_) => Err(BlogClientError::InvalidRequest(
+                format!("invalid grpc post status: {value}"),
+            )),
+        }
+    }
+
+    fn to_proto_status(status: PostStatus) -> pb::PostStatus {
+        match status {
+            PostStatus::Draft => pb::PostStatus::Draft,
+            PostStatus::Published => pb::PostStatus::Published,
+            PostStatus::Archived => pb::PostStatus::Archived,
+        }
+    }
+
+    /// Сервер без форматов присылает `CONTENT_FORMAT_UNSPECIFIED`; это обычный текст.
+    fn map_content_format(value: i32) -> ContentFormat {
+        match pb::ContentFormat::try_from(value) {
+            Ok(pb::ContentFormat::Markdown) => ContentFormat::Markdown,
+            Ok(pb::ContentFormat::Plain) | Ok(pb::ContentFormat::Unspecified) | Err(_) => {
+                ContentFormat::Plain
+            }
+        }
+    }
+
+    fn to_proto_content_format(format: ContentFormat) -> pb::ContentFormat {
+        match format {
+            ContentFormat::Plain => pb::ContentFormat::Plain,
+            ContentFormat::Markdown => pb::ContentFormat::Markdown,
+        }
+    }
+
     fn map_list_posts_response(
         proto: pb::ListPostsResponse,
     ) -> BlogClientResult<ListPostsResponseDto> {
@@ -303,6 +1283,64 @@ impl GrpcClient {
             limit: proto.limit,
             offset: proto.offset,
             total: proto.total,
+            next_cursor: (!proto.next_cursor.is_empty()).then_some(proto.next_cursor),
+        })
+    }
+
+    fn map_search_posts_response(
+        proto: pb::SearchPostsResponse,
+    ) -> BlogClientResult<SearchPostsResponse> {
+        let results = proto
+            .results
+            .into_iter()
+            .map(|hit| {
+                let post = hit.post.ok_or_else(|| {
+                    BlogClientError::InvalidRequest("grpc search hit is missing post".to_string())
+                })?;
+                Ok(SearchHit {
+                    post: Self::map_post(post)?.into(),
+                    rank: hit.rank,
+                    snippet: hit.snippet,
+                })
+            })
+            .collect::<BlogClientResult<Vec<_>>>()?;
+
+        Ok(SearchPostsResponse {
+            results,
+            limit: proto.limit,
+            offset: proto.offset,
+            total: proto.total,
+        })
+    }
+
+    fn map_revision_diff(proto: pb::RevisionDiff) -> BlogClientResult<RevisionDiff> {
+        let lines = proto
+            .lines
+            .into_iter()
+            .map(|line| {
+                let op = match pb::DiffOp::try_from(line.op) {
+                    Ok(pb::DiffOp::Equal) => DiffOp::Equal,
+                    Ok(pb::DiffOp::Insert) => DiffOp::Insert,
+                    Ok(pb::DiffOp::Delete) => DiffOp::Delete,
+                    Ok(pb::DiffOp::Unspecified) | Err(_) => {
+                        return Err(BlogClientError::InvalidRequest(
+                            "grpc diff line has unknown op".to_string(),
+                        ));
+                    }
+                };
+                Ok(DiffLine {
+                    op,
+                    text: line.text,
+                })
+            })
+            .collect::<BlogClientResult<Vec<_>>>()?;
+
+        Ok(RevisionDiff {
+            from_revision: proto.from_revision,
+            to_revision: (proto.to_revision != 0).then_some(proto.to_revision),
+            from_title: proto.from_title,
+            to_title: proto.to_title,
+            lines,
         })
     }
 
@@ -315,6 +1353,13 @@ impl GrpcClient {
         })
     }
 
+    fn to_proto_timestamp(value: chrono::DateTime<chrono::Utc>) -> prost_types::Timestamp {
+        prost_types::Timestamp {
+            seconds: value.timestamp(),
+            nanos: value.timestamp_subsec_nanos() as i32,
+        }
+    }
+
     fn attach_bearer_token<T>(
         mut request: tonic::Request<T>,
         token: &str,
@@ -331,6 +1376,16 @@ impl GrpcClient {
         request.metadata_mut().insert("authorization", header);
         Ok(request)
     }
+
+    fn attach_optional_bearer_token<T>(
+        request: tonic::Request<T>,
+        token: Option<&str>,
+    ) -> BlogClientResult<tonic::Request<T>> {
+        match token {
+            Some(token) => Self::attach_bearer_token(request, token),
+            None => Ok(request),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -364,7 +1419,9 @@ mod tests {
     fn map_auth_response_returns_error_without_user() {
         let proto = pb::AuthResponse {
             access_token: "jwt".to_string(),
+            refresh_token: "refresh".to_string(),
             user: None,
+            mfa: None,
         };
 
         let err = GrpcClient::map_auth_response(proto).expect_err("must fail");
@@ -381,12 +1438,160 @@ mod tests {
             limit: 20,
             offset: 40,
             total: 7,
+            next_cursor: String::new(),
         };
 
         let dto = GrpcClient::map_list_posts_response(proto).expect("must map");
         assert_eq!(dto.limit, 20);
         assert_eq!(dto.offset, 40);
         assert_eq!(dto.total, 7);
+        assert!(dto.next_cursor.is_none());
+    }
+
+    #[test]
+    fn map_search_posts_response_rejects_hit_without_post() {
+        let proto = pb::SearchPostsResponse {
+            results: vec![pb::SearchHit {
+                post: None,
+                rank: 0.5,
+                snippet: "<mark>rust</mark>".to_string(),
+            }],
+            limit: 20,
+            offset: 0,
+            total: 1,
+        };
+
+        let err = GrpcClient::map_search_posts_response(proto)
+            .expect_err("hit without post must be rejected");
+        assert!(matches!(err, BlogClientError::InvalidRequest(_)));
+    }
+
+    #[test]
+    fn map_revision_diff_maps_zero_target_to_current_version() {
+        let proto = pb::RevisionDiff {
+            from_revision: 2,
+            to_revision: 0,
+            from_title: "old".to_string(),
+            to_title: "new".to_string(),
+            lines: vec![pb::DiffLine {
+                op: pb::DiffOp::Insert.into(),
+                text: "added".to_string(),
+            }],
+        };
+
+        let diff = GrpcClient::map_revision_diff(proto).expect("must map");
+        assert_eq!(diff.to_revision, None);
+        assert_eq!(diff.lines[0].op, DiffOp::Insert);
+
+        let proto = pb::RevisionDiff {
+            lines: vec![pb::DiffLine {
+                op: pb::DiffOp::Unspecified.into(),
+                text: "x".to_string(),
+            }],
+            ..pb::RevisionDiff::default()
+        };
+        assert!(GrpcClient::map_revision_diff(proto).is_err());
+    }
+
+    #[test]
+    fn map_post_rejects_unspecified_status() {
+        let proto = pb::Post {
+            id: 1,
+            slug: "t".to_string(),
+            title: "t".to_string(),
+            content: "c".to_string(),
+            author_id: 2,
+            created_at: Some(prost_types::Timestamp {
+                seconds: 10,
+                nanos: 0,
+            }),
+            updated_at: Some(prost_types::Timestamp {
+                seconds: 10,
+                nanos: 0,
+            }),
+            status: pb::PostStatus::Unspecified.into(),
+            published_at: None,
+            publish_at: None,
+            deleted_at: None,
+            tags: Vec::new(),
+            version: 1,
+            author: None,
+            content_format: pb::ContentFormat::Plain.into(),
+            content_html: "<p>c</p>".to_string(),
+        };
+
+        let err = GrpcClient::map_post(proto).expect_err("must fail");
+        assert!(matches!(err, BlogClientError::InvalidRequest(_)));
+    }
+
+    #[test]
+    fn map_post_maps_author_with_empty_display_name_to_none() {
+        let ts = Some(prost_types::Timestamp {
+            seconds: 10,
+            nanos: 0,
+        });
+        let proto = pb::Post {
+            id: 1,
+            slug: "t".to_string(),
+            title: "t".to_string(),
+            content: "c".to_string(),
+            author_id: 2,
+            created_at: ts,
+            updated_at: ts,
+            status: pb::PostStatus::Published.into(),
+            published_at: ts,
+            publish_at: None,
+            deleted_at: None,
+            tags: Vec::new(),
+            version: 1,
+            author: Some(pb::PostAuthor {
+                id: 2,
+                username: "alice".to_string(),
+                display_name: String::new(),
+            }),
+            content_format: pb::ContentFormat::Unspecified.into(),
+            content_html: "<p>c</p>".to_string(),
+        };
+
+        let post = Post::from(GrpcClient::map_post(proto).expect("must map"));
+        let author = post.author.expect("author must be mapped");
+        assert_eq!(author.username, "alice");
+        assert_eq!(author.display_name, None);
+        assert_eq!(author.name(), "alice");
+        assert_eq!(post.content_format, ContentFormat::Plain);
+    }
+
+    #[test]
+    fn map_comment_maps_zero_parent_to_none_and_keeps_replies() {
+        let ts = Some(prost_types::Timestamp {
+            seconds: 10,
+            nanos: 0,
+        });
+        let reply = pb::Comment {
+            id: 2,
+            post_id: 1,
+            author_id: 3,
+            parent_id: 1,
+            content: "reply".to_string(),
+            created_at: ts,
+            updated_at: ts,
+            replies: vec![],
+        };
+        let proto = pb::Comment {
+            id: 1,
+            post_id: 1,
+            author_id: 2,
+            parent_id: 0,
+            content: "root".to_string(),
+            created_at: ts,
+            updated_at: ts,
+            replies: vec![reply],
+        };
+
+        let comment = GrpcClient::map_comment(proto).expect("must map");
+        assert_eq!(comment.parent_id, None);
+        assert_eq!(comment.replies.len(), 1);
+        assert_eq!(comment.replies[0].parent_id, Some(1));
     }
 
     #[test]
@@ -395,6 +1600,12 @@ mod tests {
             BlogClientError::from_grpc_status(tonic::Status::new(Code::Unauthenticated, ""));
         assert!(matches!(unauth, BlogClientError::Unauthorized));
 
+        let forbidden = BlogClientError::from_grpc_status(tonic::Status::new(
+            Code::PermissionDenied,
+            "email is not verified",
+        ));
+        assert!(matches!(forbidden, BlogClientError::Forbidden(_)));
+
         let not_found = BlogClientError::from_grpc_status(tonic::Status::new(Code::NotFound, ""));
         assert!(matches!(not_found, BlogClientError::NotFound));
 
@@ -406,5 +1617,23 @@ mod tests {
             BlogClientError::InvalidRequest(msg) => assert_eq!(msg, "bad input"),
             other => panic!("unexpected error: {other:?}"),
         }
+
+        let conflict = BlogClientError::from_grpc_status(tonic::Status::new(
+            Code::FailedPrecondition,
+            "version mismatch",
+        ));
+        assert!(matches!(conflict, BlogClientError::Conflict(_)));
+
+        let mut exhausted = tonic::Status::new(Code::ResourceExhausted, "too many attempts");
+        exhausted
+            .metadata_mut()
+            .insert("retry-after", "42".parse().expect("valid metadata"));
+        assert!(matches!(
+            BlogClientError::from_grpc_status(exhausted),
+            BlogClientError::TooManyRequests {
+                retry_after_secs: Some(42),
+                ..
+            }
+        ));
     }
 }
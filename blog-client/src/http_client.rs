@@ -3,7 +3,12 @@ use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::time::Duration;
 
 use crate::error::{BlogClientError, BlogClientResult};
-use crate::models::{AuthResponse, ListPostsResponse, Post, User};
+use crate::models::{
+    AuthResponse, Comment, ContentFormat, CreatedPersonalToken, DiffLine, DiffOp,
+    ListCommentsResponse, ListPostsResponse, LoginOutcome, PersonalToken, Post, PostAuthor,
+    PostStatus, Profile, ProfileUpdate, Revision, RevisionDiff, RevisionSummary, SearchHit,
+    SearchPostsResponse, Session, Tag, TotpEnrollment, User, UserRole,
+};
 
 #[derive(Debug, Serialize)]
 struct RegisterRequestDto<'a> {
@@ -22,12 +27,33 @@ struct LoginRequestDto<'a> {
 struct CreatePostRequestDto<'a> {
     title: &'a str,
     content: &'a str,
+    content_format: ContentFormat,
+    status: PostStatus,
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    publish_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+struct SchedulePostRequestDto {
+    publish_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Serialize)]
 struct UpdatePostRequestDto<'a> {
     title: &'a str,
     content: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_format: Option<ContentFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<&'a [String]>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateCommentRequestDto<'a> {
+    content: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,28 +61,183 @@ struct ErrorResponseDto {
     error: Option<String>,
 }
 
+#[derive(Serialize)]
+struct RefreshRequestDto<'a> {
+    refresh_token: &'a str,
+}
+
+#[derive(Serialize)]
+struct ChangePasswordRequestDto<'a> {
+    current_password: &'a str,
+    new_password: &'a str,
+}
+
+#[derive(Serialize)]
+struct PasswordResetRequestDto<'a> {
+    email: &'a str,
+}
+
+#[derive(Serialize)]
+struct PasswordResetConfirmDto<'a> {
+    token: &'a str,
+    new_password: &'a str,
+}
+
+#[derive(Serialize)]
+struct VerifyEmailRequestDto<'a> {
+    token: &'a str,
+}
+
+#[derive(Serialize)]
+struct MfaLoginRequestDto<'a> {
+    mfa_token: &'a str,
+    code: &'a str,
+}
+
+#[derive(Serialize)]
+struct TotpCodeRequestDto<'a> {
+    code: &'a str,
+}
+
+#[derive(Serialize)]
+struct CreatePersonalTokenRequestDto<'a> {
+    name: &'a str,
+    scopes: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_in_days: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct LogoutRequestDto<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<&'a str>,
+}
+
 #[derive(Debug, Deserialize)]
 struct AuthResponseDto {
     access_token: String,
+    refresh_token: String,
     user: UserDto,
 }
 
+/// `200` с токенами или `202`, если нужен второй фактор.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LoginResponseDto {
+    Authenticated(AuthResponseDto),
+    MfaRequired(MfaChallengeDto),
+}
+
+#[derive(Debug, Deserialize)]
+struct MfaChallengeDto {
+    mfa_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TotpEnrollmentDto {
+    secret: String,
+    otpauth_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecoveryCodesDto {
+    recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PersonalTokenDto {
+    id: i64,
+    name: String,
+    scopes: Vec<String>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedPersonalTokenDto {
+    token: PersonalTokenDto,
+    secret: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListPersonalTokensResponseDto {
+    tokens: Vec<PersonalTokenDto>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionDto {
+    id: i64,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    last_seen_at: chrono::DateTime<chrono::Utc>,
+    current: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListSessionsResponseDto {
+    sessions: Vec<SessionDto>,
+}
+
+#[derive(Debug, Serialize)]
+struct UpdateProfileRequestDto<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bio: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    website: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar_url: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileDto {
+    id: i64,
+    username: String,
+    display_name: Option<String>,
+    bio: Option<String>,
+    website: Option<String>,
+    avatar_url: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, Deserialize)]
 struct UserDto {
     id: i64,
     username: String,
     email: String,
+    role: UserRole,
+    email_verified_at: Option<chrono::DateTime<chrono::Utc>>,
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Debug, Deserialize)]
 struct PostDto {
     id: i64,
+    #[serde(default)]
+    slug: String,
     title: String,
     content: String,
+    #[serde(default)]
+    content_format: ContentFormat,
+    #[serde(default)]
+    content_html: String,
     author_id: i64,
+    status: PostStatus,
+    published_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    tags: Vec<String>,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
+    version: i64,
+    #[serde(default)]
+    author: Option<PostAuthor>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -65,37 +246,295 @@ struct ListPostsResponseDto {
     limit: u32,
     offset: u32,
     total: i64,
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ListPostsQuery<'a> {
+    limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cursor: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchHitDto {
+    post: PostDto,
+    rank: f32,
+    snippet: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchPostsResponseDto {
+    results: Vec<SearchHitDto>,
+    limit: u32,
+    offset: u32,
+    total: i64,
 }
 
 #[derive(Serialize)]
-struct ListPostsQuery {
+struct SearchPostsQuery<'a> {
+    q: &'a str,
     limit: u32,
     offset: u32,
 }
 
+#[derive(Debug, Deserialize)]
+struct CommentDto {
+    id: i64,
+    post_id: i64,
+    author_id: i64,
+    parent_id: Option<i64>,
+    content: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    replies: Vec<CommentDto>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListCommentsResponseDto {
+    comments: Vec<CommentDto>,
+    total: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RevisionSummaryDto {
+    revision: i32,
+    title: String,
+    editor_id: i64,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListRevisionsResponseDto {
+    revisions: Vec<RevisionSummaryDto>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RevisionDto {
+    post_id: i64,
+    revision: i32,
+    title: String,
+    content: String,
+    editor_id: i64,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiffLineDto {
+    op: DiffOp,
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RevisionDiffDto {
+    from_revision: i32,
+    to_revision: Option<i32>,
+    from_title: String,
+    to_title: String,
+    lines: Vec<DiffLineDto>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagDto {
+    name: String,
+    post_count: i64,
+}
+
 impl From<AuthResponseDto> for AuthResponse {
     fn from(value: AuthResponseDto) -> Self {
         Self {
             access_token: value.access_token,
-            user: User {
-                id: value.user.id,
-                username: value.user.username,
-                email: value.user.email,
-                created_at: value.user.created_at,
+            refresh_token: value.refresh_token,
+            user: value.user.into(),
+        }
+    }
+}
+
+impl From<LoginResponseDto> for LoginOutcome {
+    fn from(value: LoginResponseDto) -> Self {
+        match value {
+            LoginResponseDto::Authenticated(dto) => Self::Authenticated(dto.into()),
+            LoginResponseDto::MfaRequired(dto) => Self::MfaRequired {
+                mfa_token: dto.mfa_token,
             },
         }
     }
 }
 
+impl From<TotpEnrollmentDto> for TotpEnrollment {
+    fn from(value: TotpEnrollmentDto) -> Self {
+        Self {
+            secret: value.secret,
+            otpauth_uri: value.otpauth_uri,
+        }
+    }
+}
+
+impl From<PersonalTokenDto> for PersonalToken {
+    fn from(value: PersonalTokenDto) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            scopes: value.scopes,
+            expires_at: value.expires_at,
+            last_used_at: value.last_used_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl From<CreatedPersonalTokenDto> for CreatedPersonalToken {
+    fn from(value: CreatedPersonalTokenDto) -> Self {
+        Self {
+            token: value.token.into(),
+            secret: value.secret,
+        }
+    }
+}
+
+impl From<SessionDto> for Session {
+    fn from(value: SessionDto) -> Self {
+        Self {
+            id: value.id,
+            user_agent: value.user_agent,
+            ip_address: value.ip_address,
+            created_at: value.created_at,
+            last_seen_at: value.last_seen_at,
+            current: value.current,
+        }
+    }
+}
+
+impl From<ProfileDto> for Profile {
+    fn from(value: ProfileDto) -> Self {
+        Self {
+            id: value.id,
+            username: value.username,
+            display_name: value.display_name,
+            bio: value.bio,
+            website: value.website,
+            avatar_url: value.avatar_url,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl From<UserDto> for User {
+    fn from(value: UserDto) -> Self {
+        Self {
+            id: value.id,
+            username: value.username,
+            email: value.email,
+            role: value.role,
+            email_verified_at: value.email_verified_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
 impl From<PostDto> for Post {
     fn from(value: PostDto) -> Self {
         Self {
             id: value.id,
+            slug: value.slug,
             title: value.title,
             content: value.content,
+            content_format: value.content_format,
+            content_html: value.content_html,
+            author_id: value.author_id,
+            status: value.status,
+            published_at: value.published_at,
+            publish_at: value.publish_at,
+            deleted_at: value.deleted_at,
+            tags: value.tags,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            version: value.version,
+            author: value.author,
+        }
+    }
+}
+
+impl From<CommentDto> for Comment {
+    fn from(value: CommentDto) -> Self {
+        Self {
+            id: value.id,
+            post_id: value.post_id,
             author_id: value.author_id,
+            parent_id: value.parent_id,
+            content: value.content,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            replies: value.replies.into_iter().map(Comment::from).collect(),
+        }
+    }
+}
+
+impl From<ListCommentsResponseDto> for ListCommentsResponse {
+    fn from(value: ListCommentsResponseDto) -> Self {
+        Self {
+            comments: value.comments.into_iter().map(Comment::from).collect(),
+            total: value.total,
+        }
+    }
+}
+
+impl From<RevisionSummaryDto> for RevisionSummary {
+    fn from(value: RevisionSummaryDto) -> Self {
+        Self {
+            revision: value.revision,
+            title: value.title,
+            editor_id: value.editor_id,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl From<RevisionDto> for Revision {
+    fn from(value: RevisionDto) -> Self {
+        Self {
+            post_id: value.post_id,
+            revision: value.revision,
+            title: value.title,
+            content: value.content,
+            editor_id: value.editor_id,
+            created_at: value.created_at,
+        }
+    }
+}
+
+impl From<RevisionDiffDto> for RevisionDiff {
+    fn from(value: RevisionDiffDto) -> Self {
+        Self {
+            from_revision: value.from_revision,
+            to_revision: value.to_revision,
+            from_title: value.from_title,
+            to_title: value.to_title,
+            lines: value
+                .lines
+                .into_iter()
+                .map(|line| DiffLine {
+                    op: line.op,
+                    text: line.text,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<TagDto> for Tag {
+    fn from(value: TagDto) -> Self {
+        Self {
+            name: value.name,
+            post_count: value.post_count.max(0) as u64,
         }
     }
 }
@@ -107,6 +546,26 @@ impl From<ListPostsResponseDto> for ListPostsResponse {
             limit: value.limit,
             offset: value.offset,
             total: value.total.max(0) as u64,
+            next_cursor: value.next_cursor,
+        }
+    }
+}
+
+impl From<SearchPostsResponseDto> for SearchPostsResponse {
+    fn from(value: SearchPostsResponseDto) -> Self {
+        Self {
+            results: value
+                .results
+                .into_iter()
+                .map(|hit| SearchHit {
+                    post: hit.post.into(),
+                    rank: hit.rank,
+                    snippet: hit.snippet,
+                })
+                .collect(),
+            limit: value.limit,
+            offset: value.offset,
+            total: value.total.max(0) as u64,
         }
     }
 }
@@ -122,6 +581,7 @@ impl HttpClient {
     /// Создаёт новый HTTP-клиент с базовым URL сервера.
     pub fn new(base_url: impl Into<String>) -> Self {
         let client = Client::builder()
+            .user_agent(crate::USER_AGENT)
             .connect_timeout(Duration::from_secs(5))
             .timeout(Duration::from_secs(15))
             .build()
@@ -141,8 +601,24 @@ impl HttpClient {
         )
     }
 
+    /// ETag поста на сервере — номер версии в кавычках.
+    fn with_if_match(
+        request: reqwest::RequestBuilder,
+        expected_version: Option<i64>,
+    ) -> reqwest::RequestBuilder {
+        match expected_version {
+            Some(version) => request.header(reqwest::header::IF_MATCH, format!("\"{version}\"")),
+            None => request,
+        }
+    }
+
     async fn decode_error(response: reqwest::Response) -> BlogClientError {
         let status = response.status();
+        let retry_after_secs = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
 
         let message = match response.json::<ErrorResponseDto>().await {
             Ok(body) => body
@@ -150,7 +626,13 @@ impl HttpClient {
                 .unwrap_or_else(|| format!("http status {status}")),
             Err(_) => format!("http status {status}"),
         };
-        BlogClientError::from_http_status(status, Some(message))
+        match BlogClientError::from_http_status(status, Some(message)) {
+            BlogClientError::TooManyRequests { message, .. } => BlogClientError::TooManyRequests {
+                message,
+                retry_after_secs,
+            },
+            err => err,
+        }
     }
 
     /// универсальный helper для отправки запросов с json-payload
@@ -186,6 +668,34 @@ impl HttpClient {
             .map_err(BlogClientError::from_reqwest)
     }
 
+    /// Как `send_json`, но для ответов без тела (`202`/`204`).
+    async fn send_json_no_content<TReq>(
+        &self,
+        path: &str,
+        body: &TReq,
+        token: Option<&str>,
+    ) -> BlogClientResult<()>
+    where
+        TReq: Serialize,
+    {
+        let url = self.endpoint(path);
+
+        let mut request = self.client.request(Method::POST, url).json(body);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        Ok(())
+    }
+
     /// Регистрирует пользователя и возвращает JWT + данные пользователя.
     pub async fn register(
         &self,
@@ -204,37 +714,345 @@ impl HttpClient {
         Ok(dto.into())
     }
 
-    /// Выполняет авторизацию пользователя и возвращает JWT + данные пользователя.
-    pub async fn login(&self, username: &str, password: &str) -> BlogClientResult<AuthResponse> {
+    /// Выполняет авторизацию пользователя: возвращает JWT + данные пользователя
+    /// или промежуточный токен, если включён второй фактор.
+    pub(crate) async fn login(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> BlogClientResult<LoginOutcome> {
         let payload = LoginRequestDto { username, password };
-        let dto: AuthResponseDto = self
+        let dto: LoginResponseDto = self
             .send_json(Method::POST, "/api/auth/login", &payload, None)
             .await?;
         Ok(dto.into())
     }
 
-    /// Создаёт пост от имени авторизованного пользователя.
-    ///
-    /// Требует валидный JWT-токен.
-    pub async fn create_post(
+    /// Завершает вход кодом второго фактора.
+    pub async fn complete_mfa_login(
         &self,
-        token: &str,
-        title: &str,
-        content: &str,
-    ) -> BlogClientResult<Post> {
-        let payload = CreatePostRequestDto { title, content };
-        let dto: PostDto = self
-            .send_json(Method::POST, "/api/posts", &payload, Some(token))
-            .await?;
+        mfa_token: &str,
+        code: &str,
+    ) -> BlogClientResult<AuthResponse> {
+        let payload = MfaLoginRequestDto { mfa_token, code };
+        let dto: AuthResponseDto = self
+            .send_json(Method::POST, "/api/auth/login/mfa", &payload, None)
+            .await?;
+        Ok(dto.into())
+    }
+
+    /// Обменивает refresh-токен на новую пару токенов.
+    pub async fn refresh(&self, refresh_token: &str) -> BlogClientResult<AuthResponse> {
+        let payload = RefreshRequestDto { refresh_token };
+        let dto: AuthResponseDto = self
+            .send_json(Method::POST, "/api/auth/refresh", &payload, None)
+            .await?;
+        Ok(dto.into())
+    }
+
+    /// Отзывает access-токен и, если передан, refresh-токен текущего входа.
+    pub async fn logout(&self, token: &str, refresh_token: Option<&str>) -> BlogClientResult<()> {
+        let url = self.endpoint("/api/auth/logout");
+        let payload = LogoutRequestDto { refresh_token };
+
+        let request = self
+            .client
+            .request(Method::POST, url)
+            .bearer_auth(token)
+            .json(&payload);
+
+        let response = request
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    /// Завершает все сессии пользователя.
+    pub async fn logout_all(&self, token: &str) -> BlogClientResult<()> {
+        let url = self.endpoint("/api/auth/logout-all");
+
+        let request = self.client.request(Method::POST, url).bearer_auth(token);
+
+        let response = request
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    /// Меняет пароль; сервер завершает все сессии пользователя.
+    pub async fn change_password(
+        &self,
+        token: &str,
+        current_password: &str,
+        new_password: &str,
+    ) -> BlogClientResult<()> {
+        let payload = ChangePasswordRequestDto {
+            current_password,
+            new_password,
+        };
+        self.send_json_no_content("/api/auth/password", &payload, Some(token))
+            .await
+    }
+
+    /// Запрашивает письмо с токеном сброса пароля.
+    pub async fn request_password_reset(&self, email: &str) -> BlogClientResult<()> {
+        let payload = PasswordResetRequestDto { email };
+        self.send_json_no_content("/api/auth/password-reset/request", &payload, None)
+            .await
+    }
+
+    /// Задаёт новый пароль по токену из письма.
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> BlogClientResult<()> {
+        let payload = PasswordResetConfirmDto {
+            token,
+            new_password,
+        };
+        self.send_json_no_content("/api/auth/password-reset/confirm", &payload, None)
+            .await
+    }
+
+    /// Подтверждает email по токену из письма.
+    pub async fn verify_email(&self, token: &str) -> BlogClientResult<User> {
+        let payload = VerifyEmailRequestDto { token };
+        let user: UserDto = self
+            .send_json(Method::POST, "/api/auth/verify-email", &payload, None)
+            .await?;
+        Ok(user.into())
+    }
+
+    /// Просит сервер повторно отправить письмо подтверждения.
+    pub async fn resend_verification_email(&self, token: &str) -> BlogClientResult<()> {
+        self.send_json_no_content("/api/auth/verify-email/resend", &(), Some(token))
+            .await
+    }
+
+    /// Начинает подключение TOTP и возвращает новый секрет.
+    pub async fn start_totp_enrollment(&self, token: &str) -> BlogClientResult<TotpEnrollment> {
+        let dto: TotpEnrollmentDto = self
+            .send_json(Method::POST, "/api/auth/totp/setup", &(), Some(token))
+            .await?;
+        Ok(dto.into())
+    }
+
+    /// Подтверждает подключение TOTP кодом и возвращает коды восстановления.
+    pub async fn confirm_totp_enrollment(
+        &self,
+        token: &str,
+        code: &str,
+    ) -> BlogClientResult<Vec<String>> {
+        let payload = TotpCodeRequestDto { code };
+        let dto: RecoveryCodesDto = self
+            .send_json(
+                Method::POST,
+                "/api/auth/totp/confirm",
+                &payload,
+                Some(token),
+            )
+            .await?;
+        Ok(dto.recovery_codes)
+    }
+
+    /// Отключает TOTP по коду из аутентификатора или коду восстановления.
+    pub async fn disable_totp(&self, token: &str, code: &str) -> BlogClientResult<()> {
+        let payload = TotpCodeRequestDto { code };
+        self.send_json_no_content("/api/auth/totp/disable", &payload, Some(token))
+            .await
+    }
+
+    /// Выпускает персональный токен доступа; секрет возвращается только здесь.
+    pub async fn create_personal_token(
+        &self,
+        token: &str,
+        name: &str,
+        scopes: &[String],
+        expires_in_days: Option<u32>,
+    ) -> BlogClientResult<CreatedPersonalToken> {
+        let payload = CreatePersonalTokenRequestDto {
+            name,
+            scopes,
+            expires_in_days,
+        };
+        let dto: CreatedPersonalTokenDto = self
+            .send_json(Method::POST, "/api/auth/tokens", &payload, Some(token))
+            .await?;
+        Ok(dto.into())
+    }
+
+    /// Возвращает активные токены доступа пользователя.
+    pub async fn list_personal_tokens(&self, token: &str) -> BlogClientResult<Vec<PersonalToken>> {
+        let dto: ListPersonalTokensResponseDto =
+            self.get_authorized("/api/auth/tokens", token).await?;
+        Ok(dto.tokens.into_iter().map(PersonalToken::from).collect())
+    }
+
+    /// Отзывает токен доступа по идентификатору.
+    pub async fn revoke_personal_token(&self, token: &str, id: i64) -> BlogClientResult<()> {
+        let url = self.endpoint(&format!("/api/auth/tokens/{id}"));
+
+        let response = self
+            .client
+            .request(Method::DELETE, url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    /// Возвращает активные сессии пользователя.
+    pub async fn list_sessions(&self, token: &str) -> BlogClientResult<Vec<Session>> {
+        let dto: ListSessionsResponseDto = self.get_authorized("/api/auth/sessions", token).await?;
+        Ok(dto.sessions.into_iter().map(Session::from).collect())
+    }
+
+    /// Завершает сессию по идентификатору.
+    pub async fn revoke_session(&self, token: &str, id: i64) -> BlogClientResult<()> {
+        let url = self.endpoint(&format!("/api/auth/sessions/{id}"));
+
+        let response = self
+            .client
+            .request(Method::DELETE, url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    /// Возвращает публичный профиль пользователя.
+    pub async fn get_profile(&self, username: &str) -> BlogClientResult<Profile> {
+        let url = self.endpoint(&format!("/api/users/{username}"));
+
+        let response = self
+            .client
+            .request(Method::GET, url)
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        let dto = response
+            .json::<ProfileDto>()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        Ok(dto.into())
+    }
+
+    /// Частично обновляет профиль текущего пользователя.
+    pub async fn update_profile(
+        &self,
+        token: &str,
+        update: &ProfileUpdate,
+    ) -> BlogClientResult<Profile> {
+        let payload = UpdateProfileRequestDto {
+            display_name: update.display_name.as_deref(),
+            bio: update.bio.as_deref(),
+            website: update.website.as_deref(),
+            avatar_url: update.avatar_url.as_deref(),
+        };
+
+        let dto: ProfileDto = self
+            .send_json(Method::PATCH, "/api/users/me", &payload, Some(token))
+            .await?;
+        Ok(dto.into())
+    }
+
+    /// Создаёт пост от имени авторизованного пользователя.
+    ///
+    /// Требует валидный JWT-токен.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_post(
+        &self,
+        token: &str,
+        title: &str,
+        content: &str,
+        format: ContentFormat,
+        status: PostStatus,
+        tags: &[String],
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> BlogClientResult<Post> {
+        let payload = CreatePostRequestDto {
+            title,
+            content,
+            content_format: format,
+            status,
+            tags,
+            publish_at,
+        };
+        let dto: PostDto = self
+            .send_json(Method::POST, "/api/posts", &payload, Some(token))
+            .await?;
 
         Ok(dto.into())
     }
 
     /// Получает пост по идентификатору.
-    pub async fn get_post(&self, id: i64) -> BlogClientResult<Post> {
+    ///
+    /// С токеном автора возвращает и его черновики.
+    pub async fn get_post(&self, token: Option<&str>, id: i64) -> BlogClientResult<Post> {
         let url = self.endpoint(&format!("/api/posts/{id}"));
 
-        let request = self.client.request(Method::GET, url);
+        let mut request = self.client.request(Method::GET, url);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        let dto = response
+            .json::<PostDto>()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        Ok(dto.into())
+    }
+
+    /// Получает пост по текущему или прежнему slug.
+    ///
+    /// Прежний slug сервер перенаправляет на текущий, редирект проходит автоматически.
+    pub async fn get_post_by_slug(
+        &self,
+        token: Option<&str>,
+        slug: &str,
+    ) -> BlogClientResult<Post> {
+        let mut url = reqwest::Url::parse(&self.endpoint("/api/posts/by-slug"))
+            .map_err(|err| BlogClientError::InvalidRequest(err.to_string()))?;
+        // slug экранируется как сегмент пути
+        url.path_segments_mut()
+            .map_err(|_| {
+                BlogClientError::InvalidRequest("server url cannot have a path".to_string())
+            })?
+            .push(slug);
+
+        let mut request = self.client.request(Method::GET, url);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
 
         let response = request
             .send()
@@ -253,20 +1071,119 @@ impl HttpClient {
 
     /// Обновляет пост по идентификатору.
     ///
-    /// Требует валидный JWT-токен.
+    /// С `expected_version` отправляет `If-Match`; если пост успели изменить,
+    /// возвращает `BlogClientError::Conflict`. Требует валидный JWT-токен.
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_post(
         &self,
         token: &str,
         id: i64,
         title: &str,
         content: &str,
+        format: Option<ContentFormat>,
+        tags: Option<&[String]>,
+        expected_version: Option<i64>,
     ) -> BlogClientResult<Post> {
-        let payload = UpdatePostRequestDto { title, content };
+        let url = self.endpoint(&format!("/api/posts/{id}"));
+        let payload = UpdatePostRequestDto {
+            title,
+            content,
+            content_format: format,
+            tags,
+        };
+
+        let request = self
+            .client
+            .request(Method::PUT, url)
+            .bearer_auth(token)
+            .json(&payload);
+        let request = Self::with_if_match(request, expected_version);
+
+        let response = request
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        let dto = response
+            .json::<PostDto>()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        Ok(dto.into())
+    }
+
+    /// Перемещает пост в корзину.
+    ///
+    /// С `expected_version` отправляет `If-Match`; если пост успели изменить,
+    /// возвращает `BlogClientError::Conflict`. Требует валидный JWT-токен.
+    pub async fn delete_post(
+        &self,
+        token: &str,
+        id: i64,
+        expected_version: Option<i64>,
+    ) -> BlogClientResult<()> {
+        let url = self.endpoint(&format!("/api/posts/{id}"));
+
+        let request = self.client.request(Method::DELETE, url).bearer_auth(token);
+        let request = Self::with_if_match(request, expected_version);
+
+        let response = request
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    /// Возвращает посты из корзины текущего пользователя, сначала удалённые последними.
+    ///
+    /// Требует валидный JWT-токен.
+    pub async fn list_trash(
+        &self,
+        token: &str,
+        limit: u32,
+        offset: u32,
+    ) -> BlogClientResult<ListPostsResponse> {
+        let url = self.endpoint("/api/posts/trash");
+        let query = ListPostsQuery {
+            limit,
+            offset: Some(offset),
+            tags: None,
+            cursor: None,
+            author: None,
+        };
+
+        let response = self
+            .client
+            .request(Method::GET, url)
+            .query(&query)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        let dto = response
+            .json::<ListPostsResponseDto>()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        Ok(dto.into())
+    }
+
+    /// Восстанавливает пост из корзины. Требует валидный JWT-токен.
+    pub async fn restore_post(&self, token: &str, id: i64) -> BlogClientResult<Post> {
         let dto: PostDto = self
             .send_json(
-                Method::PUT,
-                &format!("/api/posts/{id}"),
-                &payload,
+                Method::POST,
+                &format!("/api/posts/trash/{id}/restore"),
+                &serde_json::json!({}),
                 Some(token),
             )
             .await?;
@@ -274,11 +1191,189 @@ impl HttpClient {
         Ok(dto.into())
     }
 
-    /// Удаляет пост по идентификатору.
+    /// Окончательно удаляет пост из корзины. Требует валидный JWT-токен.
+    pub async fn purge_post(&self, token: &str, id: i64) -> BlogClientResult<()> {
+        let url = self.endpoint(&format!("/api/posts/trash/{id}"));
+
+        let response = self
+            .client
+            .request(Method::DELETE, url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    /// Возвращает список постов с пагинацией `limit/offset`.
+    ///
+    /// С токеном в выборку попадают и собственные черновики.
+    /// Непустой `tags` оставляет посты, у которых есть все перечисленные теги.
+    pub async fn list_posts(
+        &self,
+        token: Option<&str>,
+        tags: &[String],
+        author: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> BlogClientResult<ListPostsResponse> {
+        let query = ListPostsQuery {
+            limit,
+            offset: Some(offset),
+            tags: (!tags.is_empty()).then(|| tags.join(",")),
+            cursor: None,
+            author,
+        };
+        self.fetch_posts(token, &query).await
+    }
+
+    /// Возвращает страницу постов после `cursor` (`next_cursor` предыдущей страницы).
+    pub async fn list_posts_by_cursor(
+        &self,
+        token: Option<&str>,
+        tags: &[String],
+        author: Option<&str>,
+        cursor: &str,
+        limit: u32,
+    ) -> BlogClientResult<ListPostsResponse> {
+        let query = ListPostsQuery {
+            limit,
+            offset: None,
+            tags: (!tags.is_empty()).then(|| tags.join(",")),
+            cursor: Some(cursor),
+            author,
+        };
+        self.fetch_posts(token, &query).await
+    }
+
+    async fn fetch_posts(
+        &self,
+        token: Option<&str>,
+        query: &ListPostsQuery<'_>,
+    ) -> BlogClientResult<ListPostsResponse> {
+        let url = self.endpoint("/api/posts");
+        let mut request = self.client.request(Method::GET, url).query(query);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        let dto = response
+            .json::<ListPostsResponseDto>()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        Ok(dto.into())
+    }
+
+    /// Полнотекстовый поиск постов; результаты упорядочены по релевантности.
+    pub async fn search_posts(
+        &self,
+        token: Option<&str>,
+        query: &str,
+        limit: u32,
+        offset: u32,
+    ) -> BlogClientResult<SearchPostsResponse> {
+        let url = self.endpoint("/api/posts/search");
+        let query = SearchPostsQuery {
+            q: query,
+            limit,
+            offset,
+        };
+
+        let mut request = self.client.request(Method::GET, url).query(&query);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        let dto = response
+            .json::<SearchPostsResponseDto>()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        Ok(dto.into())
+    }
+
+    /// Возвращает дерево комментариев поста.
+    ///
+    /// С токеном автора доступны комментарии к его черновикам.
+    pub async fn list_comments(
+        &self,
+        token: Option<&str>,
+        post_id: i64,
+    ) -> BlogClientResult<ListCommentsResponse> {
+        let url = self.endpoint(&format!("/api/posts/{post_id}/comments"));
+
+        let mut request = self.client.request(Method::GET, url);
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        let dto = response
+            .json::<ListCommentsResponseDto>()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        Ok(dto.into())
+    }
+
+    /// Добавляет комментарий к посту или ответ на комментарий `parent_id`.
     ///
     /// Требует валидный JWT-токен.
-    pub async fn delete_post(&self, token: &str, id: i64) -> BlogClientResult<()> {
-        let url = self.endpoint(&format!("/api/posts/{id}"));
+    pub async fn create_comment(
+        &self,
+        token: &str,
+        post_id: i64,
+        content: &str,
+        parent_id: Option<i64>,
+    ) -> BlogClientResult<Comment> {
+        let payload = CreateCommentRequestDto { content, parent_id };
+        let dto: CommentDto = self
+            .send_json(
+                Method::POST,
+                &format!("/api/posts/{post_id}/comments"),
+                &payload,
+                Some(token),
+            )
+            .await?;
+
+        Ok(dto.into())
+    }
+
+    /// Удаляет комментарий вместе с ответами.
+    ///
+    /// Требует валидный JWT-токен автора комментария или автора поста.
+    pub async fn delete_comment(
+        &self,
+        token: &str,
+        post_id: i64,
+        comment_id: i64,
+    ) -> BlogClientResult<()> {
+        let url = self.endpoint(&format!("/api/posts/{post_id}/comments/{comment_id}"));
 
         let request = self.client.request(Method::DELETE, url).bearer_auth(token);
 
@@ -293,15 +1388,107 @@ impl HttpClient {
         Ok(())
     }
 
-    /// Возвращает список постов с пагинацией `limit/offset`.
-    pub async fn list_posts(&self, limit: u32, offset: u32) -> BlogClientResult<ListPostsResponse> {
-        let url = self.endpoint("/api/posts");
+    /// Возвращает историю правок поста от новых ревизий к старым.
+    ///
+    /// Требует валидный JWT-токен автора поста.
+    pub async fn list_revisions(
+        &self,
+        token: &str,
+        post_id: i64,
+    ) -> BlogClientResult<Vec<RevisionSummary>> {
+        let dto: ListRevisionsResponseDto = self
+            .get_authorized(&format!("/api/posts/{post_id}/revisions"), token)
+            .await?;
+        Ok(dto
+            .revisions
+            .into_iter()
+            .map(RevisionSummary::from)
+            .collect())
+    }
 
-        let query = ListPostsQuery { limit, offset };
+    /// Возвращает прежнюю версию поста.
+    ///
+    /// Требует валидный JWT-токен автора поста.
+    pub async fn get_revision(
+        &self,
+        token: &str,
+        post_id: i64,
+        revision: i32,
+    ) -> BlogClientResult<Revision> {
+        let dto: RevisionDto = self
+            .get_authorized(&format!("/api/posts/{post_id}/revisions/{revision}"), token)
+            .await?;
+        Ok(dto.into())
+    }
 
-        let request = self.client.request(Method::GET, url).query(&query);
+    /// Построчный diff между ревизиями; `to = None` — с текущей версией.
+    ///
+    /// Требует валидный JWT-токен автора поста.
+    pub async fn diff_revisions(
+        &self,
+        token: &str,
+        post_id: i64,
+        from: i32,
+        to: Option<i32>,
+    ) -> BlogClientResult<RevisionDiff> {
+        let mut path = format!("/api/posts/{post_id}/revisions/diff?from={from}");
+        if let Some(to) = to {
+            path.push_str(&format!("&to={to}"));
+        }
+        let dto: RevisionDiffDto = self.get_authorized(&path, token).await?;
+        Ok(dto.into())
+    }
 
-        let response = request
+    /// Восстанавливает ревизию новой правкой поста.
+    ///
+    /// Требует валидный JWT-токен автора поста.
+    pub async fn restore_revision(
+        &self,
+        token: &str,
+        post_id: i64,
+        revision: i32,
+    ) -> BlogClientResult<Post> {
+        let dto: PostDto = self
+            .send_json(
+                Method::POST,
+                &format!("/api/posts/{post_id}/revisions/{revision}/restore"),
+                &serde_json::json!({}),
+                Some(token),
+            )
+            .await?;
+        Ok(dto.into())
+    }
+
+    async fn get_authorized<TRes>(&self, path: &str, token: &str) -> BlogClientResult<TRes>
+    where
+        TRes: DeserializeOwned,
+    {
+        let url = self.endpoint(path);
+
+        let response = self
+            .client
+            .request(Method::GET, url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(BlogClientError::from_reqwest)?;
+        if !response.status().is_success() {
+            return Err(Self::decode_error(response).await);
+        }
+
+        response
+            .json::<TRes>()
+            .await
+            .map_err(BlogClientError::from_reqwest)
+    }
+
+    /// Возвращает теги с количеством опубликованных постов.
+    pub async fn list_tags(&self) -> BlogClientResult<Vec<Tag>> {
+        let url = self.endpoint("/api/tags");
+
+        let response = self
+            .client
+            .request(Method::GET, url)
             .send()
             .await
             .map_err(BlogClientError::from_reqwest)?;
@@ -310,9 +1497,64 @@ impl HttpClient {
         }
 
         let dto = response
-            .json::<ListPostsResponseDto>()
+            .json::<Vec<TagDto>>()
             .await
             .map_err(BlogClientError::from_reqwest)?;
+        Ok(dto.into_iter().map(Tag::from).collect())
+    }
+
+    /// Публикует пост.
+    ///
+    /// Требует валидный JWT-токен.
+    pub async fn publish_post(&self, token: &str, id: i64) -> BlogClientResult<Post> {
+        self.post_action(token, id, "publish").await
+    }
+
+    /// Возвращает пост в черновики.
+    ///
+    /// Требует валидный JWT-токен.
+    pub async fn unpublish_post(&self, token: &str, id: i64) -> BlogClientResult<Post> {
+        self.post_action(token, id, "unpublish").await
+    }
+
+    /// Переносит пост в архив.
+    ///
+    /// Требует валидный JWT-токен.
+    pub async fn archive_post(&self, token: &str, id: i64) -> BlogClientResult<Post> {
+        self.post_action(token, id, "archive").await
+    }
+
+    /// Назначает или отменяет (`None`) отложенную публикацию черновика.
+    ///
+    /// Требует валидный JWT-токен.
+    pub async fn schedule_post(
+        &self,
+        token: &str,
+        id: i64,
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> BlogClientResult<Post> {
+        let dto: PostDto = self
+            .send_json(
+                Method::POST,
+                &format!("/api/posts/{id}/schedule"),
+                &SchedulePostRequestDto { publish_at },
+                Some(token),
+            )
+            .await?;
+
+        Ok(dto.into())
+    }
+
+    async fn post_action(&self, token: &str, id: i64, action: &str) -> BlogClientResult<Post> {
+        let dto: PostDto = self
+            .send_json(
+                Method::POST,
+                &format!("/api/posts/{id}/{action}"),
+                &serde_json::json!({}),
+                Some(token),
+            )
+            .await?;
+
         Ok(dto.into())
     }
 }
@@ -329,6 +1571,28 @@ mod tests {
         assert_eq!(full, "http://localhost:8080/api/posts");
     }
 
+    #[test]
+    fn login_response_distinguishes_mfa_challenge() {
+        let challenge: LoginResponseDto =
+            serde_json::from_str(r#"{"mfa_required":true,"mfa_token":"pending","expires_in":300}"#)
+                .expect("challenge must deserialize");
+        assert!(matches!(
+            LoginOutcome::from(challenge),
+            LoginOutcome::MfaRequired { mfa_token } if mfa_token == "pending"
+        ));
+
+        let session: LoginResponseDto = serde_json::from_str(
+            r#"{"access_token":"jwt","refresh_token":"refresh","user":{"id":1,
+                "username":"alice","email":"alice@example.com","role":"user",
+                "email_verified_at":null,"created_at":"2026-01-01T00:00:00Z"}}"#,
+        )
+        .expect("auth response must deserialize");
+        assert!(matches!(
+            LoginOutcome::from(session),
+            LoginOutcome::Authenticated(auth) if auth.access_token == "jwt"
+        ));
+    }
+
     #[test]
     fn list_posts_response_keeps_limit_and_offset() {
         let dto = ListPostsResponseDto {
@@ -336,12 +1600,14 @@ mod tests {
             limit: 20,
             offset: 40,
             total: 42,
+            next_cursor: Some("abc".to_string()),
         };
 
         let mapped = ListPostsResponse::from(dto);
         assert_eq!(mapped.limit, 20);
         assert_eq!(mapped.offset, 40);
         assert_eq!(mapped.total, 42);
+        assert_eq!(mapped.next_cursor.as_deref(), Some("abc"));
     }
 
     #[test]
@@ -349,20 +1615,32 @@ mod tests {
         let dto = ListPostsResponseDto {
             posts: vec![PostDto {
                 id: 1,
+                slug: "t".to_string(),
                 title: "t".to_string(),
                 content: "c".to_string(),
+                content_format: ContentFormat::Plain,
+                content_html: "<p>c</p>".to_string(),
                 author_id: 2,
+                status: PostStatus::Published,
+                published_at: Utc.timestamp_opt(10, 0).single(),
+                publish_at: None,
+                deleted_at: None,
+                tags: vec!["rust".to_string()],
                 created_at: Utc.timestamp_opt(10, 0).single().expect("valid ts"),
                 updated_at: Utc.timestamp_opt(20, 0).single().expect("valid ts"),
+                version: 3,
+                author: None,
             }],
             limit: 10,
             offset: 0,
             total: -7,
+            next_cursor: None,
         };
 
         let mapped = ListPostsResponse::from(dto);
         assert_eq!(mapped.total, 0);
         assert_eq!(mapped.posts.len(), 1);
         assert_eq!(mapped.posts[0].id, 1);
+        assert_eq!(mapped.posts[0].tags, vec!["rust".to_string()]);
     }
 }
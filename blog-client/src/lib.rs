@@ -5,7 +5,10 @@
 //! - gRPC (`tonic`)
 //!
 //! Клиент хранит JWT-токен после `register`/`login` и автоматически использует
-//! его в защищённых операциях.
+//! его в защищённых операциях. Если сервер отклонил истёкший токен, клиент
+//! один раз обновляет пару токенов через refresh-токен и повторяет запрос.
+//! Для скриптов вместо входа можно передать в `set_token` персональный токен
+//! доступа (`blog_pat_...`): refresh-токена у него нет, он действует до отзыва.
 #![warn(missing_docs)]
 
 mod error;
@@ -14,10 +17,19 @@ mod http_client;
 mod models;
 
 pub use error::{BlogClientError, BlogClientResult};
-pub use models::{AuthResponse, ListPostsResponse, Post, User};
+pub use models::{
+    AuthResponse, Comment, ContentFormat, CreatedPersonalToken, DiffLine, DiffOp,
+    ListCommentsResponse, ListPostsResponse, PersonalToken, Post, PostAuthor, PostStatus, Profile,
+    ProfileUpdate, Revision, RevisionDiff, RevisionSummary, SearchHit, SearchPostsResponse,
+    Session, Tag, TotpEnrollment, User, UserRole,
+};
+
+/// `User-Agent` обоих транспортов: по нему сессии клиента видны в списке устройств.
+const USER_AGENT: &str = concat!("blog-client/", env!("CARGO_PKG_VERSION"));
 
 use grpc_client::GrpcClient;
 use http_client::HttpClient;
+use models::LoginOutcome;
 
 #[derive(Debug, Clone)]
 /// Транспорт, через который `BlogClient` отправляет запросы.
@@ -35,6 +47,9 @@ pub struct BlogClient {
     http_client: Option<HttpClient>,
     grpc_client: Option<GrpcClient>,
     token: Option<String>,
+    refresh_token: Option<String>,
+    /// Промежуточный токен входа, ожидающего код второго фактора.
+    mfa_token: Option<String>,
 }
 
 impl BlogClient {
@@ -51,6 +66,8 @@ impl BlogClient {
             http_client,
             grpc_client,
             token: None,
+            refresh_token: None,
+            mfa_token: None,
         }
     }
 
@@ -64,9 +81,20 @@ impl BlogClient {
         self.token.as_deref()
     }
 
-    /// Очищает сохранённый JWT-токен.
+    /// Устанавливает refresh-токен вручную.
+    pub fn set_refresh_token(&mut self, token: impl Into<String>) {
+        self.refresh_token = Some(token.into());
+    }
+
+    /// Возвращает текущий refresh-токен, если он установлен.
+    pub fn get_refresh_token(&self) -> Option<&str> {
+        self.refresh_token.as_deref()
+    }
+
+    /// Очищает сохранённые JWT- и refresh-токены.
     pub fn clear_token(&mut self) {
         self.token = None;
+        self.refresh_token = None;
     }
 
     /// Регистрирует пользователя и сохраняет полученный JWT-токен в клиенте.
@@ -100,17 +128,20 @@ impl BlogClient {
                     .await?
             }
         };
-        self.token = Some(result.access_token.clone());
+        self.store_tokens(&result);
         Ok(result)
     }
 
     /// Выполняет вход пользователя и сохраняет полученный JWT-токен в клиенте.
+    ///
+    /// Если у пользователя включён TOTP, возвращает `BlogClientError::MfaRequired`:
+    /// клиент запоминает промежуточный токен, а вход завершает `complete_mfa_login`.
     pub async fn login(
         &mut self,
         username: &str,
         password: &str,
     ) -> BlogClientResult<AuthResponse> {
-        let result = match &self.transport {
+        let outcome = match &self.transport {
             Transport::Http(_) => {
                 self.http_client
                     .as_ref()
@@ -135,15 +166,109 @@ impl BlogClient {
             }
         };
 
-        self.token = Some(result.access_token.clone());
+        match outcome {
+            LoginOutcome::Authenticated(result) => {
+                self.mfa_token = None;
+                self.store_tokens(&result);
+                Ok(result)
+            }
+            LoginOutcome::MfaRequired { mfa_token } => {
+                self.mfa_token = Some(mfa_token);
+                Err(BlogClientError::MfaRequired)
+            }
+        }
+    }
+
+    /// Завершает вход, начатый `login`, кодом из аутентификатора или
+    /// кодом восстановления, и сохраняет полученные токены.
+    pub async fn complete_mfa_login(&mut self, code: &str) -> BlogClientResult<AuthResponse> {
+        let mfa_token = self.mfa_token.as_deref().ok_or_else(|| {
+            BlogClientError::InvalidRequest("no login is waiting for a second factor".to_string())
+        })?;
+        let result = match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .complete_mfa_login(mfa_token, code)
+                    .await?
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .complete_mfa_login(mfa_token, code)
+                    .await?
+            }
+        };
+
+        self.mfa_token = None;
+        self.store_tokens(&result);
         Ok(result)
     }
 
-    /// Создаёт новый пост.
+    /// Обменивает сохранённый refresh-токен на новую пару токенов.
     ///
-    /// Требует установленный JWT-токен.
-    pub async fn create_post(&self, title: &str, content: &str) -> BlogClientResult<Post> {
+    /// Использованный refresh-токен сервер отзывает, поэтому новая пара
+    /// сразу сохраняется в клиенте.
+    pub async fn refresh(&mut self) -> BlogClientResult<AuthResponse> {
+        let refresh_token = self
+            .refresh_token
+            .as_deref()
+            .ok_or(BlogClientError::Unauthorized)?;
+        let result = match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .refresh(refresh_token)
+                    .await?
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .refresh(refresh_token)
+                    .await?
+            }
+        };
+
+        self.store_tokens(&result);
+        Ok(result)
+    }
+
+    /// Завершает текущую сессию на сервере и очищает токены в клиенте.
+    pub async fn logout(&mut self) -> BlogClientResult<()> {
+        let result = self.try_logout().await;
+        let result = if self.refresh_after_unauthorized(&result).await {
+            self.try_logout().await
+        } else {
+            result
+        };
+
+        self.clear_token();
+        result
+    }
+
+    async fn try_logout(&self) -> BlogClientResult<()> {
         let token = self.require_token()?;
+        let refresh_token = self.refresh_token.as_deref();
         match &self.transport {
             Transport::Http(_) => {
                 self.http_client
@@ -153,7 +278,7 @@ impl BlogClient {
                             "http client is not initialized".to_string(),
                         )
                     })?
-                    .create_post(token, title, content)
+                    .logout(token, refresh_token)
                     .await
             }
             Transport::Grpc(_) => {
@@ -164,14 +289,28 @@ impl BlogClient {
                             "grpc client is not initialized".to_string(),
                         )
                     })?
-                    .create_post(token, title, content)
+                    .logout(token, refresh_token)
                     .await
             }
         }
     }
 
-    /// Возвращает пост по идентификатору.
-    pub async fn get_post(&self, id: i64) -> BlogClientResult<Post> {
+    /// Завершает все сессии пользователя (на всех устройствах) и очищает
+    /// токены в клиенте.
+    pub async fn logout_all(&mut self) -> BlogClientResult<()> {
+        let result = self.try_logout_all().await;
+        let result = if self.refresh_after_unauthorized(&result).await {
+            self.try_logout_all().await
+        } else {
+            result
+        };
+
+        self.clear_token();
+        result
+    }
+
+    async fn try_logout_all(&self) -> BlogClientResult<()> {
+        let token = self.require_token()?;
         match &self.transport {
             Transport::Http(_) => {
                 self.http_client
@@ -181,7 +320,7 @@ impl BlogClient {
                             "http client is not initialized".to_string(),
                         )
                     })?
-                    .get_post(id)
+                    .logout_all(token)
                     .await
             }
             Transport::Grpc(_) => {
@@ -192,16 +331,42 @@ impl BlogClient {
                             "grpc client is not initialized".to_string(),
                         )
                     })?
-                    .get_post(id)
+                    .logout_all(token)
                     .await
             }
         }
     }
 
-    /// Обновляет пост по идентификатору.
+    /// Меняет пароль текущего пользователя.
     ///
-    /// Требует установленный JWT-токен.
-    pub async fn update_post(&self, id: i64, title: &str, content: &str) -> BlogClientResult<Post> {
+    /// Сервер завершает все сессии, включая текущую, поэтому токены в клиенте
+    /// очищаются: дальше нужно войти с новым паролем.
+    pub async fn change_password(
+        &mut self,
+        current_password: &str,
+        new_password: &str,
+    ) -> BlogClientResult<()> {
+        let result = self
+            .try_change_password(current_password, new_password)
+            .await;
+        let result = if self.refresh_after_unauthorized(&result).await {
+            self.try_change_password(current_password, new_password)
+                .await
+        } else {
+            result
+        };
+
+        if result.is_ok() {
+            self.clear_token();
+        }
+        result
+    }
+
+    async fn try_change_password(
+        &self,
+        current_password: &str,
+        new_password: &str,
+    ) -> BlogClientResult<()> {
         let token = self.require_token()?;
         match &self.transport {
             Transport::Http(_) => {
@@ -212,7 +377,7 @@ impl BlogClient {
                             "http client is not initialized".to_string(),
                         )
                     })?
-                    .update_post(token, id, title, content)
+                    .change_password(token, current_password, new_password)
                     .await
             }
             Transport::Grpc(_) => {
@@ -223,16 +388,116 @@ impl BlogClient {
                             "grpc client is not initialized".to_string(),
                         )
                     })?
-                    .update_post(token, id, title, content)
+                    .change_password(token, current_password, new_password)
                     .await
             }
         }
     }
 
-    /// Удаляет пост по идентификатору.
+    /// Запрашивает письмо с одноразовым токеном сброса пароля.
     ///
-    /// Требует установленный JWT-токен.
-    pub async fn delete_post(&self, id: i64) -> BlogClientResult<()> {
+    /// Сервер отвечает одинаково для зарегистрированных и неизвестных адресов.
+    pub async fn request_password_reset(&self, email: &str) -> BlogClientResult<()> {
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .request_password_reset(email)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .request_password_reset(email)
+                    .await
+            }
+        }
+    }
+
+    /// Задаёт новый пароль по токену из письма. Токен одноразовый.
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> BlogClientResult<()> {
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .reset_password(token, new_password)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .reset_password(token, new_password)
+                    .await
+            }
+        }
+    }
+
+    /// Подтверждает email по токену из письма. Токен одноразовый.
+    ///
+    /// Статус подтверждения хранится в access-токене, поэтому при наличии
+    /// refresh-токена клиент сразу получает новую пару токенов.
+    pub async fn verify_email(&mut self, token: &str) -> BlogClientResult<User> {
+        let user = match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .verify_email(token)
+                    .await?
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .verify_email(token)
+                    .await?
+            }
+        };
+
+        if self.refresh_token.is_some() {
+            self.refresh().await?;
+        }
+        Ok(user)
+    }
+
+    /// Повторно отправляет письмо подтверждения email текущему пользователю.
+    pub async fn resend_verification_email(&mut self) -> BlogClientResult<()> {
+        let result = self.try_resend_verification_email().await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_resend_verification_email().await;
+        }
+        result
+    }
+
+    async fn try_resend_verification_email(&self) -> BlogClientResult<()> {
         let token = self.require_token()?;
         match &self.transport {
             Transport::Http(_) => {
@@ -243,7 +508,7 @@ impl BlogClient {
                             "http client is not initialized".to_string(),
                         )
                     })?
-                    .delete_post(token, id)
+                    .resend_verification_email(token)
                     .await
             }
             Transport::Grpc(_) => {
@@ -254,14 +519,154 @@ impl BlogClient {
                             "grpc client is not initialized".to_string(),
                         )
                     })?
-                    .delete_post(token, id)
+                    .resend_verification_email(token)
                     .await
             }
         }
     }
 
-    /// Возвращает список постов с пагинацией `limit/offset`.
-    pub async fn list_posts(&self, limit: u32, offset: u32) -> BlogClientResult<ListPostsResponse> {
+    /// Начинает подключение TOTP: возвращает секрет и `otpauth://` URI.
+    /// Второй фактор заработает после `confirm_totp_enrollment`.
+    pub async fn start_totp_enrollment(&mut self) -> BlogClientResult<TotpEnrollment> {
+        let result = self.try_start_totp_enrollment().await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_start_totp_enrollment().await;
+        }
+        result
+    }
+
+    async fn try_start_totp_enrollment(&self) -> BlogClientResult<TotpEnrollment> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .start_totp_enrollment(token)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .start_totp_enrollment(token)
+                    .await
+            }
+        }
+    }
+
+    /// Подтверждает подключение TOTP первым кодом из аутентификатора.
+    ///
+    /// Возвращает одноразовые коды восстановления; сервер их больше не покажет.
+    pub async fn confirm_totp_enrollment(&mut self, code: &str) -> BlogClientResult<Vec<String>> {
+        let result = self.try_confirm_totp_enrollment(code).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_confirm_totp_enrollment(code).await;
+        }
+        result
+    }
+
+    async fn try_confirm_totp_enrollment(&self, code: &str) -> BlogClientResult<Vec<String>> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .confirm_totp_enrollment(token, code)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .confirm_totp_enrollment(token, code)
+                    .await
+            }
+        }
+    }
+
+    /// Отключает TOTP по коду из аутентификатора или коду восстановления.
+    pub async fn disable_totp(&mut self, code: &str) -> BlogClientResult<()> {
+        let result = self.try_disable_totp(code).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_disable_totp(code).await;
+        }
+        result
+    }
+
+    async fn try_disable_totp(&self, code: &str) -> BlogClientResult<()> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .disable_totp(token, code)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .disable_totp(token, code)
+                    .await
+            }
+        }
+    }
+
+    /// Выпускает персональный токен доступа для скриптов и CI.
+    ///
+    /// Секрет из ответа показывается один раз; передайте его в `set_token`
+    /// другого клиента. Требует сессию: токеном доступа нельзя выпускать токены.
+    pub async fn create_personal_token(
+        &mut self,
+        name: &str,
+        scopes: &[String],
+        expires_in_days: Option<u32>,
+    ) -> BlogClientResult<CreatedPersonalToken> {
+        let result = self
+            .try_create_personal_token(name, scopes, expires_in_days)
+            .await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self
+                .try_create_personal_token(name, scopes, expires_in_days)
+                .await;
+        }
+        result
+    }
+
+    async fn try_create_personal_token(
+        &self,
+        name: &str,
+        scopes: &[String],
+        expires_in_days: Option<u32>,
+    ) -> BlogClientResult<CreatedPersonalToken> {
+        let token = self.require_token()?;
         match &self.transport {
             Transport::Http(_) => {
                 self.http_client
@@ -271,7 +676,7 @@ impl BlogClient {
                             "http client is not initialized".to_string(),
                         )
                     })?
-                    .list_posts(limit, offset)
+                    .create_personal_token(token, name, scopes, expires_in_days)
                     .await
             }
             Transport::Grpc(_) => {
@@ -282,12 +687,1258 @@ impl BlogClient {
                             "grpc client is not initialized".to_string(),
                         )
                     })?
-                    .list_posts(limit, offset)
+                    .create_personal_token(token, name, scopes, expires_in_days)
                     .await
             }
         }
     }
 
+    /// Возвращает активные токены доступа пользователя. Требует сессию.
+    pub async fn list_personal_tokens(&mut self) -> BlogClientResult<Vec<PersonalToken>> {
+        let result = self.try_list_personal_tokens().await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_list_personal_tokens().await;
+        }
+        result
+    }
+
+    async fn try_list_personal_tokens(&self) -> BlogClientResult<Vec<PersonalToken>> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_personal_tokens(token)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_personal_tokens(token)
+                    .await
+            }
+        }
+    }
+
+    /// Отзывает токен доступа. Требует сессию.
+    pub async fn revoke_personal_token(&mut self, id: i64) -> BlogClientResult<()> {
+        let result = self.try_revoke_personal_token(id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_revoke_personal_token(id).await;
+        }
+        result
+    }
+
+    async fn try_revoke_personal_token(&self, id: i64) -> BlogClientResult<()> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .revoke_personal_token(token, id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .revoke_personal_token(token, id)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает активные сессии пользователя: устройства, с которых выполнен вход.
+    /// Требует сессию.
+    pub async fn list_sessions(&mut self) -> BlogClientResult<Vec<Session>> {
+        let result = self.try_list_sessions().await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_list_sessions().await;
+        }
+        result
+    }
+
+    async fn try_list_sessions(&self) -> BlogClientResult<Vec<Session>> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_sessions(token)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_sessions(token)
+                    .await
+            }
+        }
+    }
+
+    /// Завершает сессию: её токены перестают приниматься. Требует сессию.
+    pub async fn revoke_session(&mut self, id: i64) -> BlogClientResult<()> {
+        let result = self.try_revoke_session(id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_revoke_session(id).await;
+        }
+        result
+    }
+
+    async fn try_revoke_session(&self, id: i64) -> BlogClientResult<()> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .revoke_session(token, id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .revoke_session(token, id)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает публичный профиль пользователя. Токен не требуется.
+    pub async fn get_profile(&self, username: &str) -> BlogClientResult<Profile> {
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .get_profile(username)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .get_profile(username)
+                    .await
+            }
+        }
+    }
+
+    /// Обновляет профиль текущего пользователя. Требует сессию.
+    pub async fn update_profile(&mut self, update: &ProfileUpdate) -> BlogClientResult<Profile> {
+        let result = self.try_update_profile(update).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_update_profile(update).await;
+        }
+        result
+    }
+
+    async fn try_update_profile(&self, update: &ProfileUpdate) -> BlogClientResult<Profile> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .update_profile(token, update)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .update_profile(token, update)
+                    .await
+            }
+        }
+    }
+
+    /// Создаёт и сразу публикует новый пост.
+    ///
+    /// Требует установленный JWT-токен.
+    pub async fn create_post(
+        &mut self,
+        title: &str,
+        content: &str,
+        format: ContentFormat,
+        tags: &[String],
+    ) -> BlogClientResult<Post> {
+        self.create_post_with_status(title, content, format, PostStatus::Published, tags, None)
+            .await
+    }
+
+    /// Создаёт черновик, видимый только автору.
+    ///
+    /// Требует установленный JWT-токен.
+    pub async fn create_draft(
+        &mut self,
+        title: &str,
+        content: &str,
+        format: ContentFormat,
+        tags: &[String],
+    ) -> BlogClientResult<Post> {
+        self.create_post_with_status(title, content, format, PostStatus::Draft, tags, None)
+            .await
+    }
+
+    /// Создаёт черновик, который сервер опубликует в `publish_at`.
+    ///
+    /// Время должно быть в будущем. Требует установленный JWT-токен.
+    pub async fn create_scheduled(
+        &mut self,
+        title: &str,
+        content: &str,
+        format: ContentFormat,
+        tags: &[String],
+        publish_at: chrono::DateTime<chrono::Utc>,
+    ) -> BlogClientResult<Post> {
+        self.create_post_with_status(
+            title,
+            content,
+            format,
+            PostStatus::Draft,
+            tags,
+            Some(publish_at),
+        )
+        .await
+    }
+
+    async fn create_post_with_status(
+        &mut self,
+        title: &str,
+        content: &str,
+        format: ContentFormat,
+        status: PostStatus,
+        tags: &[String],
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> BlogClientResult<Post> {
+        let result = self
+            .try_create_post(title, content, format, status, tags, publish_at)
+            .await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self
+                .try_create_post(title, content, format, status, tags, publish_at)
+                .await;
+        }
+        result
+    }
+
+    async fn try_create_post(
+        &self,
+        title: &str,
+        content: &str,
+        format: ContentFormat,
+        status: PostStatus,
+        tags: &[String],
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> BlogClientResult<Post> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .create_post(token, title, content, format, status, tags, publish_at)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .create_post(token, title, content, format, status, tags, publish_at)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает пост по идентификатору.
+    ///
+    /// Если токен установлен, автору доступны и его черновики.
+    pub async fn get_post(&self, id: i64) -> BlogClientResult<Post> {
+        let token = self.token.as_deref();
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .get_post(token, id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .get_post(token, id)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает пост по текущему или прежнему slug.
+    ///
+    /// Если токен установлен, автору доступны и его черновики.
+    pub async fn get_post_by_slug(&self, slug: &str) -> BlogClientResult<Post> {
+        let token = self.token.as_deref();
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .get_post_by_slug(token, slug)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .get_post_by_slug(token, slug)
+                    .await
+            }
+        }
+    }
+
+    /// Обновляет пост по идентификатору.
+    ///
+    /// `format: None` оставляет формат содержимого прежним.
+    /// `tags: None` оставляет теги без изменений, пустой срез снимает все теги.
+    /// `expected_version` — версия поста, которую правит клиент: если пост успели
+    /// изменить, возвращается `BlogClientError::Conflict`. Требует установленный JWT-токен.
+    pub async fn update_post(
+        &mut self,
+        id: i64,
+        title: &str,
+        content: &str,
+        format: Option<ContentFormat>,
+        tags: Option<&[String]>,
+        expected_version: Option<i64>,
+    ) -> BlogClientResult<Post> {
+        let result = self
+            .try_update_post(id, title, content, format, tags, expected_version)
+            .await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self
+                .try_update_post(id, title, content, format, tags, expected_version)
+                .await;
+        }
+        result
+    }
+
+    async fn try_update_post(
+        &self,
+        id: i64,
+        title: &str,
+        content: &str,
+        format: Option<ContentFormat>,
+        tags: Option<&[String]>,
+        expected_version: Option<i64>,
+    ) -> BlogClientResult<Post> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .update_post(token, id, title, content, format, tags, expected_version)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .update_post(token, id, title, content, format, tags, expected_version)
+                    .await
+            }
+        }
+    }
+
+    /// Перемещает пост в корзину; восстановить его можно через `restore_post`.
+    ///
+    /// С `expected_version` пост удаляется, только если его не меняли,
+    /// иначе `BlogClientError::Conflict`. Требует установленный JWT-токен.
+    pub async fn delete_post(
+        &mut self,
+        id: i64,
+        expected_version: Option<i64>,
+    ) -> BlogClientResult<()> {
+        let result = self.try_delete_post(id, expected_version).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_delete_post(id, expected_version).await;
+        }
+        result
+    }
+
+    async fn try_delete_post(
+        &self,
+        id: i64,
+        expected_version: Option<i64>,
+    ) -> BlogClientResult<()> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .delete_post(token, id, expected_version)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .delete_post(token, id, expected_version)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает посты из корзины, сначала удалённые последними.
+    ///
+    /// Посты в корзине окончательно удаляются сервером по истечении срока хранения.
+    /// Требует установленный JWT-токен.
+    pub async fn list_trash(
+        &mut self,
+        limit: u32,
+        offset: u32,
+    ) -> BlogClientResult<ListPostsResponse> {
+        let result = self.try_list_trash(limit, offset).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_list_trash(limit, offset).await;
+        }
+        result
+    }
+
+    async fn try_list_trash(&self, limit: u32, offset: u32) -> BlogClientResult<ListPostsResponse> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_trash(token, limit, offset)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_trash(token, limit, offset)
+                    .await
+            }
+        }
+    }
+
+    /// Восстанавливает пост из корзины с прежним статусом.
+    ///
+    /// Требует установленный JWT-токен.
+    pub async fn restore_post(&mut self, id: i64) -> BlogClientResult<Post> {
+        let result = self.try_restore_post(id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_restore_post(id).await;
+        }
+        result
+    }
+
+    async fn try_restore_post(&self, id: i64) -> BlogClientResult<Post> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .restore_post(token, id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .restore_post(token, id)
+                    .await
+            }
+        }
+    }
+
+    /// Окончательно удаляет пост из корзины вместе с комментариями.
+    ///
+    /// Требует установленный JWT-токен.
+    pub async fn purge_post(&mut self, id: i64) -> BlogClientResult<()> {
+        let result = self.try_purge_post(id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_purge_post(id).await;
+        }
+        result
+    }
+
+    async fn try_purge_post(&self, id: i64) -> BlogClientResult<()> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .purge_post(token, id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .purge_post(token, id)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает список постов с пагинацией `limit/offset`.
+    ///
+    /// Если токен установлен, в выборку попадают и собственные черновики.
+    /// Непустой `tags` оставляет посты, у которых есть все перечисленные теги,
+    /// `author` — только посты пользователя с этим логином.
+    pub async fn list_posts(
+        &self,
+        tags: &[String],
+        author: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> BlogClientResult<ListPostsResponse> {
+        let token = self.token.as_deref();
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_posts(token, tags, author, limit, offset)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_posts(token, tags, author, limit, offset)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает страницу постов в режиме курсора.
+    ///
+    /// `cursor` — `next_cursor` из предыдущего ответа, `None` — первая страница.
+    /// В отличие от `limit/offset`, посты не пропускаются и не повторяются,
+    /// если между запросами появились новые.
+    pub async fn list_posts_by_cursor(
+        &self,
+        tags: &[String],
+        author: Option<&str>,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> BlogClientResult<ListPostsResponse> {
+        let Some(cursor) = cursor else {
+            return self.list_posts(tags, author, limit, 0).await;
+        };
+        let token = self.token.as_deref();
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_posts_by_cursor(token, tags, author, cursor, limit)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_posts_by_cursor(token, tags, author, cursor, limit)
+                    .await
+            }
+        }
+    }
+
+    /// Полнотекстовый поиск по заголовкам и содержимому постов.
+    ///
+    /// Результаты упорядочены по релевантности; с токеном ищет и по собственным черновикам.
+    pub async fn search_posts(
+        &self,
+        query: &str,
+        limit: u32,
+        offset: u32,
+    ) -> BlogClientResult<SearchPostsResponse> {
+        let token = self.token.as_deref();
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .search_posts(token, query, limit, offset)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .search_posts(token, query, limit, offset)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает дерево комментариев поста.
+    ///
+    /// Если токен установлен, автору доступны комментарии к его черновикам.
+    pub async fn list_comments(&self, post_id: i64) -> BlogClientResult<ListCommentsResponse> {
+        let token = self.token.as_deref();
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_comments(token, post_id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_comments(token, post_id)
+                    .await
+            }
+        }
+    }
+
+    /// Добавляет комментарий к посту; с `parent_id` — ответ на комментарий.
+    ///
+    /// Требует установленный JWT-токен.
+    pub async fn create_comment(
+        &mut self,
+        post_id: i64,
+        content: &str,
+        parent_id: Option<i64>,
+    ) -> BlogClientResult<Comment> {
+        let result = self.try_create_comment(post_id, content, parent_id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_create_comment(post_id, content, parent_id).await;
+        }
+        result
+    }
+
+    async fn try_create_comment(
+        &self,
+        post_id: i64,
+        content: &str,
+        parent_id: Option<i64>,
+    ) -> BlogClientResult<Comment> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .create_comment(token, post_id, content, parent_id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .create_comment(token, post_id, content, parent_id)
+                    .await
+            }
+        }
+    }
+
+    /// Удаляет комментарий вместе с ответами.
+    ///
+    /// Требует установленный JWT-токен автора комментария или автора поста.
+    pub async fn delete_comment(&mut self, post_id: i64, comment_id: i64) -> BlogClientResult<()> {
+        let result = self.try_delete_comment(post_id, comment_id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_delete_comment(post_id, comment_id).await;
+        }
+        result
+    }
+
+    async fn try_delete_comment(&self, post_id: i64, comment_id: i64) -> BlogClientResult<()> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .delete_comment(token, post_id, comment_id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .delete_comment(token, post_id, comment_id)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает историю правок поста от новых ревизий к старым.
+    ///
+    /// Требует установленный JWT-токен автора поста.
+    pub async fn list_revisions(&mut self, post_id: i64) -> BlogClientResult<Vec<RevisionSummary>> {
+        let result = self.try_list_revisions(post_id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_list_revisions(post_id).await;
+        }
+        result
+    }
+
+    async fn try_list_revisions(&self, post_id: i64) -> BlogClientResult<Vec<RevisionSummary>> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_revisions(token, post_id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_revisions(token, post_id)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает прежнюю версию поста по номеру ревизии.
+    ///
+    /// Требует установленный JWT-токен автора поста.
+    pub async fn get_revision(
+        &mut self,
+        post_id: i64,
+        revision: i32,
+    ) -> BlogClientResult<Revision> {
+        let result = self.try_get_revision(post_id, revision).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_get_revision(post_id, revision).await;
+        }
+        result
+    }
+
+    async fn try_get_revision(&self, post_id: i64, revision: i32) -> BlogClientResult<Revision> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .get_revision(token, post_id, revision)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .get_revision(token, post_id, revision)
+                    .await
+            }
+        }
+    }
+
+    /// Построчный diff содержимого между ревизиями; `to = None` — с текущей версией.
+    ///
+    /// Требует установленный JWT-токен автора поста.
+    pub async fn diff_revisions(
+        &mut self,
+        post_id: i64,
+        from: i32,
+        to: Option<i32>,
+    ) -> BlogClientResult<RevisionDiff> {
+        let result = self.try_diff_revisions(post_id, from, to).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_diff_revisions(post_id, from, to).await;
+        }
+        result
+    }
+
+    async fn try_diff_revisions(
+        &self,
+        post_id: i64,
+        from: i32,
+        to: Option<i32>,
+    ) -> BlogClientResult<RevisionDiff> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .diff_revisions(token, post_id, from, to)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .diff_revisions(token, post_id, from, to)
+                    .await
+            }
+        }
+    }
+
+    /// Восстанавливает ревизию новой правкой; текущая версия попадает в историю.
+    ///
+    /// Требует установленный JWT-токен автора поста.
+    pub async fn restore_revision(
+        &mut self,
+        post_id: i64,
+        revision: i32,
+    ) -> BlogClientResult<Post> {
+        let result = self.try_restore_revision(post_id, revision).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_restore_revision(post_id, revision).await;
+        }
+        result
+    }
+
+    async fn try_restore_revision(&self, post_id: i64, revision: i32) -> BlogClientResult<Post> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .restore_revision(token, post_id, revision)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .restore_revision(token, post_id, revision)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает теги с количеством опубликованных постов.
+    pub async fn list_tags(&self) -> BlogClientResult<Vec<Tag>> {
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_tags()
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .list_tags()
+                    .await
+            }
+        }
+    }
+
+    /// Публикует пост.
+    ///
+    /// Требует установленный JWT-токен.
+    pub async fn publish_post(&mut self, id: i64) -> BlogClientResult<Post> {
+        let result = self.try_publish_post(id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_publish_post(id).await;
+        }
+        result
+    }
+
+    async fn try_publish_post(&self, id: i64) -> BlogClientResult<Post> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .publish_post(token, id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .publish_post(token, id)
+                    .await
+            }
+        }
+    }
+
+    /// Возвращает опубликованный пост в черновики.
+    ///
+    /// Требует установленный JWT-токен.
+    pub async fn unpublish_post(&mut self, id: i64) -> BlogClientResult<Post> {
+        let result = self.try_unpublish_post(id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_unpublish_post(id).await;
+        }
+        result
+    }
+
+    async fn try_unpublish_post(&self, id: i64) -> BlogClientResult<Post> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .unpublish_post(token, id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .unpublish_post(token, id)
+                    .await
+            }
+        }
+    }
+
+    /// Переносит пост в архив.
+    ///
+    /// Требует установленный JWT-токен.
+    pub async fn archive_post(&mut self, id: i64) -> BlogClientResult<Post> {
+        let result = self.try_archive_post(id).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_archive_post(id).await;
+        }
+        result
+    }
+
+    async fn try_archive_post(&self, id: i64) -> BlogClientResult<Post> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .archive_post(token, id)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .archive_post(token, id)
+                    .await
+            }
+        }
+    }
+
+    /// Назначает время публикации черновика; `None` отменяет отложенную публикацию.
+    ///
+    /// Требует установленный JWT-токен.
+    pub async fn schedule_post(
+        &mut self,
+        id: i64,
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> BlogClientResult<Post> {
+        let result = self.try_schedule_post(id, publish_at).await;
+        if self.refresh_after_unauthorized(&result).await {
+            return self.try_schedule_post(id, publish_at).await;
+        }
+        result
+    }
+
+    async fn try_schedule_post(
+        &self,
+        id: i64,
+        publish_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> BlogClientResult<Post> {
+        let token = self.require_token()?;
+        match &self.transport {
+            Transport::Http(_) => {
+                self.http_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "http client is not initialized".to_string(),
+                        )
+                    })?
+                    .schedule_post(token, id, publish_at)
+                    .await
+            }
+            Transport::Grpc(_) => {
+                self.grpc_client
+                    .as_ref()
+                    .ok_or_else(|| {
+                        BlogClientError::InvalidRequest(
+                            "grpc client is not initialized".to_string(),
+                        )
+                    })?
+                    .schedule_post(token, id, publish_at)
+                    .await
+            }
+        }
+    }
+
+    /// Если запрос отклонён как неавторизованный или запрещённый и есть refresh-токен,
+    /// обновляет пару токенов. Возвращает `true`, если запрос стоит повторить.
+    ///
+    /// Отказ в доступе тоже повод обновиться: роль и подтверждение email берутся
+    /// из access-токена и могли измениться после его выдачи.
+    async fn refresh_after_unauthorized<T>(&mut self, result: &BlogClientResult<T>) -> bool {
+        let rejected = matches!(
+            result,
+            Err(BlogClientError::Unauthorized | BlogClientError::Forbidden(_))
+        );
+        if !rejected || self.refresh_token.is_none() {
+            return false;
+        }
+
+        match self.refresh().await {
+            Ok(_) => true,
+            Err(BlogClientError::Unauthorized) => {
+                // refresh-токен отозван или истёк — сессию не восстановить
+                self.clear_token();
+                false
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn store_tokens(&mut self, auth: &AuthResponse) {
+        self.token = Some(auth.access_token.clone());
+        self.refresh_token = Some(auth.refresh_token.clone());
+    }
+
     fn require_token(&self) -> BlogClientResult<&str> {
         self.token.as_deref().ok_or(BlogClientError::Unauthorized)
     }
@@ -10,25 +10,271 @@ pub struct User {
     pub username: String,
     /// Email.
     pub email: String,
+    /// Роль пользователя.
+    pub role: UserRole,
+    /// Когда подтверждён email; `None`, пока адрес не подтверждён.
+    #[serde(default)]
+    pub email_verified_at: Option<DateTime<Utc>>,
     /// Дата и время создания пользователя (UTC).
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Роль пользователя.
+pub enum UserRole {
+    /// Обычный пользователь, управляет только своими постами.
+    #[default]
+    User,
+    /// Модератор, может править и удалять чужие посты и комментарии.
+    Moderator,
+    /// Администратор, права модератора.
+    Admin,
+}
+
+impl std::fmt::Display for UserRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            UserRole::User => "user",
+            UserRole::Moderator => "moderator",
+            UserRole::Admin => "admin",
+        };
+        f.write_str(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Статус публикации поста.
+pub enum PostStatus {
+    /// Черновик, виден только автору.
+    Draft,
+    /// Опубликован, виден всем.
+    Published,
+    /// В архиве, виден только автору.
+    Archived,
+}
+
+impl std::fmt::Display for PostStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Published => "published",
+            PostStatus::Archived => "archived",
+        };
+        f.write_str(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Формат содержимого поста.
+pub enum ContentFormat {
+    /// Обычный текст.
+    #[default]
+    Plain,
+    /// Markdown; сервер отрисовывает его в HTML.
+    Markdown,
+}
+
+impl std::fmt::Display for ContentFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            ContentFormat::Plain => "plain",
+            ContentFormat::Markdown => "markdown",
+        };
+        f.write_str(value)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Публичная модель поста.
 pub struct Post {
     /// Идентификатор поста.
     pub id: i64,
+    /// Человекочитаемый адрес поста; старые серверы его не присылают.
+    #[serde(default)]
+    pub slug: String,
     /// Заголовок поста.
     pub title: String,
     /// Содержимое поста.
     pub content: String,
+    /// Формат содержимого.
+    #[serde(default)]
+    pub content_format: ContentFormat,
+    /// Содержимое, отрисованное сервером в очищенный HTML.
+    #[serde(default)]
+    pub content_html: String,
     /// Идентификатор автора.
     pub author_id: i64,
+    /// Статус публикации.
+    pub status: PostStatus,
+    /// Дата и время публикации (UTC), если пост опубликован.
+    pub published_at: Option<DateTime<Utc>>,
+    /// Запланированное время публикации черновика (UTC).
+    #[serde(default)]
+    pub publish_at: Option<DateTime<Utc>>,
+    /// Когда пост перемещён в корзину (UTC); `None` для обычных постов.
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Теги поста в нижнем регистре, отсортированные по имени.
+    pub tags: Vec<String>,
     /// Дата и время создания поста (UTC).
     pub created_at: DateTime<Utc>,
     /// Дата и время последнего обновления поста (UTC).
     pub updated_at: DateTime<Utc>,
+    /// Версия поста; передаётся как `expected_version` при изменении.
+    pub version: i64,
+    /// Краткие сведения об авторе; старые серверы их не присылают.
+    #[serde(default)]
+    pub author: Option<PostAuthor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Автор поста.
+pub struct PostAuthor {
+    /// Идентификатор пользователя.
+    pub id: i64,
+    /// Логин.
+    pub username: String,
+    /// Отображаемое имя, если задано.
+    pub display_name: Option<String>,
+}
+
+impl PostAuthor {
+    /// Отображаемое имя, а если его нет — логин.
+    pub fn name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.username)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Комментарий к посту вместе с ответами на него.
+pub struct Comment {
+    /// Идентификатор комментария.
+    pub id: i64,
+    /// Идентификатор поста.
+    pub post_id: i64,
+    /// Идентификатор автора комментария.
+    pub author_id: i64,
+    /// Идентификатор родительского комментария, `None` для верхнего уровня.
+    pub parent_id: Option<i64>,
+    /// Текст комментария.
+    pub content: String,
+    /// Дата и время создания (UTC).
+    pub created_at: DateTime<Utc>,
+    /// Дата и время последнего обновления (UTC).
+    pub updated_at: DateTime<Utc>,
+    /// Ответы на комментарий; пусто у только что созданного комментария.
+    pub replies: Vec<Comment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Дерево комментариев поста.
+pub struct ListCommentsResponse {
+    /// Комментарии верхнего уровня с вложенными ответами.
+    pub comments: Vec<Comment>,
+    /// Общее количество комментариев с учётом ответов.
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Пост, найденный полнотекстовым поиском.
+pub struct SearchHit {
+    /// Найденный пост.
+    pub post: Post,
+    /// Релевантность: чем больше, тем выше пост в выдаче.
+    pub rank: f32,
+    /// HTML-экранированный фрагмент содержимого, совпадения обёрнуты в `<mark>`.
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Результаты поиска с параметрами пагинации.
+pub struct SearchPostsResponse {
+    /// Найденные посты по убыванию релевантности.
+    pub results: Vec<SearchHit>,
+    /// Размер страницы.
+    pub limit: u32,
+    /// Смещение от начала выборки.
+    pub offset: u32,
+    /// Общее количество найденных постов.
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Краткие сведения о ревизии поста.
+pub struct RevisionSummary {
+    /// Номер ревизии в пределах поста, начиная с 1.
+    pub revision: i32,
+    /// Заголовок поста в этой ревизии.
+    pub title: String,
+    /// Идентификатор пользователя, чья правка заменила эту версию.
+    pub editor_id: i64,
+    /// Когда версия была заменена правкой (UTC).
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Прежняя версия поста.
+pub struct Revision {
+    /// Идентификатор поста.
+    pub post_id: i64,
+    /// Номер ревизии в пределах поста, начиная с 1.
+    pub revision: i32,
+    /// Заголовок поста в этой ревизии.
+    pub title: String,
+    /// Содержимое поста в этой ревизии.
+    pub content: String,
+    /// Идентификатор пользователя, чья правка заменила эту версию.
+    pub editor_id: i64,
+    /// Когда версия была заменена правкой (UTC).
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Вид строки в diff.
+pub enum DiffOp {
+    /// Строка есть в обеих версиях.
+    Equal,
+    /// Строка добавлена.
+    Insert,
+    /// Строка удалена.
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Строка построчного diff.
+pub struct DiffLine {
+    /// Вид изменения.
+    pub op: DiffOp,
+    /// Текст строки без перевода строки.
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Построчное сравнение содержимого двух версий поста.
+pub struct RevisionDiff {
+    /// Исходная ревизия.
+    pub from_revision: i32,
+    /// Целевая ревизия; `None` — текущая версия поста.
+    pub to_revision: Option<i32>,
+    /// Заголовок в исходной ревизии.
+    pub from_title: String,
+    /// Заголовок в целевой версии.
+    pub to_title: String,
+    /// Строки содержимого с пометками изменений.
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Тег с количеством опубликованных постов.
+pub struct Tag {
+    /// Имя тега.
+    pub name: String,
+    /// Количество опубликованных постов с этим тегом.
+    pub post_count: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,10 +282,107 @@ pub struct Post {
 pub struct AuthResponse {
     /// JWT access token.
     pub access_token: String,
+    /// Refresh-токен для получения новой пары токенов.
+    pub refresh_token: String,
     /// Данные пользователя.
     pub user: User,
 }
 
+/// Итог входа по паролю на уровне транспорта.
+#[derive(Debug, Clone)]
+pub(crate) enum LoginOutcome {
+    Authenticated(AuthResponse),
+    /// У пользователя включён TOTP: токен обменивается на сессию вместе с кодом.
+    MfaRequired {
+        mfa_token: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Начатое подключение TOTP.
+pub struct TotpEnrollment {
+    /// Секрет в base32 для ручного ввода в аутентификатор.
+    pub secret: String,
+    /// `otpauth://` URI, который можно показать QR-кодом.
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Персональный токен доступа без секрета.
+pub struct PersonalToken {
+    /// Идентификатор токена.
+    pub id: i64,
+    /// Имя, уникальное среди активных токенов пользователя.
+    pub name: String,
+    /// Права: `posts:read`, `posts:write`, `comments:write`.
+    pub scopes: Vec<String>,
+    /// Когда токен истекает; `None` — бессрочный.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Когда токен последний раз использовался (с точностью до минуты).
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Дата и время создания (UTC).
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Только что созданный токен доступа.
+pub struct CreatedPersonalToken {
+    /// Описание токена.
+    pub token: PersonalToken,
+    /// Секрет для `Authorization: Bearer`; сервер показывает его только один раз.
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Активная сессия: вход с одного устройства.
+pub struct Session {
+    /// Идентификатор сессии.
+    pub id: i64,
+    /// `User-Agent` клиента при последнем входе или обновлении токенов.
+    pub user_agent: Option<String>,
+    /// IP-адрес клиента при последнем входе или обновлении токенов.
+    pub ip_address: Option<String>,
+    /// Дата и время входа (UTC).
+    pub created_at: DateTime<Utc>,
+    /// Последняя активность сессии (UTC).
+    pub last_seen_at: DateTime<Utc>,
+    /// Сессия, из которой сделан запрос.
+    pub current: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Публичный профиль пользователя.
+pub struct Profile {
+    /// Идентификатор пользователя.
+    pub id: i64,
+    /// Логин.
+    pub username: String,
+    /// Отображаемое имя.
+    pub display_name: Option<String>,
+    /// Короткий рассказ о себе.
+    pub bio: Option<String>,
+    /// Личный сайт.
+    pub website: Option<String>,
+    /// Ссылка на аватар.
+    pub avatar_url: Option<String>,
+    /// Дата и время регистрации (UTC).
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+/// Частичное обновление профиля: `None` оставляет поле как есть,
+/// пустая строка очищает его.
+pub struct ProfileUpdate {
+    /// Отображаемое имя, до 64 символов.
+    pub display_name: Option<String>,
+    /// О себе, до 500 символов.
+    pub bio: Option<String>,
+    /// Ссылка http(s) на личный сайт.
+    pub website: Option<String>,
+    /// Ссылка http(s) на аватар.
+    pub avatar_url: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Ответ списка постов с параметрами пагинации.
 pub struct ListPostsResponse {
@@ -51,4 +394,6 @@ pub struct ListPostsResponse {
     pub offset: u32,
     /// Общее количество постов.
     pub total: u64,
+    /// Курсор следующей страницы для `list_posts_by_cursor`; `None` на последней странице.
+    pub next_cursor: Option<String>,
 }
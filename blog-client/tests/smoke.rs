@@ -1,6 +1,6 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use blog_client::{BlogClient, BlogClientError, Transport};
+use blog_client::{BlogClient, BlogClientError, ContentFormat, PostStatus, Transport};
 
 fn unique_suffix() -> String {
     let nanos = SystemTime::now()
@@ -39,10 +39,18 @@ async fn http_smoke_flow() {
     assert!(client.get_token().is_some());
 
     let created = client
-        .create_post("http title", "http content")
+        .create_post(
+            "http title",
+            "http **content**",
+            ContentFormat::Markdown,
+            &["Smoke".to_string()],
+        )
         .await
         .expect("create_post must succeed");
     assert_eq!(created.title, "http title");
+    assert_eq!(created.content_format, ContentFormat::Markdown);
+    assert!(created.content_html.contains("<strong>content</strong>"));
+    assert_eq!(created.tags, vec!["smoke".to_string()]);
 
     let fetched = client
         .get_post(created.id)
@@ -50,26 +58,91 @@ async fn http_smoke_flow() {
         .expect("get_post must succeed");
     assert_eq!(fetched.id, created.id);
 
+    let publish_at = chrono::Utc::now() + chrono::Duration::hours(1);
+    let scheduled = client
+        .create_scheduled(
+            "http scheduled",
+            "http later",
+            ContentFormat::Plain,
+            &[],
+            publish_at,
+        )
+        .await
+        .expect("create_scheduled must succeed");
+    assert_eq!(scheduled.status, PostStatus::Draft);
+    assert_eq!(
+        scheduled.publish_at.map(|at| at.timestamp()),
+        Some(publish_at.timestamp())
+    );
+    let unscheduled = client
+        .schedule_post(scheduled.id, None)
+        .await
+        .expect("schedule_post must succeed");
+    assert_eq!(unscheduled.publish_at, None);
+
     let listed = client
-        .list_posts(20, 0)
+        .list_posts(&["smoke".to_string()], None, 20, 0)
         .await
         .expect("list_posts must succeed");
     assert!(listed.posts.iter().any(|post| post.id == created.id));
 
     let updated = client
-        .update_post(created.id, "http title updated", "http content updated")
+        .update_post(
+            created.id,
+            "http title updated",
+            "http content updated",
+            None,
+            None,
+            Some(created.version),
+        )
         .await
         .expect("update_post must succeed");
     assert_eq!(updated.id, created.id);
     assert_eq!(updated.title, "http title updated");
+    assert_eq!(updated.content_format, ContentFormat::Markdown);
+
+    // прежний slug после смены заголовка ведёт на тот же пост
+    assert_ne!(updated.slug, created.slug);
+    let by_old_slug = client
+        .get_post_by_slug(&created.slug)
+        .await
+        .expect("get_post_by_slug must succeed");
+    assert_eq!(by_old_slug.id, created.id);
+    assert_eq!(by_old_slug.slug, updated.slug);
+
+    let stale = client.delete_post(created.id, Some(created.version)).await;
+    assert!(matches!(stale, Err(BlogClientError::Conflict(_))));
 
     client
-        .delete_post(created.id)
+        .delete_post(created.id, Some(updated.version))
         .await
         .expect("delete_post must succeed");
 
     let after_delete = client.get_post(created.id).await;
     assert!(matches!(after_delete, Err(BlogClientError::NotFound)));
+
+    let trash = client
+        .list_trash(10, 0)
+        .await
+        .expect("list_trash must succeed");
+    assert!(trash.posts.iter().any(|post| post.id == created.id));
+
+    let restored = client
+        .restore_post(created.id)
+        .await
+        .expect("restore_post must succeed");
+    assert!(restored.deleted_at.is_none());
+
+    client
+        .delete_post(created.id, None)
+        .await
+        .expect("delete_post must succeed");
+    client
+        .purge_post(created.id)
+        .await
+        .expect("purge_post must succeed");
+    let after_purge = client.restore_post(created.id).await;
+    assert!(matches!(after_purge, Err(BlogClientError::NotFound)));
 }
 
 #[tokio::test]
@@ -101,10 +174,18 @@ async fn grpc_smoke_flow() {
     assert!(client.get_token().is_some());
 
     let created = client
-        .create_post("grpc title", "grpc content")
+        .create_post(
+            "grpc title",
+            "grpc **content**",
+            ContentFormat::Markdown,
+            &["Smoke".to_string()],
+        )
         .await
         .expect("create_post must succeed");
     assert_eq!(created.title, "grpc title");
+    assert_eq!(created.content_format, ContentFormat::Markdown);
+    assert!(created.content_html.contains("<strong>content</strong>"));
+    assert_eq!(created.tags, vec!["smoke".to_string()]);
 
     let fetched = client
         .get_post(created.id)
@@ -112,24 +193,89 @@ async fn grpc_smoke_flow() {
         .expect("get_post must succeed");
     assert_eq!(fetched.id, created.id);
 
+    let publish_at = chrono::Utc::now() + chrono::Duration::hours(1);
+    let scheduled = client
+        .create_scheduled(
+            "grpc scheduled",
+            "grpc later",
+            ContentFormat::Plain,
+            &[],
+            publish_at,
+        )
+        .await
+        .expect("create_scheduled must succeed");
+    assert_eq!(scheduled.status, PostStatus::Draft);
+    assert_eq!(
+        scheduled.publish_at.map(|at| at.timestamp()),
+        Some(publish_at.timestamp())
+    );
+    let unscheduled = client
+        .schedule_post(scheduled.id, None)
+        .await
+        .expect("schedule_post must succeed");
+    assert_eq!(unscheduled.publish_at, None);
+
     let listed = client
-        .list_posts(20, 0)
+        .list_posts(&["smoke".to_string()], None, 20, 0)
         .await
         .expect("list_posts must succeed");
     assert!(listed.posts.iter().any(|post| post.id == created.id));
 
     let updated = client
-        .update_post(created.id, "grpc title updated", "grpc content updated")
+        .update_post(
+            created.id,
+            "grpc title updated",
+            "grpc content updated",
+            None,
+            None,
+            Some(created.version),
+        )
         .await
         .expect("update_post must succeed");
     assert_eq!(updated.id, created.id);
     assert_eq!(updated.title, "grpc title updated");
+    assert_eq!(updated.content_format, ContentFormat::Markdown);
+
+    // прежний slug после смены заголовка ведёт на тот же пост
+    assert_ne!(updated.slug, created.slug);
+    let by_old_slug = client
+        .get_post_by_slug(&created.slug)
+        .await
+        .expect("get_post_by_slug must succeed");
+    assert_eq!(by_old_slug.id, created.id);
+    assert_eq!(by_old_slug.slug, updated.slug);
+
+    let stale = client.delete_post(created.id, Some(created.version)).await;
+    assert!(matches!(stale, Err(BlogClientError::Conflict(_))));
 
     client
-        .delete_post(created.id)
+        .delete_post(created.id, Some(updated.version))
         .await
         .expect("delete_post must succeed");
 
     let after_delete = client.get_post(created.id).await;
     assert!(matches!(after_delete, Err(BlogClientError::NotFound)));
+
+    let trash = client
+        .list_trash(10, 0)
+        .await
+        .expect("list_trash must succeed");
+    assert!(trash.posts.iter().any(|post| post.id == created.id));
+
+    let restored = client
+        .restore_post(created.id)
+        .await
+        .expect("restore_post must succeed");
+    assert!(restored.deleted_at.is_none());
+
+    client
+        .delete_post(created.id, None)
+        .await
+        .expect("delete_post must succeed");
+    client
+        .purge_post(created.id)
+        .await
+        .expect("purge_post must succeed");
+    let after_purge = client.restore_post(created.id).await;
+    assert!(matches!(after_purge, Err(BlogClientError::NotFound)));
 }
@@ -0,0 +1,14 @@
+
+ALTER TABLE posts
+    ADD COLUMN IF NOT EXISTS status VARCHAR(16) NOT NULL DEFAULT 'draft',
+    ADD COLUMN IF NOT EXISTS published_at TIMESTAMP WITH TIME ZONE;
+
+-- до появления статусов все посты были публичными
+UPDATE posts
+SET status = 'published',
+    published_at = created_at;
+
+ALTER TABLE posts
+    ADD CONSTRAINT posts_status_check CHECK (status IN ('draft', 'published', 'archived'));
+
+CREATE INDEX IF NOT EXISTS idx_posts_status_created_at ON posts(status, created_at DESC, id DESC);
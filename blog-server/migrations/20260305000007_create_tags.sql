@@ -0,0 +1,16 @@
+
+CREATE TABLE IF NOT EXISTS tags (
+    id BIGSERIAL PRIMARY KEY,
+    -- имя хранится уже нормализованным (нижний регистр, без пробелов по краям)
+    name VARCHAR(32) NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS post_tags (
+    post_id BIGINT NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    tag_id BIGINT NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (post_id, tag_id)
+);
+
+-- фильтрация ленты по тегу идёт от tag_id к post_id
+CREATE INDEX IF NOT EXISTS idx_post_tags_tag_id ON post_tags(tag_id);
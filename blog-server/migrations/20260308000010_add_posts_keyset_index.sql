@@ -0,0 +1,3 @@
+
+-- курсорная пагинация ленты идёт по (created_at, id) без фильтра по статусу
+CREATE INDEX IF NOT EXISTS idx_posts_created_at_id ON posts(created_at DESC, id DESC);
@@ -0,0 +1,3 @@
+
+-- номер версии поста для оптимистичных блокировок (ETag / If-Match)
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1;
@@ -0,0 +1,21 @@
+
+-- Персональные токены доступа для автоматизации (CI и скрипты)
+CREATE TABLE IF NOT EXISTS personal_access_tokens (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(64) NOT NULL,
+    -- храним только SHA-256 от токена: он показывается один раз при создании
+    token_hash VARCHAR(64) UNIQUE NOT NULL,
+    -- права токена, например {posts:write,comments:write}
+    scopes TEXT[] NOT NULL,
+    -- NULL — бессрочный токен
+    expires_at TIMESTAMP WITH TIME ZONE,
+    last_used_at TIMESTAMP WITH TIME ZONE,
+    revoked_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- имя различает токены в списке, поэтому уникально среди неотозванных
+CREATE UNIQUE INDEX IF NOT EXISTS idx_personal_access_tokens_user_name
+    ON personal_access_tokens(user_id, name)
+    WHERE revoked_at IS NULL;
@@ -0,0 +1,14 @@
+
+-- Сессии: один вход с устройства и все refresh-токены, полученные от него ротацией
+CREATE TABLE IF NOT EXISTS sessions (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- сессия жива, пока в её семье есть неотозванный и неистёкший refresh-токен
+    family_id VARCHAR(64) UNIQUE NOT NULL,
+    user_agent VARCHAR(256),
+    ip_address VARCHAR(45),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_user_id ON sessions(user_id);
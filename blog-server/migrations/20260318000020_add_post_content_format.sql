@@ -0,0 +1,19 @@
+
+-- формат исходного текста поста и HTML, отрисованный из него при сохранении
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS content_format VARCHAR(16) NOT NULL DEFAULT 'plain';
+ALTER TABLE posts
+    ADD CONSTRAINT posts_content_format_check CHECK (content_format IN ('plain', 'markdown'));
+
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS content_html TEXT;
+
+-- существующие посты — обычный текст: экранированный HTML с переносами строк через <br>,
+-- как его отрисовывает сервер
+UPDATE posts
+SET content_html = '<p>' || replace(
+    replace(replace(replace(replace(replace(replace(
+        content, E'\r\n', E'\n'),
+        '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;'),
+    E'\n', E'<br>\n') || '</p>'
+WHERE content_html IS NULL;
+
+ALTER TABLE posts ALTER COLUMN content_html SET NOT NULL;
@@ -0,0 +1,63 @@
+-- человекочитаемый адрес поста; генерируется из заголовка с транслитерацией кириллицы
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS slug VARCHAR(96);
+
+-- уникальность проверяется индексом уже во время заполнения; NULL ограничению не мешает
+ALTER TABLE posts ADD CONSTRAINT posts_slug_key UNIQUE (slug);
+
+-- существующие посты получают slug так же, как его строит сервер:
+-- транслитерация, всё кроме [a-z0-9] — дефис, не длиннее 80 символов;
+-- занятый адрес (в том числе чужой базовый slug вида foo-2) получает первый свободный суффикс
+DO $$
+DECLARE
+    post RECORD;
+    candidate TEXT;
+    n INTEGER;
+BEGIN
+    FOR post IN
+        WITH transliterated AS (
+            SELECT
+                id,
+                created_at,
+                trim(BOTH '-' FROM left(trim(BOTH '-' FROM regexp_replace(
+                    translate(
+                        replace(replace(replace(replace(replace(replace(replace(replace(replace(replace(replace(
+                            lower(title),
+                            'щ', 'shch'), 'ж', 'zh'), 'х', 'kh'), 'ц', 'ts'), 'ч', 'ch'), 'ш', 'sh'),
+                            'ю', 'yu'), 'я', 'ya'), 'ё', 'e'), 'ї', 'yi'), 'є', 'ye'),
+                        'абвгдезийклмнопрстуфыэіґъь',
+                        'abvgdeziyklmnoprstufyeig'),
+                    '[^a-z0-9]+', '-', 'g')), 80)) AS base
+            FROM posts
+            WHERE slug IS NULL
+        )
+        SELECT
+            id,
+            CASE
+                WHEN base = '' THEN 'post'
+                WHEN base ~ '^[0-9-]+$' THEN 'post-' || base
+                ELSE base
+            END AS base
+        FROM transliterated
+        ORDER BY created_at, id
+    LOOP
+        candidate := post.base;
+        n := 1;
+        WHILE EXISTS (SELECT 1 FROM posts WHERE slug = candidate) LOOP
+            n := n + 1;
+            candidate := post.base || '-' || n;
+        END LOOP;
+        UPDATE posts SET slug = candidate WHERE id = post.id;
+    END LOOP;
+END
+$$;
+
+ALTER TABLE posts ALTER COLUMN slug SET NOT NULL;
+
+-- прежние slug постов: старые ссылки после смены заголовка ведут на текущий адрес
+CREATE TABLE IF NOT EXISTS post_slug_redirects (
+    slug VARCHAR(96) PRIMARY KEY,
+    post_id BIGINT NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_post_slug_redirects_post_id ON post_slug_redirects(post_id);
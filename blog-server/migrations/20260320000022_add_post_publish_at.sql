@@ -0,0 +1,7 @@
+-- время отложенной публикации черновика; фоновая задача публикует пост, когда оно наступает
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS publish_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE posts
+    ADD CONSTRAINT posts_publish_at_draft_check CHECK (publish_at IS NULL OR status = 'draft');
+
+-- фоновая задача выбирает только запланированные посты
+CREATE INDEX IF NOT EXISTS idx_posts_publish_at ON posts(publish_at) WHERE publish_at IS NOT NULL;
@@ -0,0 +1,7 @@
+-- удалённый пост попадает в корзину: его можно восстановить, пока не истёк срок хранения
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP WITH TIME ZONE;
+
+-- корзина автора и фоновая очистка читают только удалённые посты
+CREATE INDEX IF NOT EXISTS idx_posts_trash ON posts(author_id, deleted_at DESC)
+    WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_posts_deleted_at ON posts(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,6 @@
+-- ленты отдают опубликованные посты в порядке публикации
+CREATE INDEX IF NOT EXISTS idx_posts_published_at_id ON posts(published_at DESC, id DESC)
+    WHERE status = 'published' AND deleted_at IS NULL;
+
+-- Last-Modified ленты — последнее изменение любого поста её области
+CREATE INDEX IF NOT EXISTS idx_posts_updated_at ON posts(updated_at DESC);
@@ -5,31 +5,154 @@ use argon2::{
         rand_core::OsRng,
     },
 };
+use std::sync::Arc;
 
+use chrono::{DateTime, Duration, Utc};
+use tracing::warn;
+
+use crate::application::login_throttle::{LoginThrottle, LoginThrottleSettings};
+use crate::application::session_service::SessionService;
+use crate::application::token_revocation_service::TokenRevocationService;
+use crate::data::email_verification_repository::{
+    EmailVerificationRepository, NewEmailVerificationToken,
+};
+use crate::data::mfa_repository::{MfaRepository, TotpRecord};
+use crate::data::password_reset_repository::{NewPasswordResetToken, PasswordResetRepository};
+use crate::data::refresh_token_repository::{NewRefreshToken, RefreshTokenRepository};
+use crate::data::session_repository::SessionRepository;
+use crate::data::token_revocation_repository::TokenRevocationRepository;
 use crate::data::user_repository::{NewUser, UserRepository};
 use crate::domain::error::DomainError;
-use crate::domain::user::{LoginRequest, RegisterRequest, User};
+use crate::domain::session::ClientInfo;
+use crate::domain::user::{
+    ChangePasswordRequest, LoginRequest, RegisterRequest, ResetPasswordRequest, User,
+    normalize_email,
+};
 use crate::infrastructure::jwt::JwtService;
+use crate::infrastructure::mailer::{EmailMessage, Mailer};
+use crate::infrastructure::opaque_token::{generate_opaque_token, hash_opaque_token};
+use crate::infrastructure::totp;
 
 #[derive(Debug, Clone)]
 pub(crate) struct AuthResult {
     pub(crate) user: User,
     pub(crate) access_token: String,
+    pub(crate) refresh_token: String,
+}
+
+/// Итог проверки пароля: либо готовая сессия, либо нужен второй фактор.
+#[derive(Debug, Clone)]
+pub(crate) enum LoginOutcome {
+    Authenticated(AuthResult),
+    /// Пароль верный, но включён TOTP: `mfa_token` обменивается на сессию
+    /// вместе с кодом через `complete_mfa_login`.
+    MfaRequired {
+        mfa_token: String,
+        expires_in: i64,
+    },
+}
+
+/// Начатое подключение TOTP: секрет для ручного ввода и URI для QR-кода.
+#[derive(Debug, Clone)]
+pub(crate) struct TotpEnrollment {
+    pub(crate) secret: String,
+    pub(crate) otpauth_uri: String,
+}
+
+/// Время жизни выдаваемых одноразовых токенов, в секундах.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AuthTokenTtls {
+    pub(crate) refresh_token_seconds: i64,
+    pub(crate) password_reset_seconds: i64,
+    pub(crate) email_verification_seconds: i64,
+    /// Сколько действует промежуточный токен между паролем и вторым фактором.
+    pub(crate) mfa_pending_seconds: i64,
 }
 
-pub(crate) struct AuthService<R: UserRepository> {
+pub(crate) struct AuthService<
+    R: UserRepository,
+    T: RefreshTokenRepository,
+    V: TokenRevocationRepository,
+    S: SessionRepository,
+    P: PasswordResetRepository,
+    E: EmailVerificationRepository,
+    F: MfaRepository,
+    M: Mailer,
+> {
     repo: R,
+    refresh_repo: T,
+    reset_repo: P,
+    verification_repo: E,
+    mfa_repo: F,
+    revocations: Arc<TokenRevocationService<V>>,
+    sessions: Arc<SessionService<S>>,
+    mailer: M,
     jwt: JwtService,
+    ttls: AuthTokenTtls,
+    login_throttle: LoginThrottle,
+    totp_issuer: String,
 }
 
-impl<R: UserRepository> AuthService<R> {
+impl<
+    R: UserRepository,
+    T: RefreshTokenRepository,
+    V: TokenRevocationRepository,
+    S: SessionRepository,
+    P: PasswordResetRepository,
+    E: EmailVerificationRepository,
+    F: MfaRepository,
+    M: Mailer,
+> AuthService<R, T, V, S, P, E, F, M>
+{
+    const RECOVERY_CODES_COUNT: usize = 10;
+    const DEFAULT_TOTP_ISSUER: &'static str = "rust-blog";
     const DUMMY_PASSWORD_HASH: &'static str = "$argon2id$v=19$m=19456,t=2,p=1$MDEyMzQ1Njc4OWFiY2RlZg$gwN6hT1sNdk9kI95f7n2Gl3fL0qRmBf2Ffkj2r90/0M";
 
-    pub(crate) fn new(repo: R, jwt: JwtService) -> Self {
-        Self { repo, jwt }
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        repo: R,
+        refresh_repo: T,
+        reset_repo: P,
+        verification_repo: E,
+        mfa_repo: F,
+        revocations: Arc<TokenRevocationService<V>>,
+        sessions: Arc<SessionService<S>>,
+        mailer: M,
+        jwt: JwtService,
+        ttls: AuthTokenTtls,
+    ) -> Self {
+        Self {
+            repo,
+            refresh_repo,
+            reset_repo,
+            verification_repo,
+            mfa_repo,
+            revocations,
+            sessions,
+            mailer,
+            jwt,
+            ttls,
+            login_throttle: LoginThrottle::new(LoginThrottleSettings::default()),
+            totp_issuer: Self::DEFAULT_TOTP_ISSUER.to_string(),
+        }
+    }
+
+    /// Имя сервиса, под которым учётная запись видна в приложении-аутентификаторе.
+    pub(crate) fn with_totp_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.totp_issuer = issuer.into();
+        self
+    }
+
+    pub(crate) fn with_login_throttle(mut self, settings: LoginThrottleSettings) -> Self {
+        self.login_throttle = LoginThrottle::new(settings);
+        self
     }
 
-    pub(crate) async fn register(&self, req: RegisterRequest) -> Result<AuthResult, DomainError> {
+    pub(crate) async fn register(
+        &self,
+        req: RegisterRequest,
+        client: ClientInfo,
+    ) -> Result<AuthResult, DomainError> {
         let req = req.validate()?;
 
         let password_hash = self.hash_password(&req.password)?;
@@ -37,18 +160,30 @@ impl<R: UserRepository> AuthService<R> {
         let new_user = Self::into_new_user(req, password_hash);
         let user = self.repo.create_user(new_user).await?;
 
-        let access_token = self
-            .jwt
-            .generate_token(user.id, &user.username)
-            .map_err(|err| DomainError::Unexpected(err.to_string()))?;
+        // письмо можно запросить повторно, поэтому сбой почты не отменяет регистрацию
+        if let Err(err) = self.send_verification_email(&user).await {
+            warn!(user_id = user.id, error = %err, "failed to send verification email");
+        }
 
-        Ok(AuthResult { user, access_token })
+        self.start_session(user, &client).await
     }
 
-    pub(crate) async fn login(&self, req: LoginRequest) -> Result<AuthResult, DomainError> {
+    /// Вход по логину и паролю.
+    ///
+    /// Неудачные попытки считаются по логину и по адресу клиента: пока любой
+    /// из них заблокирован, пароль не проверяется и возвращается `TooManyAttempts`.
+    /// Если у пользователя включён TOTP, вместо сессии выдаётся промежуточный токен.
+    pub(crate) async fn login(
+        &self,
+        req: LoginRequest,
+        client: ClientInfo,
+    ) -> Result<LoginOutcome, DomainError> {
         let req = req.validate()?;
+        let client_ip = client.ip;
         let username = req.username.to_string();
 
+        self.login_throttle.check(&username, client_ip)?;
+
         let user_creds = match self.repo.find_by_username(&username).await? {
             Some(user_creds) => user_creds,
             None => {
@@ -57,23 +192,515 @@ impl<R: UserRepository> AuthService<R> {
                     Ok(()) | Err(DomainError::InvalidCredentials) => {}
                     Err(err) => return Err(err),
                 }
+                // несуществующий логин учитываем так же, чтобы блокировка его не выдавала
+                self.login_throttle.record_failure(&username, client_ip);
                 return Err(DomainError::InvalidCredentials);
             }
         };
 
-        self.verify_password(&req.password, &user_creds.password_hash)?;
+        if let Err(err) = self.verify_password(&req.password, &user_creds.password_hash) {
+            if matches!(err, DomainError::InvalidCredentials) {
+                self.login_throttle.record_failure(&username, client_ip);
+            }
+            return Err(err);
+        }
+
+        let user = user_creds.user;
+        if self
+            .mfa_repo
+            .find_totp(user.id)
+            .await?
+            .is_some_and(|totp| totp.is_enabled())
+        {
+            // счётчик неудач не сбрасываем: вход ещё не завершён
+            let mfa_token = self
+                .jwt
+                .generate_mfa_token(&user, self.ttls.mfa_pending_seconds)
+                .map_err(|err| DomainError::Unexpected(err.to_string()))?;
+            return Ok(LoginOutcome::MfaRequired {
+                mfa_token,
+                expires_in: self.ttls.mfa_pending_seconds,
+            });
+        }
+
+        self.login_throttle.record_success(&username);
+        self.start_session(user, &client)
+            .await
+            .map(LoginOutcome::Authenticated)
+    }
 
-        let access_token = self
+    /// Второй шаг входа: промежуточный токен из `login` и код из аутентификатора
+    /// либо код восстановления. Неверные коды учитываются так же, как неверный пароль.
+    pub(crate) async fn complete_mfa_login(
+        &self,
+        mfa_token: &str,
+        code: &str,
+        client: ClientInfo,
+    ) -> Result<AuthResult, DomainError> {
+        let client_ip = client.ip;
+        let claims = self
             .jwt
-            .generate_token(user_creds.user.id, &user_creds.user.username)
-            .map_err(|err| DomainError::Unexpected(err.to_string()))?;
+            .verify_mfa_token(mfa_token.trim())
+            .map_err(|_| DomainError::InvalidCredentials)?;
+
+        self.login_throttle.check(&claims.username, client_ip)?;
+
+        let totp = self
+            .mfa_repo
+            .find_totp(claims.user_id)
+            .await?
+            .filter(TotpRecord::is_enabled)
+            .ok_or(DomainError::InvalidCredentials)?;
+        if !self
+            .verify_second_factor(claims.user_id, &totp, code)
+            .await?
+        {
+            self.login_throttle
+                .record_failure(&claims.username, client_ip);
+            return Err(DomainError::InvalidCredentials);
+        }
+        self.login_throttle.record_success(&claims.username);
+
+        let user = self
+            .repo
+            .find_by_id(claims.user_id)
+            .await?
+            .ok_or(DomainError::InvalidCredentials)?;
+        self.start_session(user, &client).await
+    }
+
+    /// Начинает подключение TOTP: создаёт новый секрет, заменяя неподтверждённый.
+    /// Второй фактор заработает только после `confirm_totp_enrollment`.
+    pub(crate) async fn start_totp_enrollment(
+        &self,
+        user_id: i64,
+    ) -> Result<TotpEnrollment, DomainError> {
+        let user = self
+            .repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(DomainError::NotFound(format!("user id: {user_id}")))?;
+
+        let secret = totp::generate_secret();
+        if !self.mfa_repo.set_pending_totp(user_id, &secret).await? {
+            return Err(DomainError::Validation {
+                field: "totp",
+                message: "is already enabled",
+            });
+        }
+
+        Ok(TotpEnrollment {
+            otpauth_uri: totp::otpauth_uri(&self.totp_issuer, &user.username, &secret),
+            secret,
+        })
+    }
+
+    /// Подтверждает подключение первым кодом из аутентификатора и включает TOTP.
+    ///
+    /// Возвращает коды восстановления: в БД остаются только их хэши,
+    /// поэтому показать их повторно нельзя.
+    pub(crate) async fn confirm_totp_enrollment(
+        &self,
+        user_id: i64,
+        code: &str,
+    ) -> Result<Vec<String>, DomainError> {
+        let totp = self
+            .mfa_repo
+            .find_totp(user_id)
+            .await?
+            .filter(|totp| !totp.is_enabled())
+            .ok_or(DomainError::Validation {
+                field: "totp",
+                message: "enrollment is not started",
+            })?;
+
+        let step = totp::verify_code(&totp.secret, code, Utc::now().timestamp())
+            .ok_or(Self::invalid_code())?;
+        if !self.mfa_repo.mark_step_used(user_id, step).await? {
+            return Err(Self::invalid_code());
+        }
+
+        let recovery_codes: Vec<String> = (0..Self::RECOVERY_CODES_COUNT)
+            .map(|_| totp::generate_recovery_code())
+            .collect();
+        let hashes: Vec<String> = recovery_codes
+            .iter()
+            .map(|code| hash_opaque_token(&totp::normalize_recovery_code(code)))
+            .collect();
+        if !self.mfa_repo.enable_totp(user_id, &hashes).await? {
+            // параллельное подтверждение успело раньше
+            return Err(DomainError::Validation {
+                field: "totp",
+                message: "is already enabled",
+            });
+        }
+
+        Ok(recovery_codes)
+    }
+
+    /// Отключает TOTP после проверки текущего кода или кода восстановления.
+    pub(crate) async fn disable_totp(&self, user_id: i64, code: &str) -> Result<(), DomainError> {
+        let totp = self
+            .mfa_repo
+            .find_totp(user_id)
+            .await?
+            .filter(TotpRecord::is_enabled)
+            .ok_or(DomainError::Validation {
+                field: "totp",
+                message: "is not enabled",
+            })?;
+
+        // неверный код — ошибка запроса, а не сессии: на 401 клиенты обновляют токен
+        if !self.verify_second_factor(user_id, &totp, code).await? {
+            return Err(Self::invalid_code());
+        }
+
+        self.mfa_repo.disable_totp(user_id).await
+    }
+
+    /// Принимает код TOTP (каждый шаг один раз) или неиспользованный код восстановления.
+    async fn verify_second_factor(
+        &self,
+        user_id: i64,
+        totp: &TotpRecord,
+        code: &str,
+    ) -> Result<bool, DomainError> {
+        let code = code.trim();
+        if totp::is_totp_code(code) {
+            return match totp::verify_code(&totp.secret, code, Utc::now().timestamp()) {
+                Some(step) => self.mfa_repo.mark_step_used(user_id, step).await,
+                None => Ok(false),
+            };
+        }
+
+        let code = totp::normalize_recovery_code(code);
+        if code.is_empty() {
+            return Ok(false);
+        }
+        self.mfa_repo
+            .consume_recovery_code(user_id, &hash_opaque_token(&code))
+            .await
+    }
+
+    fn invalid_code() -> DomainError {
+        DomainError::Validation {
+            field: "code",
+            message: "is invalid",
+        }
+    }
+
+    /// Обменивает refresh-токен на новую пару токенов.
+    ///
+    /// Использованный токен отзывается. Повторное предъявление уже отозванного
+    /// токена считается признаком утечки: отзывается вся семья токенов.
+    pub(crate) async fn refresh(
+        &self,
+        refresh_token: &str,
+        client: ClientInfo,
+    ) -> Result<AuthResult, DomainError> {
+        let token_hash = hash_opaque_token(refresh_token.trim());
+        let record = self
+            .refresh_repo
+            .find_by_hash(&token_hash)
+            .await?
+            .ok_or(DomainError::InvalidCredentials)?;
+
+        if record.revoked_at.is_some() {
+            warn!(
+                user_id = record.user_id,
+                "refresh token reuse detected, revoking token family"
+            );
+            self.refresh_repo.revoke_family(&record.family_id).await?;
+            return Err(DomainError::InvalidCredentials);
+        }
+        if record.expires_at <= Utc::now() {
+            return Err(DomainError::InvalidCredentials);
+        }
+
+        let user = self
+            .repo
+            .find_by_id(record.user_id)
+            .await?
+            .ok_or(DomainError::InvalidCredentials)?;
+
+        let (refresh_token, next) = self.new_refresh_token(user.id, record.family_id.clone());
+        if !self
+            .refresh_repo
+            .rotate_refresh_token(record.id, next)
+            .await?
+        {
+            // токен успели ротировать параллельно — тоже считаем повтором
+            warn!(
+                user_id = record.user_id,
+                "concurrent refresh token reuse detected, revoking token family"
+            );
+            self.refresh_repo.revoke_family(&record.family_id).await?;
+            return Err(DomainError::InvalidCredentials);
+        }
+
+        let session_id = self
+            .sessions
+            .record_refresh(&record.family_id, &client)
+            .await?;
+        let access_token = self.issue_access_token(&user, session_id)?;
 
         Ok(AuthResult {
-            user: user_creds.user,
+            user,
             access_token,
+            refresh_token,
         })
     }
 
+    /// Отзывает текущий access-токен и сессию, в которой он выдан, а если передан
+    /// refresh-токен — ещё и всю его семью (для токенов, выданных до появления сессий).
+    pub(crate) async fn logout(
+        &self,
+        user_id: i64,
+        jti: &str,
+        token_expires_at: i64,
+        session_id: Option<i64>,
+        refresh_token: Option<&str>,
+    ) -> Result<(), DomainError> {
+        let expires_at = DateTime::from_timestamp(token_expires_at, 0)
+            .ok_or_else(|| DomainError::Unexpected("invalid token exp".to_string()))?;
+        self.revocations
+            .revoke_token(jti, user_id, expires_at)
+            .await?;
+
+        if let Some(session_id) = session_id {
+            // сессию могли уже отозвать с другого устройства
+            match self.sessions.revoke_session(user_id, session_id).await {
+                Ok(()) | Err(DomainError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        if let Some(refresh_token) = refresh_token {
+            let token_hash = hash_opaque_token(refresh_token.trim());
+            // чужой или неизвестный refresh-токен молча игнорируем
+            if let Some(record) = self.refresh_repo.find_by_hash(&token_hash).await?
+                && record.user_id == user_id
+            {
+                self.refresh_repo.revoke_family(&record.family_id).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Завершает все сессии пользователя: выданные ранее access-токены
+    /// перестают приниматься, все refresh-токены отзываются.
+    pub(crate) async fn logout_all(&self, user_id: i64) -> Result<(), DomainError> {
+        self.revocations.revoke_all_for_user(user_id).await?;
+        self.refresh_repo.revoke_all_for_user(user_id).await
+    }
+
+    /// Меняет пароль после проверки текущего. Все сессии пользователя, включая
+    /// текущую, завершаются: дальше нужно войти с новым паролем.
+    pub(crate) async fn change_password(
+        &self,
+        user_id: i64,
+        req: ChangePasswordRequest,
+    ) -> Result<(), DomainError> {
+        let req = req.validate()?;
+        let user_creds = self
+            .repo
+            .find_credentials_by_id(user_id)
+            .await?
+            .ok_or(DomainError::InvalidCredentials)?;
+
+        // неверный текущий пароль — ошибка запроса, а не сессии:
+        // на 401 клиенты пытаются обновить токен
+        self.verify_password(&req.current_password, &user_creds.password_hash)
+            .map_err(|err| match err {
+                DomainError::InvalidCredentials => DomainError::Validation {
+                    field: "current_password",
+                    message: "is incorrect",
+                },
+                err => err,
+            })?;
+
+        self.set_password(user_id, &req.new_password).await
+    }
+
+    /// Отправляет на почту одноразовый токен сброса пароля. Для незарегистрированного
+    /// адреса ничего не делает, чтобы ответ не выдавал, есть ли такой пользователь.
+    pub(crate) async fn request_password_reset(&self, email: &str) -> Result<(), DomainError> {
+        let email = normalize_email(email)?;
+        let Some(user_creds) = self.repo.find_by_email(&email).await? else {
+            return Ok(());
+        };
+
+        let token = generate_opaque_token();
+        self.reset_repo
+            .create_reset_token(NewPasswordResetToken {
+                user_id: user_creds.user.id,
+                token_hash: hash_opaque_token(&token),
+                expires_at: Utc::now() + Duration::seconds(self.ttls.password_reset_seconds),
+            })
+            .await?;
+
+        self.mailer
+            .send(self.password_reset_email(&user_creds.user, &token))
+            .await
+    }
+
+    /// Задаёт новый пароль по токену из письма и завершает все сессии пользователя.
+    pub(crate) async fn reset_password(
+        &self,
+        req: ResetPasswordRequest,
+    ) -> Result<(), DomainError> {
+        let req = req.validate()?;
+        let user_id = self
+            .reset_repo
+            .consume_reset_token(&hash_opaque_token(&req.token))
+            .await?
+            .ok_or(DomainError::Validation {
+                field: "token",
+                message: "is invalid or expired",
+            })?;
+
+        self.set_password(user_id, &req.new_password).await
+    }
+
+    /// Подтверждает email по токену из письма. Токен одноразовый.
+    ///
+    /// Уже выданные access-токены несут старый статус: новый попадёт в токен
+    /// при следующем login/refresh.
+    pub(crate) async fn verify_email(&self, token: &str) -> Result<User, DomainError> {
+        let token = token.trim();
+        let user_id = self
+            .verification_repo
+            .consume_verification_token(&hash_opaque_token(token))
+            .await?
+            .ok_or(DomainError::Validation {
+                field: "token",
+                message: "is invalid or expired",
+            })?;
+
+        let user = self
+            .repo
+            .mark_email_verified(user_id)
+            .await?
+            .ok_or(DomainError::NotFound(format!("user id: {user_id}")))?;
+        self.verification_repo.invalidate_for_user(user_id).await?;
+        Ok(user)
+    }
+
+    /// Повторно отправляет письмо подтверждения; ранее выданные токены гасятся.
+    pub(crate) async fn resend_verification_email(&self, user_id: i64) -> Result<(), DomainError> {
+        let user = self
+            .repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(DomainError::NotFound(format!("user id: {user_id}")))?;
+        if user.is_email_verified() {
+            return Err(DomainError::Validation {
+                field: "email",
+                message: "is already verified",
+            });
+        }
+
+        self.verification_repo.invalidate_for_user(user_id).await?;
+        self.send_verification_email(&user).await
+    }
+
+    async fn send_verification_email(&self, user: &User) -> Result<(), DomainError> {
+        let token = generate_opaque_token();
+        self.verification_repo
+            .create_verification_token(NewEmailVerificationToken {
+                user_id: user.id,
+                token_hash: hash_opaque_token(&token),
+                expires_at: Utc::now() + Duration::seconds(self.ttls.email_verification_seconds),
+            })
+            .await?;
+
+        let hours = (self.ttls.email_verification_seconds / 3600).max(1);
+        self.mailer
+            .send(EmailMessage {
+                to: user.email.clone(),
+                subject: "Подтверждение email".to_string(),
+                body: format!(
+                    "Здравствуйте, {}!\n\n\
+                     Чтобы подтвердить адрес, используйте токен:\n\n\
+                     {token}\n\n\
+                     Токен действует {hours} ч. Если вы не регистрировались, \
+                     просто проигнорируйте это письмо.",
+                    user.username
+                ),
+            })
+            .await
+    }
+
+    async fn set_password(&self, user_id: i64, new_password: &str) -> Result<(), DomainError> {
+        let password_hash = self.hash_password(new_password)?;
+        if !self.repo.update_password(user_id, &password_hash).await? {
+            return Err(DomainError::NotFound(format!("user id: {user_id}")));
+        }
+
+        self.reset_repo.invalidate_for_user(user_id).await?;
+        self.logout_all(user_id).await
+    }
+
+    fn password_reset_email(&self, user: &User, token: &str) -> EmailMessage {
+        let minutes = (self.ttls.password_reset_seconds / 60).max(1);
+        EmailMessage {
+            to: user.email.clone(),
+            subject: "Сброс пароля".to_string(),
+            body: format!(
+                "Здравствуйте, {}!\n\n\
+                 Для вашей учётной записи запрошен сброс пароля. Токен для смены пароля:\n\n\
+                 {token}\n\n\
+                 Токен действует {minutes} мин. и может быть использован один раз.\n\
+                 Если вы не запрашивали сброс, просто проигнорируйте это письмо.",
+                user.username
+            ),
+        }
+    }
+
+    async fn start_session(
+        &self,
+        user: User,
+        client: &ClientInfo,
+    ) -> Result<AuthResult, DomainError> {
+        let family_id = generate_opaque_token();
+        let (refresh_token, new_token) = self.new_refresh_token(user.id, family_id.clone());
+        self.refresh_repo.create_refresh_token(new_token).await?;
+        let session = self
+            .sessions
+            .start_session(user.id, &family_id, client)
+            .await?;
+
+        let access_token = self.issue_access_token(&user, Some(session.id))?;
+
+        Ok(AuthResult {
+            user,
+            access_token,
+            refresh_token,
+        })
+    }
+
+    fn issue_access_token(
+        &self,
+        user: &User,
+        session_id: Option<i64>,
+    ) -> Result<String, DomainError> {
+        self.jwt
+            .generate_token(user, session_id)
+            .map_err(|err| DomainError::Unexpected(err.to_string()))
+    }
+
+    fn new_refresh_token(&self, user_id: i64, family_id: String) -> (String, NewRefreshToken) {
+        let token = generate_opaque_token();
+        let record = NewRefreshToken {
+            user_id,
+            family_id,
+            token_hash: hash_opaque_token(&token),
+            expires_at: Utc::now() + Duration::seconds(self.ttls.refresh_token_seconds),
+        };
+        (token, record)
+    }
+
     pub(crate) fn hash_password(&self, raw_password: &str) -> Result<String, DomainError> {
         let salt = SaltString::generate(&mut OsRng);
         let password_hash = Self::argon2()?
@@ -119,13 +746,43 @@ mod tests {
     use std::sync::{Arc, Mutex};
 
     use async_trait::async_trait;
-    use chrono::Utc;
+    use chrono::{DateTime, Duration, Utc};
 
-    use super::AuthService;
+    use super::{AuthService, AuthTokenTtls, LoginOutcome};
+    use crate::application::login_throttle::LoginThrottleSettings;
+    use crate::application::session_service::SessionService;
+    use crate::application::token_revocation_service::TokenRevocationService;
+    use crate::data::email_verification_repository::{
+        EmailVerificationRepository, NewEmailVerificationToken,
+    };
+    use crate::data::mfa_repository::{MfaRepository, TotpRecord};
+    use crate::data::password_reset_repository::{NewPasswordResetToken, PasswordResetRepository};
+    use crate::data::refresh_token_repository::{
+        NewRefreshToken, RefreshTokenRecord, RefreshTokenRepository,
+    };
+    use crate::data::session_repository::{NewSession, SessionRepository};
+    use crate::data::token_revocation_repository::TokenRevocationRepository;
     use crate::data::user_repository::{NewUser, UserCredentials, UserRepository};
     use crate::domain::error::DomainError;
-    use crate::domain::user::{LoginRequest, RegisterRequest, User};
+    use crate::domain::session::{ClientInfo, Session};
+    use crate::domain::user::{
+        ChangePasswordRequest, LoginRequest, RegisterRequest, ResetPasswordRequest, User,
+    };
     use crate::infrastructure::jwt::JwtService;
+    use crate::infrastructure::mailer::{EmailMessage, Mailer};
+    use crate::infrastructure::opaque_token::hash_opaque_token;
+    use crate::infrastructure::totp;
+
+    type TestAuthService = AuthService<
+        FakeUserRepo,
+        FakeRefreshTokenRepo,
+        FakeRevocationRepo,
+        FakeSessionRepo,
+        FakeOneTimeTokenRepo,
+        FakeOneTimeTokenRepo,
+        FakeMfaRepo,
+        FakeMailer,
+    >;
 
     #[derive(Clone)]
     struct FakeUserRepo {
@@ -168,6 +825,10 @@ mod tests {
             Ok(self.create_user_out.clone())
         }
 
+        async fn find_by_id(&self, id: i64) -> Result<Option<User>, DomainError> {
+            Ok((self.create_user_out.id == id).then(|| self.create_user_out.clone()))
+        }
+
         async fn find_by_username(
             &self,
             _username: &str,
@@ -179,18 +840,543 @@ mod tests {
                 .clone())
         }
 
-        async fn find_by_email(
+        async fn find_by_email(&self, email: &str) -> Result<Option<UserCredentials>, DomainError> {
+            Ok(self
+                .login_credentials
+                .lock()
+                .expect("login credentials mutex poisoned")
+                .clone()
+                .filter(|creds| creds.user.email == email))
+        }
+
+        async fn find_credentials_by_id(
             &self,
-            _email: &str,
+            id: i64,
         ) -> Result<Option<UserCredentials>, DomainError> {
+            Ok(self
+                .login_credentials
+                .lock()
+                .expect("login credentials mutex poisoned")
+                .clone()
+                .filter(|creds| creds.user.id == id))
+        }
+
+        async fn update_password(
+            &self,
+            user_id: i64,
+            password_hash: &str,
+        ) -> Result<bool, DomainError> {
+            let mut creds = self
+                .login_credentials
+                .lock()
+                .expect("login credentials mutex poisoned");
+            match creds.as_mut().filter(|creds| creds.user.id == user_id) {
+                Some(creds) => {
+                    creds.password_hash = password_hash.to_string();
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+
+        async fn mark_email_verified(&self, user_id: i64) -> Result<Option<User>, DomainError> {
+            Ok((self.create_user_out.id == user_id).then(|| {
+                self.create_user_out
+                    .clone()
+                    .with_email_verified_at(Some(Utc::now()))
+            }))
+        }
+    }
+
+    /// Одноразовые токены с флагом "использован": и для сброса пароля, и для подтверждения email.
+    #[derive(Clone, Default)]
+    struct FakeOneTimeTokenRepo {
+        tokens: Arc<Mutex<Vec<(NewPasswordResetToken, bool)>>>,
+    }
+
+    impl FakeOneTimeTokenRepo {
+        fn insert(&self, input: NewPasswordResetToken) {
+            self.tokens
+                .lock()
+                .expect("one-time tokens mutex poisoned")
+                .push((input, false));
+        }
+
+        fn stored_hashes(&self) -> Vec<String> {
+            self.tokens
+                .lock()
+                .expect("one-time tokens mutex poisoned")
+                .iter()
+                .map(|(token, _)| token.token_hash.clone())
+                .collect()
+        }
+
+        fn consume(&self, token_hash: &str) -> Option<i64> {
+            let mut tokens = self.tokens.lock().expect("one-time tokens mutex poisoned");
+            let token = tokens.iter_mut().find(|(token, used)| {
+                token.token_hash == token_hash && !used && token.expires_at > Utc::now()
+            });
+            token.map(|(token, used)| {
+                *used = true;
+                token.user_id
+            })
+        }
+
+        fn invalidate(&self, user_id: i64) {
+            for (token, used) in self
+                .tokens
+                .lock()
+                .expect("one-time tokens mutex poisoned")
+                .iter_mut()
+            {
+                if token.user_id == user_id {
+                    *used = true;
+                }
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PasswordResetRepository for FakeOneTimeTokenRepo {
+        async fn create_reset_token(
+            &self,
+            input: NewPasswordResetToken,
+        ) -> Result<(), DomainError> {
+            self.insert(input);
+            Ok(())
+        }
+
+        async fn consume_reset_token(&self, token_hash: &str) -> Result<Option<i64>, DomainError> {
+            Ok(self.consume(token_hash))
+        }
+
+        async fn invalidate_for_user(&self, user_id: i64) -> Result<(), DomainError> {
+            self.invalidate(user_id);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl EmailVerificationRepository for FakeOneTimeTokenRepo {
+        async fn create_verification_token(
+            &self,
+            input: NewEmailVerificationToken,
+        ) -> Result<(), DomainError> {
+            self.insert(NewPasswordResetToken {
+                user_id: input.user_id,
+                token_hash: input.token_hash,
+                expires_at: input.expires_at,
+            });
+            Ok(())
+        }
+
+        async fn consume_verification_token(
+            &self,
+            token_hash: &str,
+        ) -> Result<Option<i64>, DomainError> {
+            Ok(self.consume(token_hash))
+        }
+
+        async fn invalidate_for_user(&self, user_id: i64) -> Result<(), DomainError> {
+            self.invalidate(user_id);
+            Ok(())
+        }
+    }
+
+    struct FakeTotp {
+        record: TotpRecord,
+        last_used_step: Option<i64>,
+    }
+
+    /// TOTP и коды восстановления единственного пользователя.
+    #[derive(Clone, Default)]
+    struct FakeMfaRepo {
+        totp: Arc<Mutex<Option<FakeTotp>>>,
+        recovery_codes: Arc<Mutex<Vec<(String, bool)>>>,
+    }
+
+    #[async_trait]
+    impl MfaRepository for FakeMfaRepo {
+        async fn find_totp(&self, _user_id: i64) -> Result<Option<TotpRecord>, DomainError> {
+            Ok(self
+                .totp
+                .lock()
+                .expect("totp mutex poisoned")
+                .as_ref()
+                .map(|totp| totp.record.clone()))
+        }
+
+        async fn set_pending_totp(&self, _user_id: i64, secret: &str) -> Result<bool, DomainError> {
+            let mut totp = self.totp.lock().expect("totp mutex poisoned");
+            if totp.as_ref().is_some_and(|totp| totp.record.is_enabled()) {
+                return Ok(false);
+            }
+            *totp = Some(FakeTotp {
+                record: TotpRecord {
+                    secret: secret.to_string(),
+                    enabled_at: None,
+                },
+                last_used_step: None,
+            });
+            Ok(true)
+        }
+
+        async fn enable_totp(
+            &self,
+            _user_id: i64,
+            recovery_code_hashes: &[String],
+        ) -> Result<bool, DomainError> {
+            let mut totp = self.totp.lock().expect("totp mutex poisoned");
+            match totp.as_mut() {
+                Some(totp) if !totp.record.is_enabled() => {
+                    totp.record.enabled_at = Some(Utc::now())
+                }
+                _ => return Ok(false),
+            }
+            *self
+                .recovery_codes
+                .lock()
+                .expect("recovery codes mutex poisoned") = recovery_code_hashes
+                .iter()
+                .map(|hash| (hash.clone(), false))
+                .collect();
+            Ok(true)
+        }
+
+        async fn disable_totp(&self, _user_id: i64) -> Result<(), DomainError> {
+            *self.totp.lock().expect("totp mutex poisoned") = None;
+            self.recovery_codes
+                .lock()
+                .expect("recovery codes mutex poisoned")
+                .clear();
+            Ok(())
+        }
+
+        async fn mark_step_used(&self, _user_id: i64, step: i64) -> Result<bool, DomainError> {
+            let mut totp = self.totp.lock().expect("totp mutex poisoned");
+            match totp.as_mut() {
+                Some(totp) if totp.last_used_step.is_none_or(|last| last < step) => {
+                    totp.last_used_step = Some(step);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn consume_recovery_code(
+            &self,
+            _user_id: i64,
+            code_hash: &str,
+        ) -> Result<bool, DomainError> {
+            let mut codes = self
+                .recovery_codes
+                .lock()
+                .expect("recovery codes mutex poisoned");
+            match codes
+                .iter_mut()
+                .find(|(hash, used)| hash == code_hash && !used)
+            {
+                Some((_, used)) => {
+                    *used = true;
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct FakeMailer {
+        sent: Arc<Mutex<Vec<EmailMessage>>>,
+    }
+
+    impl FakeMailer {
+        fn sent(&self) -> Vec<EmailMessage> {
+            self.sent.lock().expect("sent mutex poisoned").clone()
+        }
+    }
+
+    #[async_trait]
+    impl Mailer for FakeMailer {
+        async fn send(&self, message: EmailMessage) -> Result<(), DomainError> {
+            self.sent.lock().expect("sent mutex poisoned").push(message);
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct FakeRefreshTokenRepo {
+        tokens: Arc<Mutex<Vec<RefreshTokenRecord>>>,
+        hashes: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl FakeRefreshTokenRepo {
+        fn tokens(&self) -> Vec<RefreshTokenRecord> {
+            self.tokens.lock().expect("tokens mutex poisoned").clone()
+        }
+
+        fn hashes(&self) -> Vec<String> {
+            self.hashes.lock().expect("hashes mutex poisoned").clone()
+        }
+
+        fn expire_all(&self) {
+            for token in self
+                .tokens
+                .lock()
+                .expect("tokens mutex poisoned")
+                .iter_mut()
+            {
+                token.expires_at = Utc::now() - Duration::seconds(1);
+            }
+        }
+
+        fn insert(&self, input: NewRefreshToken) {
+            let mut tokens = self.tokens.lock().expect("tokens mutex poisoned");
+            let id = tokens.len() as i64 + 1;
+            tokens.push(RefreshTokenRecord {
+                id,
+                user_id: input.user_id,
+                family_id: input.family_id,
+                expires_at: input.expires_at,
+                revoked_at: None,
+            });
+            self.hashes
+                .lock()
+                .expect("hashes mutex poisoned")
+                .push(input.token_hash);
+        }
+    }
+
+    #[async_trait]
+    impl RefreshTokenRepository for FakeRefreshTokenRepo {
+        async fn create_refresh_token(&self, input: NewRefreshToken) -> Result<(), DomainError> {
+            self.insert(input);
+            Ok(())
+        }
+
+        async fn find_by_hash(
+            &self,
+            token_hash: &str,
+        ) -> Result<Option<RefreshTokenRecord>, DomainError> {
+            let index = self.hashes().iter().position(|hash| hash == token_hash);
+            Ok(index.map(|index| self.tokens()[index].clone()))
+        }
+
+        async fn rotate_refresh_token(
+            &self,
+            current_id: i64,
+            next: NewRefreshToken,
+        ) -> Result<bool, DomainError> {
+            {
+                let mut tokens = self.tokens.lock().expect("tokens mutex poisoned");
+                let current = tokens
+                    .iter_mut()
+                    .find(|token| token.id == current_id && token.revoked_at.is_none());
+                match current {
+                    Some(token) => token.revoked_at = Some(Utc::now()),
+                    None => return Ok(false),
+                }
+            }
+            self.insert(next);
+            Ok(true)
+        }
+
+        async fn revoke_family(&self, family_id: &str) -> Result<(), DomainError> {
+            for token in self
+                .tokens
+                .lock()
+                .expect("tokens mutex poisoned")
+                .iter_mut()
+            {
+                if token.family_id == family_id && token.revoked_at.is_none() {
+                    token.revoked_at = Some(Utc::now());
+                }
+            }
+            Ok(())
+        }
+
+        async fn revoke_all_for_user(&self, user_id: i64) -> Result<(), DomainError> {
+            for token in self
+                .tokens
+                .lock()
+                .expect("tokens mutex poisoned")
+                .iter_mut()
+            {
+                if token.user_id == user_id && token.revoked_at.is_none() {
+                    token.revoked_at = Some(Utc::now());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    struct StoredSession {
+        session: Session,
+        user_id: i64,
+        family_id: String,
+    }
+
+    /// Как и в БД, сессия жива, пока жива её семья refresh-токенов.
+    #[derive(Clone)]
+    struct FakeSessionRepo {
+        refresh_repo: FakeRefreshTokenRepo,
+        sessions: Arc<Mutex<Vec<StoredSession>>>,
+    }
+
+    impl FakeSessionRepo {
+        fn new(refresh_repo: FakeRefreshTokenRepo) -> Self {
+            Self {
+                refresh_repo,
+                sessions: Arc::default(),
+            }
+        }
+
+        fn family_is_live(&self, family_id: &str) -> bool {
+            self.refresh_repo.tokens().iter().any(|token| {
+                token.family_id == family_id
+                    && token.revoked_at.is_none()
+                    && token.expires_at > Utc::now()
+            })
+        }
+
+        fn find_family(&self, predicate: impl Fn(&StoredSession) -> bool) -> Option<String> {
+            self.sessions
+                .lock()
+                .expect("sessions mutex poisoned")
+                .iter()
+                .find(|stored| predicate(stored))
+                .map(|stored| stored.family_id.clone())
+        }
+    }
+
+    #[async_trait]
+    impl SessionRepository for FakeSessionRepo {
+        async fn create_session(&self, input: NewSession) -> Result<Session, DomainError> {
+            let mut sessions = self.sessions.lock().expect("sessions mutex poisoned");
+            let session = Session {
+                id: sessions.len() as i64 + 1,
+                user_agent: input.user_agent,
+                ip_address: input.ip_address,
+                created_at: Utc::now(),
+                last_seen_at: Utc::now(),
+            };
+            sessions.push(StoredSession {
+                session: session.clone(),
+                user_id: input.user_id,
+                family_id: input.family_id,
+            });
+            Ok(session)
+        }
+
+        async fn touch_family(
+            &self,
+            family_id: &str,
+            _user_agent: Option<&str>,
+            _ip_address: Option<&str>,
+            _at: DateTime<Utc>,
+        ) -> Result<Option<i64>, DomainError> {
+            Ok(self
+                .sessions
+                .lock()
+                .expect("sessions mutex poisoned")
+                .iter()
+                .find(|stored| stored.family_id == family_id)
+                .map(|stored| stored.session.id))
+        }
+
+        async fn touch_session(
+            &self,
+            session_id: i64,
+            _at: DateTime<Utc>,
+        ) -> Result<bool, DomainError> {
+            let family_id = self.find_family(|stored| stored.session.id == session_id);
+            Ok(family_id.is_some_and(|family_id| self.family_is_live(&family_id)))
+        }
+
+        async fn list_active(&self, user_id: i64) -> Result<Vec<Session>, DomainError> {
+            let sessions = self
+                .sessions
+                .lock()
+                .expect("sessions mutex poisoned")
+                .iter()
+                .filter(|stored| stored.user_id == user_id)
+                .map(|stored| (stored.session.clone(), stored.family_id.clone()))
+                .collect::<Vec<_>>();
+            Ok(sessions
+                .into_iter()
+                .filter(|(_, family_id)| self.family_is_live(family_id))
+                .map(|(session, _)| session)
+                .collect())
+        }
+
+        async fn revoke_session(&self, user_id: i64, session_id: i64) -> Result<bool, DomainError> {
+            let Some(family_id) = self
+                .find_family(|stored| stored.session.id == session_id && stored.user_id == user_id)
+            else {
+                return Ok(false);
+            };
+            if !self.family_is_live(&family_id) {
+                return Ok(false);
+            }
+            self.refresh_repo.revoke_family(&family_id).await?;
+            Ok(true)
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct FakeRevocationRepo {
+        revoked: Arc<Mutex<Vec<String>>>,
+        cutoffs: Arc<Mutex<Vec<i64>>>,
+    }
+
+    #[async_trait]
+    impl TokenRevocationRepository for FakeRevocationRepo {
+        async fn revoke_token(
+            &self,
+            jti: &str,
+            _user_id: i64,
+            _expires_at: DateTime<Utc>,
+        ) -> Result<(), DomainError> {
+            self.revoked
+                .lock()
+                .expect("revoked mutex poisoned")
+                .push(jti.to_string());
+            Ok(())
+        }
+
+        async fn is_token_revoked(&self, jti: &str) -> Result<bool, DomainError> {
+            Ok(self
+                .revoked
+                .lock()
+                .expect("revoked mutex poisoned")
+                .iter()
+                .any(|revoked| revoked == jti))
+        }
+
+        async fn revoke_all_for_user(&self, user_id: i64) -> Result<DateTime<Utc>, DomainError> {
+            self.cutoffs
+                .lock()
+                .expect("cutoffs mutex poisoned")
+                .push(user_id);
+            Ok(Utc::now())
+        }
+
+        async fn find_user_cutoff(
+            &self,
+            _user_id: i64,
+        ) -> Result<Option<DateTime<Utc>>, DomainError> {
             Ok(None)
         }
+
+        async fn purge_expired(&self) -> Result<u64, DomainError> {
+            Ok(0)
+        }
     }
 
     #[tokio::test]
     async fn register_creates_user_and_returns_token() {
         let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
-        let service = AuthService::new(repo.clone(), test_jwt());
+        let service = test_service(repo.clone(), FakeRefreshTokenRepo::default());
 
         let req = RegisterRequest {
             username: "  valid_user  ".to_string(),
@@ -198,7 +1384,10 @@ mod tests {
             password: "very-secure-password".to_string(),
         };
 
-        let result = service.register(req).await.expect("register must succeed");
+        let result = service
+            .register(req, ClientInfo::default())
+            .await
+            .expect("register must succeed");
 
         assert_eq!(result.user.username, "valid_user");
         assert!(!result.access_token.is_empty());
@@ -215,21 +1404,24 @@ mod tests {
     async fn login_returns_invalid_credentials_for_missing_user() {
         let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
         repo.set_login_credentials(None);
-        let service = AuthService::new(repo, test_jwt());
+        let service = test_service(repo, FakeRefreshTokenRepo::default());
 
         let req = LoginRequest {
             username: "valid_user".to_string(),
             password: "some-password".to_string(),
         };
 
-        let err = service.login(req).await.expect_err("login must fail");
+        let err = service
+            .login(req, ClientInfo::default())
+            .await
+            .expect_err("login must fail");
         assert!(matches!(err, DomainError::InvalidCredentials));
     }
 
     #[tokio::test]
     async fn login_returns_invalid_credentials_for_wrong_password() {
         let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
-        let service = AuthService::new(repo.clone(), test_jwt());
+        let service = test_service(repo.clone(), FakeRefreshTokenRepo::default());
 
         let hash = service
             .hash_password("correct-password")
@@ -244,14 +1436,59 @@ mod tests {
             password: "wrong-password".to_string(),
         };
 
-        let err = service.login(req).await.expect_err("login must fail");
+        let err = service
+            .login(req, ClientInfo::default())
+            .await
+            .expect_err("login must fail");
         assert!(matches!(err, DomainError::InvalidCredentials));
     }
 
+    #[tokio::test]
+    async fn locked_out_login_rejects_even_correct_password() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let service = test_service(repo.clone(), FakeRefreshTokenRepo::default())
+            .with_login_throttle(LoginThrottleSettings {
+                free_attempts: 0,
+                max_attempts_per_username: 2,
+                max_attempts_per_ip: 100,
+                base_delay: std::time::Duration::ZERO,
+                lockout: std::time::Duration::from_secs(60),
+            });
+        repo.set_login_credentials(Some(UserCredentials {
+            user: sample_user(1, "valid_user", "valid@example.com"),
+            password_hash: service
+                .hash_password("correct-password")
+                .expect("hash must be created"),
+        }));
+        let login = |password: &str| LoginRequest {
+            username: "valid_user".to_string(),
+            password: password.to_string(),
+        };
+
+        for _ in 0..2 {
+            let err = service
+                .login(login("wrong-password"), ClientInfo::default())
+                .await
+                .expect_err("login must fail");
+            assert!(matches!(err, DomainError::InvalidCredentials));
+        }
+
+        let err = service
+            .login(login("correct-password"), ClientInfo::default())
+            .await
+            .expect_err("locked out login must fail");
+        assert!(matches!(
+            err,
+            DomainError::TooManyAttempts {
+                retry_after_secs: 60
+            }
+        ));
+    }
+
     #[tokio::test]
     async fn login_returns_token_for_valid_credentials() {
         let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
-        let service = AuthService::new(repo.clone(), test_jwt());
+        let service = test_service(repo.clone(), FakeRefreshTokenRepo::default());
 
         let hash = service
             .hash_password("correct-password")
@@ -266,17 +1503,765 @@ mod tests {
             password: "correct-password".to_string(),
         };
 
-        let result = service.login(req).await.expect("login must succeed");
+        let outcome = service
+            .login(req, ClientInfo::default())
+            .await
+            .expect("login must succeed");
+        let LoginOutcome::Authenticated(result) = outcome else {
+            panic!("login without totp must not require second factor");
+        };
         assert_eq!(result.user.id, 1);
         assert!(!result.access_token.is_empty());
     }
 
+    #[tokio::test]
+    async fn totp_login_requires_fresh_code_or_recovery_code() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let service = test_service(repo.clone(), FakeRefreshTokenRepo::default());
+        repo.set_login_credentials(Some(UserCredentials {
+            user: sample_user(1, "valid_user", "valid@example.com"),
+            password_hash: service
+                .hash_password("correct-password")
+                .expect("hash must be created"),
+        }));
+        let now = Utc::now().timestamp();
+
+        let enrollment = service
+            .start_totp_enrollment(1)
+            .await
+            .expect("enrollment must start");
+        assert!(
+            enrollment
+                .otpauth_uri
+                .starts_with("otpauth://totp/rust-blog:valid_user?secret=")
+        );
+        let code = |time: i64| totp::code_at(&enrollment.secret, time).expect("valid secret");
+        let recovery_codes = service
+            .confirm_totp_enrollment(1, &code(now))
+            .await
+            .expect("confirmation must succeed");
+        assert_eq!(recovery_codes.len(), 10);
+
+        let login = || LoginRequest {
+            username: "valid_user".to_string(),
+            password: "correct-password".to_string(),
+        };
+        let outcome = service
+            .login(login(), ClientInfo::default())
+            .await
+            .expect("password is valid");
+        let LoginOutcome::MfaRequired { mfa_token, .. } = outcome else {
+            panic!("login with totp must require second factor");
+        };
+        assert!(
+            service.jwt.verify_token(&mfa_token).is_err(),
+            "mfa token must not work as access token"
+        );
+
+        let err = service
+            .complete_mfa_login(&mfa_token, &code(now), ClientInfo::default())
+            .await
+            .expect_err("code used for confirmation must not be replayed");
+        assert!(matches!(err, DomainError::InvalidCredentials));
+        let result = service
+            .complete_mfa_login(&mfa_token, &code(now + 30), ClientInfo::default())
+            .await
+            .expect("next code must be accepted");
+        assert_eq!(result.user.id, 1);
+
+        let recovery = recovery_codes[0].to_uppercase();
+        service
+            .complete_mfa_login(&mfa_token, &recovery, ClientInfo::default())
+            .await
+            .expect("recovery code must be accepted");
+        let err = service
+            .complete_mfa_login(&mfa_token, &recovery, ClientInfo::default())
+            .await
+            .expect_err("recovery code must be single-use");
+        assert!(matches!(err, DomainError::InvalidCredentials));
+    }
+
+    #[tokio::test]
+    async fn disabled_totp_no_longer_requires_second_factor() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let service = test_service(repo.clone(), FakeRefreshTokenRepo::default());
+        repo.set_login_credentials(Some(UserCredentials {
+            user: sample_user(1, "valid_user", "valid@example.com"),
+            password_hash: service
+                .hash_password("correct-password")
+                .expect("hash must be created"),
+        }));
+        let enrollment = service
+            .start_totp_enrollment(1)
+            .await
+            .expect("enrollment must start");
+        let code = totp::code_at(&enrollment.secret, Utc::now().timestamp()).expect("valid secret");
+        let recovery_codes = service
+            .confirm_totp_enrollment(1, &code)
+            .await
+            .expect("confirmation must succeed");
+
+        let err = service
+            .start_totp_enrollment(1)
+            .await
+            .expect_err("enabled totp must not be replaced");
+        assert!(matches!(err, DomainError::Validation { field: "totp", .. }));
+        let err = service
+            .disable_totp(1, "not-a-code")
+            .await
+            .expect_err("disable requires valid code");
+        assert!(matches!(err, DomainError::Validation { field: "code", .. }));
+
+        service
+            .disable_totp(1, &recovery_codes[1])
+            .await
+            .expect("recovery code must disable totp");
+        let outcome = service
+            .login(
+                LoginRequest {
+                    username: "valid_user".to_string(),
+                    password: "correct-password".to_string(),
+                },
+                ClientInfo::default(),
+            )
+            .await
+            .expect("login must succeed");
+        assert!(matches!(outcome, LoginOutcome::Authenticated(_)));
+    }
+
+    #[tokio::test]
+    async fn register_stores_only_refresh_token_hash() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let refresh_repo = FakeRefreshTokenRepo::default();
+        let service = test_service(repo, refresh_repo.clone());
+
+        let result = service
+            .register(sample_register_request(), ClientInfo::default())
+            .await
+            .expect("register must succeed");
+
+        assert!(!result.refresh_token.is_empty());
+        let hashes = refresh_repo.hashes();
+        assert_eq!(hashes.len(), 1);
+        assert_ne!(hashes[0], result.refresh_token);
+    }
+
+    #[tokio::test]
+    async fn refresh_rotates_token_within_family() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let refresh_repo = FakeRefreshTokenRepo::default();
+        let service = test_service(repo, refresh_repo.clone());
+        let first = service
+            .register(sample_register_request(), ClientInfo::default())
+            .await
+            .expect("register must succeed");
+
+        let second = service
+            .refresh(&first.refresh_token, ClientInfo::default())
+            .await
+            .expect("refresh must succeed");
+
+        assert_eq!(second.user.id, 1);
+        assert!(!second.access_token.is_empty());
+        assert_ne!(second.refresh_token, first.refresh_token);
+
+        let tokens = refresh_repo.tokens();
+        assert_eq!(tokens.len(), 2);
+        assert!(tokens[0].revoked_at.is_some());
+        assert!(tokens[1].revoked_at.is_none());
+        assert_eq!(tokens[0].family_id, tokens[1].family_id);
+    }
+
+    #[tokio::test]
+    async fn refresh_reuse_revokes_whole_family() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let refresh_repo = FakeRefreshTokenRepo::default();
+        let service = test_service(repo, refresh_repo.clone());
+        let first = service
+            .register(sample_register_request(), ClientInfo::default())
+            .await
+            .expect("register must succeed");
+        let second = service
+            .refresh(&first.refresh_token, ClientInfo::default())
+            .await
+            .expect("refresh must succeed");
+
+        let err = service
+            .refresh(&first.refresh_token, ClientInfo::default())
+            .await
+            .expect_err("reused token must be rejected");
+        assert!(matches!(err, DomainError::InvalidCredentials));
+
+        let err = service
+            .refresh(&second.refresh_token, ClientInfo::default())
+            .await
+            .expect_err("family must be revoked after reuse");
+        assert!(matches!(err, DomainError::InvalidCredentials));
+        assert!(
+            refresh_repo
+                .tokens()
+                .iter()
+                .all(|token| token.revoked_at.is_some())
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_rejects_unknown_and_expired_tokens() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let refresh_repo = FakeRefreshTokenRepo::default();
+        let service = test_service(repo, refresh_repo.clone());
+
+        let err = service
+            .refresh("unknown-token", ClientInfo::default())
+            .await
+            .expect_err("unknown token must be rejected");
+        assert!(matches!(err, DomainError::InvalidCredentials));
+
+        let result = service
+            .register(sample_register_request(), ClientInfo::default())
+            .await
+            .expect("register must succeed");
+        refresh_repo.expire_all();
+
+        let err = service
+            .refresh(&result.refresh_token, ClientInfo::default())
+            .await
+            .expect_err("expired token must be rejected");
+        assert!(matches!(err, DomainError::InvalidCredentials));
+    }
+
+    #[tokio::test]
+    async fn logout_revokes_access_token_and_refresh_family() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let refresh_repo = FakeRefreshTokenRepo::default();
+        let revocation_repo = FakeRevocationRepo::default();
+        let service =
+            test_service_with_revocations(repo, refresh_repo.clone(), revocation_repo.clone());
+        let result = service
+            .register(sample_register_request(), ClientInfo::default())
+            .await
+            .expect("register must succeed");
+
+        service
+            .logout(
+                1,
+                "access-jti",
+                Utc::now().timestamp() + 60,
+                None,
+                Some(&result.refresh_token),
+            )
+            .await
+            .expect("logout must succeed");
+
+        assert_eq!(
+            *revocation_repo
+                .revoked
+                .lock()
+                .expect("revoked mutex poisoned"),
+            vec!["access-jti".to_string()]
+        );
+        let err = service
+            .refresh(&result.refresh_token, ClientInfo::default())
+            .await
+            .expect_err("refresh after logout must fail");
+        assert!(matches!(err, DomainError::InvalidCredentials));
+    }
+
+    #[tokio::test]
+    async fn logout_ignores_refresh_token_of_another_user() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let refresh_repo = FakeRefreshTokenRepo::default();
+        let service = test_service(repo, refresh_repo.clone());
+        let result = service
+            .register(sample_register_request(), ClientInfo::default())
+            .await
+            .expect("register must succeed");
+
+        service
+            .logout(
+                2,
+                "access-jti",
+                Utc::now().timestamp() + 60,
+                None,
+                Some(&result.refresh_token),
+            )
+            .await
+            .expect("logout must succeed");
+
+        assert!(refresh_repo.tokens()[0].revoked_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn access_tokens_carry_session_that_can_be_revoked() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let service = test_service(repo, FakeRefreshTokenRepo::default());
+        let client = ClientInfo::new(Some([10, 0, 0, 7].into()), Some("curl/8.0"));
+        let first = service
+            .register(sample_register_request(), client.clone())
+            .await
+            .expect("register must succeed");
+        let second = service
+            .register(sample_register_request(), client)
+            .await
+            .expect("register must succeed");
+
+        let session_of = |token: &str| {
+            service
+                .jwt
+                .verify_token(token)
+                .expect("valid token")
+                .sid
+                .expect("token carries session")
+        };
+        let session_id = session_of(&first.access_token);
+        let refreshed = service
+            .refresh(&first.refresh_token, ClientInfo::default())
+            .await
+            .expect("refresh must succeed");
+        assert_eq!(session_of(&refreshed.access_token), session_id);
+
+        let sessions = service.sessions.list_sessions(1).await.expect("list");
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].ip_address.as_deref(), Some("10.0.0.7"));
+        assert_eq!(sessions[0].user_agent.as_deref(), Some("curl/8.0"));
+
+        service
+            .sessions
+            .revoke_session(1, session_id)
+            .await
+            .expect("session revoked");
+        assert!(!service.sessions.is_active(session_id).await.expect("check"));
+        let err = service
+            .refresh(&refreshed.refresh_token, ClientInfo::default())
+            .await
+            .expect_err("revoked session must not refresh");
+        assert!(matches!(err, DomainError::InvalidCredentials));
+
+        // выход без refresh-токена тоже завершает сессию целиком
+        let other_session = session_of(&second.access_token);
+        service
+            .logout(
+                1,
+                "access-jti",
+                Utc::now().timestamp() + 60,
+                Some(other_session),
+                None,
+            )
+            .await
+            .expect("logout must succeed");
+        assert!(
+            service
+                .sessions
+                .list_sessions(1)
+                .await
+                .expect("list")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn logout_all_revokes_every_refresh_token() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let refresh_repo = FakeRefreshTokenRepo::default();
+        let revocation_repo = FakeRevocationRepo::default();
+        let service =
+            test_service_with_revocations(repo, refresh_repo.clone(), revocation_repo.clone());
+        for _ in 0..2 {
+            service
+                .register(sample_register_request(), ClientInfo::default())
+                .await
+                .expect("register must succeed");
+        }
+
+        service
+            .logout_all(1)
+            .await
+            .expect("logout all must succeed");
+
+        assert!(
+            refresh_repo
+                .tokens()
+                .iter()
+                .all(|token| token.revoked_at.is_some())
+        );
+        assert_eq!(
+            *revocation_repo
+                .cutoffs
+                .lock()
+                .expect("cutoffs mutex poisoned"),
+            vec![1]
+        );
+    }
+
+    #[tokio::test]
+    async fn change_password_requires_current_password_and_ends_sessions() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let refresh_repo = FakeRefreshTokenRepo::default();
+        let revocation_repo = FakeRevocationRepo::default();
+        let service = test_service_with_revocations(
+            repo.clone(),
+            refresh_repo.clone(),
+            revocation_repo.clone(),
+        );
+        let hash = service
+            .hash_password("correct-password")
+            .expect("hash must be created");
+        repo.set_login_credentials(Some(UserCredentials {
+            user: sample_user(1, "valid_user", "valid@example.com"),
+            password_hash: hash,
+        }));
+        service
+            .register(sample_register_request(), ClientInfo::default())
+            .await
+            .expect("register must succeed");
+
+        let err = service
+            .change_password(
+                1,
+                ChangePasswordRequest {
+                    current_password: "wrong-password".to_string(),
+                    new_password: "brand-new-password".to_string(),
+                },
+            )
+            .await
+            .expect_err("wrong current password must be rejected");
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                field: "current_password",
+                ..
+            }
+        ));
+
+        service
+            .change_password(
+                1,
+                ChangePasswordRequest {
+                    current_password: "correct-password".to_string(),
+                    new_password: "brand-new-password".to_string(),
+                },
+            )
+            .await
+            .expect("change must succeed");
+
+        let login = LoginRequest {
+            username: "valid_user".to_string(),
+            password: "brand-new-password".to_string(),
+        };
+        service
+            .login(login, ClientInfo::default())
+            .await
+            .expect("new password must work");
+        assert!(
+            refresh_repo.tokens()[0].revoked_at.is_some(),
+            "old sessions must be revoked"
+        );
+        assert_eq!(
+            *revocation_repo
+                .cutoffs
+                .lock()
+                .expect("cutoffs mutex poisoned"),
+            vec![1]
+        );
+    }
+
+    #[tokio::test]
+    async fn password_reset_token_is_mailed_hashed_and_single_use() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let reset_repo = FakeOneTimeTokenRepo::default();
+        let mailer = FakeMailer::default();
+        let service = test_service_with_mailer(
+            repo.clone(),
+            FakeRefreshTokenRepo::default(),
+            FakeRevocationRepo::default(),
+            reset_repo.clone(),
+            mailer.clone(),
+        );
+        repo.set_login_credentials(Some(UserCredentials {
+            user: sample_user(1, "valid_user", "valid@example.com"),
+            password_hash: service
+                .hash_password("forgotten-password")
+                .expect("hash must be created"),
+        }));
+
+        service
+            .request_password_reset("  VALID@example.com ")
+            .await
+            .expect("reset request must succeed");
+
+        let sent = mailer.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "valid@example.com");
+        let stored = reset_repo
+            .tokens
+            .lock()
+            .expect("reset tokens mutex poisoned")[0]
+            .0
+            .token_hash
+            .clone();
+        let token = sent[0]
+            .body
+            .split_whitespace()
+            .find(|word| hash_opaque_token(word) == stored)
+            .expect("mail must contain the token whose hash is stored")
+            .to_string();
+
+        let reset = || ResetPasswordRequest {
+            token: token.clone(),
+            new_password: "brand-new-password".to_string(),
+        };
+        service
+            .reset_password(reset())
+            .await
+            .expect("reset must succeed");
+        let err = service
+            .reset_password(reset())
+            .await
+            .expect_err("token must be single-use");
+        assert!(matches!(
+            err,
+            DomainError::Validation { field: "token", .. }
+        ));
+
+        let login = LoginRequest {
+            username: "valid_user".to_string(),
+            password: "brand-new-password".to_string(),
+        };
+        service
+            .login(login, ClientInfo::default())
+            .await
+            .expect("new password must work");
+    }
+
+    #[tokio::test]
+    async fn password_reset_for_unknown_email_sends_nothing() {
+        let mailer = FakeMailer::default();
+        let reset_repo = FakeOneTimeTokenRepo::default();
+        let service = test_service_with_mailer(
+            FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com")),
+            FakeRefreshTokenRepo::default(),
+            FakeRevocationRepo::default(),
+            reset_repo.clone(),
+            mailer.clone(),
+        );
+
+        service
+            .request_password_reset("nobody@example.com")
+            .await
+            .expect("unknown email must not be reported");
+        assert!(mailer.sent().is_empty());
+        assert!(
+            reset_repo
+                .tokens
+                .lock()
+                .expect("reset tokens mutex poisoned")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_reset_token_is_rejected() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let reset_repo = FakeOneTimeTokenRepo::default();
+        reset_repo
+            .create_reset_token(NewPasswordResetToken {
+                user_id: 1,
+                token_hash: hash_opaque_token("expired-token"),
+                expires_at: Utc::now() - Duration::seconds(1),
+            })
+            .await
+            .expect("insert must succeed");
+        let service = test_service_with_mailer(
+            repo,
+            FakeRefreshTokenRepo::default(),
+            FakeRevocationRepo::default(),
+            reset_repo,
+            FakeMailer::default(),
+        );
+
+        let err = service
+            .reset_password(ResetPasswordRequest {
+                token: "expired-token".to_string(),
+                new_password: "brand-new-password".to_string(),
+            })
+            .await
+            .expect_err("expired token must be rejected");
+        assert!(matches!(
+            err,
+            DomainError::Validation { field: "token", .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn register_mails_single_use_verification_token() {
+        let repo = FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com"));
+        let tokens = FakeOneTimeTokenRepo::default();
+        let mailer = FakeMailer::default();
+        let service = test_service_with_mailer(
+            repo,
+            FakeRefreshTokenRepo::default(),
+            FakeRevocationRepo::default(),
+            tokens.clone(),
+            mailer.clone(),
+        );
+
+        let registered = service
+            .register(sample_register_request(), ClientInfo::default())
+            .await
+            .expect("register must succeed");
+        assert!(!registered.user.is_email_verified());
+
+        let sent = mailer.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "valid@example.com");
+        let stored = tokens.stored_hashes();
+        let token = sent[0]
+            .body
+            .split_whitespace()
+            .find(|word| stored.contains(&hash_opaque_token(word)))
+            .expect("mail must contain the token whose hash is stored")
+            .to_string();
+
+        let user = service
+            .verify_email(&format!(" {token} "))
+            .await
+            .expect("verification must succeed");
+        assert!(user.is_email_verified());
+
+        let err = service
+            .verify_email(&token)
+            .await
+            .expect_err("token must be single-use");
+        assert!(matches!(
+            err,
+            DomainError::Validation { field: "token", .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn resend_verification_replaces_previous_token() {
+        let tokens = FakeOneTimeTokenRepo::default();
+        let mailer = FakeMailer::default();
+        let service = test_service_with_mailer(
+            FakeUserRepo::new(sample_user(1, "valid_user", "valid@example.com")),
+            FakeRefreshTokenRepo::default(),
+            FakeRevocationRepo::default(),
+            tokens.clone(),
+            mailer.clone(),
+        );
+
+        service
+            .resend_verification_email(1)
+            .await
+            .expect("first mail must be sent");
+        service
+            .resend_verification_email(1)
+            .await
+            .expect("second mail must be sent");
+
+        let sent = mailer.sent();
+        assert_eq!(sent.len(), 2);
+        let first_token = sent[0]
+            .body
+            .split_whitespace()
+            .find(|word| tokens.stored_hashes()[0] == hash_opaque_token(word))
+            .expect("first mail must contain a token")
+            .to_string();
+        let err = service
+            .verify_email(&first_token)
+            .await
+            .expect_err("previous token must be invalidated");
+        assert!(matches!(
+            err,
+            DomainError::Validation { field: "token", .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn resend_verification_rejects_verified_user() {
+        let verified = sample_user(1, "valid_user", "valid@example.com")
+            .with_email_verified_at(Some(Utc::now()));
+        let mailer = FakeMailer::default();
+        let service = test_service_with_mailer(
+            FakeUserRepo::new(verified),
+            FakeRefreshTokenRepo::default(),
+            FakeRevocationRepo::default(),
+            FakeOneTimeTokenRepo::default(),
+            mailer.clone(),
+        );
+
+        let err = service
+            .resend_verification_email(1)
+            .await
+            .expect_err("verified email must not be mailed again");
+        assert!(matches!(
+            err,
+            DomainError::Validation { field: "email", .. }
+        ));
+        assert!(mailer.sent().is_empty());
+    }
+
+    fn sample_register_request() -> RegisterRequest {
+        RegisterRequest {
+            username: "valid_user".to_string(),
+            email: "valid@example.com".to_string(),
+            password: "very-secure-password".to_string(),
+        }
+    }
+
     fn sample_user(id: i64, username: &str, email: &str) -> User {
         User::new(id, username.to_string(), email.to_string(), Utc::now())
             .expect("sample user must be valid")
     }
 
-    fn test_jwt() -> JwtService {
-        JwtService::new("0123456789abcdef0123456789abcdef", 3600)
+    fn test_service(repo: FakeUserRepo, refresh_repo: FakeRefreshTokenRepo) -> TestAuthService {
+        test_service_with_revocations(repo, refresh_repo, FakeRevocationRepo::default())
+    }
+
+    fn test_service_with_revocations(
+        repo: FakeUserRepo,
+        refresh_repo: FakeRefreshTokenRepo,
+        revocation_repo: FakeRevocationRepo,
+    ) -> TestAuthService {
+        test_service_with_mailer(
+            repo,
+            refresh_repo,
+            revocation_repo,
+            FakeOneTimeTokenRepo::default(),
+            FakeMailer::default(),
+        )
+    }
+
+    /// Один и тот же `tokens` служит хранилищем и токенов сброса пароля, и токенов подтверждения email.
+    fn test_service_with_mailer(
+        repo: FakeUserRepo,
+        refresh_repo: FakeRefreshTokenRepo,
+        revocation_repo: FakeRevocationRepo,
+        tokens: FakeOneTimeTokenRepo,
+        mailer: FakeMailer,
+    ) -> TestAuthService {
+        AuthService::new(
+            repo,
+            refresh_repo.clone(),
+            tokens.clone(),
+            tokens,
+            FakeMfaRepo::default(),
+            Arc::new(TokenRevocationService::new(
+                revocation_repo,
+                std::time::Duration::from_secs(60),
+            )),
+            Arc::new(SessionService::new(
+                FakeSessionRepo::new(refresh_repo),
+                std::time::Duration::from_secs(60),
+            )),
+            mailer,
+            JwtService::new("0123456789abcdef0123456789abcdef", 3600),
+            AuthTokenTtls {
+                refresh_token_seconds: 3600,
+                password_reset_seconds: 3600,
+                email_verification_seconds: 3600,
+                mfa_pending_seconds: 300,
+            },
+        )
     }
 }
@@ -1,6 +1,29 @@
-use crate::data::post_repository::{NewPost, Pagination, PostPatch, PostRepository};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::application::policy::{Actor, PostAction, authorize_post, authorize_post_creation};
+use crate::data::post_repository::{
+    NewPost, Pagination, PostListFilter, PostPatch, PostRepository,
+};
+use crate::data::tag_repository::TagRepository;
+use crate::domain::cursor::PostCursor;
 use crate::domain::error::DomainError;
-use crate::domain::post::{CreatePostRequest, Post, UpdatePostRequest};
+use crate::domain::personal_token::TokenScope;
+use crate::domain::post::{
+    CreatePostRequest, Post, PostStatus, UpdatePostRequest, validate_publish_at,
+};
+use crate::domain::revision::{PostRevision, RevisionDiff, diff_lines};
+use crate::domain::search::{PostSearchHit, normalize_search_query, render_highlight};
+use crate::domain::slug::{first_free_slug, slugify};
+use crate::domain::tag::{TagWithCount, normalize_tags};
+use crate::infrastructure::markdown::render_content;
+
+/// Пустой фильтр по автору равносилен его отсутствию.
+fn normalize_author(author: Option<&str>) -> Option<String> {
+    author
+        .map(str::trim)
+        .filter(|author| !author.is_empty())
+        .map(str::to_string)
+}
 
 #[derive(Debug, Clone)]
 pub(crate) struct ListPostsResult {
@@ -8,117 +31,678 @@ pub(crate) struct ListPostsResult {
     pub(crate) page: u32,
     pub(crate) page_size: u32,
     pub(crate) total: i64,
+    /// Курсор следующей страницы; `None`, если страница последняя.
+    pub(crate) next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FeedPosts {
+    pub(crate) posts: Vec<Post>,
+    /// Последнее изменение любого поста области ленты, а не только попавших в неё.
+    pub(crate) changed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SearchPostsResult {
+    pub(crate) hits: Vec<PostSearchHit>,
+    pub(crate) page: u32,
+    pub(crate) page_size: u32,
+    pub(crate) total: i64,
 }
 
-pub(crate) struct BlogService<R: PostRepository> {
+pub(crate) struct BlogService<R: PostRepository, T: TagRepository> {
     repo: R,
+    tags: T,
+    require_verified_email: bool,
 }
 
-impl<R: PostRepository> BlogService<R> {
-    pub(crate) fn new(repo: R) -> Self {
-        Self { repo }
+impl<R: PostRepository, T: TagRepository> BlogService<R, T> {
+    pub(crate) fn new(repo: R, tags: T) -> Self {
+        Self {
+            repo,
+            tags,
+            require_verified_email: false,
+        }
+    }
+
+    /// Запрещает создавать посты пользователям с неподтверждённым email.
+    pub(crate) fn with_required_email_verification(mut self, required: bool) -> Self {
+        self.require_verified_email = required;
+        self
     }
 
     pub(crate) async fn create_post(
         &self,
-        author_id: i64,
+        author: Actor,
         req: CreatePostRequest,
     ) -> Result<Post, DomainError> {
         let req = req.validate()?;
+        if let Some(publish_at) = req.publish_at {
+            validate_publish_at(publish_at, Utc::now())?;
+        }
+        authorize_post_creation(&author, self.require_verified_email)?;
 
+        let content_html = render_content(&req.content, req.content_format);
         let new_post = NewPost {
+            slug: self.unique_slug(&req.title, None).await?,
             title: req.title,
             content: req.content,
-            author_id,
+            content_format: req.content_format,
+            content_html,
+            author_id: author.user_id,
+            status: req.status,
+            publish_at: req.publish_at,
+            tags: req.tags.clone(),
         };
-        self.repo.create_post(new_post).await
+        let post = self.repo.create_post(new_post).await?;
+        Ok(post.with_tags(req.tags))
     }
 
-    pub(crate) async fn get_post(&self, id: i64) -> Result<Post, DomainError> {
-        self.repo
+    /// Возвращает пост, если он виден `viewer_id` (черновики видит только автор).
+    pub(crate) async fn get_post(
+        &self,
+        viewer_id: Option<i64>,
+        id: i64,
+    ) -> Result<Post, DomainError> {
+        let post = self
+            .repo
             .get_post(id)
             .await?
-            .ok_or(DomainError::NotFound(format!("post id: {id}")))
+            .filter(|post| post.is_visible_to(viewer_id))
+            .ok_or(DomainError::NotFound(format!("post id: {id}")))?;
+        self.load_tags(post).await
+    }
+
+    /// Пост по текущему или прежнему slug; у найденного поста `slug` всегда текущий,
+    /// поэтому вызывающий видит, что адрес устарел.
+    pub(crate) async fn get_post_by_slug(
+        &self,
+        viewer_id: Option<i64>,
+        slug: &str,
+    ) -> Result<Post, DomainError> {
+        let slug = slug.trim().to_lowercase();
+        let post = self
+            .repo
+            .get_post_by_slug(&slug)
+            .await?
+            .filter(|post| post.is_visible_to(viewer_id))
+            .ok_or(DomainError::NotFound(format!("post slug: {slug}")))?;
+        self.load_tags(post).await
     }
 
     pub(crate) async fn update_post(
         &self,
-        actor_user_id: i64,
+        actor: Actor,
         post_id: i64,
         req: UpdatePostRequest,
     ) -> Result<Post, DomainError> {
         let req = req.validate()?;
+        let current = self
+            .authorized_post(&actor, PostAction::Edit, post_id)
+            .await?;
+        let content_format = req.content_format.unwrap_or(current.content_format);
+        // slug следует за заголовком; прежний остаётся в истории для редиректов
+        let slug = if req.title == current.title {
+            current.slug
+        } else {
+            self.unique_slug(&req.title, Some(post_id)).await?
+        };
         let patch = PostPatch {
+            slug,
+            content_html: render_content(&req.content, content_format),
             title: req.title,
             content: req.content,
+            content_format,
+            expected_version: req.expected_version,
+            tags: req.tags.clone(),
         };
-        self.repo
-            .update_post_owned(post_id, actor_user_id, patch)
+        let post = self
+            .repo
+            .update_post_content(post_id, actor.user_id, patch)
             .await?
-            .ok_or(DomainError::NotFound(format!("post id: {post_id}")))
+            .ok_or(DomainError::NotFound(format!("post id: {post_id}")))?;
+        match req.tags {
+            Some(tags) => Ok(post.with_tags(tags)),
+            None => self.load_tags(post).await,
+        }
     }
 
+    /// Перемещает пост в корзину; с `expected_version` — только если его никто не изменил.
     pub(crate) async fn delete_post(
         &self,
-        actor_user_id: i64,
+        actor: Actor,
         post_id: i64,
+        expected_version: Option<i64>,
     ) -> Result<(), DomainError> {
         let original_post = self
-            .repo
-            .get_post(post_id)
-            .await?
-            .ok_or(DomainError::NotFound(format!("post id: {post_id}")))?;
+            .authorized_post(&actor, PostAction::Delete, post_id)
+            .await?;
 
-        if original_post.author_id != actor_user_id {
-            return Err(DomainError::Forbidden);
+        if let Some(expected) = expected_version
+            && expected != original_post.version
+        {
+            return Err(DomainError::VersionMismatch {
+                expected,
+                current: original_post.version,
+            });
         }
 
-        let deleted = self.repo.delete_post(post_id).await?;
+        let deleted = self.repo.delete_post(post_id, expected_version).await?;
         if !deleted {
-            return Err(DomainError::NotFound(format!("post id: {post_id}")));
+            // пост успели изменить или удалить между проверкой и удалением
+            return match (expected_version, self.repo.get_post(post_id).await?) {
+                (Some(expected), Some(current)) => Err(DomainError::VersionMismatch {
+                    expected,
+                    current: current.version,
+                }),
+                _ => Err(DomainError::NotFound(format!("post id: {post_id}"))),
+            };
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn publish_post(
+        &self,
+        actor: Actor,
+        post_id: i64,
+    ) -> Result<Post, DomainError> {
+        self.change_status(actor, post_id, PostStatus::Published)
+            .await
+    }
+
+    pub(crate) async fn unpublish_post(
+        &self,
+        actor: Actor,
+        post_id: i64,
+    ) -> Result<Post, DomainError> {
+        self.change_status(actor, post_id, PostStatus::Draft).await
+    }
+
+    pub(crate) async fn archive_post(
+        &self,
+        actor: Actor,
+        post_id: i64,
+    ) -> Result<Post, DomainError> {
+        self.change_status(actor, post_id, PostStatus::Archived)
+            .await
+    }
+
+    /// Планирует автоматическую публикацию черновика; `None` отменяет её.
+    pub(crate) async fn schedule_post(
+        &self,
+        actor: Actor,
+        post_id: i64,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> Result<Post, DomainError> {
+        let post = self
+            .authorized_post(&actor, PostAction::ChangeStatus, post_id)
+            .await?;
+        if let Some(publish_at) = publish_at {
+            validate_publish_at(publish_at, Utc::now())?;
+        }
+        if post.status != PostStatus::Draft {
+            return Err(DomainError::Validation {
+                field: "publish_at",
+                message: "can be set only for draft post",
+            });
+        }
+
+        // пост могли опубликовать между проверкой и записью
+        let post =
+            self.repo
+                .schedule_post(post_id, publish_at)
+                .await?
+                .ok_or(DomainError::Validation {
+                    field: "publish_at",
+                    message: "can be set only for draft post",
+                })?;
+        self.load_tags(post).await
+    }
+
+    /// Публикует черновики, время публикации которых наступило, пачками по `batch_size`.
+    /// Возвращает число опубликованных постов.
+    pub(crate) async fn publish_due_posts(&self, batch_size: u32) -> Result<usize, DomainError> {
+        let mut published = 0;
+        loop {
+            let ids = self.repo.publish_due_posts(Utc::now(), batch_size).await?;
+            published += ids.len();
+            if ids.len() < batch_size as usize {
+                return Ok(published);
+            }
+        }
+    }
+
+    /// Корзина автора: его удалённые посты, сначала удалённые последними.
+    pub(crate) async fn list_trash(
+        &self,
+        actor: Actor,
+        page: u32,
+        page_size: u32,
+    ) -> Result<ListPostsResult, DomainError> {
+        actor.require_scope(TokenScope::PostsRead)?;
+        let pagination = Pagination { page, page_size };
+        let posts = self.repo.list_trash(actor.user_id, pagination).await?;
+        let posts = self.load_tags_for_all(posts).await?;
+        let total = self.repo.total_trash(actor.user_id).await?;
+
+        Ok(ListPostsResult {
+            posts,
+            page,
+            page_size,
+            total,
+            next_cursor: None,
+        })
+    }
+
+    /// Возвращает пост из корзины с прежними статусом, slug и тегами.
+    pub(crate) async fn restore_post(
+        &self,
+        actor: Actor,
+        post_id: i64,
+    ) -> Result<Post, DomainError> {
+        self.authorized_trashed_post(&actor, post_id).await?;
+        let post = self
+            .repo
+            .restore_post(post_id)
+            .await?
+            .ok_or(DomainError::NotFound(format!(
+                "post id in trash: {post_id}"
+            )))?;
+        self.load_tags(post).await
+    }
+
+    /// Окончательно удаляет пост из корзины.
+    pub(crate) async fn purge_post(&self, actor: Actor, post_id: i64) -> Result<(), DomainError> {
+        self.authorized_trashed_post(&actor, post_id).await?;
+        if !self.repo.purge_post(post_id).await? {
+            // пост успели восстановить или удалить между проверкой и удалением
+            return Err(DomainError::NotFound(format!(
+                "post id in trash: {post_id}"
+            )));
         }
         Ok(())
     }
 
+    /// Окончательно удаляет посты, пролежавшие в корзине дольше `retention`,
+    /// пачками по `batch_size`. Возвращает число удалённых постов.
+    pub(crate) async fn purge_expired_posts(
+        &self,
+        retention: Duration,
+        batch_size: u32,
+    ) -> Result<usize, DomainError> {
+        let deleted_before = Utc::now() - retention;
+        let mut purged = 0;
+        loop {
+            let ids = self
+                .repo
+                .purge_deleted_posts(deleted_before, batch_size)
+                .await?;
+            purged += ids.len();
+            if ids.len() < batch_size as usize {
+                return Ok(purged);
+            }
+        }
+    }
+
+    /// Лента постов; непустой `tags` оставляет посты, у которых есть все указанные теги,
+    /// `author` — посты пользователя с этим логином.
     pub(crate) async fn list_posts(
         &self,
+        viewer_id: Option<i64>,
+        tags: &[String],
+        author: Option<&str>,
         page: u32,
         page_size: u32,
     ) -> Result<ListPostsResult, DomainError> {
         let pagination = Pagination { page, page_size };
-        let posts = self.repo.list_posts(pagination).await?;
-        let total = self.repo.total_posts().await?;
+        let filter = PostListFilter {
+            viewer_id,
+            tags: normalize_tags(tags)?,
+            author: normalize_author(author),
+        };
+        let posts = self.repo.list_posts(&filter, pagination).await?;
+        let posts = self.load_tags_for_all(posts).await?;
+        let total = self.repo.total_posts(&filter).await?;
+
+        // курсор от последнего поста позволяет перейти с offset-страницы на keyset
+        let seen = i64::from(page.saturating_sub(1)) * i64::from(page_size) + posts.len() as i64;
+        let next_cursor = posts
+            .last()
+            .filter(|_| seen < total)
+            .map(|post| PostCursor::after(post).encode());
+
+        Ok(ListPostsResult {
+            posts,
+            page,
+            page_size,
+            total,
+            next_cursor,
+        })
+    }
+
+    /// Keyset-пагинация ленты: `cursor` — `next_cursor` предыдущей страницы,
+    /// `None` — первая страница. Устойчива к вставке новых постов между запросами.
+    pub(crate) async fn list_posts_by_cursor(
+        &self,
+        viewer_id: Option<i64>,
+        tags: &[String],
+        author: Option<&str>,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<ListPostsResult, DomainError> {
+        let after = cursor.map(PostCursor::decode).transpose()?;
+        let filter = PostListFilter {
+            viewer_id,
+            tags: normalize_tags(tags)?,
+            author: normalize_author(author),
+        };
+        // лишний пост показывает, есть ли следующая страница
+        let mut posts = self
+            .repo
+            .list_posts_after(&filter, after, limit.saturating_add(1))
+            .await?;
+        let has_more = posts.len() > limit as usize;
+        posts.truncate(limit as usize);
+
+        let next_cursor = posts
+            .last()
+            .filter(|_| has_more)
+            .map(|post| PostCursor::after(post).encode());
+        let posts = self.load_tags_for_all(posts).await?;
+        let total = self.repo.total_posts(&filter).await?;
 
         Ok(ListPostsResult {
             posts,
+            page: 1,
+            page_size: limit,
+            total,
+            next_cursor,
+        })
+    }
+
+    /// Последние опубликованные посты для RSS/Atom/JSON-лент в порядке публикации.
+    pub(crate) async fn feed_posts(
+        &self,
+        tags: &[String],
+        author: Option<&str>,
+        limit: u32,
+    ) -> Result<FeedPosts, DomainError> {
+        let tags = normalize_tags(tags)?;
+        let author = normalize_author(author);
+        let posts = self
+            .repo
+            .list_feed_posts(&tags, author.as_deref(), limit)
+            .await?;
+        let posts = self.load_tags_for_all(posts).await?;
+        // пост может лишиться тега и выпасть из области, поэтому для тегов область — весь блог
+        let changed_at = self.repo.last_post_change(author.as_deref()).await?;
+        Ok(FeedPosts { posts, changed_at })
+    }
+
+    /// Полнотекстовый поиск по заголовкам и содержимому видимых `viewer_id` постов.
+    pub(crate) async fn search_posts(
+        &self,
+        viewer_id: Option<i64>,
+        query: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<SearchPostsResult, DomainError> {
+        let query = normalize_search_query(query)?;
+        let pagination = Pagination { page, page_size };
+        let hits = self
+            .repo
+            .search_posts(viewer_id, &query, pagination)
+            .await?;
+        let total = self.repo.total_search_results(viewer_id, &query).await?;
+
+        let ids: Vec<i64> = hits.iter().map(|hit| hit.post.id).collect();
+        let mut tags = self.tags.tags_for_posts(&ids).await?;
+        let hits = hits
+            .into_iter()
+            .map(|hit| {
+                let post_tags = tags.remove(&hit.post.id).unwrap_or_default();
+                PostSearchHit {
+                    post: hit.post.with_tags(post_tags),
+                    rank: hit.rank,
+                    snippet: render_highlight(&hit.snippet),
+                }
+            })
+            .collect();
+
+        Ok(SearchPostsResult {
+            hits,
             page,
             page_size,
             total,
         })
     }
+
+    pub(crate) async fn list_tags(&self) -> Result<Vec<TagWithCount>, DomainError> {
+        self.tags.list_tags_with_counts().await
+    }
+
+    /// История правок поста от новых ревизий к старым; доступна автору и модераторам.
+    pub(crate) async fn list_revisions(
+        &self,
+        actor: Actor,
+        post_id: i64,
+    ) -> Result<Vec<PostRevision>, DomainError> {
+        self.authorized_post(&actor, PostAction::ViewHistory, post_id)
+            .await?;
+        self.repo.list_revisions(post_id).await
+    }
+
+    pub(crate) async fn get_revision(
+        &self,
+        actor: Actor,
+        post_id: i64,
+        revision: i32,
+    ) -> Result<PostRevision, DomainError> {
+        self.authorized_post(&actor, PostAction::ViewHistory, post_id)
+            .await?;
+        self.find_revision(post_id, revision).await
+    }
+
+    /// Построчный diff текста между ревизиями; `to = None` сравнивает с текущей версией.
+    pub(crate) async fn diff_revisions(
+        &self,
+        actor: Actor,
+        post_id: i64,
+        from: i32,
+        to: Option<i32>,
+    ) -> Result<RevisionDiff, DomainError> {
+        let post = self
+            .authorized_post(&actor, PostAction::ViewHistory, post_id)
+            .await?;
+        let from_revision = self.find_revision(post_id, from).await?;
+        let (to_title, to_content) = match to {
+            Some(to) => {
+                let to_revision = self.find_revision(post_id, to).await?;
+                (to_revision.title, to_revision.content)
+            }
+            None => (post.title, post.content),
+        };
+
+        Ok(RevisionDiff {
+            from_revision: from,
+            to_revision: to,
+            lines: diff_lines(&from_revision.content, &to_content),
+            from_title: from_revision.title,
+            to_title,
+        })
+    }
+
+    /// Возвращает пост к старой ревизии обычной правкой, поэтому текущая версия
+    /// тоже попадает в историю и восстановление можно отменить. Теги не меняются.
+    pub(crate) async fn restore_revision(
+        &self,
+        actor: Actor,
+        post_id: i64,
+        revision: i32,
+    ) -> Result<Post, DomainError> {
+        self.authorized_post(&actor, PostAction::Edit, post_id)
+            .await?;
+        let revision = self.find_revision(post_id, revision).await?;
+        // ревизии хранят только текст: он отрисовывается в текущем формате поста
+        let req = UpdatePostRequest {
+            title: revision.title,
+            content: revision.content,
+            content_format: None,
+            tags: None,
+            expected_version: None,
+        };
+        self.update_post(actor, post_id, req).await
+    }
+
+    /// Свободный slug для заголовка; slug поста `post_id` (текущий и прежние) не считается занятым.
+    async fn unique_slug(&self, title: &str, post_id: Option<i64>) -> Result<String, DomainError> {
+        let base = slugify(title);
+        let taken = self.repo.slugs_taken(&base, post_id).await?;
+        Ok(first_free_slug(&base, &taken))
+    }
+
+    async fn authorized_post(
+        &self,
+        actor: &Actor,
+        action: PostAction,
+        post_id: i64,
+    ) -> Result<Post, DomainError> {
+        let post = self
+            .repo
+            .get_post(post_id)
+            .await?
+            .ok_or(DomainError::NotFound(format!("post id: {post_id}")))?;
+        // чужой черновик не выдаёт себя через 403: для записи он не найден, как и для чтения
+        if !post.is_visible_to(Some(actor.user_id)) && !actor.role.can_moderate() {
+            return Err(DomainError::NotFound(format!("post id: {post_id}")));
+        }
+        authorize_post(actor, action, &post)?;
+        Ok(post)
+    }
+
+    /// Восстановить или окончательно удалить пост может тот, кто вправе его удалить.
+    async fn authorized_trashed_post(
+        &self,
+        actor: &Actor,
+        post_id: i64,
+    ) -> Result<Post, DomainError> {
+        let post = self
+            .repo
+            .get_trashed_post(post_id)
+            .await?
+            .ok_or(DomainError::NotFound(format!(
+                "post id in trash: {post_id}"
+            )))?;
+        // корзину видит только её владелец: чужому удалённый пост не найден, а не запрещён
+        if !actor.is_author_of(&post) && !actor.role.can_moderate() {
+            return Err(DomainError::NotFound(format!(
+                "post id in trash: {post_id}"
+            )));
+        }
+        authorize_post(actor, PostAction::Delete, &post)?;
+        Ok(post)
+    }
+
+    async fn find_revision(
+        &self,
+        post_id: i64,
+        revision: i32,
+    ) -> Result<PostRevision, DomainError> {
+        self.repo
+            .get_revision(post_id, revision)
+            .await?
+            .ok_or(DomainError::NotFound(format!(
+                "revision {revision} of post id: {post_id}"
+            )))
+    }
+
+    async fn load_tags(&self, post: Post) -> Result<Post, DomainError> {
+        let mut tags = self.tags.tags_for_posts(&[post.id]).await?;
+        let post_tags = tags.remove(&post.id).unwrap_or_default();
+        Ok(post.with_tags(post_tags))
+    }
+
+    async fn load_tags_for_all(&self, posts: Vec<Post>) -> Result<Vec<Post>, DomainError> {
+        let ids: Vec<i64> = posts.iter().map(|post| post.id).collect();
+        let mut tags = self.tags.tags_for_posts(&ids).await?;
+        Ok(posts
+            .into_iter()
+            .map(|post| {
+                let post_tags = tags.remove(&post.id).unwrap_or_default();
+                post.with_tags(post_tags)
+            })
+            .collect())
+    }
+
+    async fn change_status(
+        &self,
+        actor: Actor,
+        post_id: i64,
+        status: PostStatus,
+    ) -> Result<Post, DomainError> {
+        self.authorized_post(&actor, PostAction::ChangeStatus, post_id)
+            .await?;
+
+        let post = self
+            .repo
+            .update_post_status(post_id, status)
+            .await?
+            .ok_or(DomainError::NotFound(format!("post id: {post_id}")))?;
+        self.load_tags(post).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{HashMap, HashSet};
     use std::sync::{Arc, Mutex};
 
     use async_trait::async_trait;
-    use chrono::Utc;
+    use chrono::{DateTime, Duration, Utc};
 
     use super::BlogService;
-    use crate::data::post_repository::{NewPost, Pagination, PostPatch, PostRepository};
+    use crate::application::policy::Actor;
+    use crate::data::post_repository::{
+        NewPost, Pagination, PostListFilter, PostPatch, PostRepository,
+    };
+    use crate::data::tag_repository::TagRepository;
+    use crate::domain::cursor::PostCursor;
     use crate::domain::error::DomainError;
-    use crate::domain::post::{CreatePostRequest, Post, UpdatePostRequest};
+    use crate::domain::post::{
+        ContentFormat, CreatePostRequest, Post, PostStatus, UpdatePostRequest,
+    };
+    use crate::domain::revision::{DiffOp, PostRevision};
+    use crate::domain::search::PostSearchHit;
+    use crate::domain::tag::TagWithCount;
+    use crate::domain::user::Role;
+
+    type ListAfterCall = (Option<PostCursor>, u32);
+    type DeleteCall = (i64, Option<i64>);
+    type ScheduleCall = (i64, Option<DateTime<Utc>>);
 
     #[derive(Clone)]
     struct FakePostRepo {
         created_input: Arc<Mutex<Option<NewPost>>>,
         post_for_get: Arc<Mutex<Option<Post>>>,
-        update_owned_result: Arc<Mutex<Option<Post>>>,
-        update_owned_call: Arc<Mutex<Option<(i64, i64, PostPatch)>>>,
+        update_content_result: Arc<Mutex<Option<Post>>>,
+        update_content_call: Arc<Mutex<Option<(i64, i64, PostPatch)>>>,
+        status_call: Arc<Mutex<Option<(i64, PostStatus)>>>,
+        delete_call: Arc<Mutex<Option<DeleteCall>>>,
         delete_result: Arc<Mutex<bool>>,
         list_result: Arc<Mutex<Vec<Post>>>,
+        list_filter: Arc<Mutex<Option<PostListFilter>>>,
+        list_after_call: Arc<Mutex<Option<ListAfterCall>>>,
         total_result: Arc<Mutex<i64>>,
+        search_result: Arc<Mutex<Vec<PostSearchHit>>>,
+        search_query: Arc<Mutex<Option<String>>>,
+        revisions: Arc<Mutex<Vec<PostRevision>>>,
+        taken_slugs: Arc<Mutex<HashSet<String>>>,
+        schedule_call: Arc<Mutex<Option<ScheduleCall>>>,
+        due_batches: Arc<Mutex<Vec<Vec<i64>>>>,
+        trashed_post: Arc<Mutex<Option<Post>>>,
+        purge_batches: Arc<Mutex<Vec<Vec<i64>>>>,
+        purge_cutoff: Arc<Mutex<Option<DateTime<Utc>>>>,
+        last_change: Arc<Mutex<Option<DateTime<Utc>>>>,
     }
 
     impl FakePostRepo {
@@ -126,11 +710,25 @@ mod tests {
             Self {
                 created_input: Arc::new(Mutex::new(None)),
                 post_for_get: Arc::new(Mutex::new(None)),
-                update_owned_result: Arc::new(Mutex::new(None)),
-                update_owned_call: Arc::new(Mutex::new(None)),
+                update_content_result: Arc::new(Mutex::new(None)),
+                update_content_call: Arc::new(Mutex::new(None)),
+                status_call: Arc::new(Mutex::new(None)),
+                delete_call: Arc::new(Mutex::new(None)),
                 delete_result: Arc::new(Mutex::new(true)),
                 list_result: Arc::new(Mutex::new(Vec::new())),
+                list_filter: Arc::new(Mutex::new(None)),
+                list_after_call: Arc::new(Mutex::new(None)),
                 total_result: Arc::new(Mutex::new(0)),
+                search_result: Arc::new(Mutex::new(Vec::new())),
+                search_query: Arc::new(Mutex::new(None)),
+                revisions: Arc::new(Mutex::new(Vec::new())),
+                taken_slugs: Arc::new(Mutex::new(HashSet::new())),
+                schedule_call: Arc::new(Mutex::new(None)),
+                due_batches: Arc::new(Mutex::new(Vec::new())),
+                trashed_post: Arc::new(Mutex::new(None)),
+                purge_batches: Arc::new(Mutex::new(Vec::new())),
+                purge_cutoff: Arc::new(Mutex::new(None)),
+                last_change: Arc::new(Mutex::new(None)),
             }
         }
     }
@@ -158,31 +756,117 @@ mod tests {
                 .clone())
         }
 
-        async fn update_post_owned(
+        async fn get_post_by_slug(&self, slug: &str) -> Result<Option<Post>, DomainError> {
+            Ok(self
+                .post_for_get
+                .lock()
+                .expect("post_for_get mutex poisoned")
+                .clone()
+                .filter(|post| post.slug == slug))
+        }
+
+        async fn slugs_taken(
+            &self,
+            prefix: &str,
+            _except_post_id: Option<i64>,
+        ) -> Result<HashSet<String>, DomainError> {
+            Ok(self
+                .taken_slugs
+                .lock()
+                .expect("taken_slugs mutex poisoned")
+                .iter()
+                .filter(|slug| slug.starts_with(prefix))
+                .cloned()
+                .collect())
+        }
+
+        async fn update_post_content(
             &self,
             post_id: i64,
-            owner_id: i64,
+            editor_id: i64,
             patch: PostPatch,
         ) -> Result<Option<Post>, DomainError> {
             *self
-                .update_owned_call
+                .update_content_call
                 .lock()
-                .expect("update_owned_call mutex poisoned") = Some((post_id, owner_id, patch));
+                .expect("update_content_call mutex poisoned") = Some((post_id, editor_id, patch));
             Ok(self
-                .update_owned_result
+                .update_content_result
                 .lock()
-                .expect("update_owned_result mutex poisoned")
+                .expect("update_content_result mutex poisoned")
                 .clone())
         }
 
-        async fn delete_post(&self, _id: i64) -> Result<bool, DomainError> {
+        async fn update_post_status(
+            &self,
+            post_id: i64,
+            status: PostStatus,
+        ) -> Result<Option<Post>, DomainError> {
+            *self.status_call.lock().expect("status_call mutex poisoned") = Some((post_id, status));
+            let post = self
+                .post_for_get
+                .lock()
+                .expect("post_for_get mutex poisoned")
+                .clone();
+            Ok(post.map(|post| {
+                let published_at = (status == PostStatus::Published).then_some(post.created_at);
+                post.with_publication(status, published_at)
+                    .expect("status must be valid")
+            }))
+        }
+
+        async fn schedule_post(
+            &self,
+            post_id: i64,
+            publish_at: Option<DateTime<Utc>>,
+        ) -> Result<Option<Post>, DomainError> {
+            *self
+                .schedule_call
+                .lock()
+                .expect("schedule_call mutex poisoned") = Some((post_id, publish_at));
+            let post = self
+                .post_for_get
+                .lock()
+                .expect("post_for_get mutex poisoned")
+                .clone();
+            Ok(post.map(|post| {
+                post.with_schedule(publish_at)
+                    .expect("schedule must be valid")
+            }))
+        }
+
+        async fn publish_due_posts(
+            &self,
+            _now: DateTime<Utc>,
+            limit: u32,
+        ) -> Result<Vec<i64>, DomainError> {
+            let mut batches = self.due_batches.lock().expect("due_batches mutex poisoned");
+            if batches.is_empty() {
+                return Ok(Vec::new());
+            }
+            let batch = batches.remove(0);
+            assert!(batch.len() <= limit as usize);
+            Ok(batch)
+        }
+
+        async fn delete_post(
+            &self,
+            id: i64,
+            expected_version: Option<i64>,
+        ) -> Result<bool, DomainError> {
+            *self.delete_call.lock().expect("delete_call mutex poisoned") =
+                Some((id, expected_version));
             Ok(*self
                 .delete_result
                 .lock()
                 .expect("delete_result mutex poisoned"))
         }
 
-        async fn list_posts(&self, _pagination: Pagination) -> Result<Vec<Post>, DomainError> {
+        async fn list_trash(
+            &self,
+            _author_id: i64,
+            _pagination: Pagination,
+        ) -> Result<Vec<Post>, DomainError> {
             Ok(self
                 .list_result
                 .lock()
@@ -190,85 +874,667 @@ mod tests {
                 .clone())
         }
 
-        async fn total_posts(&self) -> Result<i64, DomainError> {
+        async fn total_trash(&self, _author_id: i64) -> Result<i64, DomainError> {
             Ok(*self
                 .total_result
                 .lock()
                 .expect("total_result mutex poisoned"))
         }
-    }
-
-    #[tokio::test]
-    async fn create_post_normalizes_request_before_repo_call() {
-        let repo = FakePostRepo::new();
-        let service = BlogService::new(repo.clone());
 
-        let req = CreatePostRequest {
-            title: "  title  ".to_string(),
-            content: "  content  ".to_string(),
-        };
+        async fn get_trashed_post(&self, _id: i64) -> Result<Option<Post>, DomainError> {
+            Ok(self
+                .trashed_post
+                .lock()
+                .expect("trashed_post mutex poisoned")
+                .clone())
+        }
 
-        let created = service
-            .create_post(10, req)
-            .await
-            .expect("create_post must succeed");
+        async fn restore_post(&self, _id: i64) -> Result<Option<Post>, DomainError> {
+            Ok(self
+                .trashed_post
+                .lock()
+                .expect("trashed_post mutex poisoned")
+                .take()
+                .map(|post| post.with_deleted_at(None)))
+        }
 
-        assert_eq!(created.title, "title");
-        assert_eq!(created.content, "content");
+        async fn purge_post(&self, _id: i64) -> Result<bool, DomainError> {
+            Ok(self
+                .trashed_post
+                .lock()
+                .expect("trashed_post mutex poisoned")
+                .take()
+                .is_some())
+        }
 
-        let input = repo
-            .created_input
-            .lock()
+        async fn purge_deleted_posts(
+            &self,
+            deleted_before: DateTime<Utc>,
+            limit: u32,
+        ) -> Result<Vec<i64>, DomainError> {
+            *self
+                .purge_cutoff
+                .lock()
+                .expect("purge_cutoff mutex poisoned") = Some(deleted_before);
+            let mut batches = self
+                .purge_batches
+                .lock()
+                .expect("purge_batches mutex poisoned");
+            if batches.is_empty() {
+                return Ok(Vec::new());
+            }
+            let batch = batches.remove(0);
+            assert!(batch.len() <= limit as usize);
+            Ok(batch)
+        }
+
+        async fn list_feed_posts(
+            &self,
+            tags: &[String],
+            author: Option<&str>,
+            _limit: u32,
+        ) -> Result<Vec<Post>, DomainError> {
+            *self.list_filter.lock().expect("list_filter mutex poisoned") = Some(PostListFilter {
+                viewer_id: None,
+                tags: tags.to_vec(),
+                author: author.map(str::to_string),
+            });
+            Ok(self
+                .list_result
+                .lock()
+                .expect("list_result mutex poisoned")
+                .clone())
+        }
+
+        async fn last_post_change(
+            &self,
+            _author: Option<&str>,
+        ) -> Result<Option<DateTime<Utc>>, DomainError> {
+            Ok(*self.last_change.lock().expect("last_change mutex poisoned"))
+        }
+
+        async fn list_posts(
+            &self,
+            filter: &PostListFilter,
+            _pagination: Pagination,
+        ) -> Result<Vec<Post>, DomainError> {
+            *self.list_filter.lock().expect("list_filter mutex poisoned") = Some(filter.clone());
+            Ok(self
+                .list_result
+                .lock()
+                .expect("list_result mutex poisoned")
+                .clone())
+        }
+
+        async fn list_posts_after(
+            &self,
+            _filter: &PostListFilter,
+            after: Option<PostCursor>,
+            limit: u32,
+        ) -> Result<Vec<Post>, DomainError> {
+            *self
+                .list_after_call
+                .lock()
+                .expect("list_after_call mutex poisoned") = Some((after, limit));
+            let posts = self
+                .list_result
+                .lock()
+                .expect("list_result mutex poisoned")
+                .clone();
+            Ok(posts.into_iter().take(limit as usize).collect())
+        }
+
+        async fn total_posts(&self, _filter: &PostListFilter) -> Result<i64, DomainError> {
+            Ok(*self
+                .total_result
+                .lock()
+                .expect("total_result mutex poisoned"))
+        }
+
+        async fn list_revisions(&self, post_id: i64) -> Result<Vec<PostRevision>, DomainError> {
+            Ok(self
+                .revisions
+                .lock()
+                .expect("revisions mutex poisoned")
+                .iter()
+                .filter(|revision| revision.post_id == post_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_revision(
+            &self,
+            post_id: i64,
+            revision: i32,
+        ) -> Result<Option<PostRevision>, DomainError> {
+            Ok(self
+                .revisions
+                .lock()
+                .expect("revisions mutex poisoned")
+                .iter()
+                .find(|item| item.post_id == post_id && item.revision == revision)
+                .cloned())
+        }
+
+        async fn search_posts(
+            &self,
+            _viewer_id: Option<i64>,
+            query: &str,
+            _pagination: Pagination,
+        ) -> Result<Vec<PostSearchHit>, DomainError> {
+            *self
+                .search_query
+                .lock()
+                .expect("search_query mutex poisoned") = Some(query.to_string());
+            Ok(self
+                .search_result
+                .lock()
+                .expect("search_result mutex poisoned")
+                .clone())
+        }
+
+        async fn total_search_results(
+            &self,
+            _viewer_id: Option<i64>,
+            _query: &str,
+        ) -> Result<i64, DomainError> {
+            Ok(self
+                .search_result
+                .lock()
+                .expect("search_result mutex poisoned")
+                .len() as i64)
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct FakeTagRepo {
+        post_tags: Arc<Mutex<HashMap<i64, Vec<String>>>>,
+    }
+
+    #[async_trait]
+    impl TagRepository for FakeTagRepo {
+        async fn tags_for_posts(
+            &self,
+            post_ids: &[i64],
+        ) -> Result<HashMap<i64, Vec<String>>, DomainError> {
+            let post_tags = self.post_tags.lock().expect("post_tags mutex poisoned");
+            Ok(post_ids
+                .iter()
+                .filter_map(|id| post_tags.get(id).map(|tags| (*id, tags.clone())))
+                .collect())
+        }
+
+        async fn list_tags_with_counts(&self) -> Result<Vec<TagWithCount>, DomainError> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn create_post_normalizes_request_before_repo_call() {
+        let repo = FakePostRepo::new();
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        let req = CreatePostRequest {
+            title: "  title  ".to_string(),
+            content: "  content  ".to_string(),
+            content_format: ContentFormat::Plain,
+            status: PostStatus::Draft,
+            tags: Vec::new(),
+            publish_at: None,
+        };
+
+        let created = service
+            .create_post(user(10), req)
+            .await
+            .expect("create_post must succeed");
+
+        assert_eq!(created.title, "title");
+        assert_eq!(created.content, "content");
+
+        let input = repo
+            .created_input
+            .lock()
             .expect("created_input mutex poisoned")
             .clone()
             .expect("repo input must be captured");
         assert_eq!(input.title, "title");
         assert_eq!(input.content, "content");
         assert_eq!(input.author_id, 10);
+        assert_eq!(input.status, PostStatus::Draft);
+    }
+
+    #[tokio::test]
+    async fn create_post_requires_verified_email_when_enabled() {
+        let repo = FakePostRepo::new();
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default())
+            .with_required_email_verification(true);
+        let req = || CreatePostRequest {
+            title: "title".to_string(),
+            content: "content".to_string(),
+            content_format: ContentFormat::Plain,
+            status: PostStatus::Published,
+            tags: Vec::new(),
+            publish_at: None,
+        };
+
+        let err = service
+            .create_post(user(10), req())
+            .await
+            .expect_err("unverified user must be rejected");
+        assert!(matches!(err, DomainError::EmailNotVerified));
+        assert!(
+            repo.created_input
+                .lock()
+                .expect("created_input mutex poisoned")
+                .is_none()
+        );
+
+        service
+            .create_post(user(10).with_email_verified(true), req())
+            .await
+            .expect("verified user may post");
     }
 
     #[tokio::test]
     async fn get_post_returns_not_found_when_missing() {
         let repo = FakePostRepo::new();
-        let service = BlogService::new(repo);
+        let service = BlogService::new(repo, FakeTagRepo::default());
 
         let err = service
-            .get_post(42)
+            .get_post(None, 42)
             .await
             .expect_err("post must be missing");
         assert!(matches!(err, DomainError::NotFound(_)));
     }
 
     #[tokio::test]
-    async fn update_post_uses_update_post_owned_contract() {
+    async fn get_post_hides_draft_from_other_users() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_draft(7, 10));
+
+        let service = BlogService::new(repo, FakeTagRepo::default());
+        let err = service
+            .get_post(Some(99), 7)
+            .await
+            .expect_err("draft must be hidden");
+        assert!(matches!(err, DomainError::NotFound(_)));
+
+        let err = service
+            .get_post(None, 7)
+            .await
+            .expect_err("draft must be hidden");
+        assert!(matches!(err, DomainError::NotFound(_)));
+
+        let post = service
+            .get_post(Some(10), 7)
+            .await
+            .expect("author must see draft");
+        assert_eq!(post.status, PostStatus::Draft);
+    }
+
+    #[tokio::test]
+    async fn publish_post_sets_published_status_for_author() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_draft(7, 10));
+
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        let post = service
+            .publish_post(user(10), 7)
+            .await
+            .expect("publish must succeed");
+        assert_eq!(post.status, PostStatus::Published);
+        assert!(post.published_at.is_some());
+
+        let call = repo
+            .status_call
+            .lock()
+            .expect("status_call mutex poisoned")
+            .expect("status call must be captured");
+        assert_eq!(call, (7, PostStatus::Published));
+    }
+
+    #[tokio::test]
+    async fn unpublish_post_returns_forbidden_for_non_owner() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_post(7, "title", "body", 99));
+
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        let err = service
+            .unpublish_post(user(10), 7)
+            .await
+            .expect_err("must be forbidden");
+        assert!(matches!(err, DomainError::Forbidden));
+        assert!(
+            repo.status_call
+                .lock()
+                .expect("status_call mutex poisoned")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn write_to_foreign_draft_looks_like_missing_post() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_draft(7, 99));
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        let err = service
+            .delete_post(user(10), 7, None)
+            .await
+            .expect_err("stranger must not see the draft");
+        assert!(matches!(err, DomainError::NotFound(_)));
+        let err = service
+            .publish_post(user(10), 7)
+            .await
+            .expect_err("stranger must not see the draft");
+        assert!(matches!(err, DomainError::NotFound(_)));
+        assert!(
+            repo.delete_call
+                .lock()
+                .expect("delete_call mutex poisoned")
+                .is_none()
+        );
+
+        service
+            .delete_post(Actor::new(10, Role::Moderator), 7, None)
+            .await
+            .expect("moderator deletes any draft");
+    }
+
+    #[tokio::test]
+    async fn update_post_uses_update_post_content_contract() {
         let repo = FakePostRepo::new();
         *repo
-            .update_owned_result
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_post(7, "old", "body", 10));
+        *repo
+            .update_content_result
             .lock()
-            .expect("update_owned_result mutex poisoned") = Some(sample_post(7, "new", "body", 10));
+            .expect("update_content_result mutex poisoned") =
+            Some(sample_post(7, "new", "body", 10));
 
-        let service = BlogService::new(repo.clone());
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
         let req = UpdatePostRequest {
             title: "  new  ".to_string(),
             content: "  body  ".to_string(),
+            content_format: None,
+            tags: None,
+            expected_version: Some(4),
         };
 
         let updated = service
-            .update_post(10, 7, req)
+            .update_post(user(10), 7, req)
             .await
             .expect("update must succeed");
         assert_eq!(updated.id, 7);
 
         let call = repo
-            .update_owned_call
+            .update_content_call
             .lock()
-            .expect("update_owned_call mutex poisoned")
+            .expect("update_content_call mutex poisoned")
             .clone()
             .expect("update call must be captured");
         assert_eq!(call.0, 7);
         assert_eq!(call.1, 10);
         assert_eq!(call.2.title, "new");
         assert_eq!(call.2.content, "body");
+        assert_eq!(call.2.expected_version, Some(4));
+    }
+
+    #[tokio::test]
+    async fn update_post_renders_content_in_requested_or_current_format() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(
+            sample_post(7, "title", "old", 10)
+                .with_content_html(ContentFormat::Markdown, "<p>old</p>".to_string()),
+        );
+        *repo
+            .update_content_result
+            .lock()
+            .expect("update_content_result mutex poisoned") =
+            Some(sample_post(7, "title", "**new**", 10));
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        let req = |content_format| UpdatePostRequest {
+            title: "title".to_string(),
+            content: "**new**".to_string(),
+            content_format,
+            tags: None,
+            expected_version: None,
+        };
+        let patch = || {
+            repo.update_content_call
+                .lock()
+                .expect("update_content_call mutex poisoned")
+                .clone()
+                .expect("update call must be captured")
+                .2
+        };
+
+        service
+            .update_post(user(10), 7, req(None))
+            .await
+            .expect("update must succeed");
+        assert_eq!(patch().content_format, ContentFormat::Markdown);
+        assert_eq!(patch().content_html, "<p><strong>new</strong></p>\n");
+
+        service
+            .update_post(user(10), 7, req(Some(ContentFormat::Plain)))
+            .await
+            .expect("update must succeed");
+        assert_eq!(patch().content_format, ContentFormat::Plain);
+        assert_eq!(patch().content_html, "<p>**new**</p>");
+    }
+
+    #[tokio::test]
+    async fn create_post_picks_free_transliterated_slug() {
+        let repo = FakePostRepo::new();
+        *repo.taken_slugs.lock().expect("taken_slugs mutex poisoned") =
+            HashSet::from(["privet-mir".to_string(), "privet-mir-2".to_string()]);
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        service
+            .create_post(
+                user(10),
+                CreatePostRequest {
+                    title: "Привет, мир!".to_string(),
+                    content: "body".to_string(),
+                    content_format: ContentFormat::Plain,
+                    status: PostStatus::Published,
+                    tags: Vec::new(),
+                    publish_at: None,
+                },
+            )
+            .await
+            .expect("create must succeed");
+
+        let input = repo
+            .created_input
+            .lock()
+            .expect("created_input mutex poisoned")
+            .clone()
+            .expect("create call must be captured");
+        assert_eq!(input.slug, "privet-mir-3");
+    }
+
+    #[tokio::test]
+    async fn update_post_changes_slug_only_with_title() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") =
+            Some(sample_post(7, "Старое", "body", 10).with_slug("staroe".to_string()));
+        *repo
+            .update_content_result
+            .lock()
+            .expect("update_content_result mutex poisoned") =
+            Some(sample_post(7, "Старое", "body", 10));
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        let req = |title: &str| UpdatePostRequest {
+            title: title.to_string(),
+            content: "new body".to_string(),
+            content_format: None,
+            tags: None,
+            expected_version: None,
+        };
+        let patched_slug = || {
+            repo.update_content_call
+                .lock()
+                .expect("update_content_call mutex poisoned")
+                .clone()
+                .expect("update call must be captured")
+                .2
+                .slug
+        };
+
+        service
+            .update_post(user(10), 7, req("Старое"))
+            .await
+            .expect("update must succeed");
+        assert_eq!(patched_slug(), "staroe");
+
+        service
+            .update_post(user(10), 7, req("Новое название"))
+            .await
+            .expect("update must succeed");
+        assert_eq!(patched_slug(), "novoe-nazvanie");
+    }
+
+    #[tokio::test]
+    async fn get_post_by_slug_normalizes_slug_and_hides_foreign_drafts() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") =
+            Some(sample_draft(7, 10).with_slug("draft".to_string()));
+        let service = BlogService::new(repo, FakeTagRepo::default());
+
+        let post = service
+            .get_post_by_slug(Some(10), " Draft ")
+            .await
+            .expect("author sees own draft");
+        assert_eq!(post.id, 7);
+
+        let err = service
+            .get_post_by_slug(Some(11), "draft")
+            .await
+            .expect_err("draft is hidden from others");
+        assert!(matches!(err, DomainError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn create_post_schedules_draft_only_in_future() {
+        let repo = FakePostRepo::new();
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        let req = |status, publish_at| CreatePostRequest {
+            title: "title".to_string(),
+            content: "body".to_string(),
+            content_format: ContentFormat::Plain,
+            status,
+            tags: Vec::new(),
+            publish_at,
+        };
+        let tomorrow = Utc::now() + Duration::days(1);
+
+        service
+            .create_post(user(10), req(PostStatus::Draft, Some(tomorrow)))
+            .await
+            .expect("scheduled draft must be created");
+        let input = repo
+            .created_input
+            .lock()
+            .expect("created_input mutex poisoned")
+            .clone()
+            .expect("create call must be captured");
+        assert_eq!(input.publish_at, Some(tomorrow));
+
+        for invalid in [
+            req(PostStatus::Published, Some(tomorrow)),
+            req(PostStatus::Draft, Some(Utc::now() - Duration::minutes(1))),
+        ] {
+            let err = service
+                .create_post(user(10), invalid)
+                .await
+                .expect_err("schedule must be rejected");
+            assert!(matches!(
+                err,
+                DomainError::Validation {
+                    field: "publish_at",
+                    ..
+                }
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn schedule_post_requires_draft() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_post(7, "title", "body", 10));
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        let tomorrow = Utc::now() + Duration::days(1);
+
+        let err = service
+            .schedule_post(user(10), 7, Some(tomorrow))
+            .await
+            .expect_err("published post cannot be scheduled");
+        assert!(matches!(err, DomainError::Validation { .. }));
+
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_draft(7, 10));
+        let post = service
+            .schedule_post(user(10), 7, Some(tomorrow))
+            .await
+            .expect("draft can be scheduled");
+        assert_eq!(post.publish_at, Some(tomorrow));
+
+        let err = service
+            .schedule_post(user(11), 7, None)
+            .await
+            .expect_err("only author can reschedule");
+        assert!(matches!(err, DomainError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn publish_due_posts_drains_full_batches() {
+        let repo = FakePostRepo::new();
+        *repo.due_batches.lock().expect("due_batches mutex poisoned") =
+            vec![vec![1, 2], vec![3, 4], vec![5]];
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        let published = service
+            .publish_due_posts(2)
+            .await
+            .expect("publishing must succeed");
+
+        assert_eq!(published, 5);
+        assert!(
+            repo.due_batches
+                .lock()
+                .expect("due_batches mutex poisoned")
+                .is_empty()
+        );
     }
 
     #[tokio::test]
@@ -279,14 +1545,152 @@ mod tests {
             .lock()
             .expect("post_for_get mutex poisoned") = Some(sample_post(7, "title", "body", 99));
 
-        let service = BlogService::new(repo);
+        let service = BlogService::new(repo, FakeTagRepo::default());
         let err = service
-            .delete_post(10, 7)
+            .delete_post(user(10), 7, None)
             .await
             .expect_err("must be forbidden");
         assert!(matches!(err, DomainError::Forbidden));
     }
 
+    #[tokio::test]
+    async fn moderator_may_delete_foreign_post() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_post(7, "title", "body", 99));
+
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        service
+            .delete_post(Actor::new(10, Role::Moderator), 7, None)
+            .await
+            .expect("moderator may delete");
+        assert_eq!(
+            *repo.delete_call.lock().expect("delete_call mutex poisoned"),
+            Some((7, None))
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_post_rejects_stale_version() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") =
+            Some(sample_post(7, "title", "body", 10).with_version(3));
+
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        let err = service
+            .delete_post(user(10), 7, Some(2))
+            .await
+            .expect_err("stale version must be rejected");
+        assert!(matches!(
+            err,
+            DomainError::VersionMismatch {
+                expected: 2,
+                current: 3
+            }
+        ));
+        assert!(
+            repo.delete_call
+                .lock()
+                .expect("delete_call mutex poisoned")
+                .is_none()
+        );
+
+        service
+            .delete_post(user(10), 7, Some(3))
+            .await
+            .expect("current version must be deleted");
+        assert_eq!(
+            *repo.delete_call.lock().expect("delete_call mutex poisoned"),
+            Some((7, Some(3)))
+        );
+    }
+
+    #[tokio::test]
+    async fn restore_post_allows_only_author_or_moderator() {
+        let repo = FakePostRepo::new();
+        let trashed = sample_post(7, "title", "body", 10).with_deleted_at(Some(Utc::now()));
+        *repo
+            .trashed_post
+            .lock()
+            .expect("trashed_post mutex poisoned") = Some(trashed);
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        let err = service
+            .restore_post(user(11), 7)
+            .await
+            .expect_err("stranger cannot restore");
+        assert!(matches!(err, DomainError::NotFound(_)));
+
+        let post = service
+            .restore_post(user(10), 7)
+            .await
+            .expect("author restores own post");
+        assert_eq!(post.id, 7);
+        assert_eq!(post.deleted_at, None);
+    }
+
+    #[tokio::test]
+    async fn purge_post_requires_post_in_trash() {
+        let repo = FakePostRepo::new();
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        let err = service
+            .purge_post(user(10), 7)
+            .await
+            .expect_err("post outside trash cannot be purged");
+        assert!(matches!(err, DomainError::NotFound(_)));
+
+        *repo
+            .trashed_post
+            .lock()
+            .expect("trashed_post mutex poisoned") =
+            Some(sample_post(7, "title", "body", 10).with_deleted_at(Some(Utc::now())));
+        let err = service
+            .purge_post(user(11), 7)
+            .await
+            .expect_err("stranger cannot see foreign trash");
+        assert!(matches!(err, DomainError::NotFound(_)));
+        service
+            .purge_post(Actor::new(99, Role::Moderator), 7)
+            .await
+            .expect("moderator purges foreign post");
+        assert!(
+            repo.trashed_post
+                .lock()
+                .expect("trashed_post mutex poisoned")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn purge_expired_posts_drains_batches_older_than_retention() {
+        let repo = FakePostRepo::new();
+        *repo
+            .purge_batches
+            .lock()
+            .expect("purge_batches mutex poisoned") = vec![vec![1, 2], vec![3]];
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        let before = Utc::now() - Duration::days(30);
+        let purged = service
+            .purge_expired_posts(Duration::days(30), 2)
+            .await
+            .expect("purge must succeed");
+
+        assert_eq!(purged, 3);
+        let cutoff = repo
+            .purge_cutoff
+            .lock()
+            .expect("purge_cutoff mutex poisoned")
+            .expect("cutoff must be passed");
+        assert!(cutoff >= before && cutoff <= Utc::now() - Duration::days(30));
+    }
+
     #[tokio::test]
     async fn list_posts_returns_posts_and_total() {
         let repo = FakePostRepo::new();
@@ -297,18 +1701,405 @@ mod tests {
             .lock()
             .expect("total_result mutex poisoned") = 1;
 
-        let service = BlogService::new(repo);
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
         let result = service
-            .list_posts(1, 10)
+            .list_posts(Some(10), &[], None, 1, 10)
             .await
             .expect("list_posts must succeed");
 
+        let filter = repo
+            .list_filter
+            .lock()
+            .expect("list_filter mutex poisoned")
+            .clone()
+            .expect("list filter must be captured");
+        assert_eq!(filter.viewer_id, Some(10));
         assert_eq!(result.page, 1);
         assert_eq!(result.page_size, 10);
         assert_eq!(result.total, 1);
         assert_eq!(result.posts.len(), 1);
     }
 
+    #[tokio::test]
+    async fn create_post_stores_normalized_tags() {
+        let repo = FakePostRepo::new();
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        let req = CreatePostRequest {
+            title: "title".to_string(),
+            content: "content".to_string(),
+            content_format: ContentFormat::Plain,
+            status: PostStatus::Published,
+            tags: vec!["Rust".to_string(), " web ".to_string(), "rust".to_string()],
+            publish_at: None,
+        };
+        let created = service
+            .create_post(user(10), req)
+            .await
+            .expect("create_post must succeed");
+
+        let expected = vec!["rust".to_string(), "web".to_string()];
+        assert_eq!(created.tags, expected);
+        let input = repo
+            .created_input
+            .lock()
+            .expect("created_input mutex poisoned")
+            .clone()
+            .expect("create_post must be called");
+        assert_eq!(input.tags, expected);
+    }
+
+    #[tokio::test]
+    async fn update_post_without_tags_keeps_existing_tags() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_post(7, "old", "body", 10));
+        *repo
+            .update_content_result
+            .lock()
+            .expect("update_content_result mutex poisoned") =
+            Some(sample_post(7, "new", "body", 10));
+        let tags = FakeTagRepo::default();
+        tags.post_tags
+            .lock()
+            .expect("post_tags mutex poisoned")
+            .insert(7, vec!["rust".to_string()]);
+
+        let service = BlogService::new(repo, tags);
+        let req = UpdatePostRequest {
+            title: "new".to_string(),
+            content: "body".to_string(),
+            content_format: None,
+            tags: None,
+            expected_version: None,
+        };
+        let updated = service
+            .update_post(user(10), 7, req)
+            .await
+            .expect("update must succeed");
+
+        assert_eq!(updated.tags, vec!["rust".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn update_post_replaces_tags_in_content_patch() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_post(7, "old", "body", 10));
+        *repo
+            .update_content_result
+            .lock()
+            .expect("update_content_result mutex poisoned") =
+            Some(sample_post(7, "new", "body", 10));
+
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        let req = UpdatePostRequest {
+            title: "new".to_string(),
+            content: "body".to_string(),
+            content_format: None,
+            tags: Some(vec![" Web ".to_string(), "rust".to_string()]),
+            expected_version: None,
+        };
+        let updated = service
+            .update_post(user(10), 7, req)
+            .await
+            .expect("update must succeed");
+
+        let expected = vec!["web".to_string(), "rust".to_string()];
+        assert_eq!(updated.tags, expected);
+        let (_, _, patch) = repo
+            .update_content_call
+            .lock()
+            .expect("update_content_call mutex poisoned")
+            .clone()
+            .expect("update_post_content must be called");
+        assert_eq!(patch.tags, Some(expected));
+    }
+
+    #[tokio::test]
+    async fn list_posts_normalizes_filters_and_attaches_tags() {
+        let repo = FakePostRepo::new();
+        *repo.list_result.lock().expect("list_result mutex poisoned") =
+            vec![sample_post(1, "a", "b", 10), sample_post(2, "c", "d", 10)];
+        let tags = FakeTagRepo::default();
+        tags.post_tags
+            .lock()
+            .expect("post_tags mutex poisoned")
+            .insert(2, vec!["rust".to_string()]);
+
+        let service = BlogService::new(repo.clone(), tags);
+        let result = service
+            .list_posts(None, &[" Rust ".to_string()], Some(" alice "), 1, 10)
+            .await
+            .expect("list_posts must succeed");
+
+        let filter = repo
+            .list_filter
+            .lock()
+            .expect("list_filter mutex poisoned")
+            .clone()
+            .expect("list filter must be captured");
+        assert_eq!(filter.tags, vec!["rust".to_string()]);
+        assert_eq!(filter.author.as_deref(), Some("alice"));
+        assert!(result.posts[0].tags.is_empty());
+        assert_eq!(result.posts[1].tags, vec!["rust".to_string()]);
+
+        let err = service
+            .list_posts(None, &["bad tag".to_string()], None, 1, 10)
+            .await
+            .expect_err("invalid tag filter must be rejected");
+        assert!(matches!(err, DomainError::Validation { field: "tags", .. }));
+    }
+
+    #[tokio::test]
+    async fn feed_posts_report_last_change_of_whole_scope() {
+        let repo = FakePostRepo::new();
+        *repo.list_result.lock().expect("list_result mutex poisoned") =
+            vec![sample_post(1, "a", "b", 10)];
+        // пост, снятый с публикации, уже не в ленте, но сдвигает время её изменения
+        let changed_at = Utc::now() + Duration::hours(1);
+        *repo.last_change.lock().expect("last_change mutex poisoned") = Some(changed_at);
+        let tags = FakeTagRepo::default();
+        tags.post_tags
+            .lock()
+            .expect("post_tags mutex poisoned")
+            .insert(1, vec!["rust".to_string()]);
+
+        let service = BlogService::new(repo.clone(), tags);
+        let feed = service
+            .feed_posts(&[" Rust ".to_string()], Some(" alice "), 20)
+            .await
+            .expect("feed_posts must succeed");
+
+        let filter = repo
+            .list_filter
+            .lock()
+            .expect("list_filter mutex poisoned")
+            .clone()
+            .expect("feed filter must be captured");
+        assert_eq!(filter.tags, vec!["rust".to_string()]);
+        assert_eq!(filter.author.as_deref(), Some("alice"));
+        assert_eq!(feed.posts[0].tags, vec!["rust".to_string()]);
+        assert_eq!(feed.changed_at, Some(changed_at));
+    }
+
+    #[tokio::test]
+    async fn list_posts_by_cursor_fetches_extra_row_to_detect_next_page() {
+        let repo = FakePostRepo::new();
+        *repo.list_result.lock().expect("list_result mutex poisoned") = vec![
+            sample_post(3, "a", "b", 10),
+            sample_post(2, "c", "d", 10),
+            sample_post(1, "e", "f", 10),
+        ];
+        *repo
+            .total_result
+            .lock()
+            .expect("total_result mutex poisoned") = 3;
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        let first = service
+            .list_posts_by_cursor(None, &[], None, None, 2)
+            .await
+            .expect("first page must succeed");
+        assert_eq!(first.posts.len(), 2);
+        let next_cursor = first.next_cursor.expect("next page must exist");
+        let decoded = PostCursor::decode(&next_cursor).expect("cursor must decode");
+        assert_eq!(decoded.id, 2);
+        assert_eq!(
+            *repo
+                .list_after_call
+                .lock()
+                .expect("list_after_call mutex poisoned"),
+            Some((None, 3))
+        );
+
+        let last = service
+            .list_posts_by_cursor(None, &[], None, Some(&next_cursor), 5)
+            .await
+            .expect("next page must succeed");
+        assert!(last.next_cursor.is_none());
+        assert_eq!(
+            *repo
+                .list_after_call
+                .lock()
+                .expect("list_after_call mutex poisoned"),
+            Some((Some(decoded), 6))
+        );
+
+        let err = service
+            .list_posts_by_cursor(None, &[], None, Some("garbage"), 5)
+            .await
+            .expect_err("invalid cursor must be rejected");
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                field: "cursor",
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn list_posts_returns_next_cursor_only_when_more_posts_remain() {
+        let repo = FakePostRepo::new();
+        *repo.list_result.lock().expect("list_result mutex poisoned") =
+            vec![sample_post(5, "a", "b", 10), sample_post(4, "c", "d", 10)];
+        *repo
+            .total_result
+            .lock()
+            .expect("total_result mutex poisoned") = 3;
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+
+        let result = service
+            .list_posts(None, &[], None, 1, 2)
+            .await
+            .expect("list_posts must succeed");
+        let cursor = result.next_cursor.expect("next page must exist");
+        assert_eq!(
+            PostCursor::decode(&cursor).expect("cursor must decode").id,
+            4
+        );
+
+        *repo
+            .total_result
+            .lock()
+            .expect("total_result mutex poisoned") = 2;
+        let result = service
+            .list_posts(None, &[], None, 1, 2)
+            .await
+            .expect("list_posts must succeed");
+        assert!(result.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn search_posts_trims_query_and_renders_highlighted_snippets() {
+        let repo = FakePostRepo::new();
+        *repo
+            .search_result
+            .lock()
+            .expect("search_result mutex poisoned") = vec![PostSearchHit {
+            post: sample_post(3, "rust", "<b>rust</b> tips", 10),
+            rank: 0.5,
+            snippet: "<b>\u{2}rust\u{3}</b> tips".to_string(),
+        }];
+        let tags = FakeTagRepo::default();
+        tags.post_tags
+            .lock()
+            .expect("post_tags mutex poisoned")
+            .insert(3, vec!["rust".to_string()]);
+
+        let service = BlogService::new(repo.clone(), tags);
+        let result = service
+            .search_posts(None, "  rust  ", 1, 10)
+            .await
+            .expect("search_posts must succeed");
+
+        assert_eq!(
+            repo.search_query
+                .lock()
+                .expect("search_query mutex poisoned")
+                .as_deref(),
+            Some("rust")
+        );
+        assert_eq!(result.total, 1);
+        assert_eq!(
+            result.hits[0].snippet,
+            "&lt;b&gt;<mark>rust</mark>&lt;/b&gt; tips"
+        );
+        assert_eq!(result.hits[0].post.tags, vec!["rust".to_string()]);
+
+        let err = service
+            .search_posts(None, "   ", 1, 10)
+            .await
+            .expect_err("blank query must be rejected");
+        assert!(matches!(err, DomainError::Validation { field: "q", .. }));
+    }
+
+    #[tokio::test]
+    async fn diff_revisions_compares_with_current_version_by_default() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") =
+            Some(sample_post(7, "current", "one\nthree\n", 10));
+        *repo.revisions.lock().expect("revisions mutex poisoned") =
+            vec![sample_revision(7, 1, "one\ntwo\n")];
+
+        let service = BlogService::new(repo, FakeTagRepo::default());
+        let diff = service
+            .diff_revisions(user(10), 7, 1, None)
+            .await
+            .expect("diff must succeed");
+
+        assert_eq!(diff.from_title, "rev 1");
+        assert_eq!(diff.to_title, "current");
+        let ops: Vec<DiffOp> = diff.lines.iter().map(|line| line.op).collect();
+        assert_eq!(ops, vec![DiffOp::Equal, DiffOp::Delete, DiffOp::Insert]);
+
+        let err = service
+            .diff_revisions(user(10), 7, 1, Some(5))
+            .await
+            .expect_err("missing revision must be reported");
+        assert!(matches!(err, DomainError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn restore_revision_applies_old_text_as_content_update() {
+        let repo = FakePostRepo::new();
+        *repo
+            .post_for_get
+            .lock()
+            .expect("post_for_get mutex poisoned") = Some(sample_post(7, "current", "new", 10));
+        *repo
+            .update_content_result
+            .lock()
+            .expect("update_content_result mutex poisoned") =
+            Some(sample_post(7, "rev 2", "old", 10));
+        *repo.revisions.lock().expect("revisions mutex poisoned") =
+            vec![sample_revision(7, 2, "old")];
+
+        let service = BlogService::new(repo.clone(), FakeTagRepo::default());
+        let err = service
+            .restore_revision(user(99), 7, 2)
+            .await
+            .expect_err("stranger must be forbidden");
+        assert!(matches!(err, DomainError::Forbidden));
+
+        service
+            .restore_revision(user(10), 7, 2)
+            .await
+            .expect("restore must succeed");
+        let call = repo
+            .update_content_call
+            .lock()
+            .expect("update_content_call mutex poisoned")
+            .clone()
+            .expect("update call must be captured");
+        assert_eq!((call.0, call.1), (7, 10));
+        assert_eq!(call.2.title, "rev 2");
+        assert_eq!(call.2.content, "old");
+    }
+
+    fn sample_revision(post_id: i64, revision: i32, content: &str) -> PostRevision {
+        PostRevision {
+            post_id,
+            revision,
+            title: format!("rev {revision}"),
+            content: content.to_string(),
+            editor_id: 10,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn user(id: i64) -> Actor {
+        Actor::new(id, Role::User)
+    }
+
     fn sample_post(id: i64, title: &str, content: &str, author_id: i64) -> Post {
         Post::new(
             id,
@@ -320,4 +2111,10 @@ mod tests {
         )
         .expect("sample post must be valid")
     }
+
+    fn sample_draft(id: i64, author_id: i64) -> Post {
+        sample_post(id, "draft", "body", author_id)
+            .with_publication(PostStatus::Draft, None)
+            .expect("sample draft must be valid")
+    }
 }
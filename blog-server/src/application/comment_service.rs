@@ -0,0 +1,511 @@
+use crate::application::policy::{Actor, authorize_comment_creation, authorize_comment_delete};
+use crate::data::comment_repository::{CommentRepository, NewComment};
+use crate::data::post_repository::PostRepository;
+use crate::domain::comment::{Comment, CommentNode, CreateCommentRequest, build_comment_tree};
+use crate::domain::error::DomainError;
+use crate::domain::post::Post;
+
+pub(crate) struct CommentService<C: CommentRepository, P: PostRepository> {
+    comments: C,
+    posts: P,
+}
+
+impl<C: CommentRepository, P: PostRepository> CommentService<C, P> {
+    pub(crate) fn new(comments: C, posts: P) -> Self {
+        Self { comments, posts }
+    }
+
+    /// Дерево комментариев поста; пост должен быть виден `viewer_id`.
+    pub(crate) async fn list_comments(
+        &self,
+        viewer_id: Option<i64>,
+        post_id: i64,
+    ) -> Result<Vec<CommentNode>, DomainError> {
+        self.visible_post(viewer_id, post_id).await?;
+        let comments = self.comments.list_comments_for_post(post_id).await?;
+        Ok(build_comment_tree(comments))
+    }
+
+    /// Комментировать можно только видимый пользователю пост;
+    /// ответ допускается лишь на комментарий того же поста.
+    pub(crate) async fn create_comment(
+        &self,
+        author: Actor,
+        post_id: i64,
+        req: CreateCommentRequest,
+    ) -> Result<Comment, DomainError> {
+        let req = req.validate()?;
+        authorize_comment_creation(&author)?;
+        let author_id = author.user_id;
+        self.visible_post(Some(author_id), post_id).await?;
+
+        if let Some(parent_id) = req.parent_id {
+            self.comments
+                .get_comment(parent_id)
+                .await?
+                .filter(|parent| parent.post_id == post_id)
+                .ok_or(DomainError::NotFound(format!("comment id: {parent_id}")))?;
+        }
+
+        let new_comment = NewComment {
+            post_id,
+            author_id,
+            parent_id: req.parent_id,
+            content: req.content,
+        };
+        self.comments.create_comment(new_comment).await
+    }
+
+    /// Удаляет комментарий вместе с ответами. Разрешено автору комментария,
+    /// автору поста и модераторам.
+    pub(crate) async fn delete_comment(
+        &self,
+        actor: Actor,
+        post_id: i64,
+        comment_id: i64,
+    ) -> Result<(), DomainError> {
+        let comment = self
+            .comments
+            .get_comment(comment_id)
+            .await?
+            .filter(|comment| comment.post_id == post_id)
+            .ok_or(DomainError::NotFound(format!("comment id: {comment_id}")))?;
+
+        let post = self
+            .posts
+            .get_post(post_id)
+            .await?
+            .ok_or(DomainError::NotFound(format!("post id: {post_id}")))?;
+
+        authorize_comment_delete(&actor, &comment, &post)?;
+
+        let deleted = self.comments.delete_comment(comment_id).await?;
+        if !deleted {
+            return Err(DomainError::NotFound(format!("comment id: {comment_id}")));
+        }
+        Ok(())
+    }
+
+    async fn visible_post(
+        &self,
+        viewer_id: Option<i64>,
+        post_id: i64,
+    ) -> Result<Post, DomainError> {
+        self.posts
+            .get_post(post_id)
+            .await?
+            .filter(|post| post.is_visible_to(viewer_id))
+            .ok_or(DomainError::NotFound(format!("post id: {post_id}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+
+    use super::CommentService;
+    use crate::application::policy::Actor;
+    use crate::data::comment_repository::{CommentRepository, NewComment};
+    use crate::data::post_repository::{
+        NewPost, Pagination, PostListFilter, PostPatch, PostRepository,
+    };
+    use crate::domain::comment::{Comment, CreateCommentRequest};
+    use crate::domain::cursor::PostCursor;
+    use crate::domain::error::DomainError;
+    use crate::domain::post::{Post, PostStatus};
+    use crate::domain::revision::PostRevision;
+    use crate::domain::search::PostSearchHit;
+    use crate::domain::user::Role;
+
+    #[derive(Clone, Default)]
+    struct FakeCommentRepo {
+        comments: Arc<Mutex<Vec<Comment>>>,
+        created_input: Arc<Mutex<Option<NewComment>>>,
+        deleted_id: Arc<Mutex<Option<i64>>>,
+    }
+
+    #[async_trait]
+    impl CommentRepository for FakeCommentRepo {
+        async fn create_comment(&self, input: NewComment) -> Result<Comment, DomainError> {
+            *self
+                .created_input
+                .lock()
+                .expect("created_input mutex poisoned") = Some(input.clone());
+            Ok(sample_comment(
+                100,
+                input.post_id,
+                input.author_id,
+                input.parent_id,
+            ))
+        }
+
+        async fn get_comment(&self, id: i64) -> Result<Option<Comment>, DomainError> {
+            Ok(self
+                .comments
+                .lock()
+                .expect("comments mutex poisoned")
+                .iter()
+                .find(|comment| comment.id == id)
+                .cloned())
+        }
+
+        async fn list_comments_for_post(&self, post_id: i64) -> Result<Vec<Comment>, DomainError> {
+            Ok(self
+                .comments
+                .lock()
+                .expect("comments mutex poisoned")
+                .iter()
+                .filter(|comment| comment.post_id == post_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn delete_comment(&self, id: i64) -> Result<bool, DomainError> {
+            *self.deleted_id.lock().expect("deleted_id mutex poisoned") = Some(id);
+            Ok(true)
+        }
+    }
+
+    /// Отдаёт один заранее заданный пост; остальные методы в сервисе комментариев не нужны.
+    #[derive(Clone, Default)]
+    struct FakePostRepo {
+        post: Arc<Mutex<Option<Post>>>,
+    }
+
+    #[async_trait]
+    impl PostRepository for FakePostRepo {
+        async fn create_post(&self, _input: NewPost) -> Result<Post, DomainError> {
+            Err(DomainError::Unexpected("not used".to_string()))
+        }
+
+        async fn get_post(&self, id: i64) -> Result<Option<Post>, DomainError> {
+            Ok(self
+                .post
+                .lock()
+                .expect("post mutex poisoned")
+                .clone()
+                .filter(|post| post.id == id))
+        }
+
+        async fn get_post_by_slug(&self, _slug: &str) -> Result<Option<Post>, DomainError> {
+            Ok(None)
+        }
+
+        async fn slugs_taken(
+            &self,
+            _prefix: &str,
+            _except_post_id: Option<i64>,
+        ) -> Result<HashSet<String>, DomainError> {
+            Ok(HashSet::new())
+        }
+
+        async fn update_post_content(
+            &self,
+            _post_id: i64,
+            _editor_id: i64,
+            _patch: PostPatch,
+        ) -> Result<Option<Post>, DomainError> {
+            Ok(None)
+        }
+
+        async fn update_post_status(
+            &self,
+            _post_id: i64,
+            _status: PostStatus,
+        ) -> Result<Option<Post>, DomainError> {
+            Ok(None)
+        }
+
+        async fn schedule_post(
+            &self,
+            _post_id: i64,
+            _publish_at: Option<DateTime<Utc>>,
+        ) -> Result<Option<Post>, DomainError> {
+            Ok(None)
+        }
+
+        async fn publish_due_posts(
+            &self,
+            _now: DateTime<Utc>,
+            _limit: u32,
+        ) -> Result<Vec<i64>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn delete_post(
+            &self,
+            _id: i64,
+            _expected_version: Option<i64>,
+        ) -> Result<bool, DomainError> {
+            Ok(false)
+        }
+
+        async fn list_trash(
+            &self,
+            _author_id: i64,
+            _pagination: Pagination,
+        ) -> Result<Vec<Post>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn total_trash(&self, _author_id: i64) -> Result<i64, DomainError> {
+            Ok(0)
+        }
+
+        async fn get_trashed_post(&self, _id: i64) -> Result<Option<Post>, DomainError> {
+            Ok(None)
+        }
+
+        async fn restore_post(&self, _id: i64) -> Result<Option<Post>, DomainError> {
+            Ok(None)
+        }
+
+        async fn purge_post(&self, _id: i64) -> Result<bool, DomainError> {
+            Ok(false)
+        }
+
+        async fn purge_deleted_posts(
+            &self,
+            _deleted_before: DateTime<Utc>,
+            _limit: u32,
+        ) -> Result<Vec<i64>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_feed_posts(
+            &self,
+            _tags: &[String],
+            _author: Option<&str>,
+            _limit: u32,
+        ) -> Result<Vec<Post>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn last_post_change(
+            &self,
+            _author: Option<&str>,
+        ) -> Result<Option<DateTime<Utc>>, DomainError> {
+            Ok(None)
+        }
+
+        async fn list_posts(
+            &self,
+            _filter: &PostListFilter,
+            _pagination: Pagination,
+        ) -> Result<Vec<Post>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_posts_after(
+            &self,
+            _filter: &PostListFilter,
+            _after: Option<PostCursor>,
+            _limit: u32,
+        ) -> Result<Vec<Post>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn total_posts(&self, _filter: &PostListFilter) -> Result<i64, DomainError> {
+            Ok(0)
+        }
+
+        async fn list_revisions(&self, _post_id: i64) -> Result<Vec<PostRevision>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_revision(
+            &self,
+            _post_id: i64,
+            _revision: i32,
+        ) -> Result<Option<PostRevision>, DomainError> {
+            Ok(None)
+        }
+
+        async fn search_posts(
+            &self,
+            _viewer_id: Option<i64>,
+            _query: &str,
+            _pagination: Pagination,
+        ) -> Result<Vec<PostSearchHit>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn total_search_results(
+            &self,
+            _viewer_id: Option<i64>,
+            _query: &str,
+        ) -> Result<i64, DomainError> {
+            Ok(0)
+        }
+    }
+
+    fn service_with_post(
+        post: Post,
+        comments: Vec<Comment>,
+    ) -> (
+        CommentService<FakeCommentRepo, FakePostRepo>,
+        FakeCommentRepo,
+    ) {
+        let comment_repo = FakeCommentRepo::default();
+        *comment_repo
+            .comments
+            .lock()
+            .expect("comments mutex poisoned") = comments;
+        let post_repo = FakePostRepo::default();
+        *post_repo.post.lock().expect("post mutex poisoned") = Some(post);
+        (
+            CommentService::new(comment_repo.clone(), post_repo),
+            comment_repo,
+        )
+    }
+
+    #[tokio::test]
+    async fn list_comments_returns_tree_for_visible_post() {
+        let (service, _) = service_with_post(
+            sample_post(1, 10),
+            vec![
+                sample_comment(1, 1, 20, None),
+                sample_comment(2, 1, 30, Some(1)),
+            ],
+        );
+
+        let tree = service
+            .list_comments(None, 1)
+            .await
+            .expect("list_comments must succeed");
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].replies[0].comment.id, 2);
+    }
+
+    #[tokio::test]
+    async fn list_comments_hides_comments_of_foreign_draft() {
+        let draft = sample_post(1, 10)
+            .with_publication(PostStatus::Draft, None)
+            .expect("draft must be valid");
+        let (service, _) = service_with_post(draft, vec![sample_comment(1, 1, 10, None)]);
+
+        let err = service
+            .list_comments(Some(99), 1)
+            .await
+            .expect_err("draft must be hidden");
+        assert!(matches!(err, DomainError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn create_comment_rejects_parent_from_another_post() {
+        let (service, repo) =
+            service_with_post(sample_post(1, 10), vec![sample_comment(5, 2, 20, None)]);
+
+        let req = CreateCommentRequest {
+            content: "reply".to_string(),
+            parent_id: Some(5),
+        };
+        let err = service
+            .create_comment(user(30), 1, req)
+            .await
+            .expect_err("parent from another post must be rejected");
+        assert!(matches!(err, DomainError::NotFound(_)));
+        assert!(
+            repo.created_input
+                .lock()
+                .expect("created_input mutex poisoned")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn create_comment_stores_normalized_reply() {
+        let (service, repo) =
+            service_with_post(sample_post(1, 10), vec![sample_comment(5, 1, 20, None)]);
+
+        let req = CreateCommentRequest {
+            content: "  reply  ".to_string(),
+            parent_id: Some(5),
+        };
+        let created = service
+            .create_comment(user(30), 1, req)
+            .await
+            .expect("create_comment must succeed");
+        assert_eq!(created.parent_id, Some(5));
+
+        let input = repo
+            .created_input
+            .lock()
+            .expect("created_input mutex poisoned")
+            .clone()
+            .expect("repo input must be captured");
+        assert_eq!(input.content, "reply");
+        assert_eq!(input.author_id, 30);
+    }
+
+    #[tokio::test]
+    async fn delete_comment_allows_comment_author_and_post_author_only() {
+        let (service, repo) =
+            service_with_post(sample_post(1, 10), vec![sample_comment(5, 1, 20, None)]);
+
+        let err = service
+            .delete_comment(user(30), 1, 5)
+            .await
+            .expect_err("stranger must be forbidden");
+        assert!(matches!(err, DomainError::Forbidden));
+        assert!(
+            repo.deleted_id
+                .lock()
+                .expect("deleted_id mutex poisoned")
+                .is_none()
+        );
+
+        service
+            .delete_comment(user(20), 1, 5)
+            .await
+            .expect("comment author may delete");
+        service
+            .delete_comment(user(10), 1, 5)
+            .await
+            .expect("post author may delete");
+        assert_eq!(
+            *repo.deleted_id.lock().expect("deleted_id mutex poisoned"),
+            Some(5)
+        );
+    }
+
+    #[tokio::test]
+    async fn moderator_may_delete_any_comment() {
+        let (service, repo) =
+            service_with_post(sample_post(1, 10), vec![sample_comment(5, 1, 20, None)]);
+
+        service
+            .delete_comment(Actor::new(30, Role::Moderator), 1, 5)
+            .await
+            .expect("moderator may delete");
+        assert_eq!(
+            *repo.deleted_id.lock().expect("deleted_id mutex poisoned"),
+            Some(5)
+        );
+    }
+
+    fn user(id: i64) -> Actor {
+        Actor::new(id, Role::User)
+    }
+
+    fn sample_post(id: i64, author_id: i64) -> Post {
+        Post::new(id, "title", "content", author_id, Utc::now(), Utc::now())
+            .expect("sample post must be valid")
+    }
+
+    fn sample_comment(id: i64, post_id: i64, author_id: i64, parent_id: Option<i64>) -> Comment {
+        Comment {
+            id,
+            post_id,
+            author_id,
+            parent_id,
+            content: format!("comment {id}"),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
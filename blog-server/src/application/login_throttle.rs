@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+use crate::domain::error::DomainError;
+
+/// Настройки защиты входа от подбора пароля.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LoginThrottleSettings {
+    /// Сколько неудачных попыток подряд проходит без задержки.
+    pub(crate) free_attempts: u32,
+    /// После стольких неудач по логину вход блокируется на `lockout`.
+    pub(crate) max_attempts_per_username: u32,
+    /// То же для IP-адреса: порог выше, за одним адресом бывает много людей.
+    pub(crate) max_attempts_per_ip: u32,
+    /// Задержка после первой "платной" неудачи, дальше она удваивается.
+    pub(crate) base_delay: Duration,
+    /// Длительность блокировки; столько же помнятся неудачные попытки.
+    pub(crate) lockout: Duration,
+}
+
+impl Default for LoginThrottleSettings {
+    fn default() -> Self {
+        Self {
+            free_attempts: 3,
+            max_attempts_per_username: 10,
+            max_attempts_per_ip: 50,
+            base_delay: Duration::from_secs(1),
+            lockout: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ThrottleKey {
+    Username(String),
+    Ip(IpAddr),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FailureState {
+    failures: u32,
+    last_failure_at: Instant,
+    blocked_until: Instant,
+}
+
+/// Учёт неудачных входов по логину и по IP-адресу в памяти процесса.
+///
+/// Каждая неудача сверх `free_attempts` откладывает следующую попытку
+/// с экспоненциально растущей задержкой, а при достижении порога ключ
+/// блокируется на `lockout`. Пока ключ заблокирован, пароль не проверяется.
+/// Ключей в памяти не больше `max_entries`.
+pub(crate) struct LoginThrottle {
+    settings: LoginThrottleSettings,
+    max_entries: usize,
+    entries: Mutex<HashMap<ThrottleKey, FailureState>>,
+}
+
+impl LoginThrottle {
+    const MAX_ENTRIES: usize = 10_000;
+
+    pub(crate) fn new(settings: LoginThrottleSettings) -> Self {
+        Self::with_max_entries(settings, Self::MAX_ENTRIES)
+    }
+
+    fn with_max_entries(settings: LoginThrottleSettings, max_entries: usize) -> Self {
+        Self {
+            settings,
+            max_entries: max_entries.max(1),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `TooManyAttempts`, если логин или адрес сейчас заблокированы.
+    pub(crate) fn check(&self, username: &str, ip: Option<IpAddr>) -> Result<(), DomainError> {
+        self.check_at(username, ip, Instant::now())
+    }
+
+    pub(crate) fn record_failure(&self, username: &str, ip: Option<IpAddr>) {
+        self.record_failure_at(username, ip, Instant::now());
+    }
+
+    /// Успешный вход обнуляет счётчик логина; счётчик адреса остаётся,
+    /// иначе своя учётная запись позволила бы перебирать чужие.
+    pub(crate) fn record_success(&self, username: &str) {
+        self.entries
+            .lock()
+            .expect("login throttle mutex poisoned")
+            .remove(&ThrottleKey::Username(username.to_string()));
+    }
+
+    fn check_at(
+        &self,
+        username: &str,
+        ip: Option<IpAddr>,
+        now: Instant,
+    ) -> Result<(), DomainError> {
+        let entries = self.entries.lock().expect("login throttle mutex poisoned");
+        let retry_after = Self::keys(username, ip)
+            .filter_map(|key| entries.get(&key))
+            .map(|state| state.blocked_until.saturating_duration_since(now))
+            .max()
+            .unwrap_or_default();
+
+        if retry_after.is_zero() {
+            return Ok(());
+        }
+        Err(DomainError::TooManyAttempts {
+            retry_after_secs: retry_after.as_secs_f64().ceil() as u64,
+        })
+    }
+
+    fn record_failure_at(&self, username: &str, ip: Option<IpAddr>, now: Instant) {
+        let mut entries = self.entries.lock().expect("login throttle mutex poisoned");
+        for key in Self::keys(username, ip) {
+            if entries.len() >= self.max_entries && !entries.contains_key(&key) {
+                self.evict(&mut entries, now);
+            }
+
+            let max_attempts = match key {
+                ThrottleKey::Username(_) => self.settings.max_attempts_per_username,
+                ThrottleKey::Ip(_) => self.settings.max_attempts_per_ip,
+            };
+            let failures = match entries.get(&key) {
+                Some(state) if !self.is_forgotten(state, now) => state.failures + 1,
+                _ => 1,
+            };
+            let delay = self.delay(failures, max_attempts);
+
+            if failures >= max_attempts {
+                warn!(
+                    target: "audit",
+                    username,
+                    ip = ?ip,
+                    key = ?key,
+                    failures,
+                    lockout_secs = delay.as_secs(),
+                    "login locked out after repeated failures"
+                );
+            }
+
+            entries.insert(
+                key,
+                FailureState {
+                    failures,
+                    last_failure_at: now,
+                    blocked_until: now + delay,
+                },
+            );
+        }
+    }
+
+    /// Освобождает место под новый ключ: сначала забывает устаревшие неудачи, а если
+    /// этого мало — ключи, чья блокировка кончилась раньше всех, пока не освободится
+    /// десятая часть предела. Действующие блокировки вытесняются последними.
+    fn evict(&self, entries: &mut HashMap<ThrottleKey, FailureState>, now: Instant) {
+        entries.retain(|_, state| !self.is_forgotten(state, now));
+
+        let low_water = (self.max_entries - self.max_entries / 10).min(self.max_entries - 1);
+        if entries.len() <= low_water {
+            return;
+        }
+        let excess = entries.len() - low_water;
+        let mut order: Vec<(Instant, Instant)> = entries
+            .values()
+            .map(|state| (state.blocked_until, state.last_failure_at))
+            .collect();
+        let (_, &mut cutoff, _) = order.select_nth_unstable(excess - 1);
+        entries.retain(|_, state| (state.blocked_until, state.last_failure_at) > cutoff);
+    }
+
+    fn delay(&self, failures: u32, max_attempts: u32) -> Duration {
+        if failures >= max_attempts {
+            return self.settings.lockout;
+        }
+        let Some(paid) = failures.checked_sub(self.settings.free_attempts + 1) else {
+            return Duration::ZERO;
+        };
+        self.settings
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(paid))
+            .min(self.settings.lockout)
+    }
+
+    /// Неудачи старше `lockout` больше не учитываются.
+    fn is_forgotten(&self, state: &FailureState, now: Instant) -> bool {
+        now >= state.blocked_until
+            && now.saturating_duration_since(state.last_failure_at) >= self.settings.lockout
+    }
+
+    fn keys(username: &str, ip: Option<IpAddr>) -> impl Iterator<Item = ThrottleKey> {
+        std::iter::once(ThrottleKey::Username(username.to_string())).chain(ip.map(ThrottleKey::Ip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::{Duration, Instant};
+
+    use super::{LoginThrottle, LoginThrottleSettings};
+    use crate::domain::error::DomainError;
+
+    const IP: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+
+    fn throttle() -> LoginThrottle {
+        LoginThrottle::new(LoginThrottleSettings {
+            free_attempts: 2,
+            max_attempts_per_username: 6,
+            max_attempts_per_ip: 8,
+            base_delay: Duration::from_secs(1),
+            lockout: Duration::from_secs(60),
+        })
+    }
+
+    fn retry_after(throttle: &LoginThrottle, username: &str, now: Instant) -> u64 {
+        match throttle.check_at(username, Some(IP), now) {
+            Ok(()) => 0,
+            Err(DomainError::TooManyAttempts { retry_after_secs }) => retry_after_secs,
+            Err(err) => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[test]
+    fn delay_doubles_after_free_attempts_and_ends_in_lockout() {
+        let throttle = throttle();
+        let now = Instant::now();
+
+        let delays: Vec<u64> = (0..6)
+            .map(|_| {
+                throttle.record_failure_at("alice", Some(IP), now);
+                retry_after(&throttle, "alice", now)
+            })
+            .collect();
+
+        assert_eq!(delays, vec![0, 0, 1, 2, 4, 60]);
+        assert_eq!(
+            retry_after(&throttle, "alice", now + Duration::from_secs(59)),
+            1
+        );
+        assert_eq!(
+            retry_after(&throttle, "alice", now + Duration::from_secs(60)),
+            0
+        );
+    }
+
+    #[test]
+    fn failures_are_forgotten_after_lockout_period() {
+        let throttle = throttle();
+        let now = Instant::now();
+        for _ in 0..3 {
+            throttle.record_failure_at("alice", Some(IP), now);
+        }
+
+        let later = now + Duration::from_secs(60);
+        throttle.record_failure_at("alice", Some(IP), later);
+
+        assert_eq!(retry_after(&throttle, "alice", later), 0);
+    }
+
+    #[test]
+    fn username_spray_stays_within_cap_and_keeps_lockouts() {
+        let settings = LoginThrottleSettings {
+            max_attempts_per_ip: 1_000_000,
+            ..LoginThrottleSettings::default()
+        };
+        let throttle = LoginThrottle::with_max_entries(settings, 10);
+        let start = Instant::now();
+        for _ in 0..settings.max_attempts_per_username {
+            throttle.record_failure_at("alice", Some(IP), start);
+        }
+
+        for n in 0..1_000 {
+            let now = start + Duration::from_millis(n);
+            throttle.record_failure_at(&format!("user{n}"), Some(IP), now);
+            let len = throttle
+                .entries
+                .lock()
+                .expect("login throttle mutex poisoned")
+                .len();
+            assert!(len <= 10, "{len} entries exceed the cap");
+        }
+        // заблокированный логин не вытесняется ради случайных имён
+        assert!(throttle.check_at("alice", None, start).is_err());
+    }
+
+    #[test]
+    fn success_resets_username_but_not_ip() {
+        let throttle = throttle();
+        let now = Instant::now();
+        for index in 0..8 {
+            let username = if index < 4 { "alice" } else { "bob" };
+            throttle.record_failure_at(username, Some(IP), now);
+        }
+        throttle.record_success("alice");
+
+        assert!(throttle.check_at("alice", None, now).is_ok());
+        assert_eq!(retry_after(&throttle, "alice", now), 60);
+        assert_eq!(retry_after(&throttle, "carol", now), 60);
+    }
+}
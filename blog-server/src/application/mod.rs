@@ -1,2 +1,10 @@
 pub(crate) mod auth_service;
 pub(crate) mod blog_service;
+pub(crate) mod comment_service;
+pub(crate) mod login_throttle;
+pub(crate) mod personal_token_service;
+pub(crate) mod policy;
+pub(crate) mod profile_service;
+pub(crate) mod session_service;
+pub(crate) mod token_revocation_service;
+pub(crate) mod ttl_cache;
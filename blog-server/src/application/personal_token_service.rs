@@ -0,0 +1,365 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::application::policy::Actor;
+use crate::data::personal_token_repository::{NewPersonalToken, PersonalTokenRepository};
+use crate::domain::error::DomainError;
+use crate::domain::personal_token::{
+    CreatePersonalTokenRequest, PERSONAL_TOKEN_PREFIX, PersonalToken,
+};
+use crate::infrastructure::opaque_token::{generate_opaque_token, hash_opaque_token};
+
+/// Только что созданный токен: `secret` показывается один раз и нигде не хранится.
+#[derive(Debug, Clone)]
+pub(crate) struct CreatedPersonalToken {
+    pub(crate) token: PersonalToken,
+    pub(crate) secret: String,
+}
+
+/// Владелец предъявленного токена доступа и права, с которыми он действует.
+#[derive(Debug, Clone)]
+pub(crate) struct PersonalTokenAuth {
+    pub(crate) token_id: i64,
+    pub(crate) actor: Actor,
+    pub(crate) expires_at: Option<DateTime<Utc>>,
+}
+
+/// Персональные токены доступа для автоматизации: выпуск, список, отзыв и проверка.
+pub(crate) struct PersonalTokenService<T: PersonalTokenRepository> {
+    repo: T,
+}
+
+impl<T: PersonalTokenRepository> PersonalTokenService<T> {
+    const MAX_ACTIVE_TOKENS: usize = 50;
+    /// `last_used_at` обновляется не чаще раза в минуту, чтобы не писать в БД на каждый запрос.
+    const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+    pub(crate) fn new(repo: T) -> Self {
+        Self { repo }
+    }
+
+    pub(crate) async fn create_token(
+        &self,
+        user_id: i64,
+        req: CreatePersonalTokenRequest,
+    ) -> Result<CreatedPersonalToken, DomainError> {
+        let req = req.validate()?;
+        if self.repo.list_tokens(user_id).await?.len() >= Self::MAX_ACTIVE_TOKENS {
+            return Err(DomainError::Validation {
+                field: "tokens",
+                message: "too many active tokens, revoke unused ones first",
+            });
+        }
+
+        let secret = format!("{PERSONAL_TOKEN_PREFIX}{}", generate_opaque_token());
+        let token = self
+            .repo
+            .create_token(NewPersonalToken {
+                user_id,
+                name: req.name,
+                token_hash: hash_opaque_token(&secret),
+                scopes: req.scopes,
+                expires_at: req
+                    .expires_in_days
+                    .map(|days| Utc::now() + Duration::days(i64::from(days))),
+            })
+            .await?;
+
+        Ok(CreatedPersonalToken { token, secret })
+    }
+
+    pub(crate) async fn list_tokens(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<PersonalToken>, DomainError> {
+        self.repo.list_tokens(user_id).await
+    }
+
+    pub(crate) async fn revoke_token(
+        &self,
+        user_id: i64,
+        token_id: i64,
+    ) -> Result<(), DomainError> {
+        if !self.repo.revoke_token(user_id, token_id).await? {
+            return Err(DomainError::NotFound(format!("token id: {token_id}")));
+        }
+        Ok(())
+    }
+
+    /// `None` для неизвестного, отозванного или истёкшего токена.
+    pub(crate) async fn authenticate(
+        &self,
+        secret: &str,
+    ) -> Result<Option<PersonalTokenAuth>, DomainError> {
+        if !secret.starts_with(PERSONAL_TOKEN_PREFIX) {
+            return Ok(None);
+        }
+        let Some(owner) = self
+            .repo
+            .find_active_by_hash(&hash_opaque_token(secret))
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        let token = owner.token;
+        if token.is_expired(now) {
+            return Ok(None);
+        }
+        let stale = token.last_used_at.is_none_or(|last_used_at| {
+            now - last_used_at >= Duration::seconds(Self::LAST_USED_RESOLUTION_SECS)
+        });
+        if stale {
+            self.repo.touch_last_used(token.id, now).await?;
+        }
+
+        Ok(Some(PersonalTokenAuth {
+            token_id: token.id,
+            actor: Actor::new(token.user_id, owner.role)
+                .with_email_verified(owner.email_verified)
+                .with_scopes(token.scopes),
+            expires_at: token.expires_at,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+
+    use super::PersonalTokenService;
+    use crate::data::personal_token_repository::{
+        NewPersonalToken, PersonalTokenOwner, PersonalTokenRepository,
+    };
+    use crate::domain::error::DomainError;
+    use crate::domain::personal_token::{
+        CreatePersonalTokenRequest, PersonalToken, TokenScope, TokenScopes,
+    };
+    use crate::domain::user::Role;
+
+    #[derive(Clone)]
+    struct StoredToken {
+        token: PersonalToken,
+        token_hash: String,
+        revoked: bool,
+    }
+
+    #[derive(Clone, Default)]
+    struct FakePersonalTokenRepo {
+        tokens: Arc<Mutex<Vec<StoredToken>>>,
+        touches: Arc<Mutex<usize>>,
+    }
+
+    impl FakePersonalTokenRepo {
+        fn touches(&self) -> usize {
+            *self.touches.lock().expect("touches mutex poisoned")
+        }
+
+        fn expire(&self, token_id: i64) {
+            let mut tokens = self.tokens.lock().expect("tokens mutex poisoned");
+            let stored = tokens
+                .iter_mut()
+                .find(|stored| stored.token.id == token_id)
+                .expect("token exists");
+            stored.token.expires_at = Some(Utc::now() - Duration::seconds(1));
+        }
+    }
+
+    #[async_trait]
+    impl PersonalTokenRepository for FakePersonalTokenRepo {
+        async fn create_token(
+            &self,
+            input: NewPersonalToken,
+        ) -> Result<PersonalToken, DomainError> {
+            let mut tokens = self.tokens.lock().expect("tokens mutex poisoned");
+            if tokens.iter().any(|stored| {
+                !stored.revoked
+                    && stored.token.user_id == input.user_id
+                    && stored.token.name == input.name
+            }) {
+                return Err(DomainError::AlreadyExists("token name".to_string()));
+            }
+            let token = PersonalToken {
+                id: tokens.len() as i64 + 1,
+                user_id: input.user_id,
+                name: input.name,
+                scopes: input.scopes,
+                expires_at: input.expires_at,
+                last_used_at: None,
+                created_at: Utc::now(),
+            };
+            tokens.push(StoredToken {
+                token: token.clone(),
+                token_hash: input.token_hash,
+                revoked: false,
+            });
+            Ok(token)
+        }
+
+        async fn list_tokens(&self, user_id: i64) -> Result<Vec<PersonalToken>, DomainError> {
+            Ok(self
+                .tokens
+                .lock()
+                .expect("tokens mutex poisoned")
+                .iter()
+                .filter(|stored| !stored.revoked && stored.token.user_id == user_id)
+                .map(|stored| stored.token.clone())
+                .collect())
+        }
+
+        async fn find_active_by_hash(
+            &self,
+            token_hash: &str,
+        ) -> Result<Option<PersonalTokenOwner>, DomainError> {
+            Ok(self
+                .tokens
+                .lock()
+                .expect("tokens mutex poisoned")
+                .iter()
+                .find(|stored| !stored.revoked && stored.token_hash == token_hash)
+                .map(|stored| PersonalTokenOwner {
+                    token: stored.token.clone(),
+                    role: Role::User,
+                    email_verified: true,
+                }))
+        }
+
+        async fn touch_last_used(
+            &self,
+            token_id: i64,
+            at: DateTime<Utc>,
+        ) -> Result<(), DomainError> {
+            *self.touches.lock().expect("touches mutex poisoned") += 1;
+            let mut tokens = self.tokens.lock().expect("tokens mutex poisoned");
+            if let Some(stored) = tokens.iter_mut().find(|stored| stored.token.id == token_id) {
+                stored.token.last_used_at = Some(at);
+            }
+            Ok(())
+        }
+
+        async fn revoke_token(&self, user_id: i64, token_id: i64) -> Result<bool, DomainError> {
+            let mut tokens = self.tokens.lock().expect("tokens mutex poisoned");
+            let stored = tokens.iter_mut().find(|stored| {
+                !stored.revoked && stored.token.id == token_id && stored.token.user_id == user_id
+            });
+            Ok(stored.map(|stored| stored.revoked = true).is_some())
+        }
+    }
+
+    fn request(name: &str, expires_in_days: Option<u32>) -> CreatePersonalTokenRequest {
+        CreatePersonalTokenRequest {
+            name: name.to_string(),
+            scopes: [TokenScope::PostsWrite]
+                .into_iter()
+                .collect::<TokenScopes>(),
+            expires_in_days,
+        }
+    }
+
+    #[tokio::test]
+    async fn created_token_authenticates_with_its_scopes_until_revoked() {
+        let repo = FakePersonalTokenRepo::default();
+        let service = PersonalTokenService::new(repo.clone());
+
+        let created = service
+            .create_token(7, request("ci", Some(30)))
+            .await
+            .expect("token created");
+        assert!(created.secret.starts_with("blog_pat_"));
+        assert!(created.token.expires_at.is_some());
+
+        let auth = service
+            .authenticate(&created.secret)
+            .await
+            .expect("lookup succeeds")
+            .expect("token is valid");
+        assert_eq!(auth.token_id, created.token.id);
+        assert_eq!(auth.actor.user_id, 7);
+        assert_eq!(auth.actor.scopes, Some(created.token.scopes));
+        assert!(
+            service
+                .authenticate("blog_pat_unknown")
+                .await
+                .expect("lookup succeeds")
+                .is_none()
+        );
+
+        // другой пользователь не может отозвать чужой токен
+        assert!(matches!(
+            service.revoke_token(8, created.token.id).await,
+            Err(DomainError::NotFound(_))
+        ));
+        service
+            .revoke_token(7, created.token.id)
+            .await
+            .expect("token revoked");
+        assert!(
+            service
+                .authenticate(&created.secret)
+                .await
+                .expect("lookup succeeds")
+                .is_none()
+        );
+        assert!(service.list_tokens(7).await.expect("list").is_empty());
+    }
+
+    #[tokio::test]
+    async fn expired_token_is_rejected_and_last_use_is_throttled() {
+        let repo = FakePersonalTokenRepo::default();
+        let service = PersonalTokenService::new(repo.clone());
+        let created = service
+            .create_token(7, request("deploy", None))
+            .await
+            .expect("token created");
+
+        for _ in 0..3 {
+            assert!(
+                service
+                    .authenticate(&created.secret)
+                    .await
+                    .expect("lookup succeeds")
+                    .is_some()
+            );
+        }
+        assert_eq!(repo.touches(), 1);
+
+        repo.expire(created.token.id);
+        assert!(
+            service
+                .authenticate(&created.secret)
+                .await
+                .expect("lookup succeeds")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn token_names_are_unique_among_active_tokens() {
+        let service = PersonalTokenService::new(FakePersonalTokenRepo::default());
+        let first = service
+            .create_token(7, request("ci", None))
+            .await
+            .expect("token created");
+
+        assert!(matches!(
+            service.create_token(7, request("ci", None)).await,
+            Err(DomainError::AlreadyExists(_))
+        ));
+        service
+            .create_token(8, request("ci", None))
+            .await
+            .expect("names are per user");
+
+        service
+            .revoke_token(7, first.token.id)
+            .await
+            .expect("token revoked");
+        service
+            .create_token(7, request("ci", None))
+            .await
+            .expect("name is free after revocation");
+    }
+}
@@ -0,0 +1,218 @@
+use crate::domain::comment::Comment;
+use crate::domain::error::DomainError;
+use crate::domain::personal_token::{TokenScope, TokenScopes};
+use crate::domain::post::Post;
+use crate::domain::user::Role;
+
+/// Пользователь, от имени которого выполняется действие: данные из access-токена.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Actor {
+    pub(crate) user_id: i64,
+    pub(crate) role: Role,
+    pub(crate) email_verified: bool,
+    /// Права токена доступа; `None` — обычная сессия без ограничений.
+    pub(crate) scopes: Option<TokenScopes>,
+}
+
+impl Actor {
+    pub(crate) fn new(user_id: i64, role: Role) -> Self {
+        Self {
+            user_id,
+            role,
+            email_verified: false,
+            scopes: None,
+        }
+    }
+
+    pub(crate) fn with_email_verified(mut self, email_verified: bool) -> Self {
+        self.email_verified = email_verified;
+        self
+    }
+
+    /// Ограничивает действия правами персонального токена.
+    pub(crate) fn with_scopes(mut self, scopes: TokenScopes) -> Self {
+        self.scopes = Some(scopes);
+        self
+    }
+
+    /// Сессия может всё, токен доступа — только то, что разрешено его правами.
+    pub(crate) fn require_scope(&self, scope: TokenScope) -> Result<(), DomainError> {
+        match self.scopes {
+            Some(scopes) if !scopes.contains(scope) => Err(DomainError::Forbidden),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_author_of(&self, post: &Post) -> bool {
+        post.author_id == self.user_id
+    }
+}
+
+/// Действия над существующим постом, требующие прав.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PostAction {
+    /// Правка заголовка, текста и тегов, восстановление ревизии.
+    Edit,
+    /// Публикация, снятие с публикации и архивирование.
+    ChangeStatus,
+    /// Перемещение в корзину, восстановление из неё и окончательное удаление.
+    Delete,
+    /// Просмотр истории правок.
+    ViewHistory,
+}
+
+/// Автор может всё со своим постом, модераторы и администраторы — с любым.
+pub(crate) fn authorize_post(
+    actor: &Actor,
+    action: PostAction,
+    post: &Post,
+) -> Result<(), DomainError> {
+    actor.require_scope(match action {
+        PostAction::ViewHistory => TokenScope::PostsRead,
+        PostAction::Edit | PostAction::ChangeStatus | PostAction::Delete => TokenScope::PostsWrite,
+    })?;
+    let allowed = match action {
+        PostAction::Edit
+        | PostAction::ChangeStatus
+        | PostAction::Delete
+        | PostAction::ViewHistory => actor.is_author_of(post) || actor.role.can_moderate(),
+    };
+    allowed.then_some(()).ok_or(DomainError::Forbidden)
+}
+
+/// Если включено обязательное подтверждение email, писать посты можно только после него.
+pub(crate) fn authorize_post_creation(
+    actor: &Actor,
+    require_verified_email: bool,
+) -> Result<(), DomainError> {
+    actor.require_scope(TokenScope::PostsWrite)?;
+    if require_verified_email && !actor.email_verified {
+        return Err(DomainError::EmailNotVerified);
+    }
+    Ok(())
+}
+
+/// Комментировать может любой пользователь; токену доступа нужно право на комментарии.
+pub(crate) fn authorize_comment_creation(actor: &Actor) -> Result<(), DomainError> {
+    actor.require_scope(TokenScope::CommentsWrite)
+}
+
+/// Комментарий удаляет его автор, автор поста или модератор.
+pub(crate) fn authorize_comment_delete(
+    actor: &Actor,
+    comment: &Comment,
+    post: &Post,
+) -> Result<(), DomainError> {
+    actor.require_scope(TokenScope::CommentsWrite)?;
+    let allowed =
+        comment.can_be_deleted_by(actor.user_id, post.author_id) || actor.role.can_moderate();
+    allowed.then_some(()).ok_or(DomainError::Forbidden)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::{
+        Actor, PostAction, authorize_comment_creation, authorize_comment_delete, authorize_post,
+        authorize_post_creation,
+    };
+    use crate::domain::comment::Comment;
+    use crate::domain::error::DomainError;
+    use crate::domain::personal_token::{TokenScope, TokenScopes};
+    use crate::domain::post::Post;
+    use crate::domain::user::Role;
+
+    const ALL_POST_ACTIONS: [PostAction; 4] = [
+        PostAction::Edit,
+        PostAction::ChangeStatus,
+        PostAction::Delete,
+        PostAction::ViewHistory,
+    ];
+
+    #[test]
+    fn author_and_staff_may_manage_post_but_other_users_may_not() {
+        let post = sample_post(10);
+
+        for action in ALL_POST_ACTIONS {
+            assert!(authorize_post(&Actor::new(10, Role::User), action, &post).is_ok());
+            assert!(authorize_post(&Actor::new(20, Role::Moderator), action, &post).is_ok());
+            assert!(authorize_post(&Actor::new(30, Role::Admin), action, &post).is_ok());
+            assert!(matches!(
+                authorize_post(&Actor::new(40, Role::User), action, &post),
+                Err(DomainError::Forbidden)
+            ));
+        }
+    }
+
+    #[test]
+    fn unverified_user_may_post_only_when_verification_is_optional() {
+        let unverified = Actor::new(10, Role::User);
+        let verified = Actor::new(10, Role::User).with_email_verified(true);
+
+        assert!(authorize_post_creation(&unverified, false).is_ok());
+        assert!(authorize_post_creation(&verified, true).is_ok());
+        assert!(matches!(
+            authorize_post_creation(&unverified, true),
+            Err(DomainError::EmailNotVerified)
+        ));
+    }
+
+    #[test]
+    fn moderator_may_delete_any_comment() {
+        let post = sample_post(10);
+        let comment = Comment {
+            id: 1,
+            post_id: post.id,
+            author_id: 20,
+            parent_id: None,
+            content: "text".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        assert!(authorize_comment_delete(&Actor::new(20, Role::User), &comment, &post).is_ok());
+        assert!(authorize_comment_delete(&Actor::new(10, Role::User), &comment, &post).is_ok());
+        assert!(
+            authorize_comment_delete(&Actor::new(30, Role::Moderator), &comment, &post).is_ok()
+        );
+        assert!(matches!(
+            authorize_comment_delete(&Actor::new(30, Role::User), &comment, &post),
+            Err(DomainError::Forbidden)
+        ));
+    }
+
+    #[test]
+    fn personal_token_is_limited_to_its_scopes() {
+        let post = sample_post(10);
+        let writer =
+            Actor::new(10, Role::Admin).with_scopes([TokenScope::PostsWrite].into_iter().collect());
+        let reader = Actor::new(10, Role::User)
+            .with_scopes(TokenScopes::parse(&["posts:read"]).expect("known scope"));
+
+        assert!(authorize_post_creation(&writer, false).is_ok());
+        assert!(authorize_post(&writer, PostAction::Edit, &post).is_ok());
+        assert!(authorize_post(&writer, PostAction::Delete, &post).is_ok());
+        // роль администратора не расширяет права токена
+        assert!(matches!(
+            authorize_post(&writer, PostAction::ViewHistory, &post),
+            Err(DomainError::Forbidden)
+        ));
+        assert!(matches!(
+            authorize_comment_creation(&writer),
+            Err(DomainError::Forbidden)
+        ));
+
+        assert!(authorize_post(&reader, PostAction::ViewHistory, &post).is_ok());
+        assert!(matches!(
+            authorize_post_creation(&reader, false),
+            Err(DomainError::Forbidden)
+        ));
+        assert!(authorize_comment_creation(&Actor::new(10, Role::User)).is_ok());
+    }
+
+    fn sample_post(author_id: i64) -> Post {
+        let now = Utc::now();
+        Post::new(1, "title", "content", author_id, now, now).expect("valid post")
+    }
+}
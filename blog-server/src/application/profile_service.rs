@@ -0,0 +1,173 @@
+use crate::data::profile_repository::ProfileRepository;
+use crate::domain::error::DomainError;
+use crate::domain::profile::{UpdateProfileRequest, UserProfile};
+
+/// Публичные профили пользователей: просмотр любым посетителем и правка владельцем.
+pub(crate) struct ProfileService<P: ProfileRepository> {
+    repo: P,
+}
+
+impl<P: ProfileRepository> ProfileService<P> {
+    pub(crate) fn new(repo: P) -> Self {
+        Self { repo }
+    }
+
+    pub(crate) async fn get_profile(&self, username: &str) -> Result<UserProfile, DomainError> {
+        self.repo
+            .find_by_username(username.trim())
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("user: {username}")))
+    }
+
+    pub(crate) async fn update_profile(
+        &self,
+        user_id: i64,
+        req: UpdateProfileRequest,
+    ) -> Result<UserProfile, DomainError> {
+        let req = req.validate()?;
+        let current = self
+            .repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("user id: {user_id}")))?;
+
+        let profile = current.profile.clone().apply(req);
+        if profile == current.profile {
+            return Ok(current);
+        }
+        self.repo
+            .update_profile(user_id, &profile)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("user id: {user_id}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    use super::ProfileService;
+    use crate::data::profile_repository::ProfileRepository;
+    use crate::domain::error::DomainError;
+    use crate::domain::profile::{Profile, UpdateProfileRequest, UserProfile};
+
+    #[derive(Clone)]
+    struct FakeProfileRepo {
+        user: Arc<Mutex<UserProfile>>,
+        updates: Arc<Mutex<usize>>,
+    }
+
+    impl FakeProfileRepo {
+        fn new() -> Self {
+            Self {
+                user: Arc::new(Mutex::new(UserProfile {
+                    id: 7,
+                    username: "alice".to_string(),
+                    profile: Profile::default(),
+                    created_at: Utc::now(),
+                })),
+                updates: Arc::default(),
+            }
+        }
+
+        fn updates(&self) -> usize {
+            *self.updates.lock().expect("updates mutex poisoned")
+        }
+    }
+
+    #[async_trait]
+    impl ProfileRepository for FakeProfileRepo {
+        async fn find_by_username(
+            &self,
+            username: &str,
+        ) -> Result<Option<UserProfile>, DomainError> {
+            let user = self.user.lock().expect("user mutex poisoned");
+            Ok((user.username == username).then(|| user.clone()))
+        }
+
+        async fn find_by_id(&self, user_id: i64) -> Result<Option<UserProfile>, DomainError> {
+            let user = self.user.lock().expect("user mutex poisoned");
+            Ok((user.id == user_id).then(|| user.clone()))
+        }
+
+        async fn update_profile(
+            &self,
+            user_id: i64,
+            profile: &Profile,
+        ) -> Result<Option<UserProfile>, DomainError> {
+            *self.updates.lock().expect("updates mutex poisoned") += 1;
+            let mut user = self.user.lock().expect("user mutex poisoned");
+            if user.id != user_id {
+                return Ok(None);
+            }
+            user.profile = profile.clone();
+            Ok(Some(user.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn owner_updates_profile_and_it_is_visible_publicly() {
+        let repo = FakeProfileRepo::new();
+        let service = ProfileService::new(repo.clone());
+
+        let updated = service
+            .update_profile(
+                7,
+                UpdateProfileRequest {
+                    display_name: Some(" Alice ".to_string()),
+                    website: Some("https://alice.dev".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("profile updated");
+        assert_eq!(updated.profile.display_name.as_deref(), Some("Alice"));
+
+        let public = service.get_profile("alice").await.expect("profile found");
+        assert_eq!(public.profile.website.as_deref(), Some("https://alice.dev"));
+        assert!(matches!(
+            service.get_profile("bob").await,
+            Err(DomainError::NotFound(_))
+        ));
+
+        // повтор того же значения не пишет в БД
+        service
+            .update_profile(
+                7,
+                UpdateProfileRequest {
+                    display_name: Some("Alice".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("profile unchanged");
+        assert_eq!(repo.updates(), 1);
+    }
+
+    #[tokio::test]
+    async fn invalid_update_is_rejected_before_repository() {
+        let repo = FakeProfileRepo::new();
+        let service = ProfileService::new(repo.clone());
+
+        let result = service
+            .update_profile(
+                7,
+                UpdateProfileRequest {
+                    avatar_url: Some("ftp://example.com/a.png".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(DomainError::Validation {
+                field: "avatar_url",
+                ..
+            })
+        ));
+        assert_eq!(repo.updates(), 0);
+    }
+}
@@ -0,0 +1,280 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::application::ttl_cache::TtlCache;
+use crate::data::session_repository::{NewSession, SessionRepository};
+use crate::domain::error::DomainError;
+use crate::domain::session::{ClientInfo, Session};
+
+/// Сессии пользователей: устройства, с которых выполнен вход, и их отзыв.
+///
+/// Проверка сессии access-токена кэшируется на `cache_ttl`, как и проверка
+/// отзыва токенов: отзыв через этот же сервис виден сразу, с другого инстанса —
+/// не позже чем через TTL. Промах кэша заодно отмечает активность сессии.
+pub(crate) struct SessionService<S: SessionRepository> {
+    repo: S,
+    cache_ttl: Duration,
+    active_sessions: TtlCache<i64, bool>,
+}
+
+impl<S: SessionRepository> SessionService<S> {
+    pub(crate) fn new(repo: S, cache_ttl: Duration) -> Self {
+        Self {
+            repo,
+            cache_ttl,
+            active_sessions: TtlCache::new(),
+        }
+    }
+
+    /// Заводит сессию для нового входа с семьёй refresh-токенов `family_id`.
+    pub(crate) async fn start_session(
+        &self,
+        user_id: i64,
+        family_id: &str,
+        client: &ClientInfo,
+    ) -> Result<Session, DomainError> {
+        self.repo
+            .create_session(NewSession {
+                user_id,
+                family_id: family_id.to_string(),
+                user_agent: client.user_agent.clone(),
+                ip_address: client.ip.map(|ip| ip.to_string()),
+            })
+            .await
+    }
+
+    /// Отмечает обновление токенов семьи; возвращает id её сессии, если она есть.
+    pub(crate) async fn record_refresh(
+        &self,
+        family_id: &str,
+        client: &ClientInfo,
+    ) -> Result<Option<i64>, DomainError> {
+        let ip_address = client.ip.map(|ip| ip.to_string());
+        self.repo
+            .touch_family(
+                family_id,
+                client.user_agent.as_deref(),
+                ip_address.as_deref(),
+                Utc::now(),
+            )
+            .await
+    }
+
+    /// `false`, если сессия отозвана, завершена выходом или истекла.
+    pub(crate) async fn is_active(&self, session_id: i64) -> Result<bool, DomainError> {
+        if let Some(active) = self.active_sessions.get(&session_id, self.cache_ttl) {
+            return Ok(active);
+        }
+
+        let active = self.repo.touch_session(session_id, Utc::now()).await?;
+        self.active_sessions
+            .insert(session_id, active, self.cache_ttl);
+        Ok(active)
+    }
+
+    pub(crate) async fn list_sessions(&self, user_id: i64) -> Result<Vec<Session>, DomainError> {
+        self.repo.list_active(user_id).await
+    }
+
+    /// Завершает сессию: её refresh-токен перестаёт обновляться,
+    /// access-токены — приниматься.
+    pub(crate) async fn revoke_session(
+        &self,
+        user_id: i64,
+        session_id: i64,
+    ) -> Result<(), DomainError> {
+        if !self.repo.revoke_session(user_id, session_id).await? {
+            return Err(DomainError::NotFound(format!("session id: {session_id}")));
+        }
+        self.active_sessions
+            .insert(session_id, false, self.cache_ttl);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+
+    use super::SessionService;
+    use crate::data::session_repository::{NewSession, SessionRepository};
+    use crate::domain::error::DomainError;
+    use crate::domain::session::{ClientInfo, Session};
+
+    #[derive(Clone)]
+    struct StoredSession {
+        session: Session,
+        user_id: i64,
+        family_id: String,
+        revoked: bool,
+    }
+
+    #[derive(Clone, Default)]
+    struct FakeSessionRepo {
+        sessions: Arc<Mutex<Vec<StoredSession>>>,
+        touches: Arc<Mutex<usize>>,
+    }
+
+    impl FakeSessionRepo {
+        fn touches(&self) -> usize {
+            *self.touches.lock().expect("touches mutex poisoned")
+        }
+    }
+
+    #[async_trait]
+    impl SessionRepository for FakeSessionRepo {
+        async fn create_session(&self, input: NewSession) -> Result<Session, DomainError> {
+            let mut sessions = self.sessions.lock().expect("sessions mutex poisoned");
+            let now = Utc::now();
+            let session = Session {
+                id: sessions.len() as i64 + 1,
+                user_agent: input.user_agent,
+                ip_address: input.ip_address,
+                created_at: now,
+                last_seen_at: now,
+            };
+            sessions.push(StoredSession {
+                session: session.clone(),
+                user_id: input.user_id,
+                family_id: input.family_id,
+                revoked: false,
+            });
+            Ok(session)
+        }
+
+        async fn touch_family(
+            &self,
+            family_id: &str,
+            user_agent: Option<&str>,
+            ip_address: Option<&str>,
+            at: DateTime<Utc>,
+        ) -> Result<Option<i64>, DomainError> {
+            let mut sessions = self.sessions.lock().expect("sessions mutex poisoned");
+            let Some(stored) = sessions
+                .iter_mut()
+                .find(|stored| stored.family_id == family_id)
+            else {
+                return Ok(None);
+            };
+            stored.session.last_seen_at = at;
+            if let Some(user_agent) = user_agent {
+                stored.session.user_agent = Some(user_agent.to_string());
+            }
+            if let Some(ip_address) = ip_address {
+                stored.session.ip_address = Some(ip_address.to_string());
+            }
+            Ok(Some(stored.session.id))
+        }
+
+        async fn touch_session(
+            &self,
+            session_id: i64,
+            at: DateTime<Utc>,
+        ) -> Result<bool, DomainError> {
+            *self.touches.lock().expect("touches mutex poisoned") += 1;
+            let mut sessions = self.sessions.lock().expect("sessions mutex poisoned");
+            let stored = sessions
+                .iter_mut()
+                .find(|stored| stored.session.id == session_id && !stored.revoked);
+            Ok(stored
+                .map(|stored| stored.session.last_seen_at = at)
+                .is_some())
+        }
+
+        async fn list_active(&self, user_id: i64) -> Result<Vec<Session>, DomainError> {
+            Ok(self
+                .sessions
+                .lock()
+                .expect("sessions mutex poisoned")
+                .iter()
+                .filter(|stored| !stored.revoked && stored.user_id == user_id)
+                .map(|stored| stored.session.clone())
+                .collect())
+        }
+
+        async fn revoke_session(&self, user_id: i64, session_id: i64) -> Result<bool, DomainError> {
+            let mut sessions = self.sessions.lock().expect("sessions mutex poisoned");
+            let stored = sessions.iter_mut().find(|stored| {
+                !stored.revoked && stored.session.id == session_id && stored.user_id == user_id
+            });
+            Ok(stored.map(|stored| stored.revoked = true).is_some())
+        }
+    }
+
+    fn client() -> ClientInfo {
+        ClientInfo::new(Some([127, 0, 0, 1].into()), Some("blog-cli/1.0"))
+    }
+
+    #[tokio::test]
+    async fn revoked_session_is_rejected_immediately_and_hidden_from_list() {
+        let repo = FakeSessionRepo::default();
+        let service = SessionService::new(repo.clone(), Duration::from_secs(60));
+        let laptop = service
+            .start_session(7, "family-1", &client())
+            .await
+            .expect("session created");
+        let phone = service
+            .start_session(7, "family-2", &ClientInfo::default())
+            .await
+            .expect("session created");
+        assert_eq!(laptop.user_agent.as_deref(), Some("blog-cli/1.0"));
+        assert_eq!(laptop.ip_address.as_deref(), Some("127.0.0.1"));
+
+        assert!(service.is_active(laptop.id).await.expect("check succeeds"));
+
+        // чужую сессию не отозвать
+        assert!(matches!(
+            service.revoke_session(8, laptop.id).await,
+            Err(DomainError::NotFound(_))
+        ));
+        service
+            .revoke_session(7, laptop.id)
+            .await
+            .expect("session revoked");
+
+        assert!(!service.is_active(laptop.id).await.expect("check succeeds"));
+        let sessions = service.list_sessions(7).await.expect("list succeeds");
+        assert_eq!(
+            sessions
+                .iter()
+                .map(|session| session.id)
+                .collect::<Vec<_>>(),
+            vec![phone.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_updates_session_and_checks_are_cached() {
+        let repo = FakeSessionRepo::default();
+        let service = SessionService::new(repo.clone(), Duration::from_secs(60));
+        let session = service
+            .start_session(7, "family-1", &ClientInfo::default())
+            .await
+            .expect("session created");
+
+        let refreshed = service
+            .record_refresh("family-1", &client())
+            .await
+            .expect("refresh recorded");
+        assert_eq!(refreshed, Some(session.id));
+        assert_eq!(
+            service
+                .record_refresh("legacy-family", &client())
+                .await
+                .expect("refresh recorded"),
+            None
+        );
+        let listed = service.list_sessions(7).await.expect("list succeeds");
+        assert_eq!(listed[0].ip_address.as_deref(), Some("127.0.0.1"));
+
+        for _ in 0..3 {
+            assert!(service.is_active(session.id).await.expect("check succeeds"));
+        }
+        assert_eq!(repo.touches(), 1);
+    }
+}
@@ -0,0 +1,282 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::application::ttl_cache::TtlCache;
+use crate::data::token_revocation_repository::TokenRevocationRepository;
+use crate::domain::error::DomainError;
+use crate::infrastructure::jwt::Claims;
+
+/// Проверка и отзыв access-токенов до истечения `exp`.
+///
+/// Результаты проверок кэшируются в памяти процесса на `cache_ttl`: отзыв через
+/// этот же сервис виден сразу, отзыв с другого инстанса — не позже чем через TTL.
+pub(crate) struct TokenRevocationService<V: TokenRevocationRepository> {
+    repo: V,
+    cache_ttl: Duration,
+    revoked_tokens: TtlCache<String, bool>,
+    user_cutoffs: TtlCache<i64, Option<i64>>,
+}
+
+impl<V: TokenRevocationRepository> TokenRevocationService<V> {
+    pub(crate) fn new(repo: V, cache_ttl: Duration) -> Self {
+        Self {
+            repo,
+            cache_ttl,
+            revoked_tokens: TtlCache::new(),
+            user_cutoffs: TtlCache::new(),
+        }
+    }
+
+    /// `false`, если токен отозван явно или выдан до "выхода со всех устройств".
+    pub(crate) async fn is_active(&self, claims: &Claims) -> Result<bool, DomainError> {
+        if self.is_token_revoked(&claims.jti).await? {
+            return Ok(false);
+        }
+
+        // сравниваем в микросекундах: токен, выданный сразу после отсечки
+        // в ту же секунду (например, при смене пароля), остаётся действительным
+        let cutoff = self.user_cutoff(claims.user_id).await?;
+        Ok(cutoff.is_none_or(|cutoff| claims.issued_at_micros() > cutoff))
+    }
+
+    pub(crate) async fn revoke_token(
+        &self,
+        jti: &str,
+        user_id: i64,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        self.repo.revoke_token(jti, user_id, expires_at).await?;
+        self.revoked_tokens
+            .insert(jti.to_string(), true, self.cache_ttl);
+
+        // заодно чистим записи, которые больше ничего не защищают
+        self.repo.purge_expired().await?;
+        Ok(())
+    }
+
+    pub(crate) async fn revoke_all_for_user(&self, user_id: i64) -> Result<(), DomainError> {
+        let cutoff = self.repo.revoke_all_for_user(user_id).await?;
+        self.user_cutoffs
+            .insert(user_id, Some(cutoff.timestamp_micros()), self.cache_ttl);
+        Ok(())
+    }
+
+    async fn is_token_revoked(&self, jti: &str) -> Result<bool, DomainError> {
+        if let Some(revoked) = self.revoked_tokens.get(jti, self.cache_ttl) {
+            return Ok(revoked);
+        }
+
+        let revoked = self.repo.is_token_revoked(jti).await?;
+        self.revoked_tokens
+            .insert(jti.to_string(), revoked, self.cache_ttl);
+        Ok(revoked)
+    }
+
+    async fn user_cutoff(&self, user_id: i64) -> Result<Option<i64>, DomainError> {
+        if let Some(cutoff) = self.user_cutoffs.get(&user_id, self.cache_ttl) {
+            return Ok(cutoff);
+        }
+
+        let cutoff = self
+            .repo
+            .find_user_cutoff(user_id)
+            .await?
+            .map(|cutoff| cutoff.timestamp_micros());
+        self.user_cutoffs.insert(user_id, cutoff, self.cache_ttl);
+        Ok(cutoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+
+    use super::TokenRevocationService;
+    use crate::data::token_revocation_repository::TokenRevocationRepository;
+    use crate::domain::error::DomainError;
+    use crate::domain::user::Role;
+    use crate::infrastructure::jwt::Claims;
+
+    #[derive(Clone, Default)]
+    struct FakeRevocationRepo {
+        revoked: Arc<Mutex<HashSet<String>>>,
+        cutoffs: Arc<Mutex<HashMap<i64, DateTime<Utc>>>>,
+        lookups: Arc<Mutex<usize>>,
+    }
+
+    impl FakeRevocationRepo {
+        fn cutoff(&self, user_id: i64) -> DateTime<Utc> {
+            self.cutoffs.lock().expect("cutoffs mutex poisoned")[&user_id]
+        }
+
+        fn lookups(&self) -> usize {
+            *self.lookups.lock().expect("lookups mutex poisoned")
+        }
+    }
+
+    #[async_trait]
+    impl TokenRevocationRepository for FakeRevocationRepo {
+        async fn revoke_token(
+            &self,
+            jti: &str,
+            _user_id: i64,
+            _expires_at: DateTime<Utc>,
+        ) -> Result<(), DomainError> {
+            self.revoked
+                .lock()
+                .expect("revoked mutex poisoned")
+                .insert(jti.to_string());
+            Ok(())
+        }
+
+        async fn is_token_revoked(&self, jti: &str) -> Result<bool, DomainError> {
+            *self.lookups.lock().expect("lookups mutex poisoned") += 1;
+            Ok(self
+                .revoked
+                .lock()
+                .expect("revoked mutex poisoned")
+                .contains(jti))
+        }
+
+        async fn revoke_all_for_user(&self, user_id: i64) -> Result<DateTime<Utc>, DomainError> {
+            let now = Utc::now();
+            self.cutoffs
+                .lock()
+                .expect("cutoffs mutex poisoned")
+                .insert(user_id, now);
+            Ok(now)
+        }
+
+        async fn find_user_cutoff(
+            &self,
+            user_id: i64,
+        ) -> Result<Option<DateTime<Utc>>, DomainError> {
+            Ok(self
+                .cutoffs
+                .lock()
+                .expect("cutoffs mutex poisoned")
+                .get(&user_id)
+                .copied())
+        }
+
+        async fn purge_expired(&self) -> Result<u64, DomainError> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn revoked_token_becomes_inactive_immediately() {
+        let repo = FakeRevocationRepo::default();
+        let service = TokenRevocationService::new(repo, Duration::from_secs(60));
+        let claims = sample_claims("jti-1", Utc::now().timestamp());
+
+        assert!(
+            service
+                .is_active(&claims)
+                .await
+                .expect("check must succeed")
+        );
+
+        service
+            .revoke_token(&claims.jti, claims.user_id, Utc::now())
+            .await
+            .expect("revoke must succeed");
+
+        assert!(
+            !service
+                .is_active(&claims)
+                .await
+                .expect("check must succeed")
+        );
+    }
+
+    #[tokio::test]
+    async fn revoke_all_rejects_tokens_issued_before_cutoff() {
+        let repo = FakeRevocationRepo::default();
+        let service = TokenRevocationService::new(repo, Duration::from_secs(60));
+        let old = sample_claims("old", Utc::now().timestamp() - 60);
+
+        assert!(service.is_active(&old).await.expect("check must succeed"));
+
+        service
+            .revoke_all_for_user(old.user_id)
+            .await
+            .expect("revoke all must succeed");
+
+        assert!(!service.is_active(&old).await.expect("check must succeed"));
+        let fresh = sample_claims("fresh", Utc::now().timestamp() + 1);
+        assert!(service.is_active(&fresh).await.expect("check must succeed"));
+    }
+
+    #[tokio::test]
+    async fn revoke_all_compares_issue_time_below_a_second() {
+        let repo = FakeRevocationRepo::default();
+        let service = TokenRevocationService::new(repo.clone(), Duration::from_secs(60));
+        service
+            .revoke_all_for_user(1)
+            .await
+            .expect("revoke all must succeed");
+        let cutoff = repo.cutoff(1);
+
+        let mut before = sample_claims("before", cutoff.timestamp());
+        before.iat_us = Some(cutoff.timestamp_micros() - 1);
+        assert!(
+            !service
+                .is_active(&before)
+                .await
+                .expect("check must succeed")
+        );
+
+        let mut after = sample_claims("after", cutoff.timestamp());
+        after.iat_us = Some(cutoff.timestamp_micros() + 1);
+        assert!(service.is_active(&after).await.expect("check must succeed"));
+
+        // у старых токенов без iat_us та же секунда по-прежнему считается отозванной
+        let mut legacy = sample_claims("legacy", cutoff.timestamp());
+        legacy.iat_us = None;
+        assert!(
+            !service
+                .is_active(&legacy)
+                .await
+                .expect("check must succeed")
+        );
+    }
+
+    #[tokio::test]
+    async fn repeated_checks_are_served_from_cache() {
+        let repo = FakeRevocationRepo::default();
+        let service = TokenRevocationService::new(repo.clone(), Duration::from_secs(60));
+        let claims = sample_claims("jti-1", Utc::now().timestamp());
+
+        for _ in 0..3 {
+            assert!(
+                service
+                    .is_active(&claims)
+                    .await
+                    .expect("check must succeed")
+            );
+        }
+
+        assert_eq!(repo.lookups(), 1);
+    }
+
+    fn sample_claims(jti: &str, iat: i64) -> Claims {
+        Claims {
+            user_id: 1,
+            username: "valid_user".to_string(),
+            role: Role::User,
+            email_verified: true,
+            jti: jti.to_string(),
+            sid: None,
+            iat,
+            iat_us: Some(iat * 1_000_000),
+            exp: iat + 3600,
+        }
+    }
+}
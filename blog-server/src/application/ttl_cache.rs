@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Кэш результатов проверок в памяти процесса; запись живёт `ttl` с момента вставки.
+pub(crate) struct TtlCache<K, T> {
+    entries: Mutex<HashMap<K, (T, Instant)>>,
+}
+
+impl<K: Eq + Hash, T: Clone> TtlCache<K, T> {
+    const MAX_ENTRIES: usize = 10_000;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn get<Q>(&self, key: &Q, ttl: Duration) -> Option<T>
+    where
+        K: std::borrow::Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let entries = self.entries.lock().expect("ttl cache mutex poisoned");
+        entries
+            .get(key)
+            .filter(|(_, cached_at)| cached_at.elapsed() < ttl)
+            .map(|(value, _)| value.clone())
+    }
+
+    pub(crate) fn insert(&self, key: K, value: T, ttl: Duration) {
+        let mut entries = self.entries.lock().expect("ttl cache mutex poisoned");
+        if entries.len() >= Self::MAX_ENTRIES {
+            entries.retain(|_, (_, cached_at)| cached_at.elapsed() < ttl);
+        }
+        if entries.len() >= Self::MAX_ENTRIES {
+            entries.clear();
+        }
+        entries.insert(key, (value, Instant::now()));
+    }
+}
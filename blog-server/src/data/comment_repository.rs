@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+
+use crate::domain::comment::Comment;
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewComment {
+    pub(crate) post_id: i64,
+    pub(crate) author_id: i64,
+    pub(crate) parent_id: Option<i64>,
+    pub(crate) content: String,
+}
+
+#[async_trait]
+pub(crate) trait CommentRepository: Send + Sync {
+    async fn create_comment(&self, input: NewComment) -> Result<Comment, DomainError>;
+    async fn get_comment(&self, id: i64) -> Result<Option<Comment>, DomainError>;
+
+    /// Все комментарии поста в порядке создания.
+    async fn list_comments_for_post(&self, post_id: i64) -> Result<Vec<Comment>, DomainError>;
+
+    /// Удаляет комментарий вместе со всеми ответами на него.
+    async fn delete_comment(&self, id: i64) -> Result<bool, DomainError>;
+}
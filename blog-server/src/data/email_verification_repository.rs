@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewEmailVerificationToken {
+    pub(crate) user_id: i64,
+    pub(crate) token_hash: String,
+    pub(crate) expires_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub(crate) trait EmailVerificationRepository: Send + Sync {
+    async fn create_verification_token(
+        &self,
+        input: NewEmailVerificationToken,
+    ) -> Result<(), DomainError>;
+
+    /// Атомарно помечает токен использованным и возвращает id пользователя.
+    /// `None`, если токен неизвестен, истёк или уже был использован.
+    async fn consume_verification_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<i64>, DomainError>;
+
+    /// Делает недействительными все ещё не использованные токены пользователя.
+    async fn invalidate_for_user(&self, user_id: i64) -> Result<(), DomainError>;
+}
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::error::DomainError;
+
+/// Секрет TOTP пользователя; `enabled_at` пуст, пока подключение не подтверждено кодом.
+#[derive(Debug, Clone)]
+pub(crate) struct TotpRecord {
+    pub(crate) secret: String,
+    pub(crate) enabled_at: Option<DateTime<Utc>>,
+}
+
+impl TotpRecord {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+}
+
+#[async_trait]
+pub(crate) trait MfaRepository: Send + Sync {
+    async fn find_totp(&self, user_id: i64) -> Result<Option<TotpRecord>, DomainError>;
+
+    /// Сохраняет новый неподтверждённый секрет, заменяя прежний неподтверждённый.
+    /// `false`, если TOTP у пользователя уже включён.
+    async fn set_pending_totp(&self, user_id: i64, secret: &str) -> Result<bool, DomainError>;
+
+    /// Включает TOTP и заменяет коды восстановления (хэши) одной транзакцией.
+    /// `false`, если подключение не начато или уже подтверждено.
+    async fn enable_totp(
+        &self,
+        user_id: i64,
+        recovery_code_hashes: &[String],
+    ) -> Result<bool, DomainError>;
+
+    /// Удаляет секрет и все коды восстановления пользователя.
+    async fn disable_totp(&self, user_id: i64) -> Result<(), DomainError>;
+
+    /// Атомарно запоминает шаг TOTP, если он новее последнего принятого.
+    /// `false` — код этого или более раннего шага уже использовался.
+    async fn mark_step_used(&self, user_id: i64, step: i64) -> Result<bool, DomainError>;
+
+    /// Атомарно помечает код восстановления использованным.
+    /// `false`, если код неизвестен или уже был использован.
+    async fn consume_recovery_code(
+        &self,
+        user_id: i64,
+        code_hash: &str,
+    ) -> Result<bool, DomainError>;
+}
@@ -1,3 +1,13 @@
+pub(crate) mod comment_repository;
+pub(crate) mod email_verification_repository;
+pub(crate) mod mfa_repository;
+pub(crate) mod password_reset_repository;
+pub(crate) mod personal_token_repository;
 pub(crate) mod post_repository;
+pub(crate) mod profile_repository;
+pub(crate) mod refresh_token_repository;
 pub(crate) mod repositories;
+pub(crate) mod session_repository;
+pub(crate) mod tag_repository;
+pub(crate) mod token_revocation_repository;
 pub(crate) mod user_repository;
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewPasswordResetToken {
+    pub(crate) user_id: i64,
+    pub(crate) token_hash: String,
+    pub(crate) expires_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub(crate) trait PasswordResetRepository: Send + Sync {
+    async fn create_reset_token(&self, input: NewPasswordResetToken) -> Result<(), DomainError>;
+
+    /// Атомарно помечает токен использованным и возвращает id пользователя.
+    /// `None`, если токен неизвестен, истёк или уже был использован.
+    async fn consume_reset_token(&self, token_hash: &str) -> Result<Option<i64>, DomainError>;
+
+    /// Делает недействительными все ещё не использованные токены пользователя.
+    async fn invalidate_for_user(&self, user_id: i64) -> Result<(), DomainError>;
+}
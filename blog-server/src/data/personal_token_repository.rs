@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::error::DomainError;
+use crate::domain::personal_token::{PersonalToken, TokenScopes};
+use crate::domain::user::Role;
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewPersonalToken {
+    pub(crate) user_id: i64,
+    pub(crate) name: String,
+    pub(crate) token_hash: String,
+    pub(crate) scopes: TokenScopes,
+    pub(crate) expires_at: Option<DateTime<Utc>>,
+}
+
+/// Неотозванный токен вместе с текущими ролью и статусом email владельца.
+#[derive(Debug, Clone)]
+pub(crate) struct PersonalTokenOwner {
+    pub(crate) token: PersonalToken,
+    pub(crate) role: Role,
+    pub(crate) email_verified: bool,
+}
+
+#[async_trait]
+pub(crate) trait PersonalTokenRepository: Send + Sync {
+    /// `AlreadyExists`, если у пользователя есть неотозванный токен с таким именем.
+    async fn create_token(&self, input: NewPersonalToken) -> Result<PersonalToken, DomainError>;
+
+    /// Неотозванные токены пользователя, включая истёкшие, от новых к старым.
+    async fn list_tokens(&self, user_id: i64) -> Result<Vec<PersonalToken>, DomainError>;
+
+    async fn find_active_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PersonalTokenOwner>, DomainError>;
+
+    async fn touch_last_used(&self, token_id: i64, at: DateTime<Utc>) -> Result<(), DomainError>;
+
+    /// `false`, если у пользователя нет такого неотозванного токена.
+    async fn revoke_token(&self, user_id: i64, token_id: i64) -> Result<bool, DomainError>;
+}
@@ -1,19 +1,40 @@
+use std::collections::HashSet;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
+use crate::domain::cursor::PostCursor;
 use crate::domain::error::DomainError;
-use crate::domain::post::Post;
+use crate::domain::post::{ContentFormat, Post, PostStatus};
+use crate::domain::revision::PostRevision;
+use crate::domain::search::PostSearchHit;
 
 #[derive(Debug, Clone)]
 pub(crate) struct NewPost {
+    pub(crate) slug: String,
     pub(crate) title: String,
     pub(crate) content: String,
+    pub(crate) content_format: ContentFormat,
+    pub(crate) content_html: String,
     pub(crate) author_id: i64,
+    pub(crate) status: PostStatus,
+    pub(crate) publish_at: Option<DateTime<Utc>>,
+    /// Теги записываются в той же транзакции, что и сам пост.
+    pub(crate) tags: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct PostPatch {
+    /// Если отличается от текущего, прежний slug сохраняется в истории для редиректов.
+    pub(crate) slug: String,
     pub(crate) title: String,
     pub(crate) content: String,
+    pub(crate) content_format: ContentFormat,
+    pub(crate) content_html: String,
+    /// Если задано, правка применяется только к посту этой версии.
+    pub(crate) expected_version: Option<i64>,
+    /// Если задано, теги поста заменяются вместе с текстом.
+    pub(crate) tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -22,17 +43,134 @@ pub(crate) struct Pagination {
     pub(crate) page_size: u32,
 }
 
+/// Фильтр публичной ленты: опубликованные посты плюс собственные посты `viewer_id`.
+/// Непустой `tags` оставляет только посты, у которых есть все перечисленные теги,
+/// `author` — только посты пользователя с этим логином.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PostListFilter {
+    pub(crate) viewer_id: Option<i64>,
+    pub(crate) tags: Vec<String>,
+    pub(crate) author: Option<String>,
+}
+
 #[async_trait]
 pub(crate) trait PostRepository: Send + Sync {
     async fn create_post(&self, input: NewPost) -> Result<Post, DomainError>;
     async fn get_post(&self, id: i64) -> Result<Option<Post>, DomainError>;
-    async fn update_post_owned(
+    /// Пост по текущему slug или, если такого нет, по одному из прежних.
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Option<Post>, DomainError>;
+    /// Текущие и прежние slug, начинающиеся с `prefix`, кроме slug поста `except_post_id`.
+    async fn slugs_taken(
+        &self,
+        prefix: &str,
+        except_post_id: Option<i64>,
+    ) -> Result<HashSet<String>, DomainError>;
+    /// Права `editor_id` проверяет вызывающий; редактор записывается в ревизию.
+    /// Заменяемая версия сохраняется ревизией в той же транзакции,
+    /// если заголовок или текст действительно меняются; там же заменяются
+    /// теги из `patch.tags`. Несовпадение
+    /// `expected_version` с текущей версией — `DomainError::VersionMismatch`.
+    async fn update_post_content(
         &self,
         post_id: i64,
-        owner_id: i64,
+        editor_id: i64,
         patch: PostPatch,
     ) -> Result<Option<Post>, DomainError>;
-    async fn delete_post(&self, id: i64) -> Result<bool, DomainError>;
-    async fn list_posts(&self, pagination: Pagination) -> Result<Vec<Post>, DomainError>;
-    async fn total_posts(&self) -> Result<i64, DomainError>;
+    /// Любая смена статуса отменяет запланированную публикацию.
+    async fn update_post_status(
+        &self,
+        post_id: i64,
+        status: PostStatus,
+    ) -> Result<Option<Post>, DomainError>;
+    /// Планирует публикацию черновика; `None` отменяет её.
+    async fn schedule_post(
+        &self,
+        post_id: i64,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<Post>, DomainError>;
+    /// Публикует до `limit` черновиков, чьё время публикации не позже `now`; возвращает их id.
+    /// Строки, заблокированные другим экземпляром сервера, пропускаются.
+    async fn publish_due_posts(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<i64>, DomainError>;
+    /// Перемещает пост в корзину и отменяет его запланированную публикацию.
+    /// С `expected_version` удаляет пост, только если его версия совпадает.
+    /// Остальные методы, кроме методов корзины, постов в корзине не видят.
+    async fn delete_post(
+        &self,
+        id: i64,
+        expected_version: Option<i64>,
+    ) -> Result<bool, DomainError>;
+    /// Корзина автора: сначала удалённые последними.
+    async fn list_trash(
+        &self,
+        author_id: i64,
+        pagination: Pagination,
+    ) -> Result<Vec<Post>, DomainError>;
+    async fn total_trash(&self, author_id: i64) -> Result<i64, DomainError>;
+    /// Пост, если он в корзине.
+    async fn get_trashed_post(&self, id: i64) -> Result<Option<Post>, DomainError>;
+    /// Возвращает пост из корзины; `None`, если его там нет.
+    async fn restore_post(&self, id: i64) -> Result<Option<Post>, DomainError>;
+    /// Окончательно удаляет пост из корзины вместе с тегами, комментариями и ревизиями.
+    async fn purge_post(&self, id: i64) -> Result<bool, DomainError>;
+    /// Окончательно удаляет до `limit` постов, попавших в корзину раньше `deleted_before`;
+    /// возвращает их id. Строки, заблокированные другим экземпляром сервера, пропускаются.
+    async fn purge_deleted_posts(
+        &self,
+        deleted_before: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<i64>, DomainError>;
+    /// До `limit` опубликованных постов для ленты по убыванию `published_at`;
+    /// `tags` и `author` фильтруют так же, как в `PostListFilter`.
+    async fn list_feed_posts(
+        &self,
+        tags: &[String],
+        author: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError>;
+    /// Последний `updated_at` среди всех постов автора (при `None` — всего блога),
+    /// включая черновики и корзину. Публикация, снятие с публикации, удаление
+    /// и восстановление сдвигают `updated_at`, поэтому значение не убывает,
+    /// пока посты не удаляются окончательно.
+    async fn last_post_change(
+        &self,
+        author: Option<&str>,
+    ) -> Result<Option<DateTime<Utc>>, DomainError>;
+    async fn list_posts(
+        &self,
+        filter: &PostListFilter,
+        pagination: Pagination,
+    ) -> Result<Vec<Post>, DomainError>;
+    /// Keyset-страница ленты: до `limit` постов строго после `after`
+    /// в порядке `(created_at, id)` по убыванию; `None` — с начала ленты.
+    async fn list_posts_after(
+        &self,
+        filter: &PostListFilter,
+        after: Option<PostCursor>,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError>;
+    async fn total_posts(&self, filter: &PostListFilter) -> Result<i64, DomainError>;
+    /// Ревизии поста от новых к старым.
+    async fn list_revisions(&self, post_id: i64) -> Result<Vec<PostRevision>, DomainError>;
+    async fn get_revision(
+        &self,
+        post_id: i64,
+        revision: i32,
+    ) -> Result<Option<PostRevision>, DomainError>;
+    /// Полнотекстовый поиск среди постов, видимых `viewer_id`, по убыванию релевантности.
+    /// Фрагмент в `snippet` размечен маркерами `HIGHLIGHT_START`/`HIGHLIGHT_STOP`.
+    async fn search_posts(
+        &self,
+        viewer_id: Option<i64>,
+        query: &str,
+        pagination: Pagination,
+    ) -> Result<Vec<PostSearchHit>, DomainError>;
+    async fn total_search_results(
+        &self,
+        viewer_id: Option<i64>,
+        query: &str,
+    ) -> Result<i64, DomainError>;
 }
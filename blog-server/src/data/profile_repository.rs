@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::domain::error::DomainError;
+use crate::domain::profile::{Profile, UserProfile};
+
+#[async_trait]
+pub(crate) trait ProfileRepository: Send + Sync {
+    async fn find_by_username(&self, username: &str) -> Result<Option<UserProfile>, DomainError>;
+    async fn find_by_id(&self, user_id: i64) -> Result<Option<UserProfile>, DomainError>;
+
+    /// Заменяет все поля профиля; `None`, если пользователя нет.
+    async fn update_profile(
+        &self,
+        user_id: i64,
+        profile: &Profile,
+    ) -> Result<Option<UserProfile>, DomainError>;
+}
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewRefreshToken {
+    pub(crate) user_id: i64,
+    pub(crate) family_id: String,
+    pub(crate) token_hash: String,
+    pub(crate) expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RefreshTokenRecord {
+    pub(crate) id: i64,
+    pub(crate) user_id: i64,
+    pub(crate) family_id: String,
+    pub(crate) expires_at: DateTime<Utc>,
+    pub(crate) revoked_at: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+pub(crate) trait RefreshTokenRepository: Send + Sync {
+    async fn create_refresh_token(&self, input: NewRefreshToken) -> Result<(), DomainError>;
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshTokenRecord>, DomainError>;
+
+    /// Атомарно отзывает текущий токен и сохраняет следующий в той же семье.
+    /// Возвращает `false`, если текущий токен уже был отозван (гонка/повтор).
+    async fn rotate_refresh_token(
+        &self,
+        current_id: i64,
+        next: NewRefreshToken,
+    ) -> Result<bool, DomainError>;
+
+    async fn revoke_family(&self, family_id: &str) -> Result<(), DomainError>;
+    async fn revoke_all_for_user(&self, user_id: i64) -> Result<(), DomainError>;
+}
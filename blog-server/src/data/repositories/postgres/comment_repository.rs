@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::data::comment_repository::{CommentRepository, NewComment};
+use crate::domain::comment::Comment;
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresCommentRepository {
+    pool: PgPool,
+}
+
+impl PostgresCommentRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+struct CommentRow {
+    id: i64,
+    post_id: i64,
+    author_id: i64,
+    parent_id: Option<i64>,
+    content: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CommentRow> for Comment {
+    fn from(row: CommentRow) -> Self {
+        Self {
+            id: row.id,
+            post_id: row.post_id,
+            author_id: row.author_id,
+            parent_id: row.parent_id,
+            content: row.content,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[async_trait]
+impl CommentRepository for PostgresCommentRepository {
+    async fn create_comment(&self, input: NewComment) -> Result<Comment, DomainError> {
+        let row = sqlx::query_as!(
+            CommentRow,
+            r#"
+            INSERT INTO comments (post_id, author_id, parent_id, content)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, post_id, author_id, parent_id, content, created_at, updated_at
+            "#,
+            input.post_id,
+            input.author_id,
+            input.parent_id,
+            input.content,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_comment_db_error)?;
+
+        Ok(row.into())
+    }
+
+    async fn get_comment(&self, id: i64) -> Result<Option<Comment>, DomainError> {
+        let row = sqlx::query_as!(
+            CommentRow,
+            r#"
+            SELECT id, post_id, author_id, parent_id, content, created_at, updated_at
+            FROM comments
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_comment_db_error)?;
+
+        Ok(row.map(Comment::from))
+    }
+
+    async fn list_comments_for_post(&self, post_id: i64) -> Result<Vec<Comment>, DomainError> {
+        let rows = sqlx::query_as!(
+            CommentRow,
+            r#"
+            SELECT id, post_id, author_id, parent_id, content, created_at, updated_at
+            FROM comments
+            WHERE post_id = $1
+            ORDER BY created_at, id
+            "#,
+            post_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_comment_db_error)?;
+
+        Ok(rows.into_iter().map(Comment::from).collect())
+    }
+
+    async fn delete_comment(&self, id: i64) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM comments
+            WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_comment_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn map_comment_db_error(err: sqlx::Error) -> DomainError {
+    // пост или родительский комментарий удалили между проверкой и вставкой
+    if let sqlx::Error::Database(db_err) = &err
+        && db_err.code().as_deref() == Some("23503")
+    {
+        return DomainError::NotFound("post or parent comment".to_string());
+    }
+    DomainError::Unexpected(err.to_string())
+}
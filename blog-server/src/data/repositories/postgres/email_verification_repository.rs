@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::data::email_verification_repository::{
+    EmailVerificationRepository, NewEmailVerificationToken,
+};
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresEmailVerificationRepository {
+    pool: PgPool,
+}
+
+impl PostgresEmailVerificationRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EmailVerificationRepository for PostgresEmailVerificationRepository {
+    async fn create_verification_token(
+        &self,
+        input: NewEmailVerificationToken,
+    ) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO email_verification_tokens (user_id, token_hash, expires_at)
+            VALUES ($1, $2, $3)
+            "#,
+            input.user_id,
+            input.token_hash,
+            input.expires_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_email_verification_db_error)?;
+
+        Ok(())
+    }
+
+    async fn consume_verification_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<i64>, DomainError> {
+        let user_id = sqlx::query_scalar!(
+            r#"
+            UPDATE email_verification_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_email_verification_db_error)?;
+
+        Ok(user_id)
+    }
+
+    async fn invalidate_for_user(&self, user_id: i64) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE email_verification_tokens
+            SET used_at = NOW()
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_email_verification_db_error)?;
+
+        Ok(())
+    }
+}
+
+fn map_email_verification_db_error(err: sqlx::Error) -> DomainError {
+    DomainError::Unexpected(err.to_string())
+}
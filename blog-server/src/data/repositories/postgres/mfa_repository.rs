@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::data::mfa_repository::{MfaRepository, TotpRecord};
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresMfaRepository {
+    pool: PgPool,
+}
+
+impl PostgresMfaRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MfaRepository for PostgresMfaRepository {
+    async fn find_totp(&self, user_id: i64) -> Result<Option<TotpRecord>, DomainError> {
+        let record = sqlx::query_as!(
+            TotpRecord,
+            r#"
+            SELECT secret, enabled_at
+            FROM user_totp
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_mfa_db_error)?;
+
+        Ok(record)
+    }
+
+    async fn set_pending_totp(&self, user_id: i64, secret: &str) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO user_totp (user_id, secret)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET secret = EXCLUDED.secret, last_used_step = NULL, created_at = NOW()
+            WHERE user_totp.enabled_at IS NULL
+            "#,
+            user_id,
+            secret,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_mfa_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn enable_totp(
+        &self,
+        user_id: i64,
+        recovery_code_hashes: &[String],
+    ) -> Result<bool, DomainError> {
+        let mut tx = self.pool.begin().await.map_err(map_mfa_db_error)?;
+
+        let enabled = sqlx::query!(
+            r#"
+            UPDATE user_totp
+            SET enabled_at = NOW()
+            WHERE user_id = $1 AND enabled_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_mfa_db_error)?;
+        if enabled.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!("DELETE FROM mfa_recovery_codes WHERE user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_mfa_db_error)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO mfa_recovery_codes (user_id, code_hash)
+            SELECT $1, code_hash FROM UNNEST($2::text[]) AS code_hash
+            "#,
+            user_id,
+            recovery_code_hashes,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_mfa_db_error)?;
+
+        tx.commit().await.map_err(map_mfa_db_error)?;
+        Ok(true)
+    }
+
+    async fn disable_totp(&self, user_id: i64) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(map_mfa_db_error)?;
+
+        sqlx::query!("DELETE FROM user_totp WHERE user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_mfa_db_error)?;
+        sqlx::query!("DELETE FROM mfa_recovery_codes WHERE user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_mfa_db_error)?;
+
+        tx.commit().await.map_err(map_mfa_db_error)
+    }
+
+    async fn mark_step_used(&self, user_id: i64, step: i64) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE user_totp
+            SET last_used_step = $2
+            WHERE user_id = $1 AND (last_used_step IS NULL OR last_used_step < $2)
+            "#,
+            user_id,
+            step,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_mfa_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn consume_recovery_code(
+        &self,
+        user_id: i64,
+        code_hash: &str,
+    ) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE mfa_recovery_codes
+            SET used_at = NOW()
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+            user_id,
+            code_hash,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_mfa_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn map_mfa_db_error(err: sqlx::Error) -> DomainError {
+    DomainError::Unexpected(err.to_string())
+}
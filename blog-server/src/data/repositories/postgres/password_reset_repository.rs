@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::data::password_reset_repository::{NewPasswordResetToken, PasswordResetRepository};
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresPasswordResetRepository {
+    pool: PgPool,
+}
+
+impl PostgresPasswordResetRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PasswordResetRepository for PostgresPasswordResetRepository {
+    async fn create_reset_token(&self, input: NewPasswordResetToken) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+            VALUES ($1, $2, $3)
+            "#,
+            input.user_id,
+            input.token_hash,
+            input.expires_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_password_reset_db_error)?;
+
+        Ok(())
+    }
+
+    async fn consume_reset_token(&self, token_hash: &str) -> Result<Option<i64>, DomainError> {
+        // условие used_at IS NULL не даёт использовать токен дважды,
+        // даже если два запроса пришли одновременно
+        let user_id = sqlx::query_scalar!(
+            r#"
+            UPDATE password_reset_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_password_reset_db_error)?;
+
+        Ok(user_id)
+    }
+
+    async fn invalidate_for_user(&self, user_id: i64) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE password_reset_tokens
+            SET used_at = NOW()
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_password_reset_db_error)?;
+
+        Ok(())
+    }
+}
+
+fn map_password_reset_db_error(err: sqlx::Error) -> DomainError {
+    DomainError::Unexpected(err.to_string())
+}
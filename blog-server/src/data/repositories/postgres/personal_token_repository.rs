@@ -0,0 +1,174 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::data::personal_token_repository::{
+    NewPersonalToken, PersonalTokenOwner, PersonalTokenRepository,
+};
+use crate::domain::error::DomainError;
+use crate::domain::personal_token::{PersonalToken, TokenScope, TokenScopes};
+use crate::domain::user::Role;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresPersonalTokenRepository {
+    pool: PgPool,
+}
+
+impl PostgresPersonalTokenRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+struct PersonalTokenRow {
+    id: i64,
+    user_id: i64,
+    name: String,
+    scopes: Vec<String>,
+    expires_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+#[async_trait]
+impl PersonalTokenRepository for PostgresPersonalTokenRepository {
+    async fn create_token(&self, input: NewPersonalToken) -> Result<PersonalToken, DomainError> {
+        let scopes: Vec<String> = input
+            .scopes
+            .iter()
+            .map(|scope| scope.as_str().to_string())
+            .collect();
+        let row = sqlx::query_as!(
+            PersonalTokenRow,
+            r#"
+            INSERT INTO personal_access_tokens (user_id, name, token_hash, scopes, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, user_id, name, scopes, expires_at, last_used_at, created_at
+            "#,
+            input.user_id,
+            input.name,
+            input.token_hash,
+            &scopes,
+            input.expires_at,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_personal_token_db_error)?;
+
+        Ok(map_token(row))
+    }
+
+    async fn list_tokens(&self, user_id: i64) -> Result<Vec<PersonalToken>, DomainError> {
+        let rows = sqlx::query_as!(
+            PersonalTokenRow,
+            r#"
+            SELECT id, user_id, name, scopes, expires_at, last_used_at, created_at
+            FROM personal_access_tokens
+            WHERE user_id = $1 AND revoked_at IS NULL
+            ORDER BY created_at DESC, id DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_personal_token_db_error)?;
+
+        Ok(rows.into_iter().map(map_token).collect())
+    }
+
+    async fn find_active_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PersonalTokenOwner>, DomainError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT t.id, t.user_id, t.name, t.scopes, t.expires_at, t.last_used_at,
+                   t.created_at, u.role, u.email_verified_at
+            FROM personal_access_tokens t
+            JOIN users u ON u.id = t.user_id
+            WHERE t.token_hash = $1 AND t.revoked_at IS NULL
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_personal_token_db_error)?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let role = row
+            .role
+            .parse::<Role>()
+            .map_err(|err| DomainError::Unexpected(err.to_string()))?;
+        Ok(Some(PersonalTokenOwner {
+            token: map_token(PersonalTokenRow {
+                id: row.id,
+                user_id: row.user_id,
+                name: row.name,
+                scopes: row.scopes,
+                expires_at: row.expires_at,
+                last_used_at: row.last_used_at,
+                created_at: row.created_at,
+            }),
+            role,
+            email_verified: row.email_verified_at.is_some(),
+        }))
+    }
+
+    async fn touch_last_used(&self, token_id: i64, at: DateTime<Utc>) -> Result<(), DomainError> {
+        sqlx::query!(
+            "UPDATE personal_access_tokens SET last_used_at = $2 WHERE id = $1",
+            token_id,
+            at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_personal_token_db_error)?;
+
+        Ok(())
+    }
+
+    async fn revoke_token(&self, user_id: i64, token_id: i64) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE personal_access_tokens
+            SET revoked_at = NOW()
+            WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+            "#,
+            token_id,
+            user_id,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_personal_token_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Неизвестные права (например, удалённые в новой версии) просто не выдаются.
+fn map_token(row: PersonalTokenRow) -> PersonalToken {
+    PersonalToken {
+        id: row.id,
+        user_id: row.user_id,
+        name: row.name,
+        scopes: row
+            .scopes
+            .iter()
+            .filter_map(|scope| scope.parse::<TokenScope>().ok())
+            .collect::<TokenScopes>(),
+        expires_at: row.expires_at,
+        last_used_at: row.last_used_at,
+        created_at: row.created_at,
+    }
+}
+
+fn map_personal_token_db_error(err: sqlx::Error) -> DomainError {
+    if let sqlx::Error::Database(db_err) = &err
+        && db_err.code().as_deref() == Some("23505")
+    {
+        return DomainError::AlreadyExists("token name".to_string());
+    }
+    DomainError::Unexpected(err.to_string())
+}
@@ -1,10 +1,18 @@
+use std::collections::{HashMap, HashSet};
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 
-use crate::data::post_repository::{NewPost, Pagination, PostPatch, PostRepository};
+use crate::data::post_repository::{
+    NewPost, Pagination, PostListFilter, PostPatch, PostRepository,
+};
+use crate::data::repositories::postgres::tag_repository::replace_post_tags;
+use crate::domain::cursor::PostCursor;
 use crate::domain::error::DomainError;
-use crate::domain::post::Post;
+use crate::domain::post::{ContentFormat, Post, PostAuthor, PostStatus};
+use crate::domain::revision::PostRevision;
+use crate::domain::search::PostSearchHit;
 
 #[derive(Debug, Clone)]
 pub(crate) struct PostgresPostRepository {
@@ -17,57 +25,126 @@ impl PostgresPostRepository {
     }
 }
 
+/// Запрос постов в `PostRow`: колонки и JOIN автора перечислены только здесь.
+///
+/// `query_posts!("WHERE …", args…)` выбирает из `posts p`;
+/// `query_posts!(returning "UPDATE … RETURNING *", args…)` — из строк, изменённых запросом.
+/// Строка собирается из литералов через `+`, поэтому проверка `sqlx` на этапе сборки сохраняется.
+macro_rules! query_posts {
+    (@select [$($prefix:literal)*], $source:literal, $tail:literal $(, $arg:expr)*) => {
+        sqlx::query_as!(
+            PostRow,
+            $($prefix +)* r#"
+            SELECT
+                p.id as "id!",
+                p.slug as "slug!",
+                p.title as "title!",
+                p.content as "content!",
+                p.content_format as "content_format!",
+                p.content_html as "content_html!",
+                p.author_id as "author_id!",
+                p.status as "status!",
+                p.published_at,
+                p.publish_at,
+                p.deleted_at,
+                p.created_at as "created_at!",
+                p.updated_at as "updated_at!",
+                p.version as "version!",
+                u.username as "author_username!",
+                u.display_name as author_display_name
+            FROM "# + $source + r#" p
+            JOIN users u ON u.id = p.author_id
+            "# + $tail
+            $(, $arg)*
+        )
+    };
+    (returning $statement:literal $(, $arg:expr)* $(,)?) => {
+        query_posts!(@select ["WITH changed AS (" $statement ")"], "changed", "" $(, $arg)*)
+    };
+    ($tail:literal $(, $arg:expr)* $(,)?) => {
+        query_posts!(@select [], "posts", $tail $(, $arg)*)
+    };
+}
+
 struct PostRow {
     id: i64,
+    slug: String,
     title: String,
     content: String,
+    content_format: String,
+    content_html: String,
     author_id: i64,
+    status: String,
+    published_at: Option<DateTime<Utc>>,
+    publish_at: Option<DateTime<Utc>>,
+    deleted_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    version: i64,
+    author_username: String,
+    author_display_name: Option<String>,
+}
+
+struct RevisionRow {
+    post_id: i64,
+    revision: i32,
+    title: String,
+    content: String,
+    editor_id: i64,
+    created_at: DateTime<Utc>,
+}
+
+struct SearchHitRow {
+    id: i64,
+    rank: f32,
+    snippet: String,
 }
 
 #[async_trait]
 impl PostRepository for PostgresPostRepository {
     async fn create_post(&self, input: NewPost) -> Result<Post, DomainError> {
-        let row = sqlx::query_as!(
-            PostRow,
-            r#"
-            INSERT INTO posts (title, content, author_id)
-            VALUES ($1, $2, $3)
-            RETURNING id, title as "title!", content, author_id, created_at, updated_at
+        let mut tx = self.pool.begin().await.map_err(map_post_db_error)?;
+
+        let row = query_posts!(
+            returning r#"
+            INSERT INTO posts (
+                title, content, author_id, status, published_at, content_format, content_html,
+                slug, publish_at
+            )
+            VALUES (
+                $1, $2, $3, $4::VARCHAR,
+                CASE WHEN $4::VARCHAR = 'published' THEN NOW() END,
+                $5, $6, $7, $8
+            )
+            RETURNING *
             "#,
             input.title,
             input.content,
             input.author_id,
+            input.status.as_str(),
+            input.content_format.as_str(),
+            input.content_html,
+            input.slug,
+            input.publish_at,
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(map_post_db_error)?;
 
-        Post::new(
-            row.id,
-            row.title,
-            row.content,
-            row.author_id,
-            row.created_at,
-            row.updated_at,
-        )
-        .map_err(|err| DomainError::Unexpected(err.to_string()))
+        if !input.tags.is_empty() {
+            replace_post_tags(&mut tx, row.id, &input.tags)
+                .await
+                .map_err(map_post_db_error)?;
+        }
+
+        tx.commit().await.map_err(map_post_db_error)?;
+        map_row_to_post(row)
     }
 
     async fn get_post(&self, id: i64) -> Result<Option<Post>, DomainError> {
-        let row = sqlx::query_as!(
-            PostRow,
+        let row = query_posts!(
             r#"
-            SELECT
-            id,
-            title,
-            content,
-            author_id,
-            created_at,
-            updated_at
-            FROM posts
-            WHERE id = $1
+            WHERE p.id = $1 AND p.deleted_at IS NULL
             "#,
             id,
         )
@@ -78,26 +155,209 @@ impl PostRepository for PostgresPostRepository {
         row.map(map_row_to_post).transpose()
     }
 
-    async fn update_post_owned(
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Option<Post>, DomainError> {
+        let row = query_posts!(
+            r#"
+            WHERE (
+                    p.slug = $1
+                    OR p.id = (SELECT r.post_id FROM post_slug_redirects r WHERE r.slug = $1)
+                )
+              AND p.deleted_at IS NULL
+            -- текущий slug одного поста важнее прежнего slug другого
+            ORDER BY (p.slug = $1) DESC
+            LIMIT 1
+            "#,
+            slug,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        row.map(map_row_to_post).transpose()
+    }
+
+    async fn slugs_taken(
+        &self,
+        prefix: &str,
+        except_post_id: Option<i64>,
+    ) -> Result<HashSet<String>, DomainError> {
+        // в slug нет `%` и `_`, поэтому префикс не нужно экранировать для LIKE
+        let slugs = sqlx::query_scalar!(
+            r#"
+            SELECT slug as "slug!" FROM posts
+            WHERE slug LIKE $1 || '%' AND id IS DISTINCT FROM $2
+            UNION
+            SELECT slug as "slug!" FROM post_slug_redirects
+            WHERE slug LIKE $1 || '%' AND post_id IS DISTINCT FROM $2
+            "#,
+            prefix,
+            except_post_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        Ok(slugs.into_iter().collect())
+    }
+
+    async fn update_post_content(
         &self,
         post_id: i64,
-        owner_id: i64,
+        editor_id: i64,
         patch: PostPatch,
     ) -> Result<Option<Post>, DomainError> {
-        let row = sqlx::query_as!(
-            PostRow,
+        let mut tx = self.pool.begin().await.map_err(map_post_db_error)?;
+
+        // блокировка строки поста сериализует правки и нумерацию ревизий
+        let current = sqlx::query!(
             r#"
+            SELECT title, content, slug, version
+            FROM posts
+            WHERE id = $1 AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+            post_id,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(map_post_db_error)?;
+
+        let Some(current) = current else {
+            return Ok(None);
+        };
+
+        if let Some(expected) = patch.expected_version
+            && expected != current.version
+        {
+            return Err(DomainError::VersionMismatch {
+                expected,
+                current: current.version,
+            });
+        }
+
+        if current.title != patch.title || current.content != patch.content {
+            sqlx::query!(
+                r#"
+                INSERT INTO post_revisions (post_id, revision, title, content, editor_id)
+                SELECT $1, COALESCE(MAX(revision), 0) + 1, $2, $3, $4
+                FROM post_revisions
+                WHERE post_id = $1
+                "#,
+                post_id,
+                current.title,
+                current.content,
+                editor_id,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(map_post_db_error)?;
+        }
+
+        if current.slug != patch.slug {
+            // возврат к прежнему slug забирает его из истории, старый slug уходит в историю
+            sqlx::query!(
+                "DELETE FROM post_slug_redirects WHERE slug = $1 AND post_id = $2",
+                patch.slug,
+                post_id,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(map_post_db_error)?;
+            sqlx::query!(
+                r#"
+                INSERT INTO post_slug_redirects (slug, post_id)
+                VALUES ($1, $2)
+                ON CONFLICT (slug) DO UPDATE SET post_id = EXCLUDED.post_id, created_at = NOW()
+                "#,
+                current.slug,
+                post_id,
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(map_post_db_error)?;
+        }
+
+        if let Some(tags) = &patch.tags {
+            replace_post_tags(&mut tx, post_id, tags)
+                .await
+                .map_err(map_post_db_error)?;
+        }
+
+        let row = query_posts!(
+            returning r#"
             UPDATE posts
-            SET title = $3,
-                content = $4,
-                updated_at = NOW()
-            WHERE id = $1 AND author_id = $2
-            RETURNING id, title as "title!", content, author_id, created_at, updated_at
+            SET title = $2,
+                content = $3,
+                content_format = $4,
+                content_html = $5,
+                slug = $6,
+                updated_at = NOW(),
+                version = version + 1
+            WHERE id = $1
+            RETURNING *
             "#,
             post_id,
-            owner_id,
             patch.title,
             patch.content,
+            patch.content_format.as_str(),
+            patch.content_html,
+            patch.slug,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_post_db_error)?;
+
+        tx.commit().await.map_err(map_post_db_error)?;
+        map_row_to_post(row).map(Some)
+    }
+
+    async fn update_post_status(
+        &self,
+        post_id: i64,
+        status: PostStatus,
+    ) -> Result<Option<Post>, DomainError> {
+        // при повторной публикации сохраняем исходную дату, снятие с публикации её сбрасывает
+        let row = query_posts!(
+            returning r#"
+            UPDATE posts
+            SET status = $2::VARCHAR,
+                published_at = CASE
+                    WHEN $2::VARCHAR = 'published' THEN COALESCE(published_at, NOW())
+                    WHEN $2::VARCHAR = 'draft' THEN NULL
+                    ELSE published_at
+                END,
+                publish_at = NULL,
+                updated_at = NOW(),
+                version = version + 1
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+            post_id,
+            status.as_str(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        row.map(map_row_to_post).transpose()
+    }
+
+    async fn schedule_post(
+        &self,
+        post_id: i64,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<Post>, DomainError> {
+        let row = query_posts!(
+            returning r#"
+            UPDATE posts
+            SET publish_at = $2,
+                updated_at = NOW(),
+                version = version + 1
+            WHERE id = $1 AND status = 'draft' AND deleted_at IS NULL
+            RETURNING *
+            "#,
+            post_id,
+            publish_at,
         )
         .fetch_optional(&self.pool)
         .await
@@ -106,13 +366,60 @@ impl PostRepository for PostgresPostRepository {
         row.map(map_row_to_post).transpose()
     }
 
-    async fn delete_post(&self, id: i64) -> Result<bool, DomainError> {
+    async fn publish_due_posts(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<i64>, DomainError> {
+        // SKIP LOCKED: несколько экземпляров сервера разбирают очередь, не дожидаясь друг друга
+        // и не публикуя один пост дважды
+        let ids = sqlx::query_scalar!(
+            r#"
+            WITH due AS (
+                SELECT id
+                FROM posts
+                WHERE publish_at <= $1 AND status = 'draft' AND deleted_at IS NULL
+                ORDER BY publish_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE posts p
+            SET status = 'published',
+                published_at = NOW(),
+                publish_at = NULL,
+                updated_at = NOW(),
+                version = p.version + 1
+            FROM due
+            WHERE p.id = due.id
+            RETURNING p.id
+            "#,
+            now,
+            i64::from(limit),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        Ok(ids)
+    }
+
+    async fn delete_post(
+        &self,
+        id: i64,
+        expected_version: Option<i64>,
+    ) -> Result<bool, DomainError> {
+        // пост остаётся в корзине; запланированная публикация отменяется
         let result = sqlx::query!(
             r#"
-            DELETE FROM posts
-            WHERE id = $1
+            UPDATE posts
+            SET deleted_at = NOW(),
+                publish_at = NULL,
+                updated_at = NOW(),
+                version = version + 1
+            WHERE id = $1 AND deleted_at IS NULL AND ($2::BIGINT IS NULL OR version = $2)
             "#,
-            id
+            id,
+            expected_version,
         )
         .execute(&self.pool)
         .await
@@ -121,27 +428,260 @@ impl PostRepository for PostgresPostRepository {
         Ok(result.rows_affected() > 0)
     }
 
-    async fn list_posts(&self, pagination: Pagination) -> Result<Vec<Post>, DomainError> {
+    async fn list_trash(
+        &self,
+        author_id: i64,
+        pagination: Pagination,
+    ) -> Result<Vec<Post>, DomainError> {
         let limit = pagination.page_size as i64;
         let offset = (pagination.page.saturating_sub(1) as i64) * limit;
 
-        let rows = sqlx::query_as!(
-            PostRow,
+        let rows = query_posts!(
             r#"
-            SELECT
-                id,
-                title,
-                content,
-                author_id,
-                created_at,
-                updated_at
+            WHERE p.author_id = $3 AND p.deleted_at IS NOT NULL
+            ORDER BY p.deleted_at DESC, p.id DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+            limit,
+            offset,
+            author_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        rows.into_iter().map(map_row_to_post).collect()
+    }
+
+    async fn total_trash(&self, author_id: i64) -> Result<i64, DomainError> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!"
             FROM posts
-            ORDER BY created_at DESC, id DESC
+            WHERE author_id = $1 AND deleted_at IS NOT NULL
+            "#,
+            author_id,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        Ok(count)
+    }
+
+    async fn get_trashed_post(&self, id: i64) -> Result<Option<Post>, DomainError> {
+        let row = query_posts!(
+            r#"
+            WHERE p.id = $1 AND p.deleted_at IS NOT NULL
+            "#,
+            id,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        row.map(map_row_to_post).transpose()
+    }
+
+    async fn restore_post(&self, id: i64) -> Result<Option<Post>, DomainError> {
+        // slug удалённого поста остаётся занятым, поэтому восстановление не даёт конфликтов
+        let row = query_posts!(
+            returning r#"
+            UPDATE posts
+            SET deleted_at = NULL,
+                updated_at = NOW(),
+                version = version + 1
+            WHERE id = $1 AND deleted_at IS NOT NULL
+            RETURNING *
+            "#,
+            id,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        row.map(map_row_to_post).transpose()
+    }
+
+    async fn purge_post(&self, id: i64) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            "DELETE FROM posts WHERE id = $1 AND deleted_at IS NOT NULL",
+            id,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn purge_deleted_posts(
+        &self,
+        deleted_before: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<i64>, DomainError> {
+        // как и отложенная публикация, очистка безопасна при нескольких экземплярах сервера
+        let ids = sqlx::query_scalar!(
+            r#"
+            WITH expired AS (
+                SELECT id
+                FROM posts
+                WHERE deleted_at < $1
+                ORDER BY deleted_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            DELETE FROM posts p
+            USING expired
+            WHERE p.id = expired.id
+            RETURNING p.id
+            "#,
+            deleted_before,
+            i64::from(limit),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        Ok(ids)
+    }
+
+    async fn list_feed_posts(
+        &self,
+        tags: &[String],
+        author: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError> {
+        let rows = query_posts!(
+            r#"
+            WHERE p.status = 'published'
+              AND p.deleted_at IS NULL
+              AND (
+                  cardinality($2::VARCHAR[]) = 0
+                  OR p.id IN (
+                      SELECT pt.post_id
+                      FROM post_tags pt
+                      JOIN tags t ON t.id = pt.tag_id
+                      WHERE t.name = ANY($2::VARCHAR[])
+                      GROUP BY pt.post_id
+                      HAVING COUNT(*) = cardinality($2::VARCHAR[])
+                  )
+              )
+              AND ($3::VARCHAR IS NULL OR u.username = $3)
+            ORDER BY p.published_at DESC, p.id DESC
+            LIMIT $1
+            "#,
+            limit as i64,
+            tags,
+            author,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        rows.into_iter().map(map_row_to_post).collect()
+    }
+
+    async fn last_post_change(
+        &self,
+        author: Option<&str>,
+    ) -> Result<Option<DateTime<Utc>>, DomainError> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT MAX(p.updated_at)
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE $1::VARCHAR IS NULL OR u.username = $1
+            "#,
+            author,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_post_db_error)
+    }
+
+    async fn list_posts(
+        &self,
+        filter: &PostListFilter,
+        pagination: Pagination,
+    ) -> Result<Vec<Post>, DomainError> {
+        let limit = pagination.page_size as i64;
+        let offset = (pagination.page.saturating_sub(1) as i64) * limit;
+
+        let rows = query_posts!(
+            r#"
+            WHERE (p.status = 'published' OR p.author_id = $3)
+              AND p.deleted_at IS NULL
+              AND (
+                  cardinality($4::VARCHAR[]) = 0
+                  OR p.id IN (
+                      SELECT pt.post_id
+                      FROM post_tags pt
+                      JOIN tags t ON t.id = pt.tag_id
+                      WHERE t.name = ANY($4::VARCHAR[])
+                      GROUP BY pt.post_id
+                      HAVING COUNT(*) = cardinality($4::VARCHAR[])
+                  )
+              )
+              AND ($5::VARCHAR IS NULL OR u.username = $5)
+            ORDER BY p.created_at DESC, p.id DESC
             LIMIT $1
             OFFSET $2
             "#,
             limit,
             offset,
+            filter.viewer_id,
+            &filter.tags,
+            filter.author.as_deref(),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        rows.into_iter().map(map_row_to_post).collect()
+    }
+
+    async fn list_posts_after(
+        &self,
+        filter: &PostListFilter,
+        after: Option<PostCursor>,
+        limit: u32,
+    ) -> Result<Vec<Post>, DomainError> {
+        let (after_created_at, after_id) = match after {
+            Some(cursor) => (Some(cursor.created_at), Some(cursor.id)),
+            None => (None, None),
+        };
+
+        let rows = query_posts!(
+            r#"
+            WHERE (p.status = 'published' OR p.author_id = $2)
+              AND p.deleted_at IS NULL
+              AND (
+                  cardinality($3::VARCHAR[]) = 0
+                  OR p.id IN (
+                      SELECT pt.post_id
+                      FROM post_tags pt
+                      JOIN tags t ON t.id = pt.tag_id
+                      WHERE t.name = ANY($3::VARCHAR[])
+                      GROUP BY pt.post_id
+                      HAVING COUNT(*) = cardinality($3::VARCHAR[])
+                  )
+              )
+              AND (
+                  $4::TIMESTAMPTZ IS NULL
+                  OR (p.created_at, p.id) < ($4::TIMESTAMPTZ, $5::BIGINT)
+              )
+              AND ($6::VARCHAR IS NULL OR u.username = $6)
+            ORDER BY p.created_at DESC, p.id DESC
+            LIMIT $1
+            "#,
+            limit as i64,
+            filter.viewer_id,
+            &filter.tags,
+            after_created_at,
+            after_id,
+            filter.author.as_deref(),
         )
         .fetch_all(&self.pool)
         .await
@@ -150,12 +690,163 @@ impl PostRepository for PostgresPostRepository {
         rows.into_iter().map(map_row_to_post).collect()
     }
 
-    async fn total_posts(&self) -> Result<i64, DomainError> {
+    async fn total_posts(&self, filter: &PostListFilter) -> Result<i64, DomainError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE (p.status = 'published' OR p.author_id = $1)
+              AND p.deleted_at IS NULL
+              AND (
+                  cardinality($2::VARCHAR[]) = 0
+                  OR p.id IN (
+                      SELECT pt.post_id
+                      FROM post_tags pt
+                      JOIN tags t ON t.id = pt.tag_id
+                      WHERE t.name = ANY($2::VARCHAR[])
+                      GROUP BY pt.post_id
+                      HAVING COUNT(*) = cardinality($2::VARCHAR[])
+                  )
+              )
+              AND ($3::VARCHAR IS NULL OR u.username = $3)
+            "#,
+            filter.viewer_id,
+            &filter.tags,
+            filter.author.as_deref(),
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        Ok(row.count)
+    }
+
+    async fn list_revisions(&self, post_id: i64) -> Result<Vec<PostRevision>, DomainError> {
+        let rows = sqlx::query_as!(
+            RevisionRow,
+            r#"
+            SELECT post_id, revision, title, content, editor_id, created_at
+            FROM post_revisions
+            WHERE post_id = $1
+            ORDER BY revision DESC
+            "#,
+            post_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        Ok(rows.into_iter().map(map_revision_row).collect())
+    }
+
+    async fn get_revision(
+        &self,
+        post_id: i64,
+        revision: i32,
+    ) -> Result<Option<PostRevision>, DomainError> {
+        let row = sqlx::query_as!(
+            RevisionRow,
+            r#"
+            SELECT post_id, revision, title, content, editor_id, created_at
+            FROM post_revisions
+            WHERE post_id = $1 AND revision = $2
+            "#,
+            post_id,
+            revision,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        Ok(row.map(map_revision_row))
+    }
+
+    async fn search_posts(
+        &self,
+        viewer_id: Option<i64>,
+        query: &str,
+        pagination: Pagination,
+    ) -> Result<Vec<PostSearchHit>, DomainError> {
+        let limit = pagination.page_size as i64;
+        let offset = (pagination.page.saturating_sub(1) as i64) * limit;
+
+        // маркеры совпадений — управляющие символы chr(2)/chr(3), HTML из них собирает домен
+        let hits = sqlx::query_as!(
+            SearchHitRow,
+            r#"
+            SELECT
+                p.id,
+                ts_rank(p.search_vector, q.query) as "rank!",
+                ts_headline(
+                    'simple',
+                    p.content,
+                    q.query,
+                    'StartSel=' || chr(2) || ', StopSel=' || chr(3)
+                        || ', MaxFragments=2, MaxWords=30, MinWords=10'
+                ) as "snippet!"
+            FROM posts p
+            CROSS JOIN websearch_to_tsquery('simple', $4) AS q(query)
+            WHERE (p.status = 'published' OR p.author_id = $3)
+              AND p.deleted_at IS NULL
+              AND p.search_vector @@ q.query
+            ORDER BY ts_rank(p.search_vector, q.query) DESC, p.created_at DESC, p.id DESC
+            LIMIT $1
+            OFFSET $2
+            "#,
+            limit,
+            offset,
+            viewer_id,
+            query,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+
+        // сами посты выбираются общим запросом, чтобы строка поста собиралась в одном месте
+        let ids: Vec<i64> = hits.iter().map(|hit| hit.id).collect();
+        let rows = query_posts!(
+            r#"
+            WHERE p.id = ANY($1)
+            "#,
+            &ids,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_post_db_error)?;
+        let mut posts = rows
+            .into_iter()
+            .map(|row| map_row_to_post(row).map(|post| (post.id, post)))
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        // пост, удалённый между запросами, просто выпадает из выдачи
+        Ok(hits
+            .into_iter()
+            .filter_map(|hit| {
+                posts.remove(&hit.id).map(|post| PostSearchHit {
+                    post,
+                    rank: hit.rank,
+                    snippet: hit.snippet,
+                })
+            })
+            .collect())
+    }
+
+    async fn total_search_results(
+        &self,
+        viewer_id: Option<i64>,
+        query: &str,
+    ) -> Result<i64, DomainError> {
         let row = sqlx::query!(
             r#"
             SELECT COUNT(*) as "count!"
             FROM posts
-            "#
+            WHERE (status = 'published' OR author_id = $1)
+              AND deleted_at IS NULL
+              AND search_vector @@ websearch_to_tsquery('simple', $2)
+            "#,
+            viewer_id,
+            query,
         )
         .fetch_one(&self.pool)
         .await
@@ -165,7 +856,32 @@ impl PostRepository for PostgresPostRepository {
     }
 }
 
+fn map_revision_row(row: RevisionRow) -> PostRevision {
+    PostRevision {
+        post_id: row.post_id,
+        revision: row.revision,
+        title: row.title,
+        content: row.content,
+        editor_id: row.editor_id,
+        created_at: row.created_at,
+    }
+}
+
 fn map_row_to_post(row: PostRow) -> Result<Post, DomainError> {
+    let status = row
+        .status
+        .parse::<PostStatus>()
+        .map_err(|err| DomainError::Unexpected(err.to_string()))?;
+    let content_format = row
+        .content_format
+        .parse::<ContentFormat>()
+        .map_err(|err| DomainError::Unexpected(err.to_string()))?;
+
+    let author = PostAuthor {
+        id: row.author_id,
+        username: row.author_username,
+        display_name: row.author_display_name,
+    };
     Post::new(
         row.id,
         row.title,
@@ -174,6 +890,15 @@ fn map_row_to_post(row: PostRow) -> Result<Post, DomainError> {
         row.created_at,
         row.updated_at,
     )
+    .and_then(|post| post.with_publication(status, row.published_at))
+    .and_then(|post| post.with_schedule(row.publish_at))
+    .map(|post| {
+        post.with_version(row.version)
+            .with_deleted_at(row.deleted_at)
+            .with_slug(row.slug)
+            .with_content_html(content_format, row.content_html)
+            .with_author(author)
+    })
     .map_err(|err| DomainError::Unexpected(err.to_string()))
 }
 
@@ -183,5 +908,11 @@ fn map_post_db_error(err: sqlx::Error) -> DomainError {
     {
         return DomainError::NotFound("author".to_string());
     }
+    // slug выбирается до записи, и параллельная запись могла успеть занять его
+    if let sqlx::Error::Database(db_err) = &err
+        && db_err.code().as_deref() == Some("23505")
+    {
+        return DomainError::AlreadyExists("post slug".to_string());
+    }
     DomainError::Unexpected(err.to_string())
 }
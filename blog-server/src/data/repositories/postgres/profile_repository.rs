@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::data::profile_repository::ProfileRepository;
+use crate::domain::error::DomainError;
+use crate::domain::profile::{Profile, UserProfile};
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresProfileRepository {
+    pool: PgPool,
+}
+
+impl PostgresProfileRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+struct ProfileRow {
+    id: i64,
+    username: String,
+    display_name: Option<String>,
+    bio: Option<String>,
+    website: Option<String>,
+    avatar_url: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+#[async_trait]
+impl ProfileRepository for PostgresProfileRepository {
+    async fn find_by_username(&self, username: &str) -> Result<Option<UserProfile>, DomainError> {
+        let row = sqlx::query_as!(
+            ProfileRow,
+            r#"
+            SELECT id, username, display_name, bio, website, avatar_url, created_at
+            FROM users
+            WHERE username = $1
+            "#,
+            username
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_profile_db_error)?;
+
+        Ok(row.map(map_profile))
+    }
+
+    async fn find_by_id(&self, user_id: i64) -> Result<Option<UserProfile>, DomainError> {
+        let row = sqlx::query_as!(
+            ProfileRow,
+            r#"
+            SELECT id, username, display_name, bio, website, avatar_url, created_at
+            FROM users
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_profile_db_error)?;
+
+        Ok(row.map(map_profile))
+    }
+
+    async fn update_profile(
+        &self,
+        user_id: i64,
+        profile: &Profile,
+    ) -> Result<Option<UserProfile>, DomainError> {
+        let row = sqlx::query_as!(
+            ProfileRow,
+            r#"
+            UPDATE users
+            SET display_name = $2,
+                bio = $3,
+                website = $4,
+                avatar_url = $5
+            WHERE id = $1
+            RETURNING id, username, display_name, bio, website, avatar_url, created_at
+            "#,
+            user_id,
+            profile.display_name,
+            profile.bio,
+            profile.website,
+            profile.avatar_url,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_profile_db_error)?;
+
+        Ok(row.map(map_profile))
+    }
+}
+
+fn map_profile(row: ProfileRow) -> UserProfile {
+    UserProfile {
+        id: row.id,
+        username: row.username,
+        profile: Profile {
+            display_name: row.display_name,
+            bio: row.bio,
+            website: row.website,
+            avatar_url: row.avatar_url,
+        },
+        created_at: row.created_at,
+    }
+}
+
+fn map_profile_db_error(err: sqlx::Error) -> DomainError {
+    DomainError::Unexpected(err.to_string())
+}
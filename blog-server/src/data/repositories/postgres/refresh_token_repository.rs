@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::data::refresh_token_repository::{
+    NewRefreshToken, RefreshTokenRecord, RefreshTokenRepository,
+};
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresRefreshTokenRepository {
+    pool: PgPool,
+}
+
+impl PostgresRefreshTokenRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+struct RefreshTokenRow {
+    id: i64,
+    user_id: i64,
+    family_id: String,
+    expires_at: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+impl RefreshTokenRepository for PostgresRefreshTokenRepository {
+    async fn create_refresh_token(&self, input: NewRefreshToken) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            input.user_id,
+            input.family_id,
+            input.token_hash,
+            input.expires_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_refresh_token_db_error)?;
+
+        Ok(())
+    }
+
+    async fn find_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshTokenRecord>, DomainError> {
+        let row = sqlx::query_as!(
+            RefreshTokenRow,
+            r#"
+            SELECT id, user_id, family_id, expires_at, revoked_at
+            FROM refresh_tokens
+            WHERE token_hash = $1
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_refresh_token_db_error)?;
+
+        Ok(row.map(|r| RefreshTokenRecord {
+            id: r.id,
+            user_id: r.user_id,
+            family_id: r.family_id,
+            expires_at: r.expires_at,
+            revoked_at: r.revoked_at,
+        }))
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        current_id: i64,
+        next: NewRefreshToken,
+    ) -> Result<bool, DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(map_refresh_token_db_error)?;
+
+        // условие revoked_at IS NULL не даёт двум параллельным запросам
+        // ротировать один и тот же токен
+        let revoked = sqlx::query!(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE id = $1 AND revoked_at IS NULL
+            "#,
+            current_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_refresh_token_db_error)?;
+
+        if revoked.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            next.user_id,
+            next.family_id,
+            next.token_hash,
+            next.expires_at,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_refresh_token_db_error)?;
+
+        tx.commit().await.map_err(map_refresh_token_db_error)?;
+        Ok(true)
+    }
+
+    async fn revoke_family(&self, family_id: &str) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE family_id = $1 AND revoked_at IS NULL
+            "#,
+            family_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_refresh_token_db_error)?;
+
+        Ok(())
+    }
+
+    async fn revoke_all_for_user(&self, user_id: i64) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_refresh_token_db_error)?;
+
+        Ok(())
+    }
+}
+
+fn map_refresh_token_db_error(err: sqlx::Error) -> DomainError {
+    DomainError::Unexpected(err.to_string())
+}
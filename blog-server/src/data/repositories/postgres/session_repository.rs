@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::data::session_repository::{NewSession, SessionRepository};
+use crate::domain::error::DomainError;
+use crate::domain::session::Session;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresSessionRepository {
+    pool: PgPool,
+}
+
+impl PostgresSessionRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+struct SessionRow {
+    id: i64,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    created_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+}
+
+#[async_trait]
+impl SessionRepository for PostgresSessionRepository {
+    async fn create_session(&self, input: NewSession) -> Result<Session, DomainError> {
+        let row = sqlx::query_as!(
+            SessionRow,
+            r#"
+            INSERT INTO sessions (user_id, family_id, user_agent, ip_address)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, user_agent, ip_address, created_at, last_seen_at
+            "#,
+            input.user_id,
+            input.family_id,
+            input.user_agent,
+            input.ip_address,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_session_db_error)?;
+
+        Ok(map_session(row))
+    }
+
+    async fn touch_family(
+        &self,
+        family_id: &str,
+        user_agent: Option<&str>,
+        ip_address: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<Option<i64>, DomainError> {
+        let id = sqlx::query_scalar!(
+            r#"
+            UPDATE sessions
+            SET last_seen_at = $4,
+                user_agent = COALESCE($2, user_agent),
+                ip_address = COALESCE($3, ip_address)
+            WHERE family_id = $1
+            RETURNING id
+            "#,
+            family_id,
+            user_agent,
+            ip_address,
+            at,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_session_db_error)?;
+
+        Ok(id)
+    }
+
+    async fn touch_session(&self, session_id: i64, at: DateTime<Utc>) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE sessions s
+            SET last_seen_at = GREATEST(s.last_seen_at, $2)
+            WHERE s.id = $1
+              AND EXISTS (
+                  SELECT 1 FROM refresh_tokens r
+                  WHERE r.family_id = s.family_id
+                    AND r.revoked_at IS NULL
+                    AND r.expires_at > $2
+              )
+            "#,
+            session_id,
+            at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_session_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_active(&self, user_id: i64) -> Result<Vec<Session>, DomainError> {
+        let rows = sqlx::query_as!(
+            SessionRow,
+            r#"
+            SELECT s.id, s.user_agent, s.ip_address, s.created_at, s.last_seen_at
+            FROM sessions s
+            WHERE s.user_id = $1
+              AND EXISTS (
+                  SELECT 1 FROM refresh_tokens r
+                  WHERE r.family_id = s.family_id
+                    AND r.revoked_at IS NULL
+                    AND r.expires_at > NOW()
+              )
+            ORDER BY s.last_seen_at DESC, s.id DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_session_db_error)?;
+
+        Ok(rows.into_iter().map(map_session).collect())
+    }
+
+    async fn revoke_session(&self, user_id: i64, session_id: i64) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE revoked_at IS NULL
+              AND expires_at > NOW()
+              AND family_id = (
+                  SELECT family_id FROM sessions WHERE id = $1 AND user_id = $2
+              )
+            "#,
+            session_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_session_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn map_session(row: SessionRow) -> Session {
+    Session {
+        id: row.id,
+        user_agent: row.user_agent,
+        ip_address: row.ip_address,
+        created_at: row.created_at,
+        last_seen_at: row.last_seen_at,
+    }
+}
+
+fn map_session_db_error(err: sqlx::Error) -> DomainError {
+    DomainError::Unexpected(err.to_string())
+}
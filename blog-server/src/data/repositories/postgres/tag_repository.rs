@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sqlx::{PgConnection, PgPool};
+
+use crate::data::tag_repository::TagRepository;
+use crate::domain::error::DomainError;
+use crate::domain::tag::TagWithCount;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresTagRepository {
+    pool: PgPool,
+}
+
+impl PostgresTagRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TagRepository for PostgresTagRepository {
+    async fn tags_for_posts(
+        &self,
+        post_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<String>>, DomainError> {
+        if post_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT pt.post_id, t.name
+            FROM post_tags pt
+            JOIN tags t ON t.id = pt.tag_id
+            WHERE pt.post_id = ANY($1)
+            ORDER BY t.name
+            "#,
+            post_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_tag_db_error)?;
+
+        let mut tags: HashMap<i64, Vec<String>> = HashMap::new();
+        for row in rows {
+            tags.entry(row.post_id).or_default().push(row.name);
+        }
+        Ok(tags)
+    }
+
+    async fn list_tags_with_counts(&self) -> Result<Vec<TagWithCount>, DomainError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT t.name, COUNT(*) as "post_count!"
+            FROM tags t
+            JOIN post_tags pt ON pt.tag_id = t.id
+            JOIN posts p ON p.id = pt.post_id
+            WHERE p.status = 'published' AND p.deleted_at IS NULL
+            GROUP BY t.name
+            ORDER BY COUNT(*) DESC, t.name
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(map_tag_db_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TagWithCount {
+                name: row.name,
+                post_count: row.post_count,
+            })
+            .collect())
+    }
+}
+
+/// Заменяет теги поста внутри транзакции вызывающего; недостающие теги создаются.
+pub(crate) async fn replace_post_tags(
+    conn: &mut PgConnection,
+    post_id: i64,
+    tags: &[String],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM post_tags
+        WHERE post_id = $1
+        "#,
+        post_id
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if !tags.is_empty() {
+        sqlx::query!(
+            r#"
+            INSERT INTO tags (name)
+            SELECT UNNEST($1::VARCHAR[])
+            ON CONFLICT (name) DO NOTHING
+            "#,
+            tags
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO post_tags (post_id, tag_id)
+            SELECT $1, id
+            FROM tags
+            WHERE name = ANY($2::VARCHAR[])
+            "#,
+            post_id,
+            tags
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+fn map_tag_db_error(err: sqlx::Error) -> DomainError {
+    if let sqlx::Error::Database(db_err) = &err
+        && db_err.code().as_deref() == Some("23503")
+    {
+        return DomainError::NotFound("post".to_string());
+    }
+    DomainError::Unexpected(err.to_string())
+}
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::data::token_revocation_repository::TokenRevocationRepository;
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresTokenRevocationRepository {
+    pool: PgPool,
+}
+
+impl PostgresTokenRevocationRepository {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TokenRevocationRepository for PostgresTokenRevocationRepository {
+    async fn revoke_token(
+        &self,
+        jti: &str,
+        user_id: i64,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO revoked_tokens (jti, user_id, expires_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (jti) DO NOTHING
+            "#,
+            jti,
+            user_id,
+            expires_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_revocation_db_error)?;
+
+        Ok(())
+    }
+
+    async fn is_token_revoked(&self, jti: &str) -> Result<bool, DomainError> {
+        let revoked = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM revoked_tokens WHERE jti = $1) AS "revoked!"
+            "#,
+            jti
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_revocation_db_error)?;
+
+        Ok(revoked)
+    }
+
+    async fn revoke_all_for_user(&self, user_id: i64) -> Result<DateTime<Utc>, DomainError> {
+        let cutoff = sqlx::query_scalar!(
+            r#"
+            INSERT INTO user_token_cutoffs (user_id, revoked_before)
+            VALUES ($1, NOW())
+            ON CONFLICT (user_id) DO UPDATE SET revoked_before = EXCLUDED.revoked_before
+            RETURNING revoked_before
+            "#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(map_revocation_db_error)?;
+
+        Ok(cutoff)
+    }
+
+    async fn find_user_cutoff(&self, user_id: i64) -> Result<Option<DateTime<Utc>>, DomainError> {
+        let cutoff = sqlx::query_scalar!(
+            r#"
+            SELECT revoked_before
+            FROM user_token_cutoffs
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_revocation_db_error)?;
+
+        Ok(cutoff)
+    }
+
+    async fn purge_expired(&self) -> Result<u64, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM revoked_tokens
+            WHERE expires_at < NOW()
+            "#
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_revocation_db_error)?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+fn map_revocation_db_error(err: sqlx::Error) -> DomainError {
+    DomainError::Unexpected(err.to_string())
+}
@@ -4,7 +4,7 @@ use sqlx::PgPool;
 
 use crate::data::user_repository::{NewUser, UserCredentials, UserRepository};
 use crate::domain::error::DomainError;
-use crate::domain::user::User;
+use crate::domain::user::{Role, User};
 
 #[derive(Debug, Clone)]
 pub(crate) struct PostgresUserRepository {
@@ -21,6 +21,8 @@ struct UserRow {
     id: i64,
     username: String,
     email: String,
+    role: String,
+    email_verified_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
 }
 
@@ -28,6 +30,8 @@ struct UserCredentialsRow {
     id: i64,
     username: String,
     email: String,
+    role: String,
+    email_verified_at: Option<DateTime<Utc>>,
     password_hash: String,
     created_at: DateTime<Utc>,
 }
@@ -40,7 +44,7 @@ impl UserRepository for PostgresUserRepository {
             r#"
             INSERT INTO users (username, email, password_hash)
             VALUES ($1, $2, $3)
-            RETURNING id, username, email, created_at
+            RETURNING id, username, email, role, email_verified_at, created_at
             "#,
             input.username,
             input.email,
@@ -50,8 +54,24 @@ impl UserRepository for PostgresUserRepository {
         .await
         .map_err(map_user_db_error)?;
 
-        User::new(row.id, row.username, row.email, row.created_at)
-            .map_err(|err| DomainError::Unexpected(err.to_string()))
+        map_user(row)
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<User>, DomainError> {
+        let row = sqlx::query_as!(
+            UserRow,
+            r#"
+            SELECT id, username, email, role, email_verified_at, created_at
+            FROM users
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_user_db_error)?;
+
+        row.map(map_user).transpose()
     }
 
     async fn find_by_username(
@@ -65,6 +85,8 @@ impl UserRepository for PostgresUserRepository {
             id,
             username,
             email,
+            role,
+            email_verified_at,
             password_hash,
             created_at
             FROM users
@@ -75,17 +97,7 @@ impl UserRepository for PostgresUserRepository {
         .fetch_optional(&self.pool)
         .await
         .map_err(map_user_db_error)?;
-        if let Some(r) = row {
-            let user = User::new(r.id, r.username, r.email, r.created_at)
-                .map_err(|err| DomainError::Unexpected(err.to_string()))?;
-
-            Ok(Some(UserCredentials {
-                user,
-                password_hash: r.password_hash,
-            }))
-        } else {
-            Ok(None)
-        }
+        row.map(map_credentials).transpose()
     }
 
     async fn find_by_email(&self, email: &str) -> Result<Option<UserCredentials>, DomainError> {
@@ -96,6 +108,8 @@ impl UserRepository for PostgresUserRepository {
             id,
             username,
             email,
+            role,
+            email_verified_at,
             password_hash,
             created_at
             FROM users
@@ -106,20 +120,104 @@ impl UserRepository for PostgresUserRepository {
         .fetch_optional(&self.pool)
         .await
         .map_err(map_user_db_error)?;
-        if let Some(r) = row {
-            let user = User::new(r.id, r.username, r.email, r.created_at)
-                .map_err(|err| DomainError::Unexpected(err.to_string()))?;
-
-            Ok(Some(UserCredentials {
-                user,
-                password_hash: r.password_hash,
-            }))
-        } else {
-            Ok(None)
-        }
+        row.map(map_credentials).transpose()
+    }
+
+    async fn find_credentials_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<UserCredentials>, DomainError> {
+        let row = sqlx::query_as!(
+            UserCredentialsRow,
+            r#"
+            SELECT
+            id,
+            username,
+            email,
+            role,
+            email_verified_at,
+            password_hash,
+            created_at
+            FROM users
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_user_db_error)?;
+        row.map(map_credentials).transpose()
+    }
+
+    async fn update_password(
+        &self,
+        user_id: i64,
+        password_hash: &str,
+    ) -> Result<bool, DomainError> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET password_hash = $2
+            WHERE id = $1
+            "#,
+            user_id,
+            password_hash
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_user_db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn mark_email_verified(&self, user_id: i64) -> Result<Option<User>, DomainError> {
+        let row = sqlx::query_as!(
+            UserRow,
+            r#"
+            UPDATE users
+            SET email_verified_at = COALESCE(email_verified_at, NOW())
+            WHERE id = $1
+            RETURNING id, username, email, role, email_verified_at, created_at
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(map_user_db_error)?;
+
+        row.map(map_user).transpose()
     }
 }
 
+fn map_user(row: UserRow) -> Result<User, DomainError> {
+    let role = row
+        .role
+        .parse::<Role>()
+        .map_err(|err| DomainError::Unexpected(err.to_string()))?;
+    User::new(row.id, row.username, row.email, row.created_at)
+        .map(|user| {
+            user.with_role(role)
+                .with_email_verified_at(row.email_verified_at)
+        })
+        .map_err(|err| DomainError::Unexpected(err.to_string()))
+}
+
+fn map_credentials(row: UserCredentialsRow) -> Result<UserCredentials, DomainError> {
+    let user = map_user(UserRow {
+        id: row.id,
+        username: row.username,
+        email: row.email,
+        role: row.role,
+        email_verified_at: row.email_verified_at,
+        created_at: row.created_at,
+    })?;
+
+    Ok(UserCredentials {
+        user,
+        password_hash: row.password_hash,
+    })
+}
+
 fn map_user_db_error(err: sqlx::Error) -> DomainError {
     if let sqlx::Error::Database(db_err) = &err
         && db_err.code().as_deref() == Some("23505")
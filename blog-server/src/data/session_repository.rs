@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::error::DomainError;
+use crate::domain::session::Session;
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewSession {
+    pub(crate) user_id: i64,
+    /// Семья refresh-токенов этого входа.
+    pub(crate) family_id: String,
+    pub(crate) user_agent: Option<String>,
+    pub(crate) ip_address: Option<String>,
+}
+
+/// Сессия жива, пока в её семье есть неотозванный и неистёкший refresh-токен:
+/// отзыв сессии — это отзыв семьи.
+#[async_trait]
+pub(crate) trait SessionRepository: Send + Sync {
+    async fn create_session(&self, input: NewSession) -> Result<Session, DomainError>;
+
+    /// Отмечает обновление токенов: время и адрес клиента. Возвращает id сессии
+    /// семьи или `None` для входов, сделанных до появления сессий.
+    async fn touch_family(
+        &self,
+        family_id: &str,
+        user_agent: Option<&str>,
+        ip_address: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> Result<Option<i64>, DomainError>;
+
+    /// Отмечает активность живой сессии; `false`, если она отозвана или истекла.
+    async fn touch_session(&self, session_id: i64, at: DateTime<Utc>) -> Result<bool, DomainError>;
+
+    /// Живые сессии пользователя, последние активные первыми.
+    async fn list_active(&self, user_id: i64) -> Result<Vec<Session>, DomainError>;
+
+    /// Отзывает refresh-токены сессии; `false`, если у пользователя нет такой живой сессии.
+    async fn revoke_session(&self, user_id: i64, session_id: i64) -> Result<bool, DomainError>;
+}
@@ -0,0 +1,18 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::domain::error::DomainError;
+use crate::domain::tag::TagWithCount;
+
+#[async_trait]
+pub(crate) trait TagRepository: Send + Sync {
+    /// Теги для набора постов, отсортированные по имени. Посты без тегов в ответ не попадают.
+    async fn tags_for_posts(
+        &self,
+        post_ids: &[i64],
+    ) -> Result<HashMap<i64, Vec<String>>, DomainError>;
+
+    /// Теги, у которых есть хотя бы один опубликованный пост.
+    async fn list_tags_with_counts(&self) -> Result<Vec<TagWithCount>, DomainError>;
+}
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::error::DomainError;
+
+#[async_trait]
+pub(crate) trait TokenRevocationRepository: Send + Sync {
+    async fn revoke_token(
+        &self,
+        jti: &str,
+        user_id: i64,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), DomainError>;
+    async fn is_token_revoked(&self, jti: &str) -> Result<bool, DomainError>;
+
+    /// Делает недействительными все токены пользователя, выданные до текущего
+    /// момента. Возвращает сохранённую отсечку.
+    async fn revoke_all_for_user(&self, user_id: i64) -> Result<DateTime<Utc>, DomainError>;
+    async fn find_user_cutoff(&self, user_id: i64) -> Result<Option<DateTime<Utc>>, DomainError>;
+
+    /// Удаляет записи об уже истёкших токенах.
+    async fn purge_expired(&self) -> Result<u64, DomainError>;
+}
@@ -19,11 +19,20 @@ pub(crate) struct NewUser {
 #[async_trait]
 pub(crate) trait UserRepository: Send + Sync {
     async fn create_user(&self, input: NewUser) -> Result<User, DomainError>;
+    async fn find_by_id(&self, id: i64) -> Result<Option<User>, DomainError>;
     async fn find_by_username(
         &self,
         username: &str,
     ) -> Result<Option<UserCredentials>, DomainError>;
-
-    #[allow(dead_code)]
     async fn find_by_email(&self, email: &str) -> Result<Option<UserCredentials>, DomainError>;
+    async fn find_credentials_by_id(&self, id: i64)
+    -> Result<Option<UserCredentials>, DomainError>;
+
+    /// Заменяет хэш пароля; `false`, если пользователя нет.
+    async fn update_password(&self, user_id: i64, password_hash: &str)
+    -> Result<bool, DomainError>;
+
+    /// Отмечает email подтверждённым (повторный вызов не меняет дату);
+    /// возвращает обновлённого пользователя.
+    async fn mark_email_verified(&self, user_id: i64) -> Result<Option<User>, DomainError>;
 }
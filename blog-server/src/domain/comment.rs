@@ -0,0 +1,188 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::error::DomainError;
+
+pub(crate) const MAX_COMMENT_LEN: usize = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Comment {
+    pub(crate) id: i64,
+    pub(crate) post_id: i64,
+    pub(crate) author_id: i64,
+    /// `None` у комментария верхнего уровня.
+    pub(crate) parent_id: Option<i64>,
+    pub(crate) content: String,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) updated_at: DateTime<Utc>,
+}
+
+impl Comment {
+    /// Удалить комментарий может его автор или автор поста.
+    pub(crate) fn can_be_deleted_by(&self, actor_user_id: i64, post_author_id: i64) -> bool {
+        self.author_id == actor_user_id || post_author_id == actor_user_id
+    }
+}
+
+/// Комментарий вместе с ответами на него.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CommentNode {
+    pub(crate) comment: Comment,
+    pub(crate) replies: Vec<CommentNode>,
+}
+
+impl CommentNode {
+    /// Размер ветки: сам комментарий плюс все вложенные ответы.
+    pub(crate) fn thread_size(&self) -> usize {
+        1 + self.replies.iter().map(Self::thread_size).sum::<usize>()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CreateCommentRequest {
+    pub(crate) content: String,
+    pub(crate) parent_id: Option<i64>,
+}
+
+impl CreateCommentRequest {
+    pub(crate) fn validate(self) -> Result<Self, DomainError> {
+        let content = self.content.trim();
+        if content.is_empty() || content.chars().count() > MAX_COMMENT_LEN {
+            return Err(DomainError::Validation {
+                field: "content",
+                message: "must be 1..10000 chars",
+            });
+        }
+        if self.parent_id.is_some_and(|id| id <= 0) {
+            return Err(DomainError::Validation {
+                field: "parent_id",
+                message: "must be > 0",
+            });
+        }
+        Ok(Self {
+            content: content.to_string(),
+            parent_id: self.parent_id,
+        })
+    }
+}
+
+/// Собирает плоский список комментариев поста в дерево.
+/// Порядок внутри каждого уровня сохраняется из входного списка;
+/// ответы, чей родитель отсутствует в списке, поднимаются на верхний уровень.
+pub(crate) fn build_comment_tree(comments: Vec<Comment>) -> Vec<CommentNode> {
+    let known: HashSet<i64> = comments.iter().map(|comment| comment.id).collect();
+    let mut roots = Vec::new();
+    let mut children: HashMap<i64, Vec<Comment>> = HashMap::new();
+
+    for comment in comments {
+        match comment.parent_id {
+            Some(parent_id) if known.contains(&parent_id) => {
+                children.entry(parent_id).or_default().push(comment);
+            }
+            _ => roots.push(comment),
+        }
+    }
+
+    roots
+        .into_iter()
+        .map(|comment| attach_replies(comment, &mut children))
+        .collect()
+}
+
+fn attach_replies(comment: Comment, children: &mut HashMap<i64, Vec<Comment>>) -> CommentNode {
+    let replies = children
+        .remove(&comment.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|reply| attach_replies(reply, children))
+        .collect();
+    CommentNode { comment, replies }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::{Comment, CreateCommentRequest, DomainError, build_comment_tree};
+
+    #[test]
+    fn create_comment_request_validate_trims_content() {
+        let req = CreateCommentRequest {
+            content: "  nice post  ".to_string(),
+            parent_id: Some(3),
+        };
+
+        let validated = req.validate().expect("must validate");
+        assert_eq!(validated.content, "nice post");
+        assert_eq!(validated.parent_id, Some(3));
+    }
+
+    #[test]
+    fn create_comment_request_validate_rejects_blank_content() {
+        let req = CreateCommentRequest {
+            content: "   ".to_string(),
+            parent_id: None,
+        };
+
+        let err = req.validate().expect_err("blank content must be rejected");
+        assert!(matches!(
+            err,
+            DomainError::Validation {
+                field: "content",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn build_comment_tree_nests_replies_in_order() {
+        let comments = vec![
+            sample_comment(1, None),
+            sample_comment(2, Some(1)),
+            sample_comment(3, None),
+            sample_comment(4, Some(2)),
+            sample_comment(5, Some(1)),
+        ];
+
+        let tree = build_comment_tree(comments);
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].comment.id, 1);
+        assert_eq!(tree[1].comment.id, 3);
+        let replies: Vec<i64> = tree[0].replies.iter().map(|node| node.comment.id).collect();
+        assert_eq!(replies, vec![2, 5]);
+        assert_eq!(tree[0].replies[0].replies[0].comment.id, 4);
+        assert_eq!(tree[0].thread_size(), 4);
+    }
+
+    #[test]
+    fn build_comment_tree_lifts_orphaned_replies_to_top_level() {
+        let tree = build_comment_tree(vec![sample_comment(7, Some(99))]);
+
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].comment.id, 7);
+    }
+
+    #[test]
+    fn comment_can_be_deleted_by_its_author_or_post_author() {
+        let comment = sample_comment(1, None);
+
+        assert!(comment.can_be_deleted_by(20, 10));
+        assert!(comment.can_be_deleted_by(10, 10));
+        assert!(!comment.can_be_deleted_by(30, 10));
+    }
+
+    fn sample_comment(id: i64, parent_id: Option<i64>) -> Comment {
+        Comment {
+            id,
+            post_id: 1,
+            author_id: 20,
+            parent_id,
+            content: format!("comment {id}"),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+
+use super::error::DomainError;
+use super::post::Post;
+
+const CURSOR_VERSION: &str = "v1";
+
+/// Позиция в ленте постов: `(created_at, id)` последнего поста на странице.
+/// Лента отсортирована по убыванию этой пары, поэтому следующая страница —
+/// строго меньшие значения. Клиенту отдаётся как непрозрачная base64-строка.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PostCursor {
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) id: i64,
+}
+
+impl PostCursor {
+    pub(crate) fn after(post: &Post) -> Self {
+        Self {
+            created_at: post.created_at,
+            id: post.id,
+        }
+    }
+
+    /// Postgres хранит время с точностью до микросекунд, её и сохраняем в курсоре.
+    pub(crate) fn encode(&self) -> String {
+        let raw = format!(
+            "{CURSOR_VERSION}:{}:{}",
+            self.created_at.timestamp_micros(),
+            self.id
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub(crate) fn decode(cursor: &str) -> Result<Self, DomainError> {
+        Self::try_decode(cursor.trim()).ok_or(DomainError::Validation {
+            field: "cursor",
+            message: "invalid cursor",
+        })
+    }
+
+    fn try_decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        let raw = String::from_utf8(bytes).ok()?;
+        let mut parts = raw.split(':');
+        if parts.next()? != CURSOR_VERSION {
+            return None;
+        }
+        let micros = parts.next()?.parse::<i64>().ok()?;
+        let id = parts.next()?.parse::<i64>().ok()?;
+        if parts.next().is_some() || id <= 0 {
+            return None;
+        }
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros)?,
+            id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{DomainError, PostCursor};
+
+    #[test]
+    fn post_cursor_roundtrips_with_microsecond_precision() {
+        let cursor = PostCursor {
+            created_at: Utc
+                .timestamp_opt(1_700_000_000, 123_456_000)
+                .single()
+                .expect("valid ts"),
+            id: 42,
+        };
+
+        let decoded = PostCursor::decode(&cursor.encode()).expect("cursor must decode");
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn post_cursor_rejects_garbage() {
+        for cursor in ["", "not base64!", "djE6YWJjOjE", "djI6MTow"] {
+            let err = PostCursor::decode(cursor).expect_err("cursor must be rejected");
+            assert!(matches!(
+                err,
+                DomainError::Validation {
+                    field: "cursor",
+                    ..
+                }
+            ));
+        }
+    }
+}
@@ -17,9 +17,21 @@ pub(crate) enum DomainError {
     #[error("forbidden")]
     Forbidden,
 
+    /// Действие доступно только после подтверждения email.
+    #[error("email is not verified")]
+    EmailNotVerified,
+
+    /// Клиент правил устаревшую версию ресурса.
+    #[error("version mismatch: expected {expected}, current {current}")]
+    VersionMismatch { expected: i64, current: i64 },
+
     #[error("invalid credentials")]
     InvalidCredentials,
 
+    /// Слишком много неудачных попыток входа; повторить можно через `retry_after_secs`.
+    #[error("too many failed login attempts, retry after {retry_after_secs} s")]
+    TooManyAttempts { retry_after_secs: u64 },
+
     #[error("unexpected domain error: {0}")]
     Unexpected(String),
 }
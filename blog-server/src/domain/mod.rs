@@ -1,3 +1,12 @@
+pub(crate) mod comment;
+pub(crate) mod cursor;
 pub(crate) mod error;
+pub(crate) mod personal_token;
 pub(crate) mod post;
+pub(crate) mod profile;
+pub(crate) mod revision;
+pub(crate) mod search;
+pub(crate) mod session;
+pub(crate) mod slug;
+pub(crate) mod tag;
 pub(crate) mod user;
@@ -0,0 +1,213 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+
+use super::error::DomainError;
+
+/// Префикс токенов доступа: по нему сервер отличает их от JWT,
+/// а сканеры секретов находят утёкшие токены в логах и репозиториях.
+pub(crate) const PERSONAL_TOKEN_PREFIX: &str = "blog_pat_";
+pub(crate) const MAX_TOKEN_NAME_LEN: usize = 64;
+pub(crate) const MAX_TOKEN_LIFETIME_DAYS: u32 = 365;
+
+/// Право токена доступа. Управлять аккаунтом и другими токенами токен не может никогда.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenScope {
+    /// Черновики и история правок своих постов.
+    PostsRead,
+    /// Создание, правка, смена статуса и удаление постов.
+    PostsWrite,
+    /// Создание и удаление комментариев.
+    CommentsWrite,
+}
+
+impl TokenScope {
+    pub(crate) const ALL: [TokenScope; 3] = [
+        TokenScope::PostsRead,
+        TokenScope::PostsWrite,
+        TokenScope::CommentsWrite,
+    ];
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            TokenScope::PostsRead => "posts:read",
+            TokenScope::PostsWrite => "posts:write",
+            TokenScope::CommentsWrite => "comments:write",
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl FromStr for TokenScope {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        TokenScope::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == value.trim())
+            .ok_or(DomainError::Validation {
+                field: "scopes",
+                message: "must be one of posts:read, posts:write, comments:write",
+            })
+    }
+}
+
+/// Набор прав токена. Копируемый, чтобы помещаться в `Actor`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TokenScopes(u8);
+
+impl TokenScopes {
+    pub(crate) fn contains(self, scope: TokenScope) -> bool {
+        self.0 & scope.bit() != 0
+    }
+
+    pub(crate) fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub(crate) fn iter(self) -> impl Iterator<Item = TokenScope> {
+        TokenScope::ALL
+            .into_iter()
+            .filter(move |scope| self.contains(*scope))
+    }
+
+    /// Разбирает имена прав; неизвестное имя — ошибка валидации.
+    pub(crate) fn parse<S: AsRef<str>>(names: &[S]) -> Result<Self, DomainError> {
+        names
+            .iter()
+            .map(|name| name.as_ref().parse::<TokenScope>())
+            .collect()
+    }
+}
+
+impl FromIterator<TokenScope> for TokenScopes {
+    fn from_iter<I: IntoIterator<Item = TokenScope>>(iter: I) -> Self {
+        Self(iter.into_iter().fold(0, |bits, scope| bits | scope.bit()))
+    }
+}
+
+/// Персональный токен доступа без секрета: секрет показывается один раз при создании.
+#[derive(Debug, Clone)]
+pub(crate) struct PersonalToken {
+    pub(crate) id: i64,
+    pub(crate) user_id: i64,
+    pub(crate) name: String,
+    pub(crate) scopes: TokenScopes,
+    /// `None` — бессрочный токен.
+    pub(crate) expires_at: Option<DateTime<Utc>>,
+    pub(crate) last_used_at: Option<DateTime<Utc>>,
+    pub(crate) created_at: DateTime<Utc>,
+}
+
+impl PersonalToken {
+    pub(crate) fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CreatePersonalTokenRequest {
+    pub(crate) name: String,
+    pub(crate) scopes: TokenScopes,
+    /// Срок жизни в днях; `None` — бессрочный токен.
+    pub(crate) expires_in_days: Option<u32>,
+}
+
+impl CreatePersonalTokenRequest {
+    pub(crate) fn validate(self) -> Result<Self, DomainError> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err(DomainError::Validation {
+                field: "name",
+                message: "must not be empty",
+            });
+        }
+        if name.chars().count() > MAX_TOKEN_NAME_LEN {
+            return Err(DomainError::Validation {
+                field: "name",
+                message: "must be at most 64 characters",
+            });
+        }
+        if self.scopes.is_empty() {
+            return Err(DomainError::Validation {
+                field: "scopes",
+                message: "must not be empty",
+            });
+        }
+        if self
+            .expires_in_days
+            .is_some_and(|days| days == 0 || days > MAX_TOKEN_LIFETIME_DAYS)
+        {
+            return Err(DomainError::Validation {
+                field: "expires_in_days",
+                message: "must be between 1 and 365",
+            });
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            scopes: self.scopes,
+            expires_in_days: self.expires_in_days,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CreatePersonalTokenRequest, TokenScope, TokenScopes};
+    use crate::domain::error::DomainError;
+
+    #[test]
+    fn scopes_round_trip_through_names() {
+        let scopes = TokenScopes::parse(&["posts:write", " comments:write", "posts:write"])
+            .expect("known scopes");
+        assert!(scopes.contains(TokenScope::PostsWrite));
+        assert!(scopes.contains(TokenScope::CommentsWrite));
+        assert!(!scopes.contains(TokenScope::PostsRead));
+        assert_eq!(
+            scopes.iter().map(TokenScope::as_str).collect::<Vec<_>>(),
+            vec!["posts:write", "comments:write"]
+        );
+
+        assert!(matches!(
+            TokenScopes::parse(&["admin"]),
+            Err(DomainError::Validation {
+                field: "scopes",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn create_request_requires_name_scopes_and_sane_expiry() {
+        let request = |name: &str, scopes: &[&str], expires_in_days| CreatePersonalTokenRequest {
+            name: name.to_string(),
+            scopes: TokenScopes::parse(scopes).expect("known scopes"),
+            expires_in_days,
+        };
+
+        let valid = request("  ci  ", &["posts:write"], Some(90))
+            .validate()
+            .expect("valid request");
+        assert_eq!(valid.name, "ci");
+
+        for (invalid, field) in [
+            (request(" ", &["posts:write"], None), "name"),
+            (request(&"x".repeat(65), &["posts:write"], None), "name"),
+            (request("ci", &[], None), "scopes"),
+            (request("ci", &["posts:write"], Some(0)), "expires_in_days"),
+            (
+                request("ci", &["posts:write"], Some(366)),
+                "expires_in_days",
+            ),
+        ] {
+            assert!(matches!(
+                invalid.validate(),
+                Err(DomainError::Validation { field: actual, .. }) if actual == field
+            ));
+        }
+    }
+}
@@ -1,29 +1,145 @@
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::error::DomainError;
+use super::tag::normalize_tags;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PostStatus {
+    Draft,
+    Published,
+    Archived,
+}
+
+impl PostStatus {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Published => "published",
+            PostStatus::Archived => "archived",
+        }
+    }
+}
+
+impl FromStr for PostStatus {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "draft" => Ok(PostStatus::Draft),
+            "published" => Ok(PostStatus::Published),
+            "archived" => Ok(PostStatus::Archived),
+            _ => Err(DomainError::Validation {
+                field: "status",
+                message: "must be one of draft, published, archived",
+            }),
+        }
+    }
+}
+
+/// Формат исходного текста поста: от него зависит, как сервер отрисует HTML.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ContentFormat {
+    #[default]
+    Plain,
+    Markdown,
+}
+
+impl ContentFormat {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ContentFormat::Plain => "plain",
+            ContentFormat::Markdown => "markdown",
+        }
+    }
+}
+
+impl FromStr for ContentFormat {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "plain" => Ok(ContentFormat::Plain),
+            "markdown" => Ok(ContentFormat::Markdown),
+            _ => Err(DomainError::Validation {
+                field: "content_format",
+                message: "must be one of plain, markdown",
+            }),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct Post {
     pub(crate) id: i64,
+    /// Уникальный человекочитаемый адрес поста, строится из заголовка.
+    pub(crate) slug: String,
     pub(crate) title: String,
     pub(crate) content: String,
+    pub(crate) content_format: ContentFormat,
+    /// Безопасный HTML, отрисованный из `content` при сохранении.
+    pub(crate) content_html: String,
     pub(crate) author_id: i64,
+    pub(crate) status: PostStatus,
+    pub(crate) published_at: Option<DateTime<Utc>>,
+    /// Когда черновик будет опубликован автоматически; `None` — публикация не запланирована.
+    pub(crate) publish_at: Option<DateTime<Utc>>,
+    /// Когда пост перемещён в корзину; `None` — пост не удалён.
+    pub(crate) deleted_at: Option<DateTime<Utc>>,
+    pub(crate) tags: Vec<String>,
     pub(crate) created_at: DateTime<Utc>,
     pub(crate) updated_at: DateTime<Utc>,
+    /// Растёт при каждом изменении поста; отдаётся клиентам как ETag.
+    pub(crate) version: i64,
+    /// Заполняется репозиторием, чтобы клиентам не искать автора отдельным запросом.
+    pub(crate) author: Option<PostAuthor>,
+}
+
+/// Краткие сведения об авторе поста.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PostAuthor {
+    pub(crate) id: i64,
+    pub(crate) username: String,
+    pub(crate) display_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct CreatePostRequest {
     pub(crate) title: String,
     pub(crate) content: String,
+    pub(crate) content_format: ContentFormat,
+    pub(crate) status: PostStatus,
+    pub(crate) tags: Vec<String>,
+    /// Время автоматической публикации; допустимо только для черновика.
+    pub(crate) publish_at: Option<DateTime<Utc>>,
 }
 
 impl CreatePostRequest {
     pub(crate) fn validate(self) -> Result<Self, DomainError> {
+        // архивировать можно только уже существующий пост
+        if self.status == PostStatus::Archived {
+            return Err(DomainError::Validation {
+                field: "status",
+                message: "must be draft or published",
+            });
+        }
+        if self.publish_at.is_some() && self.status != PostStatus::Draft {
+            return Err(DomainError::Validation {
+                field: "publish_at",
+                message: "can be set only for draft post",
+            });
+        }
         Ok(Self {
             title: normalize_title(&self.title)?,
             content: normalize_content(&self.content)?,
+            content_format: self.content_format,
+            status: self.status,
+            tags: normalize_tags(&self.tags)?,
+            publish_at: self.publish_at,
         })
     }
 }
@@ -32,6 +148,12 @@ impl CreatePostRequest {
 pub(crate) struct UpdatePostRequest {
     pub(crate) title: String,
     pub(crate) content: String,
+    /// `None` оставляет формат поста без изменений.
+    pub(crate) content_format: Option<ContentFormat>,
+    /// `None` оставляет теги без изменений, пустой список снимает все теги.
+    pub(crate) tags: Option<Vec<String>>,
+    /// Версия, которую видел клиент; `None` — обновить без проверки.
+    pub(crate) expected_version: Option<i64>,
 }
 
 impl UpdatePostRequest {
@@ -39,6 +161,9 @@ impl UpdatePostRequest {
         Ok(Self {
             title: normalize_title(&self.title)?,
             content: normalize_content(&self.content)?,
+            content_format: self.content_format,
+            tags: self.tags.as_deref().map(normalize_tags).transpose()?,
+            expected_version: self.expected_version,
         })
     }
 }
@@ -66,13 +191,104 @@ impl Post {
 
         Ok(Self {
             id,
+            slug: String::new(),
             title,
             content,
+            content_format: ContentFormat::Plain,
+            content_html: String::new(),
             author_id,
+            status: PostStatus::Published,
+            published_at: Some(created_at),
+            publish_at: None,
+            deleted_at: None,
+            tags: Vec::new(),
             created_at,
             updated_at,
+            version: 1,
+            author: None,
         })
     }
+
+    pub(crate) fn with_publication(
+        mut self,
+        status: PostStatus,
+        published_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, DomainError> {
+        if status == PostStatus::Published && published_at.is_none() {
+            return Err(DomainError::Validation {
+                field: "published_at",
+                message: "must be set for published post",
+            });
+        }
+        self.status = status;
+        self.published_at = published_at;
+        Ok(self)
+    }
+
+    /// Запланированная публикация бывает только у черновика.
+    pub(crate) fn with_schedule(
+        mut self,
+        publish_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, DomainError> {
+        if publish_at.is_some() && self.status != PostStatus::Draft {
+            return Err(DomainError::Validation {
+                field: "publish_at",
+                message: "can be set only for draft post",
+            });
+        }
+        self.publish_at = publish_at;
+        Ok(self)
+    }
+
+    pub(crate) fn with_version(mut self, version: i64) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub(crate) fn with_deleted_at(mut self, deleted_at: Option<DateTime<Utc>>) -> Self {
+        self.deleted_at = deleted_at;
+        self
+    }
+
+    pub(crate) fn with_slug(mut self, slug: String) -> Self {
+        self.slug = slug;
+        self
+    }
+
+    pub(crate) fn with_content_html(mut self, format: ContentFormat, html: String) -> Self {
+        self.content_format = format;
+        self.content_html = html;
+        self
+    }
+
+    pub(crate) fn with_author(mut self, author: PostAuthor) -> Self {
+        self.author = Some(author);
+        self
+    }
+
+    pub(crate) fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Черновики и архив видны только автору, опубликованные посты — всем.
+    pub(crate) fn is_visible_to(&self, viewer_id: Option<i64>) -> bool {
+        self.status == PostStatus::Published || viewer_id == Some(self.author_id)
+    }
+}
+
+/// Публикацию можно запланировать только на будущее.
+pub(crate) fn validate_publish_at(
+    publish_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), DomainError> {
+    if publish_at <= now {
+        return Err(DomainError::Validation {
+            field: "publish_at",
+            message: "must be in the future",
+        });
+    }
+    Ok(())
 }
 
 fn validate_positive_i64(field: &'static str, value: i64) -> Result<(), DomainError> {
@@ -111,13 +327,19 @@ fn normalize_content(content: &str) -> Result<String, DomainError> {
 mod tests {
     use chrono::{Duration, Utc};
 
-    use super::{CreatePostRequest, DomainError, Post, UpdatePostRequest};
+    use super::{
+        ContentFormat, CreatePostRequest, DomainError, Post, PostStatus, UpdatePostRequest,
+    };
 
     #[test]
     fn create_post_request_validate_rejects_empty_title() {
         let req = CreatePostRequest {
             title: "   ".to_string(),
             content: "valid content".to_string(),
+            content_format: ContentFormat::Plain,
+            status: PostStatus::Published,
+            tags: Vec::new(),
+            publish_at: None,
         };
 
         let err = req.validate().expect_err("title must be rejected");
@@ -129,6 +351,9 @@ mod tests {
         let req = UpdatePostRequest {
             title: "valid title".to_string(),
             content: "   ".to_string(),
+            content_format: None,
+            tags: None,
+            expected_version: None,
         };
 
         let err = req.validate().expect_err("content must be rejected");
@@ -140,11 +365,90 @@ mod tests {
         let req = CreatePostRequest {
             title: "  title  ".to_string(),
             content: "  content  ".to_string(),
+            content_format: ContentFormat::Plain,
+            status: PostStatus::Draft,
+            tags: vec![" Rust ".to_string(), "rust".to_string()],
+            publish_at: None,
         };
 
         let validated = req.validate().expect("must validate");
         assert_eq!(validated.title, "title");
         assert_eq!(validated.content, "content");
+        assert_eq!(validated.status, PostStatus::Draft);
+        assert_eq!(validated.tags, vec!["rust".to_string()]);
+    }
+
+    #[test]
+    fn create_post_request_validate_rejects_archived_status() {
+        let req = CreatePostRequest {
+            title: "title".to_string(),
+            content: "content".to_string(),
+            content_format: ContentFormat::Plain,
+            status: PostStatus::Archived,
+            tags: Vec::new(),
+            publish_at: None,
+        };
+
+        let err = req.validate().expect_err("archived must be rejected");
+        assert_validation_field(err, "status");
+    }
+
+    #[test]
+    fn update_post_request_validate_keeps_missing_tags_untouched() {
+        let req = UpdatePostRequest {
+            title: "title".to_string(),
+            content: "content".to_string(),
+            content_format: None,
+            tags: None,
+            expected_version: None,
+        };
+        assert!(req.validate().expect("must validate").tags.is_none());
+
+        let req = UpdatePostRequest {
+            title: "title".to_string(),
+            content: "content".to_string(),
+            content_format: None,
+            tags: Some(vec!["bad tag".to_string()]),
+            expected_version: None,
+        };
+        let err = req.validate().expect_err("tag must be rejected");
+        assert_validation_field(err, "tags");
+    }
+
+    #[test]
+    fn post_status_round_trips_through_str() {
+        for status in [
+            PostStatus::Draft,
+            PostStatus::Published,
+            PostStatus::Archived,
+        ] {
+            let parsed: PostStatus = status.as_str().parse().expect("must parse");
+            assert_eq!(parsed, status);
+        }
+        assert!("deleted".parse::<PostStatus>().is_err());
+    }
+
+    #[test]
+    fn with_publication_requires_published_at_for_published_post() {
+        let now = Utc::now();
+        let post = Post::new(1, "Title", "Content", 10, now, now).expect("post must be valid");
+
+        let err = post
+            .with_publication(PostStatus::Published, None)
+            .expect_err("published_at is required");
+        assert_validation_field(err, "published_at");
+    }
+
+    #[test]
+    fn draft_is_visible_only_to_author() {
+        let now = Utc::now();
+        let post = Post::new(1, "Title", "Content", 10, now, now)
+            .and_then(|post| post.with_publication(PostStatus::Draft, None))
+            .expect("post must be valid");
+
+        assert!(post.is_visible_to(Some(10)));
+        assert!(!post.is_visible_to(Some(11)));
+        assert!(!post.is_visible_to(None));
     }
 
     #[test]
@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use validator::ValidateUrl;
+
+use super::error::DomainError;
+
+pub(crate) const MAX_DISPLAY_NAME_LEN: usize = 64;
+pub(crate) const MAX_BIO_LEN: usize = 500;
+pub(crate) const MAX_URL_LEN: usize = 2048;
+
+/// Необязательные поля профиля; `None` — не заполнено.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Profile {
+    pub(crate) display_name: Option<String>,
+    pub(crate) bio: Option<String>,
+    pub(crate) website: Option<String>,
+    pub(crate) avatar_url: Option<String>,
+}
+
+impl Profile {
+    /// Применяет частичное обновление; запрос ожидается уже провалидированным.
+    pub(crate) fn apply(self, update: UpdateProfileRequest) -> Self {
+        let merge = |current: Option<String>, new: Option<String>| match new {
+            Some(value) if value.is_empty() => None,
+            Some(value) => Some(value),
+            None => current,
+        };
+        Self {
+            display_name: merge(self.display_name, update.display_name),
+            bio: merge(self.bio, update.bio),
+            website: merge(self.website, update.website),
+            avatar_url: merge(self.avatar_url, update.avatar_url),
+        }
+    }
+}
+
+/// Публичный профиль пользователя: без email и роли.
+#[derive(Debug, Clone)]
+pub(crate) struct UserProfile {
+    pub(crate) id: i64,
+    pub(crate) username: String,
+    pub(crate) profile: Profile,
+    pub(crate) created_at: DateTime<Utc>,
+}
+
+/// Частичное обновление профиля: `None` оставляет поле как есть,
+/// пустая строка очищает его.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UpdateProfileRequest {
+    pub(crate) display_name: Option<String>,
+    pub(crate) bio: Option<String>,
+    pub(crate) website: Option<String>,
+    pub(crate) avatar_url: Option<String>,
+}
+
+impl UpdateProfileRequest {
+    pub(crate) fn validate(self) -> Result<Self, DomainError> {
+        Ok(Self {
+            display_name: self
+                .display_name
+                .map(|value| {
+                    normalize_text(
+                        "display_name",
+                        &value,
+                        MAX_DISPLAY_NAME_LEN,
+                        "must be at most 64 characters",
+                    )
+                })
+                .transpose()?,
+            bio: self
+                .bio
+                .map(|value| {
+                    normalize_text("bio", &value, MAX_BIO_LEN, "must be at most 500 characters")
+                })
+                .transpose()?,
+            website: self
+                .website
+                .map(|value| normalize_url("website", &value))
+                .transpose()?,
+            avatar_url: self
+                .avatar_url
+                .map(|value| normalize_url("avatar_url", &value))
+                .transpose()?,
+        })
+    }
+}
+
+fn normalize_text(
+    field: &'static str,
+    value: &str,
+    max_len: usize,
+    too_long: &'static str,
+) -> Result<String, DomainError> {
+    let value = value.trim();
+    if value.chars().count() > max_len {
+        return Err(DomainError::Validation {
+            field,
+            message: too_long,
+        });
+    }
+    if value.chars().any(|ch| ch.is_control() && ch != '\n') {
+        return Err(DomainError::Validation {
+            field,
+            message: "must not contain control characters",
+        });
+    }
+    Ok(value.to_string())
+}
+
+/// Ссылки из профиля выводятся как есть, поэтому принимаются только http(s).
+fn normalize_url(field: &'static str, value: &str) -> Result<String, DomainError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(String::new());
+    }
+    let scheme_ok = ["http://", "https://"].iter().any(|scheme| {
+        value
+            .get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    });
+    if value.len() > MAX_URL_LEN || !scheme_ok || !value.validate_url() {
+        return Err(DomainError::Validation {
+            field,
+            message: "must be an http(s) URL of at most 2048 characters",
+        });
+    }
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Profile, UpdateProfileRequest};
+    use crate::domain::error::DomainError;
+
+    #[test]
+    fn update_is_trimmed_and_applied_partially() {
+        let current = Profile {
+            display_name: Some("Alice".to_string()),
+            bio: Some("Rustacean".to_string()),
+            website: Some("https://alice.dev".to_string()),
+            avatar_url: None,
+        };
+        let update = UpdateProfileRequest {
+            display_name: Some("  Alice L.  ".to_string()),
+            bio: Some(" ".to_string()),
+            website: None,
+            avatar_url: Some("https://cdn.example.com/a.png".to_string()),
+        }
+        .validate()
+        .expect("valid update");
+
+        let profile = current.apply(update);
+        assert_eq!(profile.display_name.as_deref(), Some("Alice L."));
+        assert_eq!(profile.bio, None);
+        assert_eq!(profile.website.as_deref(), Some("https://alice.dev"));
+        assert_eq!(
+            profile.avatar_url.as_deref(),
+            Some("https://cdn.example.com/a.png")
+        );
+    }
+
+    #[test]
+    fn invalid_fields_are_rejected() {
+        for (update, field) in [
+            (
+                UpdateProfileRequest {
+                    display_name: Some("x".repeat(65)),
+                    ..Default::default()
+                },
+                "display_name",
+            ),
+            (
+                UpdateProfileRequest {
+                    bio: Some("x".repeat(501)),
+                    ..Default::default()
+                },
+                "bio",
+            ),
+            (
+                UpdateProfileRequest {
+                    website: Some("javascript:alert(1)".to_string()),
+                    ..Default::default()
+                },
+                "website",
+            ),
+            (
+                UpdateProfileRequest {
+                    avatar_url: Some("not a url".to_string()),
+                    ..Default::default()
+                },
+                "avatar_url",
+            ),
+        ] {
+            assert!(matches!(
+                update.validate(),
+                Err(DomainError::Validation { field: actual, .. }) if actual == field
+            ));
+        }
+    }
+}
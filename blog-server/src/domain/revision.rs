@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+
+/// Версия поста, заменённая обновлением. Номера идут подряд с 1 в пределах поста;
+/// текущая версия хранится в самом посте и ревизией не является.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PostRevision {
+    pub(crate) post_id: i64,
+    pub(crate) revision: i32,
+    pub(crate) title: String,
+    pub(crate) content: String,
+    /// Кто сделал правку, заменившую эту версию.
+    pub(crate) editor_id: i64,
+    pub(crate) created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct DiffLine {
+    pub(crate) op: DiffOp,
+    /// Строка без завершающего перевода строки.
+    pub(crate) text: String,
+}
+
+/// Построчное сравнение двух версий поста.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RevisionDiff {
+    pub(crate) from_revision: i32,
+    /// `None` — сравнение с текущей версией поста.
+    pub(crate) to_revision: Option<i32>,
+    pub(crate) from_title: String,
+    pub(crate) to_title: String,
+    pub(crate) lines: Vec<DiffLine>,
+}
+
+pub(crate) fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| DiffLine {
+            op: match change.tag() {
+                ChangeTag::Equal => DiffOp::Equal,
+                ChangeTag::Insert => DiffOp::Insert,
+                ChangeTag::Delete => DiffOp::Delete,
+            },
+            text: change.value().trim_end_matches(['\r', '\n']).to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffLine, DiffOp, diff_lines};
+
+    fn line(op: DiffOp, text: &str) -> DiffLine {
+        DiffLine {
+            op,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_lines_marks_changed_lines() {
+        let diff = diff_lines("one\ntwo\nthree\n", "one\n2\nthree\nfour\n");
+
+        assert_eq!(
+            diff,
+            vec![
+                line(DiffOp::Equal, "one"),
+                line(DiffOp::Delete, "two"),
+                line(DiffOp::Insert, "2"),
+                line(DiffOp::Equal, "three"),
+                line(DiffOp::Insert, "four"),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_lines_of_equal_texts_has_only_equal_lines() {
+        let diff = diff_lines("a\nb\n", "a\nb\n");
+
+        assert!(diff.iter().all(|line| line.op == DiffOp::Equal));
+        assert_eq!(diff.len(), 2);
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use super::error::DomainError;
+use super::post::Post;
+
+pub(crate) const MAX_SEARCH_QUERY_LEN: usize = 200;
+
+/// Маркеры начала и конца совпадения, которыми репозиторий размечает фрагмент.
+/// Управляющие символы не встречаются в обычном тексте, поэтому после
+/// экранирования HTML их можно безопасно заменить на `<mark>`.
+pub(crate) const HIGHLIGHT_START: char = '\u{2}';
+pub(crate) const HIGHLIGHT_STOP: char = '\u{3}';
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PostSearchHit {
+    pub(crate) post: Post,
+    pub(crate) rank: f32,
+    /// Фрагмент содержимого: HTML-экранированный текст с совпадениями в `<mark>`.
+    pub(crate) snippet: String,
+}
+
+pub(crate) fn normalize_search_query(query: &str) -> Result<String, DomainError> {
+    let query = query.trim();
+    if query.is_empty() || query.chars().count() > MAX_SEARCH_QUERY_LEN {
+        return Err(DomainError::Validation {
+            field: "q",
+            message: "must be 1..200 chars",
+        });
+    }
+    Ok(query.to_string())
+}
+
+/// Экранирует HTML во фрагменте и заменяет маркеры совпадений на `<mark>`.
+pub(crate) fn render_highlight(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len() + 16);
+    for ch in raw.chars() {
+        match ch {
+            HIGHLIGHT_START => out.push_str("<mark>"),
+            HIGHLIGHT_STOP => out.push_str("</mark>"),
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DomainError, normalize_search_query, render_highlight};
+
+    #[test]
+    fn normalize_search_query_trims_and_rejects_blank() {
+        assert_eq!(
+            normalize_search_query("  rust axum ").expect("must be valid"),
+            "rust axum"
+        );
+
+        let err = normalize_search_query("   ").expect_err("blank query must be rejected");
+        assert!(matches!(err, DomainError::Validation { field: "q", .. }));
+    }
+
+    #[test]
+    fn render_highlight_escapes_html_and_marks_matches() {
+        let raw = "a <script> & \u{2}rust\u{3} \"x\"";
+
+        assert_eq!(
+            render_highlight(raw),
+            "a &lt;script&gt; &amp; <mark>rust</mark> &quot;x&quot;"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use std::net::IpAddr;
+
+use chrono::{DateTime, Utc};
+
+pub(crate) const MAX_USER_AGENT_LEN: usize = 256;
+
+/// Откуда пришёл запрос входа или обновления токенов.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClientInfo {
+    pub(crate) ip: Option<IpAddr>,
+    pub(crate) user_agent: Option<String>,
+}
+
+impl ClientInfo {
+    /// Пустой `User-Agent` отбрасывается, слишком длинный обрезается.
+    pub(crate) fn new(ip: Option<IpAddr>, user_agent: Option<&str>) -> Self {
+        let user_agent = user_agent
+            .map(str::trim)
+            .filter(|user_agent| !user_agent.is_empty())
+            .map(|user_agent| user_agent.chars().take(MAX_USER_AGENT_LEN).collect());
+        Self { ip, user_agent }
+    }
+}
+
+/// Активная сессия: один вход и все токены, полученные от него обновлением.
+#[derive(Debug, Clone)]
+pub(crate) struct Session {
+    pub(crate) id: i64,
+    pub(crate) user_agent: Option<String>,
+    pub(crate) ip_address: Option<String>,
+    pub(crate) created_at: DateTime<Utc>,
+    /// Последний вход, обновление токенов или запрос с токеном этой сессии
+    /// (с точностью до кэша проверки сессий).
+    pub(crate) last_seen_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientInfo, MAX_USER_AGENT_LEN};
+
+    #[test]
+    fn client_info_drops_blank_and_truncates_long_user_agent() {
+        assert!(ClientInfo::new(None, Some("   ")).user_agent.is_none());
+
+        let client = ClientInfo::new(None, Some(&"я".repeat(MAX_USER_AGENT_LEN + 10)));
+        assert_eq!(
+            client
+                .user_agent
+                .map(|user_agent| user_agent.chars().count()),
+            Some(MAX_USER_AGENT_LEN)
+        );
+    }
+}
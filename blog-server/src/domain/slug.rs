@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+/// Предельная длина slug без суффикса `-N`, который добавляется при совпадениях.
+pub(crate) const MAX_SLUG_LEN: usize = 80;
+
+/// Строит из заголовка URL-безопасный slug: `[a-z0-9]` и одиночные дефисы.
+///
+/// Кириллица транслитерируется, остальные символы становятся разделителями.
+/// Чисто числовой slug получает префикс `post-`, чтобы не путаться с id поста.
+/// Миграция `add_post_slugs` строит slug существующих постов по тем же правилам.
+pub(crate) fn slugify(title: &str) -> String {
+    let mut slug = String::with_capacity(title.len());
+    for ch in title.chars().flat_map(char::to_lowercase) {
+        match ch {
+            'a'..='z' | '0'..='9' => slug.push(ch),
+            _ => match transliterate(ch) {
+                Some(latin) => slug.push_str(latin),
+                // ъ и ь не дают ни буквы, ни разделителя
+                None if matches!(ch, 'ъ' | 'ь') => {}
+                None => {
+                    if !slug.is_empty() && !slug.ends_with('-') {
+                        slug.push('-');
+                    }
+                }
+            },
+        }
+    }
+
+    let mut slug = slug.trim_end_matches('-').to_string();
+    if slug.len() > MAX_SLUG_LEN {
+        // slug уже ASCII, поэтому срез по байтам безопасен
+        slug.truncate(MAX_SLUG_LEN);
+        slug = slug.trim_end_matches('-').to_string();
+    }
+
+    if slug.is_empty() {
+        return "post".to_string();
+    }
+    if slug
+        .bytes()
+        .all(|byte| byte.is_ascii_digit() || byte == b'-')
+    {
+        return format!("post-{slug}");
+    }
+    slug
+}
+
+/// Первый свободный вариант `base`, `base-2`, `base-3`…; `taken` — занятые другими постами slug.
+pub(crate) fn first_free_slug(base: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("infinite sequence has a free slug")
+}
+
+fn transliterate(ch: char) -> Option<&'static str> {
+    let latin = match ch {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' | 'ё' | 'э' => "e",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ю' => "yu",
+        'я' => "ya",
+        'ї' => "yi",
+        'є' => "ye",
+        _ => return None,
+    };
+    Some(latin)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{MAX_SLUG_LEN, first_free_slug, slugify};
+
+    #[test]
+    fn slugify_transliterates_cyrillic_and_collapses_separators() {
+        assert_eq!(slugify("Привет, мир!"), "privet-mir");
+        assert_eq!(slugify("  Щука и ёжик — Объём  "), "shchuka-i-ezhik-obem");
+        assert_eq!(slugify("Rust 2024: async/await"), "rust-2024-async-await");
+        assert_eq!(slugify("Café"), "caf");
+    }
+
+    #[test]
+    fn slugify_never_returns_empty_or_numeric_slug() {
+        assert_eq!(slugify("!!!"), "post");
+        assert_eq!(slugify("2026"), "post-2026");
+        assert_eq!(slugify("10.03"), "post-10-03");
+    }
+
+    #[test]
+    fn slugify_limits_length_without_trailing_dash() {
+        let title = format!("{} tail", "a".repeat(MAX_SLUG_LEN - 1));
+        let slug = slugify(&title);
+        assert_eq!(slug, "a".repeat(MAX_SLUG_LEN - 1));
+    }
+
+    #[test]
+    fn first_free_slug_appends_counter() {
+        let taken = HashSet::from(["hello".to_string(), "hello-2".to_string()]);
+        assert_eq!(first_free_slug("hello", &taken), "hello-3");
+        assert_eq!(first_free_slug("world", &taken), "world");
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use super::error::DomainError;
+
+pub(crate) const MAX_TAG_LEN: usize = 32;
+pub(crate) const MAX_TAGS_PER_POST: usize = 10;
+
+/// Тег вместе с количеством опубликованных постов, к которым он привязан.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TagWithCount {
+    pub(crate) name: String,
+    pub(crate) post_count: i64,
+}
+
+/// Приводит тег к каноническому виду: без пробелов по краям, в нижнем регистре.
+/// Допустимы буквы, цифры, `-` и `_`.
+pub(crate) fn normalize_tag(tag: &str) -> Result<String, DomainError> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() || tag.chars().count() > MAX_TAG_LEN {
+        return Err(DomainError::Validation {
+            field: "tags",
+            message: "each tag must be 1..32 chars",
+        });
+    }
+    if !tag
+        .chars()
+        .all(|ch| ch.is_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(DomainError::Validation {
+            field: "tags",
+            message: "tags may contain only letters, digits, '-' and '_'",
+        });
+    }
+    Ok(tag)
+}
+
+/// Нормализует список тегов, убирая повторы с сохранением исходного порядка.
+pub(crate) fn normalize_tags(tags: &[String]) -> Result<Vec<String>, DomainError> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = normalize_tag(tag)?;
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    if normalized.len() > MAX_TAGS_PER_POST {
+        return Err(DomainError::Validation {
+            field: "tags",
+            message: "at most 10 tags per post",
+        });
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DomainError, normalize_tag, normalize_tags};
+
+    #[test]
+    fn normalize_tag_trims_and_lowercases() {
+        assert_eq!(normalize_tag("  Rust ").expect("must be valid"), "rust");
+        assert_eq!(
+            normalize_tag("Веб-Разработка").expect("must be valid"),
+            "веб-разработка"
+        );
+    }
+
+    #[test]
+    fn normalize_tag_rejects_invalid_characters() {
+        for tag in ["", "   ", "two words", "c++", &"x".repeat(33)] {
+            let err = normalize_tag(tag).expect_err("tag must be rejected");
+            assert!(matches!(err, DomainError::Validation { field: "tags", .. }));
+        }
+    }
+
+    #[test]
+    fn normalize_tags_deduplicates_preserving_order() {
+        let tags = vec!["Rust".to_string(), "web".to_string(), "rust ".to_string()];
+        assert_eq!(
+            normalize_tags(&tags).expect("must be valid"),
+            vec!["rust".to_string(), "web".to_string()]
+        );
+    }
+
+    #[test]
+    fn normalize_tags_limits_count() {
+        let tags: Vec<String> = (0..11).map(|i| format!("tag{i}")).collect();
+        let err = normalize_tags(&tags).expect_err("too many tags");
+        assert!(matches!(err, DomainError::Validation { field: "tags", .. }));
+    }
+}
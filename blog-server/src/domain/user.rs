@@ -1,9 +1,44 @@
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use validator::ValidateEmail;
 
 use super::error::DomainError;
 
+/// Роль пользователя. Модераторы и администраторы могут править и удалять чужие
+/// посты и комментарии; роль назначается в базе данных.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Role {
+    #[default]
+    User,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    pub(crate) fn can_moderate(self) -> bool {
+        matches!(self, Role::Moderator | Role::Admin)
+    }
+}
+
+impl FromStr for Role {
+    type Err = DomainError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "user" => Ok(Role::User),
+            "moderator" => Ok(Role::Moderator),
+            "admin" => Ok(Role::Admin),
+            _ => Err(DomainError::Validation {
+                field: "role",
+                message: "must be one of user, moderator, admin",
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct RegisterRequest {
     pub(crate) username: String,
@@ -15,13 +50,7 @@ impl RegisterRequest {
     pub(crate) fn validate(self) -> Result<Self, DomainError> {
         let username = normalize_register_username(&self.username)?;
         let email = normalize_email(&self.email)?;
-        let password_len = self.password.chars().count();
-        if !(8..=128).contains(&password_len) {
-            return Err(DomainError::Validation {
-                field: "password",
-                message: "must be 8..128 chars",
-            });
-        }
+        validate_new_password("password", &self.password)?;
         Ok(Self {
             username,
             email,
@@ -59,11 +88,57 @@ impl LoginRequest {
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct ChangePasswordRequest {
+    pub(crate) current_password: String,
+    pub(crate) new_password: String,
+}
+
+impl ChangePasswordRequest {
+    pub(crate) fn validate(self) -> Result<Self, DomainError> {
+        if self.current_password.is_empty() {
+            return Err(DomainError::Validation {
+                field: "current_password",
+                message: "must not be empty",
+            });
+        }
+        validate_new_password("new_password", &self.new_password)?;
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ResetPasswordRequest {
+    /// Одноразовый токен из письма.
+    pub(crate) token: String,
+    pub(crate) new_password: String,
+}
+
+impl ResetPasswordRequest {
+    pub(crate) fn validate(self) -> Result<Self, DomainError> {
+        let token = self.token.trim();
+        if token.is_empty() || token.len() > 256 {
+            return Err(DomainError::Validation {
+                field: "token",
+                message: "must be 1..256 chars",
+            });
+        }
+        validate_new_password("new_password", &self.new_password)?;
+        Ok(Self {
+            token: token.to_string(),
+            new_password: self.new_password,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct User {
     pub(crate) id: i64,
     pub(crate) username: String,
     pub(crate) email: String,
+    pub(crate) role: Role,
+    /// Когда пользователь подтвердил владение email; `None` — ещё не подтвердил.
+    pub(crate) email_verified_at: Option<DateTime<Utc>>,
     pub(crate) created_at: DateTime<Utc>,
 }
 
@@ -87,9 +162,25 @@ impl User {
             id,
             username,
             email,
+            role: Role::User,
+            email_verified_at: None,
             created_at,
         })
     }
+
+    pub(crate) fn with_role(mut self, role: Role) -> Self {
+        self.role = role;
+        self
+    }
+
+    pub(crate) fn with_email_verified_at(mut self, verified_at: Option<DateTime<Utc>>) -> Self {
+        self.email_verified_at = verified_at;
+        self
+    }
+
+    pub(crate) fn is_email_verified(&self) -> bool {
+        self.email_verified_at.is_some()
+    }
 }
 
 fn normalize_register_username(username: &str) -> Result<String, DomainError> {
@@ -103,7 +194,17 @@ fn normalize_register_username(username: &str) -> Result<String, DomainError> {
     Ok(username.to_string())
 }
 
-fn normalize_email(email: &str) -> Result<String, DomainError> {
+fn validate_new_password(field: &'static str, password: &str) -> Result<(), DomainError> {
+    if !(8..=128).contains(&password.chars().count()) {
+        return Err(DomainError::Validation {
+            field,
+            message: "must be 8..128 chars",
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn normalize_email(email: &str) -> Result<String, DomainError> {
     let email = email.trim().to_lowercase();
     if !email.validate_email() {
         return Err(DomainError::Validation {
@@ -116,7 +217,10 @@ fn normalize_email(email: &str) -> Result<String, DomainError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{RegisterRequest, User, normalize_email, normalize_register_username};
+    use super::{
+        RegisterRequest, ResetPasswordRequest, Role, User, normalize_email,
+        normalize_register_username,
+    };
     use chrono::Utc;
 
     #[test]
@@ -125,6 +229,21 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn role_is_parsed_and_only_staff_can_moderate() {
+        assert_eq!("user".parse::<Role>().expect("must parse"), Role::User);
+        assert_eq!(
+            "moderator".parse::<Role>().expect("must parse"),
+            Role::Moderator
+        );
+        assert_eq!("admin".parse::<Role>().expect("must parse"), Role::Admin);
+        assert!("root".parse::<Role>().is_err());
+
+        assert!(!Role::User.can_moderate());
+        assert!(Role::Moderator.can_moderate());
+        assert!(Role::Admin.can_moderate());
+    }
+
     #[test]
     fn normalize_email_trims_and_lowercases() {
         let value = normalize_email("  TeSt@Example.COM ").expect("must be valid");
@@ -155,4 +274,25 @@ mod tests {
         assert_eq!(validated.username, "valid_user");
         assert_eq!(validated.email, "test@example.com");
     }
+
+    #[test]
+    fn reset_password_request_trims_token_and_checks_new_password() {
+        let req = ResetPasswordRequest {
+            token: "  abc  ".to_string(),
+            new_password: "very-secure-password".to_string(),
+        };
+        assert_eq!(req.validate().expect("must be valid").token, "abc");
+
+        let short = ResetPasswordRequest {
+            token: "abc".to_string(),
+            new_password: "short".to_string(),
+        };
+        assert!(matches!(
+            short.validate(),
+            Err(super::DomainError::Validation {
+                field: "new_password",
+                ..
+            })
+        ));
+    }
 }
@@ -14,3 +14,51 @@ pub async fn create_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
 pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
     sqlx::migrate!("./migrations").run(pool).await
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeBounds;
+
+    use sqlx::PgPool;
+
+    const POST_SLUGS_VERSION: i64 = 20260319000021;
+
+    /// Накатывает миграции с версиями из `versions`, не трогая таблицу `_sqlx_migrations`.
+    async fn apply_migrations(pool: &PgPool, versions: impl RangeBounds<i64>) {
+        let migrator = sqlx::migrate!("./migrations");
+        for migration in migrator.iter().filter(|m| versions.contains(&m.version)) {
+            sqlx::raw_sql(&migration.sql)
+                .execute(pool)
+                .await
+                .unwrap_or_else(|err| panic!("migration {} failed: {err}", migration.version));
+        }
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL with a role allowed to create databases"]
+    async fn slug_backfill_skips_slugs_taken_by_other_titles(pool: PgPool) {
+        apply_migrations(&pool, ..POST_SLUGS_VERSION).await;
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO users (id, username, email, password_hash)
+            VALUES (1, 'alice', 'alice@example.com', 'x');
+            INSERT INTO posts (id, title, content, content_html, author_id, created_at) VALUES
+                (1, 'Foo', 'a', 'a', 1, '2026-01-01T00:00:00Z'),
+                (2, 'Foo', 'b', 'b', 1, '2026-01-02T00:00:00Z'),
+                (3, 'Foo 2', 'c', 'c', 1, '2026-01-03T00:00:00Z'),
+                (4, 'Foo-2', 'd', 'd', 1, '2026-01-04T00:00:00Z');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("seed posts");
+
+        apply_migrations(&pool, POST_SLUGS_VERSION..=POST_SLUGS_VERSION).await;
+
+        let slugs: Vec<String> = sqlx::query_scalar("SELECT slug FROM posts ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .expect("read slugs");
+        assert_eq!(slugs, ["foo", "foo-2", "foo-2-2", "foo-2-3"]);
+    }
+}
@@ -0,0 +1,564 @@
+use std::fmt::Write as _;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::domain::post::Post;
+
+/// Формат ленты; выбирается расширением в адресе (`/feed.rss`, `/feed.atom`, `/feed.json`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FeedFormat {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl FeedFormat {
+    pub(crate) fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "rss" => Some(Self::Rss),
+            "atom" => Some(Self::Atom),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn extension(self) -> &'static str {
+        match self {
+            Self::Rss => "rss",
+            Self::Atom => "atom",
+            Self::Json => "json",
+        }
+    }
+
+    pub(crate) fn content_type(self) -> &'static str {
+        match self {
+            Self::Rss => "application/rss+xml; charset=utf-8",
+            Self::Atom => "application/atom+xml; charset=utf-8",
+            Self::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+}
+
+/// Общие для всех лент название блога и публичный адрес сервера.
+#[derive(Debug, Clone)]
+pub(crate) struct FeedConfig {
+    pub(crate) title: String,
+    /// Без завершающего `/`; из него строятся абсолютные ссылки лент.
+    pub(crate) base_url: String,
+}
+
+impl Default for FeedConfig {
+    fn default() -> Self {
+        Self {
+            title: "rust-blog".to_string(),
+            base_url: "http://localhost:8080".to_string(),
+        }
+    }
+}
+
+/// Лента: последние опубликованные посты всего блога, автора или тега.
+#[derive(Debug, Clone)]
+pub(crate) struct Feed {
+    pub(crate) title: String,
+    pub(crate) base_url: String,
+    /// Путь ленты без расширения, например `/users/alice/feed`.
+    pub(crate) path: String,
+    /// Новые посты сверху: по убыванию даты публикации.
+    pub(crate) posts: Vec<Post>,
+    /// Последнее изменение любого поста области ленты, включая снятые с публикации
+    /// и удалённые: в отличие от `updated_at` самих постов, не уменьшается, когда пост
+    /// пропадает из ленты.
+    pub(crate) changed_at: Option<DateTime<Utc>>,
+}
+
+impl Feed {
+    /// Время последнего изменения ленты для `Last-Modified`, `lastBuildDate` и `<updated>`.
+    pub(crate) fn updated_at(&self) -> Option<DateTime<Utc>> {
+        self.posts
+            .iter()
+            .map(|post| post.updated_at)
+            .max()
+            .max(self.changed_at)
+    }
+
+    /// Сильный ETag ленты: меняется при правке, появлении и исчезновении любого поста.
+    pub(crate) fn etag(&self, format: FeedFormat) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(format.extension());
+        hasher.update(&self.title);
+        hasher.update(&self.base_url);
+        hasher.update(&self.path);
+        for post in &self.posts {
+            hasher.update(post.id.to_be_bytes());
+            hasher.update(post.updated_at.timestamp_micros().to_be_bytes());
+        }
+        format!("\"{}\"", hex::encode(&hasher.finalize()[..16]))
+    }
+
+    pub(crate) fn render(&self, format: FeedFormat) -> String {
+        match format {
+            FeedFormat::Rss => self.render_rss(),
+            FeedFormat::Atom => self.render_atom(),
+            FeedFormat::Json => self.render_json(),
+        }
+    }
+
+    fn feed_url(&self, format: FeedFormat) -> String {
+        format!("{}{}.{}", self.base_url, self.path, format.extension())
+    }
+
+    /// Постоянный идентификатор поста: в отличие от slug, не меняется вместе с заголовком.
+    fn post_id_url(&self, post: &Post) -> String {
+        format!("{}/api/posts/{}", self.base_url, post.id)
+    }
+
+    fn post_url(&self, post: &Post) -> String {
+        format!("{}/api/posts/by-slug/{}", self.base_url, post.slug)
+    }
+
+    fn render_rss(&self) -> String {
+        let mut out = String::with_capacity(1024);
+        out.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        out.push_str(r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/"><channel>"#);
+        push_element(&mut out, "title", &self.title);
+        push_element(&mut out, "link", &self.base_url);
+        push_element(&mut out, "description", &self.title);
+        let _ = write!(
+            out,
+            r#"<atom:link href="{}" rel="self" type="application/rss+xml"/>"#,
+            escape_xml(&self.feed_url(FeedFormat::Rss))
+        );
+        if let Some(updated_at) = self.updated_at() {
+            push_element(&mut out, "lastBuildDate", &updated_at.to_rfc2822());
+        }
+
+        for post in &self.posts {
+            out.push_str("<item>");
+            push_element(&mut out, "title", &post.title);
+            push_element(&mut out, "link", &self.post_url(post));
+            let _ = write!(
+                out,
+                r#"<guid isPermaLink="true">{}</guid>"#,
+                escape_xml(&self.post_id_url(post))
+            );
+            push_element(&mut out, "pubDate", &published_at(post).to_rfc2822());
+            if let Some(author) = author_name(post) {
+                push_element(&mut out, "dc:creator", author);
+            }
+            for tag in &post.tags {
+                push_element(&mut out, "category", tag);
+            }
+            push_element(&mut out, "description", &post.content_html);
+            out.push_str("</item>");
+        }
+
+        out.push_str("</channel></rss>");
+        out
+    }
+
+    fn render_atom(&self) -> String {
+        // пустой ленте всё равно нужен <updated>
+        let updated_at = self.updated_at().unwrap_or(DateTime::UNIX_EPOCH);
+
+        let mut out = String::with_capacity(1024);
+        out.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        out.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+        push_element(&mut out, "id", &self.feed_url(FeedFormat::Atom));
+        push_element(&mut out, "title", &self.title);
+        push_element(&mut out, "updated", &atom_date(updated_at));
+        let _ = write!(
+            out,
+            r#"<link rel="self" type="application/atom+xml" href="{}"/><link rel="alternate" href="{}"/>"#,
+            escape_xml(&self.feed_url(FeedFormat::Atom)),
+            escape_xml(&self.base_url)
+        );
+
+        for post in &self.posts {
+            out.push_str("<entry>");
+            push_element(&mut out, "id", &self.post_id_url(post));
+            push_element(&mut out, "title", &post.title);
+            let _ = write!(
+                out,
+                r#"<link rel="alternate" href="{}"/>"#,
+                escape_xml(&self.post_url(post))
+            );
+            push_element(&mut out, "published", &atom_date(published_at(post)));
+            push_element(&mut out, "updated", &atom_date(post.updated_at));
+            if let Some(author) = author_name(post) {
+                out.push_str("<author>");
+                push_element(&mut out, "name", author);
+                out.push_str("</author>");
+            }
+            for tag in &post.tags {
+                let _ = write!(out, r#"<category term="{}"/>"#, escape_xml(tag));
+            }
+            let _ = write!(
+                out,
+                r#"<content type="html">{}</content>"#,
+                escape_xml(&post.content_html)
+            );
+            out.push_str("</entry>");
+        }
+
+        out.push_str("</feed>");
+        out
+    }
+
+    fn render_json(&self) -> String {
+        let feed = JsonFeed {
+            version: "https://jsonfeed.org/version/1.1",
+            title: &self.title,
+            home_page_url: &self.base_url,
+            feed_url: self.feed_url(FeedFormat::Json),
+            items: self
+                .posts
+                .iter()
+                .map(|post| JsonFeedItem {
+                    id: self.post_id_url(post),
+                    url: self.post_url(post),
+                    title: &post.title,
+                    content_html: &post.content_html,
+                    date_published: published_at(post),
+                    date_modified: post.updated_at,
+                    authors: author_name(post)
+                        .map(|name| JsonFeedAuthor { name })
+                        .into_iter()
+                        .collect(),
+                    tags: &post.tags,
+                })
+                .collect(),
+        };
+        serde_json::to_string(&feed).expect("json feed is always serializable")
+    }
+}
+
+/// Проверяет условный запрос: `true`, если у клиента актуальная копия и хватит `304`.
+///
+/// Как требует RFC 9110, при `If-None-Match` заголовок `If-Modified-Since` не смотрится.
+pub(crate) fn is_not_modified(
+    if_none_match: Option<&str>,
+    if_modified_since: Option<&str>,
+    etag: &str,
+    last_modified: Option<DateTime<Utc>>,
+) -> bool {
+    if let Some(if_none_match) = if_none_match {
+        return if_none_match.split(',').map(str::trim).any(|candidate| {
+            candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+        });
+    }
+
+    let (Some(if_modified_since), Some(last_modified)) = (if_modified_since, last_modified) else {
+        return false;
+    };
+    DateTime::parse_from_rfc2822(if_modified_since.trim())
+        .is_ok_and(|since| last_modified.timestamp() <= since.timestamp())
+}
+
+/// Дата в формате HTTP (`Sun, 06 Nov 1994 08:49:37 GMT`) для `Last-Modified`.
+pub(crate) fn http_date(at: DateTime<Utc>) -> String {
+    at.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    feed_url: String,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem<'a> {
+    id: String,
+    url: String,
+    title: &'a str,
+    content_html: &'a str,
+    date_published: DateTime<Utc>,
+    date_modified: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<JsonFeedAuthor<'a>>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+}
+
+#[derive(Serialize)]
+struct JsonFeedAuthor<'a> {
+    name: &'a str,
+}
+
+fn published_at(post: &Post) -> DateTime<Utc> {
+    post.published_at.unwrap_or(post.created_at)
+}
+
+fn author_name(post: &Post) -> Option<&str> {
+    post.author
+        .as_ref()
+        .map(|author| author.display_name.as_deref().unwrap_or(&author.username))
+}
+
+fn atom_date(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn push_element(out: &mut String, name: &str, text: &str) {
+    let _ = write!(out, "<{name}>{}</{name}>", escape_xml(text));
+}
+
+/// Экранирует текст для XML; управляющие символы, запрещённые в XML 1.0, отбрасываются.
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(ch),
+            _ if ch.is_control() => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{Feed, FeedFormat, http_date, is_not_modified};
+    use crate::domain::post::{Post, PostAuthor, PostStatus};
+
+    fn post(id: i64, title: &str, updated_at: DateTime<Utc>) -> Post {
+        let mut post =
+            Post::new(id, "title", "text", 1, updated_at, updated_at).expect("valid post");
+        // заголовок задаётся напрямую, чтобы проверить экранирование в обход нормализации
+        post.title = title.to_string();
+        post.slug = format!("post-{id}");
+        post.content_html = "<p>a &amp; <b>b</b></p>".to_string();
+        post.status = PostStatus::Published;
+        post.published_at = Some(updated_at);
+        post.tags = vec!["rust".to_string(), "web".to_string()];
+        post.author = Some(PostAuthor {
+            id: 1,
+            username: "alice".to_string(),
+            display_name: Some("Alice & Co".to_string()),
+        });
+        post
+    }
+
+    fn feed() -> Feed {
+        Feed {
+            title: "Blog <dev>".to_string(),
+            base_url: "https://blog.example.com".to_string(),
+            path: "/feed".to_string(),
+            posts: vec![
+                post(
+                    2,
+                    "Second \u{1} <post>",
+                    Utc.timestamp_opt(1_700_000_100, 0).unwrap(),
+                ),
+                post(1, "First", Utc.timestamp_opt(1_700_000_000, 0).unwrap()),
+            ],
+            changed_at: None,
+        }
+    }
+
+    fn child<'a, 'i>(node: roxmltree::Node<'a, 'i>, name: &str) -> roxmltree::Node<'a, 'i> {
+        node.children()
+            .find(|child| child.tag_name().name() == name)
+            .unwrap_or_else(|| panic!("<{}> must contain <{name}>", node.tag_name().name()))
+    }
+
+    fn text<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> &'a str {
+        child(node, name).text().unwrap_or_default()
+    }
+
+    #[test]
+    fn rss_feed_is_well_formed_rss_2() {
+        let xml = feed().render(FeedFormat::Rss);
+        let doc = roxmltree::Document::parse(&xml).expect("rss must be well-formed xml");
+
+        let rss = doc.root_element();
+        assert_eq!(rss.tag_name().name(), "rss");
+        assert_eq!(rss.attribute("version"), Some("2.0"));
+        let channel = child(rss, "channel");
+        assert_eq!(text(channel, "title"), "Blog <dev>");
+        assert_eq!(text(channel, "link"), "https://blog.example.com");
+        let self_link = channel
+            .children()
+            .find(|node| node.has_tag_name(("http://www.w3.org/2005/Atom", "link")))
+            .expect("rss must link to itself");
+        assert_eq!(self_link.attribute("rel"), Some("self"));
+        assert_eq!(
+            self_link.attribute("href"),
+            Some("https://blog.example.com/feed.rss")
+        );
+
+        let items: Vec<_> = channel
+            .children()
+            .filter(|node| node.has_tag_name("item"))
+            .collect();
+        assert_eq!(items.len(), 2);
+        let item = items[0];
+        assert_eq!(text(item, "title"), "Second  <post>");
+        assert_eq!(
+            text(item, "link"),
+            "https://blog.example.com/api/posts/by-slug/post-2"
+        );
+        assert_eq!(text(item, "guid"), "https://blog.example.com/api/posts/2");
+        assert!(DateTime::parse_from_rfc2822(text(item, "pubDate")).is_ok());
+        assert_eq!(
+            item.children()
+                .find(|node| node.has_tag_name(("http://purl.org/dc/elements/1.1/", "creator")))
+                .and_then(|node| node.text()),
+            Some("Alice & Co")
+        );
+        assert_eq!(
+            item.children()
+                .filter(|node| node.has_tag_name("category"))
+                .count(),
+            2
+        );
+        assert_eq!(text(item, "description"), "<p>a &amp; <b>b</b></p>");
+    }
+
+    #[test]
+    fn atom_feed_is_well_formed_atom_1() {
+        const ATOM: &str = "http://www.w3.org/2005/Atom";
+        let xml = feed().render(FeedFormat::Atom);
+        let doc = roxmltree::Document::parse(&xml).expect("atom must be well-formed xml");
+
+        let root = doc.root_element();
+        assert!(root.has_tag_name((ATOM, "feed")));
+        assert_eq!(text(root, "id"), "https://blog.example.com/feed.atom");
+        assert_eq!(text(root, "updated"), "2023-11-14T22:15:00Z");
+        assert!(root.children().any(|node| {
+            node.has_tag_name((ATOM, "link"))
+                && node.attribute("rel") == Some("self")
+                && node.attribute("href") == Some("https://blog.example.com/feed.atom")
+        }));
+
+        let entries: Vec<_> = root
+            .children()
+            .filter(|node| node.has_tag_name((ATOM, "entry")))
+            .collect();
+        assert_eq!(entries.len(), 2);
+        let entry = entries[1];
+        assert_eq!(text(entry, "id"), "https://blog.example.com/api/posts/1");
+        assert_eq!(text(entry, "title"), "First");
+        assert_eq!(text(entry, "published"), "2023-11-14T22:13:20Z");
+        assert_eq!(text(entry, "updated"), "2023-11-14T22:13:20Z");
+        assert_eq!(text(child(entry, "author"), "name"), "Alice & Co");
+        assert_eq!(
+            child(entry, "link").attribute("href"),
+            Some("https://blog.example.com/api/posts/by-slug/post-1")
+        );
+        let content = child(entry, "content");
+        assert_eq!(content.attribute("type"), Some("html"));
+        assert_eq!(content.text(), Some("<p>a &amp; <b>b</b></p>"));
+    }
+
+    #[test]
+    fn empty_atom_feed_still_has_updated() {
+        let mut feed = feed();
+        feed.posts.clear();
+        let xml = feed.render(FeedFormat::Atom);
+        let doc = roxmltree::Document::parse(&xml).expect("atom must be well-formed xml");
+
+        assert_eq!(text(doc.root_element(), "updated"), "1970-01-01T00:00:00Z");
+        assert_eq!(feed.updated_at(), None);
+    }
+
+    #[test]
+    fn json_feed_follows_version_1_1() {
+        let json: serde_json::Value =
+            serde_json::from_str(&feed().render(FeedFormat::Json)).expect("json feed must parse");
+
+        assert_eq!(json["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(json["feed_url"], "https://blog.example.com/feed.json");
+        let item = &json["items"][0];
+        assert_eq!(item["id"], "https://blog.example.com/api/posts/2");
+        assert_eq!(item["content_html"], "<p>a &amp; <b>b</b></p>");
+        assert_eq!(item["authors"][0]["name"], "Alice & Co");
+        assert_eq!(item["tags"], serde_json::json!(["rust", "web"]));
+    }
+
+    #[test]
+    fn updated_at_does_not_go_back_when_post_leaves_feed() {
+        let mut feed = feed();
+        assert_eq!(
+            feed.updated_at(),
+            Utc.timestamp_opt(1_700_000_100, 0).single()
+        );
+
+        // новейший пост сняли с публикации: его правка остаётся последним изменением области
+        let unpublished_at = Utc.timestamp_opt(1_700_000_200, 0).unwrap();
+        feed.posts.remove(0);
+        feed.changed_at = Some(unpublished_at);
+        assert_eq!(feed.updated_at(), Some(unpublished_at));
+
+        feed.posts.clear();
+        assert_eq!(feed.updated_at(), Some(unpublished_at));
+    }
+
+    #[test]
+    fn etag_changes_when_any_post_changes_or_disappears() {
+        let feed = feed();
+        let etag = feed.etag(FeedFormat::Rss);
+        assert_eq!(etag, feed.clone().etag(FeedFormat::Rss));
+        assert_ne!(etag, feed.etag(FeedFormat::Atom));
+
+        let mut edited = feed.clone();
+        edited.posts[1].updated_at = Utc.timestamp_opt(1_700_000_001, 0).unwrap();
+        assert_ne!(etag, edited.etag(FeedFormat::Rss));
+
+        let mut shorter = feed;
+        shorter.posts.pop();
+        assert_ne!(etag, shorter.etag(FeedFormat::Rss));
+    }
+
+    #[test]
+    fn conditional_request_prefers_if_none_match() {
+        let last_modified = Utc.timestamp_opt(1_700_000_100, 500).unwrap();
+        let date = http_date(last_modified);
+        assert_eq!(date, "Tue, 14 Nov 2023 22:15:00 GMT");
+
+        assert!(is_not_modified(
+            Some("\"x\", W/\"abc\""),
+            None,
+            "\"abc\"",
+            None
+        ));
+        assert!(is_not_modified(Some("*"), None, "\"abc\"", None));
+        assert!(!is_not_modified(
+            Some("\"old\""),
+            Some(&date),
+            "\"abc\"",
+            Some(last_modified)
+        ));
+
+        assert!(is_not_modified(
+            None,
+            Some(&date),
+            "\"abc\"",
+            Some(last_modified)
+        ));
+        assert!(!is_not_modified(
+            None,
+            Some("Tue, 14 Nov 2023 22:14:59 GMT"),
+            "\"abc\"",
+            Some(last_modified)
+        ));
+        assert!(!is_not_modified(
+            None,
+            Some("garbage"),
+            "\"abc\"",
+            Some(last_modified)
+        ));
+        assert!(!is_not_modified(None, Some(&date), "\"abc\"", None));
+    }
+}
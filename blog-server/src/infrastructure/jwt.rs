@@ -1,8 +1,28 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use chrono::{Duration, Utc};
-use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+    OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, decode_header, encode,
+};
+use rsa::pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use rsa::traits::PublicKeyParts;
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::domain::user::{Role, User};
+use crate::infrastructure::opaque_token::generate_opaque_token;
+
 #[derive(Debug, Error)]
 pub(crate) enum JwtError {
     #[error("token encode failed")]
@@ -10,65 +30,648 @@ pub(crate) enum JwtError {
 
     #[error("token decode/validation failed")]
     Decode(#[source] jsonwebtoken::errors::Error),
+
+    #[error("token is signed with unknown key")]
+    UnknownKey,
+
+    #[error("failed to read JWT key '{kid}' from {path}")]
+    ReadKey {
+        kid: String,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("invalid JWT key '{kid}': {reason}")]
+    InvalidKey { kid: String, reason: String },
+
+    #[error("invalid JWT keys configuration: {0}")]
+    Config(&'static str),
+}
+
+/// Алгоритм асимметричного ключа подписи.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwtKeyAlgorithm {
+    Rs256,
+    EdDsa,
+}
+
+impl FromStr for JwtKeyAlgorithm {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_ascii_uppercase().as_str() {
+            "RS256" => Ok(Self::Rs256),
+            "EDDSA" => Ok(Self::EdDsa),
+            _ => Err(format!(
+                "unsupported algorithm '{raw}', expected RS256 or EdDSA"
+            )),
+        }
+    }
+}
+
+impl JwtKeyAlgorithm {
+    fn algorithm(self) -> Algorithm {
+        match self {
+            Self::Rs256 => Algorithm::RS256,
+            Self::EdDsa => Algorithm::EdDSA,
+        }
+    }
+
+    fn jwk_algorithm(self) -> KeyAlgorithm {
+        match self {
+            Self::Rs256 => KeyAlgorithm::RS256,
+            Self::EdDsa => KeyAlgorithm::EdDSA,
+        }
+    }
+}
+
+/// Ключ из настроек: идентификатор `kid`, алгоритм и PEM-файл.
+///
+/// Файл с закрытым ключом позволяет и подписывать, и проверять; файл с открытым
+/// ключом — только проверять (так остаются в ротации выведенные из оборота ключи).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtKeySource {
+    pub kid: String,
+    pub algorithm: JwtKeyAlgorithm,
+    pub path: PathBuf,
+}
+
+/// Разбирает список `kid=алгоритм:путь` через запятую, например
+/// `2026-10=EdDSA:/etc/blog/jwt-2026-10.pem,2026-04=RS256:/etc/blog/jwt-2026-04.pub.pem`.
+pub(crate) fn parse_key_sources(raw: &str) -> Result<Vec<JwtKeySource>, String> {
+    let mut sources: Vec<JwtKeySource> = Vec::new();
+    for entry in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (kid, key) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("expected <kid>=<algorithm>:<path>, got '{entry}'"))?;
+        let (algorithm, path) = key
+            .split_once(':')
+            .ok_or_else(|| format!("expected <kid>=<algorithm>:<path>, got '{entry}'"))?;
+        let (kid, path) = (kid.trim(), path.trim());
+        if kid.is_empty() || path.is_empty() {
+            return Err(format!("kid and path must not be empty in '{entry}'"));
+        }
+        if sources.iter().any(|source| source.kid == kid) {
+            return Err(format!("duplicate kid '{kid}'"));
+        }
+        sources.push(JwtKeySource {
+            kid: kid.to_string(),
+            algorithm: algorithm.parse()?,
+            path: PathBuf::from(path),
+        });
+    }
+    Ok(sources)
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct Claims {
     pub(crate) user_id: i64,
     pub(crate) username: String,
+    /// Роль на момент выдачи токена; в токенах без роли — обычный пользователь.
+    #[serde(default)]
+    pub(crate) role: Role,
+    /// Подтверждён ли email на момент выдачи токена.
+    #[serde(default)]
+    pub(crate) email_verified: bool,
+    /// Уникальный идентификатор токена, по нему токен можно отозвать.
+    pub(crate) jti: String,
+    /// Сессия, в которой выдан токен; у токенов до появления сессий её нет.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) sid: Option<i64>,
+    pub(crate) iat: i64,
+    /// Момент выдачи в микросекундах: `iat` секундный, а отсечка отзыва точнее.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) iat_us: Option<i64>,
     pub(crate) exp: i64,
 }
 
+impl Claims {
+    /// Момент выдачи в микросекундах; у токенов без `iat_us` — начало секунды `iat`.
+    pub(crate) fn issued_at_micros(&self) -> i64 {
+        self.iat_us.unwrap_or(self.iat * 1_000_000)
+    }
+}
+
+/// Промежуточный токен входа с вторым фактором: подтверждает только пароль.
+///
+/// Несёт `aud`, поэтому `verify_token` его не принимает (у access-токенов
+/// аудитории нет), а `verify_mfa_token` не принимает access-токены.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct MfaClaims {
+    pub(crate) user_id: i64,
+    pub(crate) username: String,
+    pub(crate) aud: String,
+    pub(crate) iat: i64,
+    pub(crate) exp: i64,
+}
+
+/// Ключ проверки подписи; выбирается по `kid` из заголовка токена.
+#[derive(Clone)]
+struct VerificationKey {
+    /// У общего HS256-секрета `kid` нет: так подписаны токены до появления ключей.
+    kid: Option<String>,
+    algorithm: Algorithm,
+    key: DecodingKey,
+    /// Открытая часть для JWKS; у HS256-секрета её нет.
+    jwk: Option<Jwk>,
+}
+
+#[derive(Clone)]
 pub(crate) struct JwtService {
-    pub(crate) secret: String,
+    /// Заголовок новых токенов: алгоритм и `kid` активного ключа.
+    header: Header,
+    signing_key: EncodingKey,
+    verification_keys: Vec<VerificationKey>,
     pub(crate) ttl_seconds: i64,
 }
 
 impl JwtService {
     const DEFAULT_TTL_SECONDS: i64 = 24 * 60 * 60;
+    const MFA_AUDIENCE: &'static str = "mfa-pending";
+    const LEEWAY_SECONDS: u64 = 10;
+    const MIN_RSA_BITS: usize = 2048;
 
+    /// Подпись общим секретом HS256: проверять токены может только владелец секрета.
     pub(crate) fn new(secret: &str, ttl_seconds: i64) -> Self {
-        let ttl_seconds = if ttl_seconds > 0 {
-            ttl_seconds
-        } else {
-            Self::DEFAULT_TTL_SECONDS
+        JwtService {
+            header: Header::new(Algorithm::HS256),
+            signing_key: EncodingKey::from_secret(secret.as_bytes()),
+            verification_keys: vec![Self::shared_secret_key(secret)],
+            ttl_seconds: Self::effective_ttl(ttl_seconds),
+        }
+    }
+
+    /// Загружает ключи из файлов. Подписывает ключ `active_kid` (по умолчанию —
+    /// первый с закрытой частью), остальные только проверяют токены.
+    ///
+    /// Без ключей работает как [`JwtService::new`]; с ключами `secret` остаётся
+    /// лишь для проверки выданных ранее HS256-токенов без `kid`.
+    pub(crate) fn load(
+        sources: &[JwtKeySource],
+        active_kid: Option<&str>,
+        secret: Option<&str>,
+        ttl_seconds: i64,
+    ) -> Result<Self, JwtError> {
+        let keys = sources
+            .iter()
+            .map(|source| {
+                let pem =
+                    std::fs::read_to_string(&source.path).map_err(|err| JwtError::ReadKey {
+                        kid: source.kid.clone(),
+                        path: source.path.clone(),
+                        source: err,
+                    })?;
+                load_pem_key(&source.kid, source.algorithm, &pem)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_keys(keys, active_kid, secret, ttl_seconds)
+    }
+
+    fn from_keys(
+        keys: Vec<(VerificationKey, Option<EncodingKey>)>,
+        active_kid: Option<&str>,
+        secret: Option<&str>,
+        ttl_seconds: i64,
+    ) -> Result<Self, JwtError> {
+        if keys.is_empty() {
+            let secret = secret.ok_or(JwtError::Config(
+                "either a shared secret or keys are required",
+            ))?;
+            return Ok(Self::new(secret, ttl_seconds));
+        }
+
+        let active = match active_kid {
+            Some(kid) => keys
+                .iter()
+                .position(|(key, _)| key.kid.as_deref() == Some(kid))
+                .ok_or_else(|| JwtError::InvalidKey {
+                    kid: kid.to_string(),
+                    reason: "active key is not configured".to_string(),
+                })?,
+            None => keys
+                .iter()
+                .position(|(_, signing)| signing.is_some())
+                .ok_or(JwtError::Config("no private key to sign tokens with"))?,
         };
+        let (active_key, signing_key) = &keys[active];
+        let signing_key = signing_key.clone().ok_or_else(|| JwtError::InvalidKey {
+            kid: active_key.kid.clone().unwrap_or_default(),
+            reason: "active key must be a private key".to_string(),
+        })?;
+        let mut header = Header::new(active_key.algorithm);
+        header.kid = active_key.kid.clone();
 
-        JwtService {
-            secret: secret.into(),
-            ttl_seconds,
+        let mut verification_keys: Vec<VerificationKey> =
+            keys.into_iter().map(|(key, _)| key).collect();
+        if let Some(secret) = secret {
+            verification_keys.push(Self::shared_secret_key(secret));
+        }
+
+        Ok(JwtService {
+            header,
+            signing_key,
+            verification_keys,
+            ttl_seconds: Self::effective_ttl(ttl_seconds),
+        })
+    }
+
+    /// Открытые ключи для `/.well-known/jwks.json`; общий секрет сюда не попадает.
+    pub(crate) fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self
+                .verification_keys
+                .iter()
+                .filter_map(|key| key.jwk.clone())
+                .collect(),
         }
     }
 
-    pub(crate) fn generate_token(&self, user_id: i64, username: &str) -> Result<String, JwtError> {
-        let exp = (Utc::now() + Duration::seconds(self.ttl_seconds)).timestamp();
+    pub(crate) fn generate_token(
+        &self,
+        user: &User,
+        session_id: Option<i64>,
+    ) -> Result<String, JwtError> {
+        let now = Utc::now();
 
         let claims = Claims {
-            user_id,
-            username: username.into(),
-            exp,
+            user_id: user.id,
+            username: user.username.clone(),
+            role: user.role,
+            email_verified: user.is_email_verified(),
+            jti: generate_opaque_token(),
+            sid: session_id,
+            iat: now.timestamp(),
+            iat_us: Some(now.timestamp_micros()),
+            exp: (now + Duration::seconds(self.ttl_seconds)).timestamp(),
         };
 
-        encode(
-            &Header::new(Algorithm::HS256),
-            &claims,
-            &EncodingKey::from_secret(self.secret.as_bytes()),
-        )
-        .map_err(JwtError::Encode)
+        self.sign(&claims)
     }
 
     pub(crate) fn verify_token(&self, token: &str) -> Result<Claims, JwtError> {
-        let mut validation = Validation::new(Algorithm::HS256);
+        self.verify(token, |_| {})
+    }
+
+    pub(crate) fn generate_mfa_token(
+        &self,
+        user: &User,
+        ttl_seconds: i64,
+    ) -> Result<String, JwtError> {
+        let now = Utc::now();
+
+        let claims = MfaClaims {
+            user_id: user.id,
+            username: user.username.clone(),
+            aud: Self::MFA_AUDIENCE.to_string(),
+            iat: now.timestamp(),
+            exp: (now + Duration::seconds(ttl_seconds)).timestamp(),
+        };
+
+        self.sign(&claims)
+    }
+
+    pub(crate) fn verify_mfa_token(&self, token: &str) -> Result<MfaClaims, JwtError> {
+        self.verify(token, |validation| {
+            validation.set_audience(&[Self::MFA_AUDIENCE]);
+            validation.set_required_spec_claims(&["exp", "aud"]);
+        })
+    }
+
+    fn sign<C: Serialize>(&self, claims: &C) -> Result<String, JwtError> {
+        encode(&self.header, claims, &self.signing_key).map_err(JwtError::Encode)
+    }
+
+    /// Ключ ищется по `kid`, а алгоритм берётся из ключа, а не из заголовка:
+    /// токен с чужим `alg` для известного `kid` не пройдёт проверку.
+    fn verify<C: DeserializeOwned>(
+        &self,
+        token: &str,
+        configure: impl FnOnce(&mut Validation),
+    ) -> Result<C, JwtError> {
+        let header = decode_header(token).map_err(JwtError::Decode)?;
+        let key = self
+            .verification_keys
+            .iter()
+            .find(|key| key.kid == header.kid)
+            .ok_or(JwtError::UnknownKey)?;
+
+        let mut validation = Validation::new(key.algorithm);
         validation.validate_exp = true;
-        validation.leeway = 10;
+        validation.leeway = Self::LEEWAY_SECONDS;
+        configure(&mut validation);
+
+        let token_data = decode::<C>(token, &key.key, &validation).map_err(JwtError::Decode)?;
+        Ok(token_data.claims)
+    }
+
+    fn shared_secret_key(secret: &str) -> VerificationKey {
+        VerificationKey {
+            kid: None,
+            algorithm: Algorithm::HS256,
+            key: DecodingKey::from_secret(secret.as_bytes()),
+            jwk: None,
+        }
+    }
+
+    fn effective_ttl(ttl_seconds: i64) -> i64 {
+        if ttl_seconds > 0 {
+            ttl_seconds
+        } else {
+            Self::DEFAULT_TTL_SECONDS
+        }
+    }
+}
+
+/// Разбирает PEM с закрытым (PKCS#8, для RSA и PKCS#1) или открытым ключом.
+///
+/// Открытая часть вычисляется сама, поэтому для подписывающего ключа
+/// достаточно одного файла.
+fn load_pem_key(
+    kid: &str,
+    algorithm: JwtKeyAlgorithm,
+    pem: &str,
+) -> Result<(VerificationKey, Option<EncodingKey>), JwtError> {
+    let invalid = |reason: String| JwtError::InvalidKey {
+        kid: kid.to_string(),
+        reason,
+    };
+    let is_private = pem.contains("PRIVATE KEY");
+
+    let (key, params, signing_key) = match algorithm {
+        JwtKeyAlgorithm::Rs256 => {
+            let public = if is_private {
+                RsaPrivateKey::from_pkcs8_pem(pem)
+                    .or_else(|_| RsaPrivateKey::from_pkcs1_pem(pem))
+                    .map_err(|err| invalid(err.to_string()))?
+                    .to_public_key()
+            } else {
+                RsaPublicKey::from_public_key_pem(pem)
+                    .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+                    .map_err(|err| invalid(err.to_string()))?
+            };
+            if public.size() * 8 < JwtService::MIN_RSA_BITS {
+                return Err(invalid(format!(
+                    "RSA key must be at least {} bits",
+                    JwtService::MIN_RSA_BITS
+                )));
+            }
+            let (n, e) = (public.n().to_bytes_be(), public.e().to_bytes_be());
+            let signing_key = is_private
+                .then(|| EncodingKey::from_rsa_pem(pem.as_bytes()))
+                .transpose()
+                .map_err(|err| invalid(err.to_string()))?;
+            (
+                DecodingKey::from_rsa_raw_components(&n, &e),
+                AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n: URL_SAFE_NO_PAD.encode(n),
+                    e: URL_SAFE_NO_PAD.encode(e),
+                }),
+                signing_key,
+            )
+        }
+        JwtKeyAlgorithm::EdDsa => {
+            let public = if is_private {
+                SigningKey::from_pkcs8_pem(pem)
+                    .map_err(|err| invalid(err.to_string()))?
+                    .verifying_key()
+            } else {
+                VerifyingKey::from_public_key_pem(pem).map_err(|err| invalid(err.to_string()))?
+            };
+            let signing_key = is_private
+                .then(|| EncodingKey::from_ed_pem(pem.as_bytes()))
+                .transpose()
+                .map_err(|err| invalid(err.to_string()))?;
+            (
+                DecodingKey::from_ed_der(public.as_bytes()),
+                AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x: URL_SAFE_NO_PAD.encode(public.as_bytes()),
+                }),
+                signing_key,
+            )
+        }
+    };
+
+    let jwk = Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(algorithm.jwk_algorithm()),
+            key_id: Some(kid.to_string()),
+            ..Default::default()
+        },
+        algorithm: params,
+    };
+    let key = VerificationKey {
+        kid: Some(kid.to_string()),
+        algorithm: algorithm.algorithm(),
+        key,
+        jwk: Some(jwk),
+    };
+    Ok((key, signing_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::jwk::AlgorithmParameters;
+    use jsonwebtoken::{DecodingKey, Validation, decode};
+
+    use super::{Claims, JwtError, JwtKeyAlgorithm, JwtService, load_pem_key, parse_key_sources};
+    use crate::domain::user::User;
 
-        let token_data = decode::<Claims>(
-            token,
-            &DecodingKey::from_secret(self.secret.as_bytes()),
-            &validation,
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+    const RS256_PRIVATE: &str = include_str!("../../testdata/jwt/rs256-private.pem");
+    const RS256_PUBLIC: &str = include_str!("../../testdata/jwt/rs256-public.pem");
+    const ED25519_PRIVATE: &str = include_str!("../../testdata/jwt/ed25519-private.pem");
+    const ED25519_PUBLIC: &str = include_str!("../../testdata/jwt/ed25519-public.pem");
+
+    fn user() -> User {
+        User::new(7, "alice", "alice@example.com", chrono::Utc::now()).expect("valid user")
+    }
+
+    fn service(
+        keys: &[(&str, JwtKeyAlgorithm, &str)],
+        active_kid: Option<&str>,
+        secret: Option<&str>,
+    ) -> Result<JwtService, JwtError> {
+        let keys = keys
+            .iter()
+            .map(|(kid, algorithm, pem)| load_pem_key(kid, *algorithm, pem))
+            .collect::<Result<Vec<_>, _>>()?;
+        JwtService::from_keys(keys, active_kid, secret, 3600)
+    }
+
+    #[test]
+    fn key_sources_are_parsed_from_settings() {
+        let sources = parse_key_sources(" new=EdDSA:/keys/new.pem, old=rs256:/keys/old.pub.pem ")
+            .expect("valid key list");
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].kid, "new");
+        assert_eq!(sources[0].algorithm, JwtKeyAlgorithm::EdDsa);
+        assert_eq!(sources[1].algorithm, JwtKeyAlgorithm::Rs256);
+        assert_eq!(sources[1].path.to_str(), Some("/keys/old.pub.pem"));
+
+        assert!(parse_key_sources("new=HS256:/keys/new.pem").is_err());
+        assert!(parse_key_sources("new:/keys/new.pem").is_err());
+        assert!(parse_key_sources("a=EdDSA:/a.pem,a=EdDSA:/b.pem").is_err());
+        assert_eq!(parse_key_sources("").expect("empty list"), Vec::new());
+    }
+
+    #[test]
+    fn asymmetric_tokens_carry_kid_and_verify_with_public_key_only() {
+        for (algorithm, private, public) in [
+            (JwtKeyAlgorithm::Rs256, RS256_PRIVATE, RS256_PUBLIC),
+            (JwtKeyAlgorithm::EdDsa, ED25519_PRIVATE, ED25519_PUBLIC),
+        ] {
+            let signer = service(&[("k1", algorithm, private)], None, None).expect("signer");
+            let token = signer.generate_token(&user(), None).expect("token");
+            let header = jsonwebtoken::decode_header(&token).expect("header");
+            assert_eq!(header.kid.as_deref(), Some("k1"));
+
+            // проверяющему сервису достаточно открытого ключа
+            let verifier = service(
+                &[
+                    ("k1", algorithm, public),
+                    ("signer", JwtKeyAlgorithm::EdDsa, ED25519_PRIVATE),
+                ],
+                Some("signer"),
+                None,
+            )
+            .expect("verifier");
+            assert_eq!(verifier.verify_token(&token).expect("claims").user_id, 7);
+            assert!(verifier.verify_mfa_token(&token).is_err());
+        }
+    }
+
+    #[test]
+    fn rotation_keeps_old_tokens_valid_and_signs_with_active_key() {
+        let before = service(
+            &[("old", JwtKeyAlgorithm::EdDsa, ED25519_PRIVATE)],
+            None,
+            None,
         )
-        .map_err(JwtError::Decode)?;
+        .expect("old service");
+        let old_token = before.generate_token(&user(), None).expect("token");
 
-        Ok(token_data.claims)
+        let after = service(
+            &[
+                ("new", JwtKeyAlgorithm::Rs256, RS256_PRIVATE),
+                ("old", JwtKeyAlgorithm::EdDsa, ED25519_PUBLIC),
+            ],
+            Some("new"),
+            None,
+        )
+        .expect("rotated service");
+        assert!(after.verify_token(&old_token).is_ok());
+
+        let new_token = after.generate_mfa_token(&user(), 300).expect("mfa token");
+        let header = jsonwebtoken::decode_header(&new_token).expect("header");
+        assert_eq!(header.kid.as_deref(), Some("new"));
+        assert!(after.verify_mfa_token(&new_token).is_ok());
+        assert!(matches!(
+            before.verify_mfa_token(&new_token),
+            Err(JwtError::UnknownKey)
+        ));
+
+        // открытым ключом подписывать нельзя
+        assert!(
+            service(
+                &[("old", JwtKeyAlgorithm::EdDsa, ED25519_PUBLIC)],
+                Some("old"),
+                None
+            )
+            .is_err()
+        );
+        assert!(
+            service(
+                &[("old", JwtKeyAlgorithm::EdDsa, ED25519_PUBLIC)],
+                None,
+                None
+            )
+            .is_err()
+        );
+        assert!(
+            service(
+                &[("new", JwtKeyAlgorithm::Rs256, RS256_PRIVATE)],
+                Some("missing"),
+                None
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn shared_secret_only_verifies_legacy_tokens_once_keys_are_configured() {
+        let legacy = JwtService::new(SECRET, 3600);
+        let legacy_token = legacy.generate_token(&user(), None).expect("token");
+        assert!(legacy.jwks().keys.is_empty());
+
+        let rotated = service(
+            &[("k1", JwtKeyAlgorithm::EdDsa, ED25519_PRIVATE)],
+            None,
+            Some(SECRET),
+        )
+        .expect("service");
+        assert!(rotated.verify_token(&legacy_token).is_ok());
+
+        let token = rotated.generate_token(&user(), None).expect("token");
+        assert!(legacy.verify_token(&token).is_err());
+
+        // подмена алгоритма на HS256 с открытым ключом в роли секрета не проходит
+        let forged = jsonwebtoken::encode(
+            &jsonwebtoken::Header {
+                kid: Some("k1".to_string()),
+                ..jsonwebtoken::Header::new(jsonwebtoken::Algorithm::HS256)
+            },
+            &rotated.verify_token(&token).expect("claims"),
+            &jsonwebtoken::EncodingKey::from_secret(ED25519_PUBLIC.as_bytes()),
+        )
+        .expect("forged token");
+        assert!(rotated.verify_token(&forged).is_err());
+    }
+
+    #[test]
+    fn jwks_exposes_only_public_keys() {
+        let service = service(
+            &[
+                ("rsa", JwtKeyAlgorithm::Rs256, RS256_PRIVATE),
+                ("ed", JwtKeyAlgorithm::EdDsa, ED25519_PUBLIC),
+            ],
+            None,
+            Some(SECRET),
+        )
+        .expect("service");
+        let jwks = service.jwks();
+        assert_eq!(jwks.keys.len(), 2);
+
+        let json = serde_json::to_value(&jwks).expect("serializable");
+        assert_eq!(json["keys"][0]["kty"], "RSA");
+        assert_eq!(json["keys"][0]["kid"], "rsa");
+        assert_eq!(json["keys"][0]["alg"], "RS256");
+        assert_eq!(json["keys"][0]["use"], "sig");
+        assert!(json["keys"][0].get("d").is_none());
+        assert_eq!(json["keys"][1]["kty"], "OKP");
+        assert_eq!(json["keys"][1]["crv"], "Ed25519");
+
+        // опубликованного ключа достаточно для проверки токена сторонним сервисом
+        let token = service.generate_token(&user(), Some(42)).expect("token");
+        let jwk = jwks.find("rsa").expect("published key");
+        assert!(matches!(jwk.algorithm, AlgorithmParameters::RSA(_)));
+        let claims = decode::<Claims>(
+            &token,
+            &DecodingKey::from_jwk(jwk).expect("decoding key"),
+            &Validation::new(jsonwebtoken::Algorithm::RS256),
+        )
+        .expect("valid token")
+        .claims;
+        assert_eq!(claims.username, "alice");
+        assert_eq!(claims.sid, Some(42));
+        assert_eq!(claims.issued_at_micros() / 1_000_000, claims.iat);
     }
 }
@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::io::AsyncWriteExt;
+use tracing::info;
+
+use crate::domain::error::DomainError;
+
+/// Письмо пользователю.
+#[derive(Debug, Clone)]
+pub(crate) struct EmailMessage {
+    pub(crate) to: String,
+    pub(crate) subject: String,
+    pub(crate) body: String,
+}
+
+#[async_trait]
+pub(crate) trait Mailer: Send + Sync {
+    async fn send(&self, message: EmailMessage) -> Result<(), DomainError>;
+}
+
+/// Почта для локальной разработки: письма ничего не отправляют, а пишутся
+/// в лог и, если задан `outbox`, дописываются в этот файл.
+#[derive(Debug, Clone)]
+pub(crate) struct LocalMailer {
+    outbox: Option<PathBuf>,
+}
+
+impl LocalMailer {
+    pub(crate) fn new(outbox: Option<PathBuf>) -> Self {
+        Self { outbox }
+    }
+}
+
+#[async_trait]
+impl Mailer for LocalMailer {
+    async fn send(&self, message: EmailMessage) -> Result<(), DomainError> {
+        let Some(path) = &self.outbox else {
+            info!(
+                to = %message.to,
+                subject = %message.subject,
+                body = %message.body,
+                "email is not delivered, local mailer"
+            );
+            return Ok(());
+        };
+
+        let entry = format!(
+            "Date: {}\nTo: {}\nSubject: {}\n\n{}\n\n",
+            Utc::now().to_rfc2822(),
+            message.to,
+            message.subject,
+            message.body
+        );
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .map_err(|err| DomainError::Unexpected(format!("mail outbox: {err}")))?;
+        // tokio::fs::File пишет в фоне: без flush письмо может не дойти до диска
+        file.write_all(entry.as_bytes())
+            .await
+            .map_err(|err| DomainError::Unexpected(format!("mail outbox: {err}")))?;
+        file.flush()
+            .await
+            .map_err(|err| DomainError::Unexpected(format!("mail outbox: {err}")))?;
+
+        info!(to = %message.to, subject = %message.subject, outbox = %path.display(), "email written to outbox");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EmailMessage, LocalMailer, Mailer};
+    use crate::infrastructure::opaque_token::generate_opaque_token;
+
+    #[tokio::test]
+    async fn local_mailer_appends_messages_to_outbox() {
+        let path =
+            std::env::temp_dir().join(format!("blog-outbox-{}.txt", generate_opaque_token()));
+        let mailer = LocalMailer::new(Some(path.clone()));
+
+        for subject in ["first", "second"] {
+            mailer
+                .send(EmailMessage {
+                    to: "user@example.com".to_string(),
+                    subject: subject.to_string(),
+                    body: "token: abc".to_string(),
+                })
+                .await
+                .expect("send must succeed");
+        }
+
+        let outbox = std::fs::read_to_string(&path).expect("outbox must exist");
+        std::fs::remove_file(&path).ok();
+        assert!(outbox.contains("To: user@example.com"));
+        assert!(outbox.contains("Subject: first"));
+        assert!(outbox.contains("Subject: second"));
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use ammonia::Builder;
+use pulldown_cmark::{Options, Parser, html};
+
+use crate::domain::post::ContentFormat;
+
+/// Разрешённые теги и атрибуты: всё остальное, включая `<script>`, `style`
+/// и обработчики событий, вырезается. Ссылки — только http(s) и mailto.
+static SANITIZER: LazyLock<Builder<'static>> = LazyLock::new(|| {
+    let mut builder = Builder::empty();
+    builder
+        .tags(HashSet::from([
+            "p",
+            "br",
+            "hr",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "strong",
+            "em",
+            "del",
+            "code",
+            "pre",
+            "blockquote",
+            "ul",
+            "ol",
+            "li",
+            "a",
+            "img",
+            "table",
+            "thead",
+            "tbody",
+            "tr",
+            "th",
+            "td",
+        ]))
+        .tag_attributes(HashMap::from([
+            ("a", HashSet::from(["href", "title"])),
+            ("img", HashSet::from(["src", "alt", "title"])),
+            ("ol", HashSet::from(["start"])),
+        ]))
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .link_rel(Some("noopener noreferrer nofollow"));
+    builder
+});
+
+/// Отрисовывает содержимое поста в HTML, безопасный для вставки в страницу.
+pub(crate) fn render_content(content: &str, format: ContentFormat) -> String {
+    match format {
+        ContentFormat::Plain => render_plain(content),
+        ContentFormat::Markdown => render_markdown(content),
+    }
+}
+
+/// Обычный текст: экранирование и переносы строк через `<br>`.
+/// Миграция `add_post_content_format` отрисовывает старые посты так же.
+fn render_plain(content: &str) -> String {
+    let mut out = String::with_capacity(content.len() + 16);
+    out.push_str("<p>");
+    for ch in content.replace("\r\n", "\n").chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            '\n' => out.push_str("<br>\n"),
+            _ => out.push(ch),
+        }
+    }
+    out.push_str("</p>");
+    out
+}
+
+/// Сырой HTML внутри Markdown не доверенный: он проходит тот же фильтр, что и результат.
+fn render_markdown(content: &str) -> String {
+    let parser = Parser::new_ext(
+        content,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH,
+    );
+    let mut unsafe_html = String::with_capacity(content.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, parser);
+    SANITIZER.clean(&unsafe_html).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_content;
+    use crate::domain::post::ContentFormat;
+
+    #[test]
+    fn plain_text_is_escaped_and_keeps_line_breaks() {
+        assert_eq!(
+            render_content("a <b> & \"c\"\r\nnext", ContentFormat::Plain),
+            "<p>a &lt;b&gt; &amp; &quot;c&quot;<br>\nnext</p>"
+        );
+    }
+
+    #[test]
+    fn markdown_is_rendered_to_allowlisted_html() {
+        let html = render_content(
+            "# Title\n\n**bold** and [link](https://example.com)\n\n- item",
+            ContentFormat::Markdown,
+        );
+
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains(
+            "<a href=\"https://example.com\" rel=\"noopener noreferrer nofollow\">link</a>"
+        ));
+        assert!(html.contains("<li>item</li>"));
+    }
+
+    #[test]
+    fn markdown_drops_scripts_event_handlers_and_unsafe_urls() {
+        let html = render_content(
+            "<script>alert(1)</script>\n\n<img src=\"https://example.com/a.png\" onerror=\"alert(2)\">\n\n[x](javascript:alert(3)) <span style=\"color:red\">s</span>",
+            ContentFormat::Markdown,
+        );
+
+        assert!(!html.contains("script"));
+        assert!(!html.contains("alert"));
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("style"));
+        assert!(html.contains("<img src=\"https://example.com/a.png\">"));
+    }
+}
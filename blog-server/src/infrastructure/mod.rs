@@ -1,4 +1,10 @@
 pub(crate) mod database;
+pub(crate) mod feed;
 pub(crate) mod jwt;
 pub(crate) mod logging;
+pub(crate) mod mailer;
+pub(crate) mod markdown;
+pub(crate) mod opaque_token;
+pub(crate) mod rate_limiter;
 pub(crate) mod settings;
+pub(crate) mod totp;
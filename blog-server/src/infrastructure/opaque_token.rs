@@ -0,0 +1,39 @@
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+const TOKEN_BYTES: usize = 32;
+
+/// Генерирует случайный токен (hex), который отдаётся клиенту как есть.
+pub(crate) fn generate_opaque_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// SHA-256 от токена: в БД хранится только хэш, сам токен не восстановить.
+pub(crate) fn hash_opaque_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_opaque_token, hash_opaque_token};
+
+    #[test]
+    fn generated_tokens_are_unique_and_hex() {
+        let first = generate_opaque_token();
+        let second = generate_opaque_token();
+
+        assert_eq!(first.len(), 64);
+        assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn hash_is_stable_and_differs_from_token() {
+        let token = generate_opaque_token();
+
+        assert_eq!(hash_opaque_token(&token), hash_opaque_token(&token));
+        assert_ne!(hash_opaque_token(&token), token);
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Источник времени; в тестах подменяется управляемыми часами.
+pub(crate) trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Квота: `requests` запросов за `period`, в формате `120/60` (запросов/секунд).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateQuota {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl FromStr for RateQuota {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let (requests, seconds) = raw
+            .trim()
+            .split_once('/')
+            .ok_or_else(|| format!("expected <requests>/<seconds>, got '{raw}'"))?;
+        let requests: u32 = requests
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid request count in '{raw}'"))?;
+        let seconds: u64 = seconds
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid period in '{raw}'"))?;
+        if requests == 0 || seconds == 0 {
+            return Err(format!("quota '{raw}' must be > 0"));
+        }
+        Ok(Self {
+            requests,
+            period: Duration::from_secs(seconds),
+        })
+    }
+}
+
+/// Отдельная квота для путей с префиксом `path_prefix`
+/// (HTTP-путь или gRPC-метод вида `/blog.BlogService/Login`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteRateLimit {
+    pub path_prefix: String,
+    pub quota: RateQuota,
+}
+
+/// Разбирает список `префикс=квота` через запятую, например
+/// `/api/auth=30/60,/blog.BlogService/Login=30/60`.
+pub(crate) fn parse_route_limits(raw: &str) -> Result<Vec<RouteRateLimit>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (prefix, quota) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected <path prefix>=<quota>, got '{entry}'"))?;
+            let prefix = prefix.trim();
+            if !prefix.starts_with('/') {
+                return Err(format!("path prefix '{prefix}' must start with '/'"));
+            }
+            Ok(RouteRateLimit {
+                path_prefix: prefix.to_string(),
+                quota: quota.parse()?,
+            })
+        })
+        .collect()
+}
+
+/// Итог проверки одного запроса: разрешён ли он и что сообщить клиенту.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RateLimitDecision {
+    pub(crate) allowed: bool,
+    pub(crate) limit: u32,
+    pub(crate) remaining: u32,
+    /// Через сколько корзина наполнится полностью.
+    pub(crate) reset_after: Duration,
+    /// Через сколько появится следующий токен; только для отклонённых запросов.
+    pub(crate) retry_after: Option<Duration>,
+}
+
+/// Корзина заводится на пару (правило, клиент); `None` — квота по умолчанию.
+type BucketKey = (Option<usize>, String);
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Ограничитель запросов по алгоритму token bucket.
+///
+/// Для каждого клиента и каждого правила своя корзина ёмкостью `requests`,
+/// которая равномерно наполняется за `period`. Состояние живёт в памяти процесса,
+/// число корзин не превышает `max_entries`.
+pub(crate) struct RateLimiter<C: Clock = SystemClock> {
+    default_quota: RateQuota,
+    /// Отсортированы по убыванию длины префикса: выигрывает самое точное правило.
+    routes: Vec<RouteRateLimit>,
+    /// Через столько простоя любая корзина гарантированно полна.
+    longest_period: Duration,
+    max_entries: usize,
+    buckets: Mutex<HashMap<BucketKey, Bucket>>,
+    clock: C,
+}
+
+impl RateLimiter<SystemClock> {
+    pub(crate) fn new(default_quota: RateQuota, routes: Vec<RouteRateLimit>) -> Self {
+        Self::with_clock(default_quota, routes, SystemClock)
+    }
+}
+
+impl<C: Clock> RateLimiter<C> {
+    const DEFAULT_MAX_ENTRIES: usize = 100_000;
+
+    pub(crate) fn with_clock(
+        default_quota: RateQuota,
+        mut routes: Vec<RouteRateLimit>,
+        clock: C,
+    ) -> Self {
+        routes.sort_by_key(|route| std::cmp::Reverse(route.path_prefix.len()));
+        let longest_period = routes
+            .iter()
+            .map(|route| route.quota.period)
+            .fold(default_quota.period, Duration::max);
+        Self {
+            default_quota,
+            routes,
+            longest_period,
+            max_entries: Self::DEFAULT_MAX_ENTRIES,
+            buckets: Mutex::new(HashMap::new()),
+            clock,
+        }
+    }
+
+    /// Предел числа корзин в памяти; при переполнении вытесняются самые давние.
+    pub(crate) fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Списывает токен из корзины клиента `client_key` для пути `path`.
+    pub(crate) fn check(&self, path: &str, client_key: &str) -> RateLimitDecision {
+        let (rule, quota) = self.quota_for(path);
+        let capacity = f64::from(quota.requests);
+        let period = quota.period.as_secs_f64();
+        // время в токены и обратно: сначала умножаем, потом делим, чтобы не копить погрешность
+        let tokens_for = |elapsed: Duration| elapsed.as_secs_f64() * capacity / period;
+        let time_for = |tokens: f64| Duration::from_secs_f64(tokens * period / capacity);
+        let now = self.clock.now();
+
+        let key = (rule, client_key.to_string());
+        let mut buckets = self.buckets.lock().expect("rate limiter mutex poisoned");
+        if buckets.len() >= self.max_entries && !buckets.contains_key(&key) {
+            self.evict(&mut buckets, now);
+        }
+
+        let bucket = buckets.entry(key).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + tokens_for(elapsed)).min(capacity);
+        bucket.updated_at = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        RateLimitDecision {
+            allowed,
+            limit: quota.requests,
+            remaining: bucket.tokens.floor() as u32,
+            reset_after: time_for(capacity - bucket.tokens),
+            retry_after: (!allowed).then(|| time_for(1.0 - bucket.tokens)),
+        }
+    }
+
+    /// Освобождает место под новую корзину: сначала выбрасывает полные, а если их
+    /// не хватило — самые давние, пока не освободится десятая часть предела.
+    /// Поэтому полный проход по карте случается не чаще раза на десятую часть вставок.
+    fn evict(&self, buckets: &mut HashMap<BucketKey, Bucket>, now: Instant) {
+        // полная корзина ничем не отличается от отсутствующей
+        let longest_period = self.longest_period;
+        buckets
+            .retain(|_, bucket| now.saturating_duration_since(bucket.updated_at) < longest_period);
+
+        let low_water = (self.max_entries - self.max_entries / 10).min(self.max_entries - 1);
+        if buckets.len() <= low_water {
+            return;
+        }
+        let excess = buckets.len() - low_water;
+        let mut updated: Vec<Instant> = buckets.values().map(|bucket| bucket.updated_at).collect();
+        let (_, &mut cutoff, _) = updated.select_nth_unstable(excess - 1);
+        buckets.retain(|_, bucket| bucket.updated_at > cutoff);
+    }
+
+    fn quota_for(&self, path: &str) -> (Option<usize>, RateQuota) {
+        self.routes
+            .iter()
+            .position(|route| path.starts_with(&route.path_prefix))
+            .map(|index| (Some(index), self.routes[index].quota))
+            .unwrap_or((None, self.default_quota))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use super::{Clock, RateLimiter, RateQuota, RouteRateLimit, parse_route_limits};
+
+    #[derive(Clone)]
+    struct FakeClock {
+        now: Arc<Mutex<Instant>>,
+    }
+
+    impl FakeClock {
+        fn new() -> Self {
+            Self {
+                now: Arc::new(Mutex::new(Instant::now())),
+            }
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.now.lock().expect("clock mutex poisoned") += by;
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            *self.now.lock().expect("clock mutex poisoned")
+        }
+    }
+
+    fn quota(raw: &str) -> RateQuota {
+        raw.parse().expect("valid quota")
+    }
+
+    #[test]
+    fn bucket_rejects_when_empty_and_refills_over_time() {
+        let clock = FakeClock::new();
+        let limiter = RateLimiter::with_clock(quota("3/30"), Vec::new(), clock.clone());
+
+        let remaining: Vec<u32> = (0..3)
+            .map(|_| limiter.check("/api/posts", "ip:1").remaining)
+            .collect();
+        assert_eq!(remaining, vec![2, 1, 0]);
+
+        let rejected = limiter.check("/api/posts", "ip:1");
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.retry_after, Some(Duration::from_secs(10)));
+        assert_eq!(rejected.reset_after, Duration::from_secs(30));
+
+        clock.advance(Duration::from_secs(10));
+        assert!(limiter.check("/api/posts", "ip:1").allowed);
+        assert!(!limiter.check("/api/posts", "ip:1").allowed);
+
+        clock.advance(Duration::from_secs(300));
+        let refilled = limiter.check("/api/posts", "ip:1");
+        assert!(refilled.allowed);
+        assert_eq!(refilled.remaining, 2);
+    }
+
+    #[test]
+    fn clients_and_routes_have_separate_buckets() {
+        let clock = FakeClock::new();
+        let routes = vec![RouteRateLimit {
+            path_prefix: "/api/auth".to_string(),
+            quota: quota("1/60"),
+        }];
+        let limiter = RateLimiter::with_clock(quota("100/60"), routes, clock);
+
+        assert!(limiter.check("/api/auth/login", "ip:1").allowed);
+        assert!(!limiter.check("/api/auth/login", "ip:1").allowed);
+        assert!(!limiter.check("/api/auth/register", "ip:1").allowed);
+        assert!(limiter.check("/api/auth/login", "user:7").allowed);
+
+        let other_route = limiter.check("/api/posts", "ip:1");
+        assert!(other_route.allowed);
+        assert_eq!(other_route.limit, 100);
+    }
+
+    #[test]
+    fn longest_matching_prefix_wins() {
+        let routes =
+            parse_route_limits("/api=50/60, /api/auth/login=5/60").expect("valid route limits");
+        let limiter = RateLimiter::with_clock(quota("100/60"), routes, FakeClock::new());
+
+        assert_eq!(limiter.check("/api/auth/login", "ip:1").limit, 5);
+        assert_eq!(limiter.check("/api/posts", "ip:1").limit, 50);
+        assert_eq!(limiter.check("/healthz", "ip:1").limit, 100);
+    }
+
+    #[test]
+    fn bucket_count_never_exceeds_cap() {
+        let clock = FakeClock::new();
+        let limiter =
+            RateLimiter::with_clock(quota("2/60"), Vec::new(), clock.clone()).with_max_entries(10);
+
+        assert!(limiter.check("/api/posts", "ip:hot").allowed);
+        assert!(limiter.check("/api/posts", "ip:hot").allowed);
+        for n in 0..1_000 {
+            clock.advance(Duration::from_millis(1));
+            limiter.check("/api/posts", &format!("ip:{n}"));
+            // активный клиент вытесняется последним и не получает новую квоту
+            assert!(!limiter.check("/api/posts", "ip:hot").allowed);
+            let len = limiter.buckets.lock().expect("mutex poisoned").len();
+            assert!(len <= 10, "{len} buckets exceed the cap");
+        }
+    }
+
+    #[test]
+    fn invalid_quotas_are_rejected() {
+        assert!("10".parse::<RateQuota>().is_err());
+        assert!("0/60".parse::<RateQuota>().is_err());
+        assert!("10/0".parse::<RateQuota>().is_err());
+        assert!(parse_route_limits("api=10/60").is_err());
+        assert!(parse_route_limits("/api").is_err());
+        assert_eq!(
+            parse_route_limits(" ").expect("empty list is valid"),
+            Vec::new()
+        );
+    }
+}
@@ -1,10 +1,33 @@
 use anyhow::{Context, Result, anyhow};
 
+use crate::infrastructure::jwt::{JwtKeySource, parse_key_sources};
+use crate::infrastructure::rate_limiter::{RateQuota, RouteRateLimit, parse_route_limits};
+
 #[derive(Debug, Clone)]
 pub struct Settings {
     pub database_url: String,
-    pub jwt_secret: String,
+    /// Общий секрет HS256; при заданных `jwt_keys` только проверяет старые токены.
+    pub jwt_secret: Option<String>,
+    pub jwt_keys: Vec<JwtKeySource>,
+    pub jwt_active_kid: Option<String>,
     pub jwt_ttl_seconds: i64,
+    pub refresh_token_ttl_seconds: i64,
+    pub password_reset_ttl_seconds: i64,
+    pub email_verification_ttl_seconds: i64,
+    pub require_email_verification: bool,
+    pub mfa_pending_ttl_seconds: i64,
+    pub totp_issuer: String,
+    pub mail_outbox_path: Option<String>,
+    pub login_free_attempts: u32,
+    pub login_max_attempts_per_username: u32,
+    pub login_max_attempts_per_ip: u32,
+    pub login_backoff_base_secs: u64,
+    pub login_lockout_secs: u64,
+    pub token_revocation_cache_ttl_secs: u64,
+    pub rate_limit_default: RateQuota,
+    pub rate_limit_routes: Vec<RouteRateLimit>,
+    /// Сколько клиентов ограничитель помнит одновременно; лишние вытесняются.
+    pub rate_limit_max_clients: usize,
     pub http_addr: String,
     pub grpc_addr: String,
     pub cors_origins: Vec<String>,
@@ -16,20 +39,116 @@ pub struct Settings {
     pub grpc_request_timeout_secs: u64,
     pub grpc_max_decoding_message_size_bytes: usize,
     pub grpc_max_encoding_message_size_bytes: usize,
+    /// Период проверки отложенных публикаций.
+    pub publish_scheduler_interval_secs: u64,
+    /// Сколько постов публикуется за одну транзакцию.
+    pub publish_scheduler_batch_size: u32,
+    /// Сколько дней удалённый пост хранится в корзине до окончательного удаления.
+    pub trash_retention_days: u32,
+    /// Период фоновой очистки корзины.
+    pub trash_purge_interval_secs: u64,
+    /// Сколько постов удаляется из корзины за одну транзакцию.
+    pub trash_purge_batch_size: u32,
+    /// Публичный адрес сервера без завершающего `/`: абсолютные ссылки в RSS/Atom/JSON Feed.
+    pub public_base_url: String,
+    /// Название блога в заголовках лент.
+    pub feed_title: String,
 }
 
+/// По умолчанию вход и регистрация ограничены строже остального API.
+const DEFAULT_RATE_LIMIT_ROUTES: &str =
+    "/api/auth=30/60,/blog.BlogService/Login=30/60,/blog.BlogService/Register=30/60";
+
 impl Settings {
     pub fn from_env() -> Result<Self> {
         let database_url = get_required("DATABASE_URL").context("DATABASE_URL is required")?;
-        let jwt_secret = get_required("JWT_SECRET").context("JWT_SECRET is required")?;
+        let jwt_secret = std::env::var("JWT_SECRET")
+            .ok()
+            .map(|secret| secret.trim().to_string())
+            .filter(|secret| !secret.is_empty());
+        let jwt_keys = parse_key_sources(&std::env::var("JWT_KEYS").unwrap_or_default())
+            .map_err(|err| anyhow!("Failed to parse JWT_KEYS: {err}"))?;
+        let jwt_active_kid = std::env::var("JWT_ACTIVE_KID")
+            .ok()
+            .map(|kid| kid.trim().to_string())
+            .filter(|kid| !kid.is_empty());
         let jwt_ttl_seconds: i64 = std::env::var("JWT_TTL_SECONDS")
             .unwrap_or_else(|_| "3600".to_string())
             .parse()
             .context("Failed to parse JWT_TTL_SECONDS, expecting integer")?;
+        let refresh_token_ttl_seconds: i64 = std::env::var("REFRESH_TOKEN_TTL_SECONDS")
+            .unwrap_or_else(|_| (30 * 24 * 60 * 60).to_string())
+            .parse()
+            .context("Failed to parse REFRESH_TOKEN_TTL_SECONDS, expecting integer")?;
+        if refresh_token_ttl_seconds <= 0 {
+            return Err(anyhow!("REFRESH_TOKEN_TTL_SECONDS must be > 0"));
+        }
+        let password_reset_ttl_seconds: i64 = std::env::var("PASSWORD_RESET_TTL_SECONDS")
+            .unwrap_or_else(|_| (60 * 60).to_string())
+            .parse()
+            .context("Failed to parse PASSWORD_RESET_TTL_SECONDS, expecting integer")?;
+        if password_reset_ttl_seconds <= 0 {
+            return Err(anyhow!("PASSWORD_RESET_TTL_SECONDS must be > 0"));
+        }
+        let email_verification_ttl_seconds: i64 = std::env::var("EMAIL_VERIFICATION_TTL_SECONDS")
+            .unwrap_or_else(|_| (24 * 60 * 60).to_string())
+            .parse()
+            .context("Failed to parse EMAIL_VERIFICATION_TTL_SECONDS, expecting integer")?;
+        if email_verification_ttl_seconds <= 0 {
+            return Err(anyhow!("EMAIL_VERIFICATION_TTL_SECONDS must be > 0"));
+        }
+        let require_email_verification = parse_bool_env("REQUIRE_EMAIL_VERIFICATION", false)?;
+        let mfa_pending_ttl_seconds: i64 = std::env::var("MFA_PENDING_TTL_SECONDS")
+            .unwrap_or_else(|_| (5 * 60).to_string())
+            .parse()
+            .context("Failed to parse MFA_PENDING_TTL_SECONDS, expecting integer")?;
+        if mfa_pending_ttl_seconds <= 0 {
+            return Err(anyhow!("MFA_PENDING_TTL_SECONDS must be > 0"));
+        }
+        let totp_issuer = std::env::var("TOTP_ISSUER")
+            .ok()
+            .map(|issuer| issuer.trim().to_string())
+            .filter(|issuer| !issuer.is_empty())
+            .unwrap_or_else(|| "rust-blog".to_string());
+        let mail_outbox_path = std::env::var("MAIL_OUTBOX_PATH")
+            .ok()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty());
+
+        let login_free_attempts: u32 = std::env::var("LOGIN_FREE_ATTEMPTS")
+            .unwrap_or_else(|_| "3".to_string())
+            .parse()
+            .context("Failed to parse LOGIN_FREE_ATTEMPTS, expecting integer")?;
+        let login_max_attempts_per_username = parse_u32_env("LOGIN_MAX_ATTEMPTS_PER_USERNAME", 10)?;
+        let login_max_attempts_per_ip = parse_u32_env("LOGIN_MAX_ATTEMPTS_PER_IP", 50)?;
+        let login_backoff_base_secs = parse_u64_env("LOGIN_BACKOFF_BASE_SECS", 1)?;
+        let login_lockout_secs = parse_u64_env("LOGIN_LOCKOUT_SECS", 15 * 60)?;
 
-        if jwt_secret.chars().count() < 32 {
-            return Err(anyhow!("JWT_SECRET must be at least 32 characters"));
+        match &jwt_secret {
+            Some(secret) if secret.chars().count() < 32 => {
+                return Err(anyhow!("JWT_SECRET must be at least 32 characters"));
+            }
+            None if jwt_keys.is_empty() => {
+                return Err(anyhow!("JWT_SECRET is required when JWT_KEYS is not set"));
+            }
+            _ => {}
         }
+        if jwt_active_kid.is_some() && jwt_keys.is_empty() {
+            return Err(anyhow!("JWT_ACTIVE_KID requires JWT_KEYS"));
+        }
+
+        let token_revocation_cache_ttl_secs = parse_u64_env("TOKEN_REVOCATION_CACHE_TTL_SECS", 30)?;
+
+        let rate_limit_default: RateQuota = std::env::var("RATE_LIMIT_DEFAULT")
+            .unwrap_or_else(|_| "300/60".to_string())
+            .parse()
+            .map_err(|err| anyhow!("Failed to parse RATE_LIMIT_DEFAULT: {err}"))?;
+        let rate_limit_routes = parse_route_limits(
+            &std::env::var("RATE_LIMIT_ROUTES")
+                .unwrap_or_else(|_| DEFAULT_RATE_LIMIT_ROUTES.to_string()),
+        )
+        .map_err(|err| anyhow!("Failed to parse RATE_LIMIT_ROUTES: {err}"))?;
+        let rate_limit_max_clients = parse_usize_env("RATE_LIMIT_MAX_CLIENTS", 100_000)?;
 
         let http_addr = std::env::var("HTTP_ADDR").unwrap_or_else(|_| "0.0.0.0:8080".to_string());
         let grpc_addr = std::env::var("GRPC_ADDR").unwrap_or_else(|_| "0.0.0.0:50051".to_string());
@@ -50,11 +169,49 @@ impl Settings {
             parse_usize_env("GRPC_MAX_DECODING_MESSAGE_SIZE_BYTES", 4 * 1024 * 1024)?;
         let grpc_max_encoding_message_size_bytes =
             parse_usize_env("GRPC_MAX_ENCODING_MESSAGE_SIZE_BYTES", 4 * 1024 * 1024)?;
+        let publish_scheduler_interval_secs = parse_u64_env("PUBLISH_SCHEDULER_INTERVAL_SECS", 15)?;
+        let publish_scheduler_batch_size = parse_u32_env("PUBLISH_SCHEDULER_BATCH_SIZE", 100)?;
+        let trash_retention_days = parse_u32_env("TRASH_RETENTION_DAYS", 30)?;
+        let trash_purge_interval_secs = parse_u64_env("TRASH_PURGE_INTERVAL_SECS", 60 * 60)?;
+        let trash_purge_batch_size = parse_u32_env("TRASH_PURGE_BATCH_SIZE", 100)?;
+        let public_base_url = std::env::var("PUBLIC_BASE_URL")
+            .ok()
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| "http://localhost:8080".to_string());
+        if !public_base_url.starts_with("http://") && !public_base_url.starts_with("https://") {
+            return Err(anyhow!(
+                "PUBLIC_BASE_URL must start with http:// or https://"
+            ));
+        }
+        let feed_title = std::env::var("FEED_TITLE")
+            .ok()
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty())
+            .unwrap_or_else(|| "rust-blog".to_string());
 
         Ok(Self {
             database_url,
             jwt_secret,
+            jwt_keys,
+            jwt_active_kid,
             jwt_ttl_seconds,
+            refresh_token_ttl_seconds,
+            password_reset_ttl_seconds,
+            email_verification_ttl_seconds,
+            require_email_verification,
+            mfa_pending_ttl_seconds,
+            totp_issuer,
+            mail_outbox_path,
+            login_free_attempts,
+            login_max_attempts_per_username,
+            login_max_attempts_per_ip,
+            login_backoff_base_secs,
+            login_lockout_secs,
+            token_revocation_cache_ttl_secs,
+            rate_limit_default,
+            rate_limit_routes,
+            rate_limit_max_clients,
             http_addr,
             grpc_addr,
             cors_origins,
@@ -66,6 +223,13 @@ impl Settings {
             grpc_request_timeout_secs,
             grpc_max_decoding_message_size_bytes,
             grpc_max_encoding_message_size_bytes,
+            publish_scheduler_interval_secs,
+            publish_scheduler_batch_size,
+            trash_retention_days,
+            trash_purge_interval_secs,
+            trash_purge_batch_size,
+            public_base_url,
+            feed_title,
         })
     }
 }
@@ -99,6 +263,29 @@ fn parse_usize_env(key: &str, default: usize) -> Result<usize> {
     Ok(value)
 }
 
+fn parse_u32_env(key: &str, default: u32) -> Result<u32> {
+    let value = std::env::var(key)
+        .unwrap_or_else(|_| default.to_string())
+        .parse::<u32>()
+        .with_context(|| format!("Failed to parse {key}, expecting positive integer"))?;
+
+    if value == 0 {
+        return Err(anyhow!("{key} must be > 0"));
+    }
+    Ok(value)
+}
+
+fn parse_bool_env(key: &str, default: bool) -> Result<bool> {
+    let Ok(raw) = std::env::var(key) else {
+        return Ok(default);
+    };
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(anyhow!("Failed to parse {key}, expecting true or false")),
+    }
+}
+
 fn parse_u64_env(key: &str, default: u64) -> Result<u64> {
     let value = std::env::var(key)
         .unwrap_or_else(|_| default.to_string())
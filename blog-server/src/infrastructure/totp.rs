@@ -0,0 +1,221 @@
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha1::Sha1;
+
+/// Длина шага TOTP в секундах (RFC 6238, значение по умолчанию у приложений-аутентификаторов).
+pub(crate) const TOTP_PERIOD_SECS: i64 = 30;
+const TOTP_DIGITS: u32 = 6;
+/// 160 бит — рекомендуемая длина секрета для HMAC-SHA1 (RFC 4226).
+const SECRET_BYTES: usize = 20;
+/// Сколько соседних шагов принимается, чтобы пережить расхождение часов.
+const ALLOWED_SKEW_STEPS: i64 = 1;
+const RECOVERY_CODE_BYTES: usize = 5;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Генерирует случайный секрет TOTP в base32 (без `=`), как его ждут аутентификаторы.
+pub(crate) fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    base32_encode(&bytes)
+}
+
+/// Номер шага TOTP для unix-времени `unix_time`.
+pub(crate) fn step_at(unix_time: i64) -> i64 {
+    unix_time.div_euclid(TOTP_PERIOD_SECS)
+}
+
+/// Проверяет шестизначный код против секрета с допуском в один шаг в обе стороны.
+///
+/// Возвращает номер совпавшего шага: по нему вызывающий код отсекает повторное
+/// использование того же кода.
+pub(crate) fn verify_code(secret: &str, code: &str, unix_time: i64) -> Option<i64> {
+    let code = code.trim();
+    if !is_totp_code(code) {
+        return None;
+    }
+    let code: u32 = code.parse().ok()?;
+    let secret = base32_decode(secret)?;
+    let current = step_at(unix_time);
+
+    (current - ALLOWED_SKEW_STEPS..=current + ALLOWED_SKEW_STEPS)
+        .find(|&step| step >= 0 && hotp(&secret, step as u64) == code)
+}
+
+/// Код для момента `unix_time`, как его покажет аутентификатор.
+#[cfg(test)]
+pub(crate) fn code_at(secret: &str, unix_time: i64) -> Option<String> {
+    let secret = base32_decode(secret)?;
+    Some(format!("{:06}", hotp(&secret, step_at(unix_time) as u64)))
+}
+
+/// Похож ли ввод на код из аутентификатора (а не на код восстановления).
+pub(crate) fn is_totp_code(code: &str) -> bool {
+    code.len() == TOTP_DIGITS as usize && code.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// URI для QR-кода: `otpauth://totp/<issuer>:<account>?secret=...`.
+pub(crate) fn otpauth_uri(issuer: &str, account: &str, secret: &str) -> String {
+    let issuer = percent_encode(issuer);
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}\
+         &algorithm=SHA1&digits={TOTP_DIGITS}&period={TOTP_PERIOD_SECS}",
+        account = percent_encode(account),
+    )
+}
+
+/// Одноразовый код восстановления вида `abcd-efgh`.
+pub(crate) fn generate_recovery_code() -> String {
+    let mut bytes = [0u8; RECOVERY_CODE_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    let code = base32_encode(&bytes).to_ascii_lowercase();
+    let (head, tail) = code.split_at(code.len() / 2);
+    format!("{head}-{tail}")
+}
+
+/// Приводит введённый код восстановления к виду, от которого считается хэш:
+/// регистр, дефисы и пробелы не важны.
+pub(crate) fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// HOTP (RFC 4226) с динамическим усечением до `TOTP_DIGITS` цифр.
+fn hotp(secret: &[u8], counter: u64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("hmac accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = usize::from(digest[digest.len() - 1] & 0x0f);
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    binary % 10u32.pow(TOTP_DIGITS)
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+/// Декодирует base32 без учёта регистра, пробелов и `=`; `None` для чужих символов.
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in encoded.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&letter| letter as char == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+fn percent_encode(raw: &str) -> String {
+    raw.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        base32_decode, base32_encode, code_at, generate_recovery_code, generate_secret, hotp,
+        normalize_recovery_code, otpauth_uri, step_at, verify_code,
+    };
+
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn codes_match_rfc_6238_sha1_vectors() {
+        // в RFC коды восьмизначные, шестизначные — их последние шесть цифр
+        let vectors = [
+            (59, 287_082),
+            (1_111_111_109, 81_804),
+            (1_111_111_111, 50_471),
+            (1_234_567_890, 5_924),
+            (2_000_000_000, 279_037),
+        ];
+        for (time, expected) in vectors {
+            assert_eq!(hotp(RFC_SECRET, step_at(time) as u64), expected, "t={time}");
+        }
+    }
+
+    #[test]
+    fn verify_accepts_adjacent_steps_only() {
+        let secret = base32_encode(RFC_SECRET);
+        let code = code_at(&secret, 1_111_111_109).expect("valid secret");
+        assert_eq!(code, "081804");
+
+        assert_eq!(
+            verify_code(&secret, &code, 1_111_111_109),
+            Some(step_at(1_111_111_109))
+        );
+        assert!(verify_code(&secret, &code, 1_111_111_109 + 30).is_some());
+        assert!(verify_code(&secret, &code, 1_111_111_109 - 30).is_some());
+        assert!(verify_code(&secret, &code, 1_111_111_109 + 90).is_none());
+        assert!(verify_code(&secret, "12345", 1_111_111_109).is_none());
+        assert!(verify_code(&secret, "abcdef", 1_111_111_109).is_none());
+    }
+
+    #[test]
+    fn base32_round_trips_and_matches_rfc_4648() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(base32_decode("mzxw6ytboi======"), Some(b"foobar".to_vec()));
+        assert_eq!(base32_decode("MZ1"), None);
+
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 32);
+        assert_eq!(base32_decode(&secret).map(|bytes| bytes.len()), Some(20));
+    }
+
+    #[test]
+    fn otpauth_uri_escapes_labels() {
+        let uri = otpauth_uri("rust blog", "alice@example.com", "ABC");
+        assert_eq!(
+            uri,
+            "otpauth://totp/rust%20blog:alice%40example.com?secret=ABC&issuer=rust%20blog\
+             &algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn recovery_codes_are_normalized_for_hashing() {
+        let code = generate_recovery_code();
+        assert_eq!(code.len(), 9);
+        assert_eq!(code.chars().nth(4), Some('-'));
+        assert_eq!(
+            normalize_recovery_code(&format!(" {} ", code.to_uppercase())),
+            code.replace('-', "")
+        );
+    }
+}
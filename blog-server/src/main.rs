@@ -1,5 +1,6 @@
 use anyhow::Result;
 use std::sync::Arc;
+use std::time::Duration;
 
 mod application;
 mod data;
@@ -8,16 +9,35 @@ mod infrastructure;
 mod presentation;
 mod server;
 
-use application::auth_service::AuthService;
+use application::auth_service::{AuthService, AuthTokenTtls};
 use application::blog_service::BlogService;
+use application::comment_service::CommentService;
+use application::login_throttle::LoginThrottleSettings;
+use application::personal_token_service::PersonalTokenService;
+use application::profile_service::ProfileService;
+use application::session_service::SessionService;
+use application::token_revocation_service::TokenRevocationService;
+use data::repositories::postgres::comment_repository::PostgresCommentRepository;
+use data::repositories::postgres::email_verification_repository::PostgresEmailVerificationRepository;
+use data::repositories::postgres::mfa_repository::PostgresMfaRepository;
+use data::repositories::postgres::password_reset_repository::PostgresPasswordResetRepository;
+use data::repositories::postgres::personal_token_repository::PostgresPersonalTokenRepository;
 use data::repositories::postgres::post_repository::PostgresPostRepository;
+use data::repositories::postgres::profile_repository::PostgresProfileRepository;
+use data::repositories::postgres::refresh_token_repository::PostgresRefreshTokenRepository;
+use data::repositories::postgres::session_repository::PostgresSessionRepository;
+use data::repositories::postgres::tag_repository::PostgresTagRepository;
+use data::repositories::postgres::token_revocation_repository::PostgresTokenRevocationRepository;
 use data::repositories::postgres::user_repository::PostgresUserRepository;
 use infrastructure::database::{create_pool, run_migrations};
+use infrastructure::feed::FeedConfig;
 use infrastructure::jwt::JwtService;
 use infrastructure::logging::init_logging;
+use infrastructure::mailer::LocalMailer;
+use infrastructure::rate_limiter::RateLimiter;
 use infrastructure::settings::Settings;
 use presentation::AppState;
-use server::{run_grpc, run_http};
+use server::{run_grpc, run_http, run_publish_scheduler, run_trash_purger};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -31,21 +51,90 @@ async fn main() -> Result<()> {
 
     let user_repo = PostgresUserRepository::new(pool.clone());
     let post_repo = PostgresPostRepository::new(pool.clone());
-    let jwt = Arc::new(JwtService::new(
-        &settings.jwt_secret,
-        settings.jwt_ttl_seconds,
+    let tag_repo = PostgresTagRepository::new(pool.clone());
+    let comment_repo = PostgresCommentRepository::new(pool.clone());
+    let refresh_token_repo = PostgresRefreshTokenRepository::new(pool.clone());
+    let password_reset_repo = PostgresPasswordResetRepository::new(pool.clone());
+    let email_verification_repo = PostgresEmailVerificationRepository::new(pool.clone());
+    let mfa_repo = PostgresMfaRepository::new(pool.clone());
+    let revocations = Arc::new(TokenRevocationService::new(
+        PostgresTokenRevocationRepository::new(pool.clone()),
+        Duration::from_secs(settings.token_revocation_cache_ttl_secs),
     ));
-    let auth_service = Arc::new(AuthService::new(
-        user_repo,
-        JwtService::new(&settings.jwt_secret, 24 * 60 * 60),
+    let sessions = Arc::new(SessionService::new(
+        PostgresSessionRepository::new(pool.clone()),
+        Duration::from_secs(settings.token_revocation_cache_ttl_secs),
     ));
-    let blog_service = Arc::new(BlogService::new(post_repo));
+    let jwt = JwtService::load(
+        &settings.jwt_keys,
+        settings.jwt_active_kid.as_deref(),
+        settings.jwt_secret.as_deref(),
+        settings.jwt_ttl_seconds,
+    )?;
+    let auth_service = Arc::new(
+        AuthService::new(
+            user_repo,
+            refresh_token_repo,
+            password_reset_repo,
+            email_verification_repo,
+            mfa_repo,
+            revocations.clone(),
+            sessions.clone(),
+            LocalMailer::new(settings.mail_outbox_path.clone().map(Into::into)),
+            jwt.clone(),
+            AuthTokenTtls {
+                refresh_token_seconds: settings.refresh_token_ttl_seconds,
+                password_reset_seconds: settings.password_reset_ttl_seconds,
+                email_verification_seconds: settings.email_verification_ttl_seconds,
+                mfa_pending_seconds: settings.mfa_pending_ttl_seconds,
+            },
+        )
+        .with_totp_issuer(settings.totp_issuer.clone())
+        .with_login_throttle(LoginThrottleSettings {
+            free_attempts: settings.login_free_attempts,
+            max_attempts_per_username: settings.login_max_attempts_per_username,
+            max_attempts_per_ip: settings.login_max_attempts_per_ip,
+            base_delay: Duration::from_secs(settings.login_backoff_base_secs),
+            lockout: Duration::from_secs(settings.login_lockout_secs),
+        }),
+    );
+    let comment_service = Arc::new(CommentService::new(comment_repo, post_repo.clone()));
+    let blog_service = Arc::new(
+        BlogService::new(post_repo, tag_repo)
+            .with_required_email_verification(settings.require_email_verification),
+    );
 
-    let state = AppState::new(auth_service, blog_service, jwt);
+    let state = AppState::new(
+        auth_service,
+        blog_service,
+        comment_service,
+        revocations,
+        sessions,
+        Arc::new(PersonalTokenService::new(
+            PostgresPersonalTokenRepository::new(pool.clone()),
+        )),
+        Arc::new(ProfileService::new(PostgresProfileRepository::new(
+            pool.clone(),
+        ))),
+        Arc::new(jwt),
+        Arc::new(
+            RateLimiter::new(
+                settings.rate_limit_default,
+                settings.rate_limit_routes.clone(),
+            )
+            .with_max_entries(settings.rate_limit_max_clients),
+        ),
+    )
+    .with_feed(FeedConfig {
+        title: settings.feed_title.clone(),
+        base_url: settings.public_base_url.clone(),
+    });
 
     tokio::try_join!(
         run_http(&settings, state.clone()),
-        run_grpc(&settings, state)
+        run_grpc(&settings, state.clone()),
+        run_publish_scheduler(&settings, state.clone()),
+        run_trash_purger(&settings, state)
     )?;
     Ok(())
 }
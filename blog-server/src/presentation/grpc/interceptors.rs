@@ -1,27 +1,148 @@
 use tonic::metadata::MetadataMap;
-use tonic::{Code, Status};
+use tonic::{Code, Request, Status};
 
-use crate::infrastructure::jwt::JwtService;
+use super::status::map_domain_error;
+use crate::application::personal_token_service::PersonalTokenAuth;
+use crate::application::policy::Actor;
+use crate::domain::personal_token::{PERSONAL_TOKEN_PREFIX, TokenScope, TokenScopes};
+use crate::domain::session::ClientInfo;
+use crate::domain::user::Role;
+use crate::infrastructure::jwt::Claims;
+use crate::presentation::AppState;
 
 #[derive(Debug, Clone)]
 pub(crate) struct GrpcAuthContext {
     pub(crate) user_id: i64,
     // pub(crate) username: String,
+    pub(crate) role: Role,
+    pub(crate) email_verified: bool,
+    /// Для токена доступа — `pat-<id>`.
+    pub(crate) jti: String,
+    pub(crate) token_expires_at: i64,
+    /// Права токена доступа; `None` — обычная сессия.
+    pub(crate) scopes: Option<TokenScopes>,
+    /// Сессия access-токена; `None` для токена доступа.
+    pub(crate) session_id: Option<i64>,
 }
 
-pub(crate) fn authenticate_request(
-    jwt: &JwtService,
+impl GrpcAuthContext {
+    pub(crate) fn actor(&self) -> Actor {
+        let actor = Actor::new(self.user_id, self.role).with_email_verified(self.email_verified);
+        match self.scopes {
+            Some(scopes) => actor.with_scopes(scopes),
+            None => actor,
+        }
+    }
+
+    fn from_claims(claims: Claims) -> Self {
+        Self {
+            user_id: claims.user_id,
+            // username: claims.username,
+            role: claims.role,
+            email_verified: claims.email_verified,
+            jti: claims.jti,
+            token_expires_at: claims.exp,
+            scopes: None,
+            session_id: claims.sid,
+        }
+    }
+
+    fn from_personal_token(auth: PersonalTokenAuth) -> Self {
+        Self {
+            user_id: auth.actor.user_id,
+            role: auth.actor.role,
+            email_verified: auth.actor.email_verified,
+            jti: format!("pat-{}", auth.token_id),
+            token_expires_at: auth
+                .expires_at
+                .map_or(i64::MAX, |expires_at| expires_at.timestamp()),
+            scopes: auth.actor.scopes,
+            session_id: None,
+        }
+    }
+}
+
+/// Принимает access-токены (JWT) и персональные токены доступа.
+pub(crate) async fn authenticate_request(
+    state: &AppState,
     metadata: &MetadataMap,
 ) -> Result<GrpcAuthContext, Status> {
     let token = parse_bearer_token(metadata)?;
-    let claims = jwt
+
+    if token.starts_with(PERSONAL_TOKEN_PREFIX) {
+        return state
+            .personal_tokens
+            .authenticate(token)
+            .await
+            .map_err(map_domain_error)?
+            .map(GrpcAuthContext::from_personal_token)
+            .ok_or_else(|| Status::new(Code::Unauthenticated, "invalid token"));
+    }
+
+    let claims = state
+        .jwt
         .verify_token(token)
         .map_err(|_| Status::new(Code::Unauthenticated, "invalid token"))?;
 
-    Ok(GrpcAuthContext {
-        user_id: claims.user_id,
-        // username: claims.username,
-    })
+    if !state
+        .revocations
+        .is_active(&claims)
+        .await
+        .map_err(map_domain_error)?
+    {
+        return Err(Status::new(Code::Unauthenticated, "token revoked"));
+    }
+    if let Some(session_id) = claims.sid
+        && !state
+            .sessions
+            .is_active(session_id)
+            .await
+            .map_err(map_domain_error)?
+    {
+        return Err(Status::new(Code::Unauthenticated, "session revoked"));
+    }
+
+    Ok(GrpcAuthContext::from_claims(claims))
+}
+
+/// Для управления аккаунтом и токенами: только сессия, токен доступа получает `PERMISSION_DENIED`.
+pub(crate) async fn authenticate_session(
+    state: &AppState,
+    metadata: &MetadataMap,
+) -> Result<GrpcAuthContext, Status> {
+    let auth = authenticate_request(state, metadata).await?;
+    if auth.scopes.is_some() {
+        return Err(Status::new(
+            Code::PermissionDenied,
+            "personal access tokens cannot manage the account",
+        ));
+    }
+    Ok(auth)
+}
+
+/// Для публичных методов: валидный токен даёт контекст пользователя,
+/// отсутствующий, невалидный или отозванный — анонимный вызов.
+pub(crate) async fn authenticate_optional(
+    state: &AppState,
+    metadata: &MetadataMap,
+) -> Option<GrpcAuthContext> {
+    // черновики токену доступа видны только с правом на чтение постов
+    authenticate_request(state, metadata)
+        .await
+        .ok()
+        .filter(|auth| {
+            auth.scopes
+                .is_none_or(|scopes| scopes.contains(TokenScope::PostsRead))
+        })
+}
+
+/// Устройство, с которого пришёл вызов: попадает в список сессий.
+pub(crate) fn client_info<T>(request: &Request<T>) -> ClientInfo {
+    let user_agent = request
+        .metadata()
+        .get("user-agent")
+        .and_then(|value| value.to_str().ok());
+    ClientInfo::new(request.remote_addr().map(|addr| addr.ip()), user_agent)
 }
 
 fn parse_bearer_token(metadata: &MetadataMap) -> Result<&str, Status> {
@@ -1,20 +1,48 @@
 use chrono::{DateTime, Utc};
 use prost_types::Timestamp;
+use tonic::Status;
 
-use crate::application::auth_service::AuthResult;
-use crate::application::blog_service::ListPostsResult;
+use crate::application::auth_service::{
+    AuthResult, LoginOutcome, TotpEnrollment as DomainTotpEnrollment,
+};
+use crate::application::blog_service::{ListPostsResult, SearchPostsResult};
+use crate::application::personal_token_service::CreatedPersonalToken as DomainCreatedPersonalToken;
+use crate::domain::comment::{
+    Comment as DomainComment, CommentNode, CreateCommentRequest as DomainCreateCommentRequest,
+};
+use crate::domain::error::DomainError;
+use crate::domain::personal_token::{
+    CreatePersonalTokenRequest as DomainCreatePersonalTokenRequest,
+    PersonalToken as DomainPersonalToken, TokenScopes,
+};
 use crate::domain::post::{
-    CreatePostRequest as DomainCreatePostRequest, Post as DomainPost,
+    ContentFormat as DomainContentFormat, CreatePostRequest as DomainCreatePostRequest,
+    Post as DomainPost, PostStatus as DomainPostStatus,
     UpdatePostRequest as DomainUpdatePostRequest,
 };
+use crate::domain::profile::{
+    UpdateProfileRequest as DomainUpdateProfileRequest, UserProfile as DomainUserProfile,
+};
+use crate::domain::revision::{
+    DiffLine as DomainDiffLine, DiffOp as DomainDiffOp, PostRevision,
+    RevisionDiff as DomainRevisionDiff,
+};
+use crate::domain::session::Session as DomainSession;
+use crate::domain::tag::TagWithCount;
 use crate::domain::user::{
-    LoginRequest as DomainLoginRequest, RegisterRequest as DomainRegisterRequest,
-    User as DomainUser,
+    ChangePasswordRequest as DomainChangePasswordRequest, LoginRequest as DomainLoginRequest,
+    RegisterRequest as DomainRegisterRequest, ResetPasswordRequest as DomainResetPasswordRequest,
+    Role as DomainRole, User as DomainUser,
 };
 
 use super::proto::{
-    AuthResponse, CreatePostRequest, ListPostsResponse, LoginRequest, Post, RegisterRequest,
-    UpdatePostRequest, User,
+    AuthResponse, ChangePasswordRequest, Comment, ContentFormat, CreateCommentRequest,
+    CreatePersonalTokenRequest, CreatePostRequest, CreatedPersonalToken, DiffLine, DiffOp,
+    ListCommentsResponse, ListPersonalTokensResponse, ListPostsResponse, ListRevisionsResponse,
+    ListSessionsResponse, ListTagsResponse, LoginRequest, MfaChallenge, PersonalToken, Post,
+    PostAuthor, PostStatus, Profile, RegisterRequest, ResetPasswordRequest, Revision, RevisionDiff,
+    RevisionSummary, SearchHit, SearchPostsResponse, Session, Tag, TotpEnrollment,
+    UpdatePostRequest, UpdateProfileRequest, User, UserRole,
 };
 
 pub(crate) fn to_domain_register_request(input: RegisterRequest) -> DomainRegisterRequest {
@@ -32,24 +60,127 @@ pub(crate) fn to_domain_login_request(input: LoginRequest) -> DomainLoginRequest
     }
 }
 
-pub(crate) fn to_domain_create_post_request(input: CreatePostRequest) -> DomainCreatePostRequest {
-    DomainCreatePostRequest {
+pub(crate) fn to_domain_change_password_request(
+    input: ChangePasswordRequest,
+) -> DomainChangePasswordRequest {
+    DomainChangePasswordRequest {
+        current_password: input.current_password,
+        new_password: input.new_password,
+    }
+}
+
+pub(crate) fn to_domain_reset_password_request(
+    input: ResetPasswordRequest,
+) -> DomainResetPasswordRequest {
+    DomainResetPasswordRequest {
+        token: input.token,
+        new_password: input.new_password,
+    }
+}
+
+pub(crate) fn to_domain_create_post_request(
+    input: CreatePostRequest,
+) -> Result<DomainCreatePostRequest, Status> {
+    let publish_at = to_domain_timestamp(input.publish_at)?;
+    let status = match PostStatus::try_from(input.status) {
+        Ok(PostStatus::Unspecified) if publish_at.is_some() => DomainPostStatus::Draft,
+        Ok(PostStatus::Unspecified) | Ok(PostStatus::Published) => DomainPostStatus::Published,
+        Ok(PostStatus::Draft) => DomainPostStatus::Draft,
+        Ok(PostStatus::Archived) => DomainPostStatus::Archived,
+        Err(_) => return Err(Status::invalid_argument("unknown post status")),
+    };
+
+    Ok(DomainCreatePostRequest {
+        title: input.title,
+        content: input.content,
+        content_format: to_domain_content_format(input.content_format)?.unwrap_or_default(),
+        status,
+        tags: input.tags,
+        publish_at,
+    })
+}
+
+pub(crate) fn to_domain_update_post_request(
+    input: UpdatePostRequest,
+) -> Result<DomainUpdatePostRequest, Status> {
+    Ok(DomainUpdatePostRequest {
         title: input.title,
         content: input.content,
+        content_format: to_domain_content_format(input.content_format)?,
+        tags: input.tags.map(|list| list.tags),
+        expected_version: (input.expected_version != 0).then_some(input.expected_version),
+    })
+}
+
+/// `None` для `CONTENT_FORMAT_UNSPECIFIED`.
+fn to_domain_content_format(value: i32) -> Result<Option<DomainContentFormat>, Status> {
+    match ContentFormat::try_from(value) {
+        Ok(ContentFormat::Unspecified) => Ok(None),
+        Ok(ContentFormat::Plain) => Ok(Some(DomainContentFormat::Plain)),
+        Ok(ContentFormat::Markdown) => Ok(Some(DomainContentFormat::Markdown)),
+        Err(_) => Err(Status::invalid_argument("unknown content format")),
     }
 }
 
-pub(crate) fn to_domain_update_post_request(input: UpdatePostRequest) -> DomainUpdatePostRequest {
-    DomainUpdatePostRequest {
-        title: input.title,
+pub(crate) fn to_domain_create_comment_request(
+    input: CreateCommentRequest,
+) -> DomainCreateCommentRequest {
+    DomainCreateCommentRequest {
         content: input.content,
+        parent_id: (input.parent_id != 0).then_some(input.parent_id),
+    }
+}
+
+pub(crate) fn to_domain_create_personal_token_request(
+    input: CreatePersonalTokenRequest,
+) -> Result<DomainCreatePersonalTokenRequest, DomainError> {
+    Ok(DomainCreatePersonalTokenRequest {
+        name: input.name,
+        scopes: TokenScopes::parse(&input.scopes)?,
+        expires_in_days: (input.expires_in_days != 0).then_some(input.expires_in_days),
+    })
+}
+
+pub(crate) fn to_domain_update_profile_request(
+    input: UpdateProfileRequest,
+) -> DomainUpdateProfileRequest {
+    DomainUpdateProfileRequest {
+        display_name: input.display_name,
+        bio: input.bio,
+        website: input.website,
+        avatar_url: input.avatar_url,
     }
 }
 
 pub(crate) fn to_proto_auth_response(result: AuthResult) -> AuthResponse {
     AuthResponse {
         access_token: result.access_token,
+        refresh_token: result.refresh_token,
         user: Some(to_proto_user(result.user)),
+        mfa: None,
+    }
+}
+
+pub(crate) fn to_proto_login_response(outcome: LoginOutcome) -> AuthResponse {
+    match outcome {
+        LoginOutcome::Authenticated(result) => to_proto_auth_response(result),
+        LoginOutcome::MfaRequired {
+            mfa_token,
+            expires_in,
+        } => AuthResponse {
+            mfa: Some(MfaChallenge {
+                mfa_token,
+                expires_in,
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+pub(crate) fn to_proto_totp_enrollment(enrollment: DomainTotpEnrollment) -> TotpEnrollment {
+    TotpEnrollment {
+        secret: enrollment.secret,
+        otpauth_uri: enrollment.otpauth_uri,
     }
 }
 
@@ -59,17 +190,56 @@ pub(crate) fn to_proto_user(user: DomainUser) -> User {
         username: user.username,
         email: user.email,
         created_at: Some(to_proto_timestamp(user.created_at)),
+        role: to_proto_user_role(user.role).into(),
+        email_verified_at: user.email_verified_at.map(to_proto_timestamp),
+    }
+}
+
+fn to_proto_user_role(role: DomainRole) -> UserRole {
+    match role {
+        DomainRole::User => UserRole::User,
+        DomainRole::Moderator => UserRole::Moderator,
+        DomainRole::Admin => UserRole::Admin,
     }
 }
 
 pub(crate) fn to_proto_post(post: DomainPost) -> Post {
     Post {
         id: post.id,
+        slug: post.slug,
         title: post.title,
         content: post.content,
         author_id: post.author_id,
+        content_format: to_proto_content_format(post.content_format).into(),
+        content_html: post.content_html,
         created_at: Some(to_proto_timestamp(post.created_at)),
         updated_at: Some(to_proto_timestamp(post.updated_at)),
+        status: to_proto_post_status(post.status).into(),
+        published_at: post.published_at.map(to_proto_timestamp),
+        publish_at: post.publish_at.map(to_proto_timestamp),
+        deleted_at: post.deleted_at.map(to_proto_timestamp),
+        tags: post.tags,
+        version: post.version,
+        author: post.author.map(|author| PostAuthor {
+            id: author.id,
+            username: author.username,
+            display_name: author.display_name.unwrap_or_default(),
+        }),
+    }
+}
+
+fn to_proto_content_format(format: DomainContentFormat) -> ContentFormat {
+    match format {
+        DomainContentFormat::Plain => ContentFormat::Plain,
+        DomainContentFormat::Markdown => ContentFormat::Markdown,
+    }
+}
+
+fn to_proto_post_status(status: DomainPostStatus) -> PostStatus {
+    match status {
+        DomainPostStatus::Draft => PostStatus::Draft,
+        DomainPostStatus::Published => PostStatus::Published,
+        DomainPostStatus::Archived => PostStatus::Archived,
     }
 }
 
@@ -83,9 +253,200 @@ pub(crate) fn to_proto_list_posts_response(result: ListPostsResult) -> ListPosts
         limit: result.page_size,
         offset,
         total: result.total.max(0) as u64,
+        next_cursor: result.next_cursor.unwrap_or_default(),
     }
 }
 
+pub(crate) fn to_proto_search_posts_response(result: SearchPostsResult) -> SearchPostsResponse {
+    let offset = result
+        .page
+        .saturating_sub(1)
+        .saturating_mul(result.page_size);
+    SearchPostsResponse {
+        results: result
+            .hits
+            .into_iter()
+            .map(|hit| SearchHit {
+                post: Some(to_proto_post(hit.post)),
+                rank: hit.rank,
+                snippet: hit.snippet,
+            })
+            .collect(),
+        limit: result.page_size,
+        offset,
+        total: result.total.max(0) as u64,
+    }
+}
+
+pub(crate) fn to_proto_list_revisions_response(
+    revisions: Vec<PostRevision>,
+) -> ListRevisionsResponse {
+    ListRevisionsResponse {
+        revisions: revisions
+            .into_iter()
+            .map(|revision| RevisionSummary {
+                revision: revision.revision,
+                title: revision.title,
+                editor_id: revision.editor_id,
+                created_at: Some(to_proto_timestamp(revision.created_at)),
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn to_proto_revision(revision: PostRevision) -> Revision {
+    Revision {
+        post_id: revision.post_id,
+        revision: revision.revision,
+        title: revision.title,
+        content: revision.content,
+        editor_id: revision.editor_id,
+        created_at: Some(to_proto_timestamp(revision.created_at)),
+    }
+}
+
+pub(crate) fn to_proto_revision_diff(diff: DomainRevisionDiff) -> RevisionDiff {
+    RevisionDiff {
+        from_revision: diff.from_revision,
+        to_revision: diff.to_revision.unwrap_or_default(),
+        from_title: diff.from_title,
+        to_title: diff.to_title,
+        lines: diff.lines.into_iter().map(to_proto_diff_line).collect(),
+    }
+}
+
+fn to_proto_diff_line(line: DomainDiffLine) -> DiffLine {
+    let op = match line.op {
+        DomainDiffOp::Equal => DiffOp::Equal,
+        DomainDiffOp::Insert => DiffOp::Insert,
+        DomainDiffOp::Delete => DiffOp::Delete,
+    };
+    DiffLine {
+        op: op.into(),
+        text: line.text,
+    }
+}
+
+pub(crate) fn to_proto_list_tags_response(tags: Vec<TagWithCount>) -> ListTagsResponse {
+    ListTagsResponse {
+        tags: tags
+            .into_iter()
+            .map(|tag| Tag {
+                name: tag.name,
+                post_count: tag.post_count.max(0) as u64,
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn to_proto_comment(comment: DomainComment) -> Comment {
+    Comment {
+        id: comment.id,
+        post_id: comment.post_id,
+        author_id: comment.author_id,
+        parent_id: comment.parent_id.unwrap_or_default(),
+        content: comment.content,
+        created_at: Some(to_proto_timestamp(comment.created_at)),
+        updated_at: Some(to_proto_timestamp(comment.updated_at)),
+        replies: Vec::new(),
+    }
+}
+
+fn to_proto_comment_node(node: CommentNode) -> Comment {
+    Comment {
+        replies: node
+            .replies
+            .into_iter()
+            .map(to_proto_comment_node)
+            .collect(),
+        ..to_proto_comment(node.comment)
+    }
+}
+
+pub(crate) fn to_proto_list_comments_response(tree: Vec<CommentNode>) -> ListCommentsResponse {
+    let total = tree.iter().map(CommentNode::thread_size).sum::<usize>() as u64;
+    ListCommentsResponse {
+        comments: tree.into_iter().map(to_proto_comment_node).collect(),
+        total,
+    }
+}
+
+pub(crate) fn to_proto_personal_token(token: DomainPersonalToken) -> PersonalToken {
+    PersonalToken {
+        id: token.id,
+        name: token.name,
+        scopes: token
+            .scopes
+            .iter()
+            .map(|scope| scope.as_str().to_string())
+            .collect(),
+        expires_at: token.expires_at.map(to_proto_timestamp),
+        last_used_at: token.last_used_at.map(to_proto_timestamp),
+        created_at: Some(to_proto_timestamp(token.created_at)),
+    }
+}
+
+pub(crate) fn to_proto_created_personal_token(
+    created: DomainCreatedPersonalToken,
+) -> CreatedPersonalToken {
+    CreatedPersonalToken {
+        token: Some(to_proto_personal_token(created.token)),
+        secret: created.secret,
+    }
+}
+
+pub(crate) fn to_proto_list_personal_tokens_response(
+    tokens: Vec<DomainPersonalToken>,
+) -> ListPersonalTokensResponse {
+    ListPersonalTokensResponse {
+        tokens: tokens.into_iter().map(to_proto_personal_token).collect(),
+    }
+}
+
+pub(crate) fn to_proto_list_sessions_response(
+    sessions: Vec<DomainSession>,
+    current_session: Option<i64>,
+) -> ListSessionsResponse {
+    ListSessionsResponse {
+        sessions: sessions
+            .into_iter()
+            .map(|session| Session {
+                id: session.id,
+                user_agent: session.user_agent.unwrap_or_default(),
+                ip_address: session.ip_address.unwrap_or_default(),
+                created_at: Some(to_proto_timestamp(session.created_at)),
+                last_seen_at: Some(to_proto_timestamp(session.last_seen_at)),
+                current: current_session == Some(session.id),
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn to_proto_profile(user: DomainUserProfile) -> Profile {
+    Profile {
+        id: user.id,
+        username: user.username,
+        display_name: user.profile.display_name.unwrap_or_default(),
+        bio: user.profile.bio.unwrap_or_default(),
+        website: user.profile.website.unwrap_or_default(),
+        avatar_url: user.profile.avatar_url.unwrap_or_default(),
+        created_at: Some(to_proto_timestamp(user.created_at)),
+    }
+}
+
+pub(crate) fn to_domain_timestamp(
+    value: Option<Timestamp>,
+) -> Result<Option<DateTime<Utc>>, Status> {
+    value
+        .map(|value| {
+            u32::try_from(value.nanos)
+                .ok()
+                .and_then(|nanos| DateTime::from_timestamp(value.seconds, nanos))
+                .ok_or_else(|| Status::invalid_argument("invalid timestamp"))
+        })
+        .transpose()
+}
+
 fn to_proto_timestamp(value: DateTime<Utc>) -> Timestamp {
     Timestamp {
         seconds: value.timestamp(),
@@ -4,6 +4,18 @@ pub(crate) mod pb {
 
 pub(crate) use pb::blog_service_server::{BlogService, BlogServiceServer};
 pub(crate) use pb::{
-    AuthResponse, CreatePostRequest, DeletePostRequest, GetPostRequest, ListPostsRequest,
-    ListPostsResponse, LoginRequest, Post, RegisterRequest, UpdatePostRequest, User,
+    ArchivePostRequest, AuthResponse, ChangePasswordRequest, Comment, CompleteMfaLoginRequest,
+    ContentFormat, CreateCommentRequest, CreatePersonalTokenRequest, CreatePostRequest,
+    CreatedPersonalToken, DeleteCommentRequest, DeletePostRequest, DiffLine, DiffOp,
+    DiffRevisionsRequest, GetPostBySlugRequest, GetPostRequest, GetProfileRequest,
+    GetRevisionRequest, ListCommentsRequest, ListCommentsResponse, ListPersonalTokensResponse,
+    ListPostsRequest, ListPostsResponse, ListRevisionsRequest, ListRevisionsResponse,
+    ListSessionsResponse, ListTagsResponse, ListTrashRequest, LoginRequest, LogoutRequest,
+    MfaChallenge, PersonalToken, Post, PostAuthor, PostStatus, Profile, PublishPostRequest,
+    PurgePostRequest, RecoveryCodes, RefreshRequest, RegisterRequest, RequestPasswordResetRequest,
+    ResetPasswordRequest, RestorePostRequest, RestoreRevisionRequest, Revision, RevisionDiff,
+    RevisionSummary, RevokePersonalTokenRequest, RevokeSessionRequest, SchedulePostRequest,
+    SearchHit, SearchPostsRequest, SearchPostsResponse, Session, Tag, TotpCodeRequest,
+    TotpEnrollment, UnpublishPostRequest, UpdatePostRequest, UpdateProfileRequest, User, UserRole,
+    VerifyEmailRequest,
 };
@@ -3,17 +3,35 @@ use tonic::{Request, Response, Status};
 use crate::presentation::{
     AppState,
     grpc::mappers::{
-        to_domain_create_post_request, to_domain_login_request, to_domain_register_request,
-        to_domain_update_post_request, to_proto_auth_response, to_proto_list_posts_response,
-        to_proto_post,
+        to_domain_change_password_request, to_domain_create_comment_request,
+        to_domain_create_personal_token_request, to_domain_create_post_request,
+        to_domain_login_request, to_domain_register_request, to_domain_reset_password_request,
+        to_domain_timestamp, to_domain_update_post_request, to_domain_update_profile_request,
+        to_proto_auth_response, to_proto_comment, to_proto_created_personal_token,
+        to_proto_list_comments_response, to_proto_list_personal_tokens_response,
+        to_proto_list_posts_response, to_proto_list_revisions_response,
+        to_proto_list_sessions_response, to_proto_list_tags_response, to_proto_login_response,
+        to_proto_post, to_proto_profile, to_proto_revision, to_proto_revision_diff,
+        to_proto_search_posts_response, to_proto_totp_enrollment, to_proto_user,
     },
 };
 
-use super::interceptors::authenticate_request;
+use super::interceptors::{
+    authenticate_optional, authenticate_request, authenticate_session, client_info,
+};
 use super::proto::{
-    AuthResponse, BlogService, BlogServiceServer, CreatePostRequest, DeletePostRequest,
-    GetPostRequest, ListPostsRequest, ListPostsResponse, LoginRequest, Post, RegisterRequest,
-    UpdatePostRequest,
+    ArchivePostRequest, AuthResponse, BlogService, BlogServiceServer, ChangePasswordRequest,
+    Comment, CompleteMfaLoginRequest, CreateCommentRequest, CreatePersonalTokenRequest,
+    CreatePostRequest, CreatedPersonalToken, DeleteCommentRequest, DeletePostRequest,
+    DiffRevisionsRequest, GetPostBySlugRequest, GetPostRequest, GetProfileRequest,
+    GetRevisionRequest, ListCommentsRequest, ListCommentsResponse, ListPersonalTokensResponse,
+    ListPostsRequest, ListPostsResponse, ListRevisionsRequest, ListRevisionsResponse,
+    ListSessionsResponse, ListTagsResponse, ListTrashRequest, LoginRequest, LogoutRequest, Post,
+    Profile, PublishPostRequest, PurgePostRequest, RecoveryCodes, RefreshRequest, RegisterRequest,
+    RequestPasswordResetRequest, ResetPasswordRequest, RestorePostRequest, RestoreRevisionRequest,
+    Revision, RevisionDiff, RevokePersonalTokenRequest, RevokeSessionRequest, SchedulePostRequest,
+    SearchPostsRequest, SearchPostsResponse, TotpCodeRequest, TotpEnrollment, UnpublishPostRequest,
+    UpdatePostRequest, UpdateProfileRequest, User, VerifyEmailRequest,
 };
 use super::status::map_domain_error;
 
@@ -42,12 +60,13 @@ impl BlogService for GrpcBlogService {
         &self,
         request: Request<RegisterRequest>,
     ) -> Result<Response<AuthResponse>, Status> {
+        let client = client_info(&request);
         let req = to_domain_register_request(request.into_inner());
 
         let result = self
             .state
             .auth_service
-            .register(req)
+            .register(req, client)
             .await
             .map_err(map_domain_error)?;
         let response = to_proto_auth_response(result);
@@ -58,12 +77,31 @@ impl BlogService for GrpcBlogService {
         &self,
         request: Request<LoginRequest>,
     ) -> Result<Response<AuthResponse>, Status> {
+        let client = client_info(&request);
         let req = to_domain_login_request(request.into_inner());
 
+        let outcome = self
+            .state
+            .auth_service
+            .login(req, client)
+            .await
+            .map_err(map_domain_error)?;
+
+        let response = to_proto_login_response(outcome);
+        Ok(Response::new(response))
+    }
+
+    async fn complete_mfa_login(
+        &self,
+        request: Request<CompleteMfaLoginRequest>,
+    ) -> Result<Response<AuthResponse>, Status> {
+        let client = client_info(&request);
+        let req = request.into_inner();
+
         let result = self
             .state
             .auth_service
-            .login(req)
+            .complete_mfa_login(&req.mfa_token, &req.code, client)
             .await
             .map_err(map_domain_error)?;
 
@@ -71,18 +109,304 @@ impl BlogService for GrpcBlogService {
         Ok(Response::new(response))
     }
 
+    async fn refresh(
+        &self,
+        request: Request<RefreshRequest>,
+    ) -> Result<Response<AuthResponse>, Status> {
+        let client = client_info(&request);
+        let result = self
+            .state
+            .auth_service
+            .refresh(&request.into_inner().refresh_token, client)
+            .await
+            .map_err(map_domain_error)?;
+
+        let response = to_proto_auth_response(result);
+        Ok(Response::new(response))
+    }
+
+    async fn logout(&self, request: Request<LogoutRequest>) -> Result<Response<()>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        let refresh_token = request.into_inner().refresh_token;
+        let refresh_token = Some(refresh_token.as_str()).filter(|token| !token.trim().is_empty());
+        self.state
+            .auth_service
+            .logout(
+                auth.user_id,
+                &auth.jti,
+                auth.token_expires_at,
+                auth.session_id,
+                refresh_token,
+            )
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn logout_all(&self, request: Request<()>) -> Result<Response<()>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        self.state
+            .auth_service
+            .logout_all(auth.user_id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn change_password(
+        &self,
+        request: Request<ChangePasswordRequest>,
+    ) -> Result<Response<()>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+        let req = to_domain_change_password_request(request.into_inner());
+
+        self.state
+            .auth_service
+            .change_password(auth.user_id, req)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn request_password_reset(
+        &self,
+        request: Request<RequestPasswordResetRequest>,
+    ) -> Result<Response<()>, Status> {
+        self.state
+            .auth_service
+            .request_password_reset(&request.into_inner().email)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn reset_password(
+        &self,
+        request: Request<ResetPasswordRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req = to_domain_reset_password_request(request.into_inner());
+
+        self.state
+            .auth_service
+            .reset_password(req)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn verify_email(
+        &self,
+        request: Request<VerifyEmailRequest>,
+    ) -> Result<Response<User>, Status> {
+        let user = self
+            .state
+            .auth_service
+            .verify_email(&request.into_inner().token)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_user(user)))
+    }
+
+    async fn resend_verification_email(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<()>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        self.state
+            .auth_service
+            .resend_verification_email(auth.user_id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn start_totp_enrollment(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<TotpEnrollment>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        let enrollment = self
+            .state
+            .auth_service
+            .start_totp_enrollment(auth.user_id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_totp_enrollment(enrollment)))
+    }
+
+    async fn confirm_totp_enrollment(
+        &self,
+        request: Request<TotpCodeRequest>,
+    ) -> Result<Response<RecoveryCodes>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        let recovery_codes = self
+            .state
+            .auth_service
+            .confirm_totp_enrollment(auth.user_id, &request.into_inner().code)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(RecoveryCodes { recovery_codes }))
+    }
+
+    async fn disable_totp(
+        &self,
+        request: Request<TotpCodeRequest>,
+    ) -> Result<Response<()>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        self.state
+            .auth_service
+            .disable_totp(auth.user_id, &request.into_inner().code)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn create_personal_token(
+        &self,
+        request: Request<CreatePersonalTokenRequest>,
+    ) -> Result<Response<CreatedPersonalToken>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        let req = to_domain_create_personal_token_request(request.into_inner())
+            .map_err(map_domain_error)?;
+        let created = self
+            .state
+            .personal_tokens
+            .create_token(auth.user_id, req)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_created_personal_token(created)))
+    }
+
+    async fn list_personal_tokens(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<ListPersonalTokensResponse>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        let tokens = self
+            .state
+            .personal_tokens
+            .list_tokens(auth.user_id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_list_personal_tokens_response(
+            tokens,
+        )))
+    }
+
+    async fn revoke_personal_token(
+        &self,
+        request: Request<RevokePersonalTokenRequest>,
+    ) -> Result<Response<()>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        self.state
+            .personal_tokens
+            .revoke_token(auth.user_id, request.into_inner().id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn list_sessions(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<ListSessionsResponse>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        let sessions = self
+            .state
+            .sessions
+            .list_sessions(auth.user_id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_list_sessions_response(
+            sessions,
+            auth.session_id,
+        )))
+    }
+
+    async fn revoke_session(
+        &self,
+        request: Request<RevokeSessionRequest>,
+    ) -> Result<Response<()>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        self.state
+            .sessions
+            .revoke_session(auth.user_id, request.into_inner().id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn get_profile(
+        &self,
+        request: Request<GetProfileRequest>,
+    ) -> Result<Response<Profile>, Status> {
+        let profile = self
+            .state
+            .profiles
+            .get_profile(&request.into_inner().username)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_profile(profile)))
+    }
+
+    async fn update_profile(
+        &self,
+        request: Request<UpdateProfileRequest>,
+    ) -> Result<Response<Profile>, Status> {
+        let auth = authenticate_session(self.state(), request.metadata()).await?;
+
+        let profile = self
+            .state
+            .profiles
+            .update_profile(
+                auth.user_id,
+                to_domain_update_profile_request(request.into_inner()),
+            )
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_profile(profile)))
+    }
+
     async fn create_post(
         &self,
         request: Request<CreatePostRequest>,
     ) -> Result<Response<Post>, Status> {
-        let auth = authenticate_request(self.state().jwt.as_ref(), request.metadata())?;
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
 
-        let req = to_domain_create_post_request(request.into_inner());
+        let req = to_domain_create_post_request(request.into_inner())?;
 
         let result = self
             .state
             .blog_service
-            .create_post(auth.user_id, req)
+            .create_post(auth.actor(), req)
             .await
             .map_err(map_domain_error)?;
 
@@ -91,10 +415,14 @@ impl BlogService for GrpcBlogService {
     }
 
     async fn get_post(&self, request: Request<GetPostRequest>) -> Result<Response<Post>, Status> {
+        let viewer_id = authenticate_optional(self.state(), request.metadata())
+            .await
+            .map(|auth| auth.user_id);
+
         let result = self
             .state
             .blog_service
-            .get_post(request.into_inner().id)
+            .get_post(viewer_id, request.into_inner().id)
             .await
             .map_err(map_domain_error)?;
 
@@ -102,19 +430,37 @@ impl BlogService for GrpcBlogService {
         Ok(Response::new(response))
     }
 
+    async fn get_post_by_slug(
+        &self,
+        request: Request<GetPostBySlugRequest>,
+    ) -> Result<Response<Post>, Status> {
+        let viewer_id = authenticate_optional(self.state(), request.metadata())
+            .await
+            .map(|auth| auth.user_id);
+
+        let result = self
+            .state
+            .blog_service
+            .get_post_by_slug(viewer_id, &request.into_inner().slug)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_post(result)))
+    }
+
     async fn update_post(
         &self,
         request: Request<UpdatePostRequest>,
     ) -> Result<Response<Post>, Status> {
-        let auth = authenticate_request(self.state().jwt.as_ref(), request.metadata())?;
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
 
         let input = request.into_inner();
         let post_id = input.id;
-        let req = to_domain_update_post_request(input);
+        let req = to_domain_update_post_request(input)?;
         let result = self
             .state
             .blog_service
-            .update_post(auth.user_id, post_id, req)
+            .update_post(auth.actor(), post_id, req)
             .await
             .map_err(map_domain_error)?;
 
@@ -126,11 +472,13 @@ impl BlogService for GrpcBlogService {
         &self,
         request: Request<DeletePostRequest>,
     ) -> Result<Response<()>, Status> {
-        let auth = authenticate_request(self.state().jwt.as_ref(), request.metadata())?;
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
 
+        let input = request.into_inner();
+        let expected_version = (input.expected_version != 0).then_some(input.expected_version);
         self.state
             .blog_service
-            .delete_post(auth.user_id, request.into_inner().id)
+            .delete_post(auth.actor(), input.id, expected_version)
             .await
             .map_err(map_domain_error)?;
 
@@ -144,6 +492,9 @@ impl BlogService for GrpcBlogService {
         const DEFAULT_LIMIT: u32 = 20;
         const MAX_LIMIT: u32 = 100;
 
+        let viewer_id = authenticate_optional(self.state(), request.metadata())
+            .await
+            .map(|auth| auth.user_id);
         let input = request.into_inner();
         let limit = if input.limit == 0 {
             DEFAULT_LIMIT
@@ -151,22 +502,325 @@ impl BlogService for GrpcBlogService {
             input.limit
         };
         let offset = input.offset;
+        let author = (!input.author.is_empty()).then_some(input.author.as_str());
 
         if limit > MAX_LIMIT {
             return Err(Status::invalid_argument(format!(
                 "limit must be in 1..={MAX_LIMIT}"
             )));
         }
-        let page = (offset / limit) + 1;
-        let page_size = limit;
+
+        let result = if input.cursor.is_empty() {
+            let page = (offset / limit) + 1;
+            let page_size = limit;
+            self.state
+                .blog_service
+                .list_posts(viewer_id, &input.tags, author, page, page_size)
+                .await
+        } else {
+            if offset != 0 {
+                return Err(Status::invalid_argument(
+                    "cursor and offset are mutually exclusive",
+                ));
+            }
+            self.state
+                .blog_service
+                .list_posts_by_cursor(viewer_id, &input.tags, author, Some(&input.cursor), limit)
+                .await
+        }
+        .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_list_posts_response(result)))
+    }
+
+    async fn list_trash(
+        &self,
+        request: Request<ListTrashRequest>,
+    ) -> Result<Response<ListPostsResponse>, Status> {
+        const DEFAULT_LIMIT: u32 = 20;
+        const MAX_LIMIT: u32 = 100;
+
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+        let input = request.into_inner();
+        let limit = if input.limit == 0 {
+            DEFAULT_LIMIT
+        } else {
+            input.limit
+        };
+        if limit > MAX_LIMIT {
+            return Err(Status::invalid_argument(format!(
+                "limit must be in 1..={MAX_LIMIT}"
+            )));
+        }
 
         let result = self
             .state
             .blog_service
-            .list_posts(page, page_size)
+            .list_trash(auth.actor(), input.offset / limit + 1, limit)
             .await
             .map_err(map_domain_error)?;
 
         Ok(Response::new(to_proto_list_posts_response(result)))
     }
+
+    async fn restore_post(
+        &self,
+        request: Request<RestorePostRequest>,
+    ) -> Result<Response<Post>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let result = self
+            .state
+            .blog_service
+            .restore_post(auth.actor(), request.into_inner().id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_post(result)))
+    }
+
+    async fn purge_post(&self, request: Request<PurgePostRequest>) -> Result<Response<()>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        self.state
+            .blog_service
+            .purge_post(auth.actor(), request.into_inner().id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn search_posts(
+        &self,
+        request: Request<SearchPostsRequest>,
+    ) -> Result<Response<SearchPostsResponse>, Status> {
+        const DEFAULT_LIMIT: u32 = 20;
+        const MAX_LIMIT: u32 = 100;
+
+        let viewer_id = authenticate_optional(self.state(), request.metadata())
+            .await
+            .map(|auth| auth.user_id);
+        let input = request.into_inner();
+        let limit = if input.limit == 0 {
+            DEFAULT_LIMIT
+        } else {
+            input.limit
+        };
+
+        if limit > MAX_LIMIT {
+            return Err(Status::invalid_argument(format!(
+                "limit must be in 1..={MAX_LIMIT}"
+            )));
+        }
+        let page = (input.offset / limit) + 1;
+
+        let result = self
+            .state
+            .blog_service
+            .search_posts(viewer_id, &input.query, page, limit)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_search_posts_response(result)))
+    }
+
+    async fn publish_post(
+        &self,
+        request: Request<PublishPostRequest>,
+    ) -> Result<Response<Post>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let result = self
+            .state
+            .blog_service
+            .publish_post(auth.actor(), request.into_inner().id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_post(result)))
+    }
+
+    async fn unpublish_post(
+        &self,
+        request: Request<UnpublishPostRequest>,
+    ) -> Result<Response<Post>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let result = self
+            .state
+            .blog_service
+            .unpublish_post(auth.actor(), request.into_inner().id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_post(result)))
+    }
+
+    async fn archive_post(
+        &self,
+        request: Request<ArchivePostRequest>,
+    ) -> Result<Response<Post>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let result = self
+            .state
+            .blog_service
+            .archive_post(auth.actor(), request.into_inner().id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_post(result)))
+    }
+
+    async fn schedule_post(
+        &self,
+        request: Request<SchedulePostRequest>,
+    ) -> Result<Response<Post>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+        let input = request.into_inner();
+        let publish_at = to_domain_timestamp(input.publish_at)?;
+
+        let result = self
+            .state
+            .blog_service
+            .schedule_post(auth.actor(), input.id, publish_at)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_post(result)))
+    }
+
+    async fn list_revisions(
+        &self,
+        request: Request<ListRevisionsRequest>,
+    ) -> Result<Response<ListRevisionsResponse>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let input = request.into_inner();
+        let revisions = self
+            .state
+            .blog_service
+            .list_revisions(auth.actor(), input.post_id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_list_revisions_response(revisions)))
+    }
+
+    async fn get_revision(
+        &self,
+        request: Request<GetRevisionRequest>,
+    ) -> Result<Response<Revision>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let input = request.into_inner();
+        let revision = self
+            .state
+            .blog_service
+            .get_revision(auth.actor(), input.post_id, input.revision)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_revision(revision)))
+    }
+
+    async fn diff_revisions(
+        &self,
+        request: Request<DiffRevisionsRequest>,
+    ) -> Result<Response<RevisionDiff>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let input = request.into_inner();
+        let to = (input.to_revision != 0).then_some(input.to_revision);
+        let diff = self
+            .state
+            .blog_service
+            .diff_revisions(auth.actor(), input.post_id, input.from_revision, to)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_revision_diff(diff)))
+    }
+
+    async fn restore_revision(
+        &self,
+        request: Request<RestoreRevisionRequest>,
+    ) -> Result<Response<Post>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let input = request.into_inner();
+        let post = self
+            .state
+            .blog_service
+            .restore_revision(auth.actor(), input.post_id, input.revision)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_post(post)))
+    }
+
+    async fn list_tags(&self, _request: Request<()>) -> Result<Response<ListTagsResponse>, Status> {
+        let tags = self
+            .state
+            .blog_service
+            .list_tags()
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_list_tags_response(tags)))
+    }
+
+    async fn list_comments(
+        &self,
+        request: Request<ListCommentsRequest>,
+    ) -> Result<Response<ListCommentsResponse>, Status> {
+        let viewer_id = authenticate_optional(self.state(), request.metadata())
+            .await
+            .map(|auth| auth.user_id);
+
+        let tree = self
+            .state
+            .comment_service
+            .list_comments(viewer_id, request.into_inner().post_id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_list_comments_response(tree)))
+    }
+
+    async fn create_comment(
+        &self,
+        request: Request<CreateCommentRequest>,
+    ) -> Result<Response<Comment>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let input = request.into_inner();
+        let post_id = input.post_id;
+        let req = to_domain_create_comment_request(input);
+        let result = self
+            .state
+            .comment_service
+            .create_comment(auth.actor(), post_id, req)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(to_proto_comment(result)))
+    }
+
+    async fn delete_comment(
+        &self,
+        request: Request<DeleteCommentRequest>,
+    ) -> Result<Response<()>, Status> {
+        let auth = authenticate_request(self.state(), request.metadata()).await?;
+
+        let input = request.into_inner();
+        self.state
+            .comment_service
+            .delete_comment(auth.actor(), input.post_id, input.comment_id)
+            .await
+            .map_err(map_domain_error)?;
+
+        Ok(Response::new(()))
+    }
 }
@@ -9,6 +9,15 @@ pub(crate) fn map_domain_error(err: DomainError) -> Status {
         DomainError::InvalidCredentials => Status::unauthenticated(err.to_string()),
         DomainError::NotFound(_) => Status::not_found(err.to_string()),
         DomainError::Forbidden => Status::permission_denied(err.to_string()),
+        DomainError::EmailNotVerified => Status::permission_denied(err.to_string()),
+        DomainError::VersionMismatch { .. } => Status::failed_precondition(err.to_string()),
+        DomainError::TooManyAttempts { retry_after_secs } => {
+            let mut status = Status::resource_exhausted(err.to_string());
+            status
+                .metadata_mut()
+                .insert("retry-after", retry_after_secs.into());
+            status
+        }
         DomainError::Unexpected(_) => Status::internal("internal error"),
     }
 }
@@ -1,7 +1,7 @@
 use crate::domain::error::DomainError;
 use axum::{
     Json,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header::RETRY_AFTER},
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
@@ -37,6 +37,13 @@ struct ErrorBody {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let retry_after_secs = match &self {
+            AppError::Domain(DomainError::TooManyAttempts { retry_after_secs }) => {
+                Some(*retry_after_secs)
+            }
+            _ => None,
+        };
+
         let (status, msg) = match self {
             AppError::Domain(err) => {
                 let (status, msg) = match &err {
@@ -44,7 +51,15 @@ impl IntoResponse for AppError {
                     DomainError::AlreadyExists(_) => (StatusCode::CONFLICT, err.to_string()),
                     DomainError::InvalidCredentials => (StatusCode::UNAUTHORIZED, err.to_string()),
                     DomainError::NotFound(_) => (StatusCode::NOT_FOUND, err.to_string()),
-                    DomainError::Forbidden => (StatusCode::FORBIDDEN, err.to_string()),
+                    DomainError::Forbidden | DomainError::EmailNotVerified => {
+                        (StatusCode::FORBIDDEN, err.to_string())
+                    }
+                    DomainError::VersionMismatch { .. } => {
+                        (StatusCode::PRECONDITION_FAILED, err.to_string())
+                    }
+                    DomainError::TooManyAttempts { .. } => {
+                        (StatusCode::TOO_MANY_REQUESTS, err.to_string())
+                    }
                     DomainError::Unexpected(_) => (
                         StatusCode::INTERNAL_SERVER_ERROR,
                         "internal error".to_string(),
@@ -62,6 +77,12 @@ impl IntoResponse for AppError {
             ),
         };
 
-        (status, Json(ErrorBody { error: msg })).into_response()
+        let mut response = (status, Json(ErrorBody { error: msg })).into_response();
+        if let Some(secs) = retry_after_secs {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(secs));
+        }
+        response
     }
 }
@@ -1,12 +1,24 @@
-use axum::{Json, extract::State, http::StatusCode};
+use std::net::SocketAddr;
+
+use axum::{
+    Json,
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
-use crate::domain::user::{LoginRequest, RegisterRequest, User};
+use crate::application::auth_service::{AuthResult, LoginOutcome, TotpEnrollment};
+use crate::domain::session::ClientInfo;
+use crate::domain::user::{
+    ChangePasswordRequest, LoginRequest, RegisterRequest, ResetPasswordRequest, Role, User,
+};
 use crate::presentation::AppState;
 use crate::presentation::http::app_error::AppResult;
+use crate::presentation::http::middleware::auth::AuthenticatedUser;
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub(crate) struct RegisterDto {
@@ -26,26 +38,152 @@ pub(crate) struct LoginDto {
     pub(crate) password: String,
 }
 
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct RefreshDto {
+    #[validate(length(min = 1, max = 256))]
+    pub(crate) refresh_token: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct LogoutDto {
+    /// Если передан, отзывается и refresh-токен этого входа.
+    #[validate(length(min = 1, max = 256))]
+    pub(crate) refresh_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct ChangePasswordDto {
+    #[validate(length(min = 1, max = 128))]
+    pub(crate) current_password: String,
+    #[validate(length(min = 8, max = 128))]
+    pub(crate) new_password: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct PasswordResetRequestDto {
+    #[validate(email)]
+    pub(crate) email: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct PasswordResetConfirmDto {
+    /// Токен из письма.
+    #[validate(length(min = 1, max = 256))]
+    pub(crate) token: String,
+    #[validate(length(min = 8, max = 128))]
+    pub(crate) new_password: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct VerifyEmailDto {
+    /// Токен из письма.
+    #[validate(length(min = 1, max = 256))]
+    pub(crate) token: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct MfaLoginDto {
+    /// Промежуточный токен из ответа `202` на `/api/auth/login`.
+    #[validate(length(min = 1, max = 2048))]
+    pub(crate) mfa_token: String,
+    /// Код из аутентификатора или код восстановления.
+    #[validate(length(min = 1, max = 32))]
+    pub(crate) code: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct TotpCodeDto {
+    /// Код из аутентификатора; для отключения подходит и код восстановления.
+    #[validate(length(min = 1, max = 32))]
+    pub(crate) code: String,
+}
+
+/// Ответ на вход с включённым TOTP: сессии ещё нет, нужен второй шаг.
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct MfaChallengeDto {
+    pub(crate) mfa_required: bool,
+    pub(crate) mfa_token: String,
+    /// Через сколько секунд `mfa_token` перестанет приниматься.
+    pub(crate) expires_in: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct TotpEnrollmentDto {
+    /// Секрет в base32 для ручного ввода.
+    pub(crate) secret: String,
+    /// `otpauth://` URI для QR-кода.
+    pub(crate) otpauth_uri: String,
+}
+
+impl From<TotpEnrollment> for TotpEnrollmentDto {
+    fn from(enrollment: TotpEnrollment) -> Self {
+        Self {
+            secret: enrollment.secret,
+            otpauth_uri: enrollment.otpauth_uri,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct RecoveryCodesDto {
+    /// Одноразовые коды восстановления; повторно их не показать.
+    pub(crate) recovery_codes: Vec<String>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct AuthResponseDto {
     pub(crate) access_token: String,
+    pub(crate) refresh_token: String,
     pub(crate) user: UserDto,
 }
 
+impl From<AuthResult> for AuthResponseDto {
+    fn from(result: AuthResult) -> Self {
+        Self {
+            access_token: result.access_token,
+            refresh_token: result.refresh_token,
+            user: result.user.into(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct UserDto {
     pub(crate) id: i64,
     pub(crate) username: String,
     pub(crate) email: String,
+    pub(crate) role: RoleDto,
+    /// Момент подтверждения email; `null`, пока адрес не подтверждён.
+    pub(crate) email_verified_at: Option<DateTime<Utc>>,
     pub(crate) created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum RoleDto {
+    User,
+    Moderator,
+    Admin,
+}
+
+impl From<Role> for RoleDto {
+    fn from(role: Role) -> Self {
+        match role {
+            Role::User => Self::User,
+            Role::Moderator => Self::Moderator,
+            Role::Admin => Self::Admin,
+        }
+    }
+}
+
 impl From<User> for UserDto {
     fn from(user: User) -> Self {
         Self {
             id: user.id,
             username: user.username,
             email: user.email,
+            role: user.role.into(),
+            email_verified_at: user.email_verified_at,
             created_at: user.created_at,
         }
     }
@@ -65,6 +203,8 @@ impl From<User> for UserDto {
 )]
 pub(crate) async fn register(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(dto): Json<RegisterDto>,
 ) -> AppResult<(StatusCode, Json<AuthResponseDto>)> {
     dto.validate()?;
@@ -75,15 +215,12 @@ pub(crate) async fn register(
         password: dto.password,
     };
 
-    let result = state.auth_service.register(req).await?;
+    let result = state
+        .auth_service
+        .register(req, client_info(peer, &headers))
+        .await?;
 
-    Ok((
-        StatusCode::CREATED,
-        Json(AuthResponseDto {
-            access_token: result.access_token,
-            user: result.user.into(),
-        }),
-    ))
+    Ok((StatusCode::CREATED, Json(AuthResponseDto::from(result))))
 }
 
 #[utoipa::path(
@@ -93,15 +230,19 @@ pub(crate) async fn register(
     request_body = LoginDto,
     responses(
         (status = 200, description = "Login successful", body = AuthResponseDto),
+        (status = 202, description = "Password accepted, second factor required", body = MfaChallengeDto),
         (status = 400, description = "Validation error"),
         (status = 401, description = "Invalid credentials"),
+        (status = 429, description = "Too many failed attempts, see Retry-After"),
         (status = 500, description = "Internal error")
     )
 )]
 pub(crate) async fn login(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(dto): Json<LoginDto>,
-) -> AppResult<(StatusCode, Json<AuthResponseDto>)> {
+) -> AppResult<Response> {
     dto.validate()?;
 
     let req = LoginRequest {
@@ -109,13 +250,377 @@ pub(crate) async fn login(
         password: dto.password,
     };
 
-    let result = state.auth_service.login(req).await?;
+    let outcome = state
+        .auth_service
+        .login(req, client_info(peer, &headers))
+        .await?;
 
-    Ok((
-        StatusCode::OK,
-        Json(AuthResponseDto {
-            access_token: result.access_token,
-            user: result.user.into(),
-        }),
-    ))
+    Ok(match outcome {
+        LoginOutcome::Authenticated(result) => {
+            (StatusCode::OK, Json(AuthResponseDto::from(result))).into_response()
+        }
+        LoginOutcome::MfaRequired {
+            mfa_token,
+            expires_in,
+        } => (
+            StatusCode::ACCEPTED,
+            Json(MfaChallengeDto {
+                mfa_required: true,
+                mfa_token,
+                expires_in,
+            }),
+        )
+            .into_response(),
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/login/mfa",
+    tag = "auth",
+    request_body = MfaLoginDto,
+    responses(
+        (status = 200, description = "Second factor accepted", body = AuthResponseDto),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Invalid or expired mfa token, invalid code"),
+        (status = 429, description = "Too many failed attempts, see Retry-After"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn complete_mfa_login(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(dto): Json<MfaLoginDto>,
+) -> AppResult<(StatusCode, Json<AuthResponseDto>)> {
+    dto.validate()?;
+
+    let result = state
+        .auth_service
+        .complete_mfa_login(&dto.mfa_token, &dto.code, client_info(peer, &headers))
+        .await?;
+
+    Ok((StatusCode::OK, Json(AuthResponseDto::from(result))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    tag = "auth",
+    request_body = RefreshDto,
+    responses(
+        (status = 200, description = "Tokens rotated", body = AuthResponseDto),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Invalid, expired or reused refresh token"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn refresh(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(dto): Json<RefreshDto>,
+) -> AppResult<(StatusCode, Json<AuthResponseDto>)> {
+    dto.validate()?;
+
+    let result = state
+        .auth_service
+        .refresh(&dto.refresh_token, client_info(peer, &headers))
+        .await?;
+
+    Ok((StatusCode::OK, Json(AuthResponseDto::from(result))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "auth",
+    request_body(content = Option<LogoutDto>, description = "Optional refresh token to revoke"),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Current access token and its session revoked"),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn logout(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    dto: Option<Json<LogoutDto>>,
+) -> AppResult<StatusCode> {
+    let refresh_token = match dto {
+        Some(Json(dto)) => {
+            dto.validate()?;
+            dto.refresh_token
+        }
+        None => None,
+    };
+
+    state
+        .auth_service
+        .logout(
+            user.user_id,
+            &user.jti,
+            user.token_expires_at,
+            user.session_id,
+            refresh_token.as_deref(),
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout-all",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "All sessions of the user revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn logout_all(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> AppResult<StatusCode> {
+    state.auth_service.logout_all(user.user_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/password",
+    tag = "auth",
+    request_body = ChangePasswordDto,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Password changed, all sessions revoked"),
+        (status = 400, description = "Validation error or wrong current password"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn change_password(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(dto): Json<ChangePasswordDto>,
+) -> AppResult<StatusCode> {
+    dto.validate()?;
+
+    let req = ChangePasswordRequest {
+        current_password: dto.current_password,
+        new_password: dto.new_password,
+    };
+    state
+        .auth_service
+        .change_password(user.user_id, req)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/password-reset/request",
+    tag = "auth",
+    request_body = PasswordResetRequestDto,
+    responses(
+        (status = 202, description = "Reset token sent if the email is registered"),
+        (status = 400, description = "Validation error"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn request_password_reset(
+    State(state): State<AppState>,
+    Json(dto): Json<PasswordResetRequestDto>,
+) -> AppResult<StatusCode> {
+    dto.validate()?;
+
+    state
+        .auth_service
+        .request_password_reset(&dto.email)
+        .await?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/password-reset/confirm",
+    tag = "auth",
+    request_body = PasswordResetConfirmDto,
+    responses(
+        (status = 204, description = "Password reset, all sessions revoked"),
+        (status = 400, description = "Validation error, invalid or expired token"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn reset_password(
+    State(state): State<AppState>,
+    Json(dto): Json<PasswordResetConfirmDto>,
+) -> AppResult<StatusCode> {
+    dto.validate()?;
+
+    let req = ResetPasswordRequest {
+        token: dto.token,
+        new_password: dto.new_password,
+    };
+    state.auth_service.reset_password(req).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/verify-email",
+    tag = "auth",
+    request_body = VerifyEmailDto,
+    responses(
+        (status = 200, description = "Email verified", body = UserDto),
+        (status = 400, description = "Validation error, invalid or expired token"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn verify_email(
+    State(state): State<AppState>,
+    Json(dto): Json<VerifyEmailDto>,
+) -> AppResult<(StatusCode, Json<UserDto>)> {
+    dto.validate()?;
+
+    let user = state.auth_service.verify_email(&dto.token).await?;
+
+    Ok((StatusCode::OK, Json(UserDto::from(user))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/verify-email/resend",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 202, description = "Verification email sent"),
+        (status = 400, description = "Email is already verified"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn resend_verification_email(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> AppResult<StatusCode> {
+    state
+        .auth_service
+        .resend_verification_email(user.user_id)
+        .await?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/totp/setup",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "New TOTP secret, not active until confirmed", body = TotpEnrollmentDto),
+        (status = 400, description = "TOTP is already enabled"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn start_totp_enrollment(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> AppResult<(StatusCode, Json<TotpEnrollmentDto>)> {
+    let enrollment = state
+        .auth_service
+        .start_totp_enrollment(user.user_id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(TotpEnrollmentDto::from(enrollment))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/totp/confirm",
+    tag = "auth",
+    request_body = TotpCodeDto,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "TOTP enabled", body = RecoveryCodesDto),
+        (status = 400, description = "Validation error, invalid code or enrollment not started"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn confirm_totp_enrollment(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(dto): Json<TotpCodeDto>,
+) -> AppResult<(StatusCode, Json<RecoveryCodesDto>)> {
+    dto.validate()?;
+
+    let recovery_codes = state
+        .auth_service
+        .confirm_totp_enrollment(user.user_id, &dto.code)
+        .await?;
+
+    Ok((StatusCode::OK, Json(RecoveryCodesDto { recovery_codes })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/totp/disable",
+    tag = "auth",
+    request_body = TotpCodeDto,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "TOTP disabled, recovery codes removed"),
+        (status = 400, description = "Validation error, invalid code or TOTP not enabled"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn disable_totp(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(dto): Json<TotpCodeDto>,
+) -> AppResult<StatusCode> {
+    dto.validate()?;
+
+    state
+        .auth_service
+        .disable_totp(user.user_id, &dto.code)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Открытые ключи подписи access-токенов (RFC 7517) для сторонних сервисов.
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tag = "auth",
+    responses(
+        (status = 200, description = "JWK Set with public signing keys; empty when tokens are signed with a shared secret")
+    )
+)]
+pub(crate) async fn jwks(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        // ключи меняются только при перезапуске с новыми настройками
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(state.jwt.jwks()),
+    )
+}
+
+/// Устройство, с которого пришёл запрос: попадает в список сессий.
+fn client_info(peer: SocketAddr, headers: &HeaderMap) -> ClientInfo {
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok());
+    ClientInfo::new(Some(peer.ip()), user_agent)
 }
@@ -0,0 +1,187 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::domain::comment::{Comment, CommentNode, CreateCommentRequest};
+use crate::presentation::AppState;
+use crate::presentation::http::app_error::AppResult;
+use crate::presentation::http::middleware::auth::AuthenticatedUser;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct CreateCommentDto {
+    #[validate(length(min = 1, max = 10000))]
+    pub(crate) content: String,
+    /// Id комментария, на который отвечаем; без поля — комментарий верхнего уровня.
+    pub(crate) parent_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct CommentDto {
+    pub(crate) id: i64,
+    pub(crate) post_id: i64,
+    pub(crate) author_id: i64,
+    pub(crate) parent_id: Option<i64>,
+    pub(crate) content: String,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct CommentThreadDto {
+    pub(crate) id: i64,
+    pub(crate) post_id: i64,
+    pub(crate) author_id: i64,
+    pub(crate) parent_id: Option<i64>,
+    pub(crate) content: String,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) updated_at: DateTime<Utc>,
+    #[schema(no_recursion)]
+    pub(crate) replies: Vec<CommentThreadDto>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ListCommentsResponseDto {
+    /// Комментарии верхнего уровня с вложенными ответами.
+    pub(crate) comments: Vec<CommentThreadDto>,
+    /// Общее количество комментариев с учётом ответов.
+    pub(crate) total: u64,
+}
+
+impl From<Comment> for CommentDto {
+    fn from(comment: Comment) -> Self {
+        Self {
+            id: comment.id,
+            post_id: comment.post_id,
+            author_id: comment.author_id,
+            parent_id: comment.parent_id,
+            content: comment.content,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+        }
+    }
+}
+
+impl From<CommentNode> for CommentThreadDto {
+    fn from(node: CommentNode) -> Self {
+        let comment = node.comment;
+        Self {
+            id: comment.id,
+            post_id: comment.post_id,
+            author_id: comment.author_id,
+            parent_id: comment.parent_id,
+            content: comment.content,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+            replies: node.replies.into_iter().map(Self::from).collect(),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments",
+    tag = "comments",
+    security(
+        (),
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id")
+    ),
+    responses(
+        (status = 200, description = "Comments listed", body = ListCommentsResponseDto),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn list_comments(
+    State(state): State<AppState>,
+    viewer: Option<AuthenticatedUser>,
+    Path(id): Path<i64>,
+) -> AppResult<(StatusCode, Json<ListCommentsResponseDto>)> {
+    let viewer_id = viewer.map(|viewer| viewer.user_id);
+    let tree = state.comment_service.list_comments(viewer_id, id).await?;
+
+    let total = tree.iter().map(CommentNode::thread_size).sum::<usize>() as u64;
+    let comments = tree.into_iter().map(CommentThreadDto::from).collect();
+    Ok((
+        StatusCode::OK,
+        Json(ListCommentsResponseDto { comments, total }),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/comments",
+    tag = "comments",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id")
+    ),
+    request_body = CreateCommentDto,
+    responses(
+        (status = 201, description = "Comment created", body = CommentDto),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Personal access token lacks comments:write"),
+        (status = 404, description = "Post or parent comment not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn create_comment(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(id): Path<i64>,
+    Json(dto): Json<CreateCommentDto>,
+) -> AppResult<(StatusCode, Json<CommentDto>)> {
+    dto.validate()?;
+    let req = CreateCommentRequest {
+        content: dto.content,
+        parent_id: dto.parent_id,
+    };
+
+    let result = state
+        .comment_service
+        .create_comment(auth.actor(), id, req)
+        .await?;
+    Ok((StatusCode::CREATED, Json(CommentDto::from(result))))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}/comments/{comment_id}",
+    tag = "comments",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id"),
+        ("comment_id" = i64, Path, description = "Comment id")
+    ),
+    responses(
+        (status = 204, description = "Comment and its replies deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Comment not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn delete_comment(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path((id, comment_id)): Path<(i64, i64)>,
+) -> AppResult<StatusCode> {
+    state
+        .comment_service
+        .delete_comment(auth.actor(), id, comment_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,179 @@
+use axum::{
+    extract::{FromRequestParts, Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+
+use crate::domain::error::DomainError;
+use crate::domain::tag::normalize_tag;
+use crate::infrastructure::feed::{Feed, FeedFormat, http_date, is_not_modified};
+use crate::presentation::AppState;
+use crate::presentation::http::app_error::{AppError, AppResult};
+
+/// Сколько последних по дате публикации постов попадает в ленту.
+const FEED_SIZE: u32 = 20;
+
+/// Формат берётся из расширения последнего сегмента пути: `feed.rss`, `feed.atom`, `feed.json`.
+impl<S> FromRequestParts<S> for FeedFormat
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let path = parts.uri.path();
+        path.rsplit_once('.')
+            .and_then(|(_, extension)| FeedFormat::from_extension(extension))
+            .ok_or_else(|| DomainError::NotFound(format!("feed: {path}")).into())
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/feed.{format}",
+    tag = "feeds",
+    params(
+        ("format" = String, Path, description = "rss, atom or json"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous response"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified from a previous response")
+    ),
+    responses(
+        (status = 200, description = "Latest published posts as RSS 2.0, Atom 1.0 or JSON Feed 1.1",
+            headers(
+                ("ETag" = String, description = "Changes whenever any post in the feed changes"),
+                ("Last-Modified" = String, description = "Last change of any post in the feed's scope, including unpublished and deleted ones")
+            )),
+        (status = 304, description = "Feed not modified"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn site_feed(
+    State(state): State<AppState>,
+    format: FeedFormat,
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    let result = state.blog_service.feed_posts(&[], None, FEED_SIZE).await?;
+    let feed = Feed {
+        title: state.feed.title.clone(),
+        base_url: state.feed.base_url.clone(),
+        path: "/feed".to_string(),
+        posts: result.posts,
+        changed_at: result.changed_at,
+    };
+    Ok(feed_response(&feed, format, &headers))
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{username}/feed.{format}",
+    tag = "feeds",
+    params(
+        ("username" = String, Path, description = "Author username"),
+        ("format" = String, Path, description = "rss, atom or json"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous response"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified from a previous response")
+    ),
+    responses(
+        (status = 200, description = "Latest published posts of the author"),
+        (status = 304, description = "Feed not modified"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn author_feed(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+    format: FeedFormat,
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    let author = state.profiles.get_profile(&username).await?;
+    let result = state
+        .blog_service
+        .feed_posts(&[], Some(&author.username), FEED_SIZE)
+        .await?;
+    let name = author
+        .profile
+        .display_name
+        .as_deref()
+        .unwrap_or(&author.username);
+    let feed = Feed {
+        title: format!("{} — {name}", state.feed.title),
+        base_url: state.feed.base_url.clone(),
+        path: format!("/users/{}/feed", author.username),
+        posts: result.posts,
+        changed_at: result.changed_at,
+    };
+    Ok(feed_response(&feed, format, &headers))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tags/{tag}/feed.{format}",
+    tag = "feeds",
+    params(
+        ("tag" = String, Path, description = "Tag name"),
+        ("format" = String, Path, description = "rss, atom or json"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous response"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified from a previous response")
+    ),
+    responses(
+        (status = 200, description = "Latest published posts with the tag"),
+        (status = 304, description = "Feed not modified"),
+        (status = 400, description = "Invalid tag"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn tag_feed(
+    State(state): State<AppState>,
+    Path(tag): Path<String>,
+    format: FeedFormat,
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    let tag = normalize_tag(&tag)?;
+    let result = state
+        .blog_service
+        .feed_posts(std::slice::from_ref(&tag), None, FEED_SIZE)
+        .await?;
+    let feed = Feed {
+        title: format!("{} — #{tag}", state.feed.title),
+        base_url: state.feed.base_url.clone(),
+        path: format!("/tags/{tag}/feed"),
+        posts: result.posts,
+        changed_at: result.changed_at,
+    };
+    Ok(feed_response(&feed, format, &headers))
+}
+
+/// Отдаёт ленту или `304`, если копия клиента по `ETag`/`Last-Modified` ещё актуальна.
+fn feed_response(feed: &Feed, format: FeedFormat, request_headers: &HeaderMap) -> Response {
+    let etag = feed.etag(format);
+    let last_modified = feed.updated_at();
+
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        headers.insert(header::ETAG, value);
+    }
+    if let Some(value) = last_modified.and_then(|at| HeaderValue::from_str(&http_date(at)).ok()) {
+        headers.insert(header::LAST_MODIFIED, value);
+    }
+
+    let header_str = |name| {
+        request_headers
+            .get(name)
+            .and_then(|value: &HeaderValue| value.to_str().ok())
+    };
+    if is_not_modified(
+        header_str(header::IF_NONE_MATCH),
+        header_str(header::IF_MODIFIED_SINCE),
+        &etag,
+        last_modified,
+    ) {
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+    (StatusCode::OK, headers, feed.render(format)).into_response()
+}
@@ -1,2 +1,9 @@
 pub(crate) mod auth;
+pub(crate) mod comments;
+pub(crate) mod feeds;
 pub(crate) mod posts;
+pub(crate) mod revisions;
+pub(crate) mod sessions;
+pub(crate) mod tags;
+pub(crate) mod tokens;
+pub(crate) mod users;
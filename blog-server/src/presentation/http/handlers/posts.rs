@@ -1,25 +1,90 @@
 use axum::{
     Json,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, HeaderName, StatusCode, header},
+    response::{IntoResponse, Redirect, Response},
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
-use crate::domain::post::{Post, UpdatePostRequest};
+use crate::application::blog_service::SearchPostsResult;
+use crate::domain::error::DomainError;
+use crate::domain::post::{ContentFormat, Post, PostAuthor, PostStatus, UpdatePostRequest};
+use crate::domain::search::PostSearchHit;
 use crate::presentation::AppState;
 use crate::presentation::http::app_error::AppResult;
 use crate::presentation::http::middleware::auth::AuthenticatedUser;
 use crate::{application::blog_service::ListPostsResult, domain::post::CreatePostRequest};
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PostStatusDto {
+    Draft,
+    Published,
+    Archived,
+}
+
+impl From<PostStatus> for PostStatusDto {
+    fn from(status: PostStatus) -> Self {
+        match status {
+            PostStatus::Draft => Self::Draft,
+            PostStatus::Published => Self::Published,
+            PostStatus::Archived => Self::Archived,
+        }
+    }
+}
+
+impl From<PostStatusDto> for PostStatus {
+    fn from(status: PostStatusDto) -> Self {
+        match status {
+            PostStatusDto::Draft => Self::Draft,
+            PostStatusDto::Published => Self::Published,
+            PostStatusDto::Archived => Self::Archived,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ContentFormatDto {
+    Plain,
+    Markdown,
+}
+
+impl From<ContentFormat> for ContentFormatDto {
+    fn from(format: ContentFormat) -> Self {
+        match format {
+            ContentFormat::Plain => Self::Plain,
+            ContentFormat::Markdown => Self::Markdown,
+        }
+    }
+}
+
+impl From<ContentFormatDto> for ContentFormat {
+    fn from(format: ContentFormatDto) -> Self {
+        match format {
+            ContentFormatDto::Plain => Self::Plain,
+            ContentFormatDto::Markdown => Self::Markdown,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub(crate) struct CreatePostDto {
     #[validate(length(min = 1, max = 255))]
     pub(crate) title: String,
     #[validate(length(min = 1))]
     pub(crate) content: String,
+    /// `plain` (по умолчанию) или `markdown`.
+    pub(crate) content_format: Option<ContentFormatDto>,
+    /// `draft` или `published` (по умолчанию; при заданном `publish_at` — `draft`).
+    pub(crate) status: Option<PostStatusDto>,
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    /// Время отложенной публикации черновика, RFC 3339; должно быть в будущем.
+    pub(crate) publish_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
@@ -28,6 +93,16 @@ pub(crate) struct UpdatePostDto {
     pub(crate) title: String,
     #[validate(length(min = 1))]
     pub(crate) content: String,
+    /// Без поля формат не меняется.
+    pub(crate) content_format: Option<ContentFormatDto>,
+    /// Без поля теги не меняются, пустой список снимает все теги.
+    pub(crate) tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct SchedulePostDto {
+    /// Время публикации, RFC 3339; `null` отменяет отложенную публикацию.
+    pub(crate) publish_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize, Validate, ToSchema)]
@@ -35,22 +110,112 @@ pub(crate) struct PaginationQuery {
     #[validate(range(min = 1, max = 100))]
     pub(crate) limit: Option<u32>,
     pub(crate) offset: Option<u32>,
+    /// Теги через запятую: в выдачу попадают посты, у которых есть все перечисленные теги.
+    pub(crate) tags: Option<String>,
+    /// `next_cursor` предыдущей страницы; несовместим с `offset`.
+    pub(crate) cursor: Option<String>,
+    /// Логин автора: в выдачу попадают только его посты.
+    pub(crate) author: Option<String>,
+}
+
+impl PaginationQuery {
+    fn tag_filter(&self) -> Vec<String> {
+        self.tags
+            .as_deref()
+            .map(|tags| {
+                tags.split(',')
+                    .filter(|tag| !tag.trim().is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct SearchQuery {
+    /// Поисковый запрос в синтаксисе `websearch_to_tsquery`: слова, "фразы", `or`, `-исключение`.
+    #[serde(default)]
+    pub(crate) q: String,
+    #[validate(range(min = 1, max = 100))]
+    pub(crate) limit: Option<u32>,
+    pub(crate) offset: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct TrashQuery {
+    #[validate(range(min = 1, max = 100))]
+    pub(crate) limit: Option<u32>,
+    pub(crate) offset: Option<u32>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct PostDto {
     pub(crate) id: i64,
+    /// Человекочитаемый адрес: `GET /api/posts/by-slug/{slug}`.
+    pub(crate) slug: String,
     pub(crate) title: String,
+    /// Исходный текст в формате `content_format`.
     pub(crate) content: String,
+    pub(crate) content_format: ContentFormatDto,
+    /// Санитизированный HTML из `content`: без скриптов, стилей и обработчиков событий.
+    pub(crate) content_html: String,
     pub(crate) author_id: i64,
+    pub(crate) status: PostStatusDto,
+    pub(crate) published_at: Option<DateTime<Utc>>,
+    /// Запланированное время публикации черновика.
+    pub(crate) publish_at: Option<DateTime<Utc>>,
+    /// Когда пост перемещён в корзину; заполнено только в выдаче корзины.
+    pub(crate) deleted_at: Option<DateTime<Utc>>,
+    pub(crate) tags: Vec<String>,
     pub(crate) created_at: DateTime<Utc>,
     pub(crate) updated_at: DateTime<Utc>,
+    /// Совпадает с `ETag`; передаётся в `If-Match` при изменении поста.
+    pub(crate) version: i64,
+    pub(crate) author: Option<PostAuthorDto>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct PostAuthorDto {
+    pub(crate) id: i64,
+    pub(crate) username: String,
+    /// `null`, если автор не указал отображаемое имя.
+    pub(crate) display_name: Option<String>,
+}
+
+impl From<PostAuthor> for PostAuthorDto {
+    fn from(author: PostAuthor) -> Self {
+        Self {
+            id: author.id,
+            username: author.username,
+            display_name: author.display_name,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct ListPostsResponseDto {
     pub(crate) posts: Vec<PostDto>,
     pub(crate) limit: u32,
+    /// В режиме курсора всегда 0.
+    pub(crate) offset: u32,
+    pub(crate) total: i64,
+    /// Передаётся в `cursor` для следующей страницы; `null` на последней странице.
+    pub(crate) next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SearchHitDto {
+    pub(crate) post: PostDto,
+    pub(crate) rank: f32,
+    /// Фрагмент содержимого с HTML-экранированием; совпадения обёрнуты в `<mark>`.
+    pub(crate) snippet: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SearchPostsResponseDto {
+    pub(crate) results: Vec<SearchHitDto>,
+    pub(crate) limit: u32,
     pub(crate) offset: u32,
     pub(crate) total: i64,
 }
@@ -59,15 +224,53 @@ impl From<Post> for PostDto {
     fn from(post: Post) -> Self {
         Self {
             id: post.id,
+            slug: post.slug,
             title: post.title,
             content: post.content,
+            content_format: post.content_format.into(),
+            content_html: post.content_html,
             author_id: post.author_id,
+            status: post.status.into(),
+            published_at: post.published_at,
+            publish_at: post.publish_at,
+            deleted_at: post.deleted_at,
+            tags: post.tags,
             created_at: post.created_at,
             updated_at: post.updated_at,
+            version: post.version,
+            author: post.author.map(PostAuthorDto::from),
         }
     }
 }
 
+type EtagHeader = [(HeaderName, String); 1];
+
+/// Сильный ETag поста — номер версии в кавычках.
+fn post_etag(post: &Post) -> EtagHeader {
+    [(header::ETAG, format!("\"{}\"", post.version))]
+}
+
+/// Версия из `If-Match`; без заголовка или с `*` изменение не проверяется.
+fn if_match_version(headers: &HeaderMap) -> AppResult<Option<i64>> {
+    let invalid = || DomainError::Validation {
+        field: "If-Match",
+        message: "must be * or a strong ETag of the post",
+    };
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value.to_str().map_err(|_| invalid())?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    let version = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .and_then(|value| value.parse::<i64>().ok())
+        .ok_or_else(invalid)?;
+    Ok(Some(version))
+}
+
 impl From<ListPostsResult> for ListPostsResponseDto {
     fn from(result: ListPostsResult) -> Self {
         let offset = result
@@ -79,6 +282,7 @@ impl From<ListPostsResult> for ListPostsResponseDto {
             limit: result.page_size,
             offset,
             total: result.total,
+            next_cursor: result.next_cursor,
         }
     }
 }
@@ -87,9 +291,16 @@ impl From<ListPostsResult> for ListPostsResponseDto {
     get,
     path = "/api/posts",
     tag = "posts",
+    security(
+        (),
+        ("bearer_auth" = [])
+    ),
     params(
         ("limit" = Option<u32>, Query, description = "Items per page (1..=100)"),
-        ("offset" = Option<u32>, Query, description = "Offset from the beginning (>= 0)")
+        ("offset" = Option<u32>, Query, description = "Offset from the beginning (>= 0)"),
+        ("tags" = Option<String>, Query, description = "Comma-separated tags; posts must have all of them"),
+        ("cursor" = Option<String>, Query, description = "Opaque cursor from `next_cursor`; mutually exclusive with offset"),
+        ("author" = Option<String>, Query, description = "Only posts by the user with this username")
     ),
     responses(
         (status = 200, description = "Posts listed", body = ListPostsResponseDto),
@@ -99,39 +310,178 @@ impl From<ListPostsResult> for ListPostsResponseDto {
 )]
 pub(crate) async fn list_posts(
     State(state): State<AppState>,
+    viewer: Option<AuthenticatedUser>,
     Query(query): Query<PaginationQuery>,
 ) -> AppResult<(StatusCode, Json<ListPostsResponseDto>)> {
+    query.validate()?;
+    let limit = query.limit.unwrap_or(20);
+    let viewer_id = viewer.map(|viewer| viewer.user_id);
+    let tags = query.tag_filter();
+    let author = query.author.as_deref();
+
+    let result = if let Some(cursor) = query.cursor.as_deref() {
+        if query.offset.is_some() {
+            return Err(DomainError::Validation {
+                field: "cursor",
+                message: "cursor and offset are mutually exclusive",
+            }
+            .into());
+        }
+        state
+            .blog_service
+            .list_posts_by_cursor(viewer_id, &tags, author, Some(cursor), limit)
+            .await?
+    } else {
+        let offset = query.offset.unwrap_or(0);
+        let page = (offset / limit) + 1;
+        let page_size = limit;
+        state
+            .blog_service
+            .list_posts(viewer_id, &tags, author, page, page_size)
+            .await?
+    };
+
+    Ok((StatusCode::OK, Json(ListPostsResponseDto::from(result))))
+}
+
+impl From<PostSearchHit> for SearchHitDto {
+    fn from(hit: PostSearchHit) -> Self {
+        Self {
+            post: PostDto::from(hit.post),
+            rank: hit.rank,
+            snippet: hit.snippet,
+        }
+    }
+}
+
+impl From<SearchPostsResult> for SearchPostsResponseDto {
+    fn from(result: SearchPostsResult) -> Self {
+        let offset = result
+            .page
+            .saturating_sub(1)
+            .saturating_mul(result.page_size);
+        Self {
+            results: result.hits.into_iter().map(SearchHitDto::from).collect(),
+            limit: result.page_size,
+            offset,
+            total: result.total,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/search",
+    tag = "posts",
+    security(
+        (),
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("q" = String, Query, description = "Search query (1..=200 chars)"),
+        ("limit" = Option<u32>, Query, description = "Items per page (1..=100)"),
+        ("offset" = Option<u32>, Query, description = "Offset from the beginning (>= 0)")
+    ),
+    responses(
+        (status = 200, description = "Search results ordered by relevance", body = SearchPostsResponseDto),
+        (status = 400, description = "Validation error"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn search_posts(
+    State(state): State<AppState>,
+    viewer: Option<AuthenticatedUser>,
+    Query(query): Query<SearchQuery>,
+) -> AppResult<(StatusCode, Json<SearchPostsResponseDto>)> {
     query.validate()?;
     let limit = query.limit.unwrap_or(20);
     let offset = query.offset.unwrap_or(0);
     let page = (offset / limit) + 1;
-    let page_size = limit;
 
-    let result = state.blog_service.list_posts(page, page_size).await?;
+    let viewer_id = viewer.map(|viewer| viewer.user_id);
+    let result = state
+        .blog_service
+        .search_posts(viewer_id, &query.q, page, limit)
+        .await?;
 
-    Ok((StatusCode::OK, Json(ListPostsResponseDto::from(result))))
+    Ok((StatusCode::OK, Json(SearchPostsResponseDto::from(result))))
 }
 
 #[utoipa::path(
     get,
     path = "/api/posts/{id}",
     tag = "posts",
+    security(
+        (),
+        ("bearer_auth" = [])
+    ),
     params(
         ("id" = i64, Path, description = "Post id")
     ),
     responses(
-        (status = 200, description = "Post found", body = PostDto),
+        (status = 200, description = "Post found", body = PostDto,
+            headers(("ETag" = String, description = "Post version for If-Match"))),
         (status = 404, description = "Post not found"),
         (status = 500, description = "Internal error")
     )
 )]
 pub(crate) async fn get_post(
     State(state): State<AppState>,
+    viewer: Option<AuthenticatedUser>,
     Path(id): Path<i64>,
-) -> AppResult<(StatusCode, Json<PostDto>)> {
-    let result = state.blog_service.get_post(id).await?;
+) -> AppResult<(StatusCode, EtagHeader, Json<PostDto>)> {
+    let viewer_id = viewer.map(|viewer| viewer.user_id);
+    let result = state.blog_service.get_post(viewer_id, id).await?;
 
-    Ok((StatusCode::OK, Json(PostDto::from(result))))
+    Ok((
+        StatusCode::OK,
+        post_etag(&result),
+        Json(PostDto::from(result)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/by-slug/{slug}",
+    tag = "posts",
+    security(
+        (),
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("slug" = String, Path, description = "Current or former post slug")
+    ),
+    responses(
+        (status = 200, description = "Post found", body = PostDto,
+            headers(("ETag" = String, description = "Post version for If-Match"))),
+        (status = 308, description = "Slug is outdated, Location points to the current one"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn get_post_by_slug(
+    State(state): State<AppState>,
+    viewer: Option<AuthenticatedUser>,
+    Path(slug): Path<String>,
+) -> AppResult<Response> {
+    let viewer_id = viewer.map(|viewer| viewer.user_id);
+    let result = state
+        .blog_service
+        .get_post_by_slug(viewer_id, &slug)
+        .await?;
+
+    // прежний slug или slug не в канонической форме ведут на текущий адрес
+    if result.slug != slug {
+        let location = format!("/api/posts/by-slug/{}", result.slug);
+        return Ok(Redirect::permanent(&location).into_response());
+    }
+
+    Ok((
+        StatusCode::OK,
+        post_etag(&result),
+        Json(PostDto::from(result)),
+    )
+        .into_response())
 }
 
 #[utoipa::path(
@@ -146,6 +496,7 @@ pub(crate) async fn get_post(
         (status = 201, description = "Post created", body = PostDto),
         (status = 400, description = "Validation error"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Email is not verified"),
         (status = 500, description = "Internal error")
     )
 )]
@@ -158,9 +509,23 @@ pub(crate) async fn create_post(
     let req = CreatePostRequest {
         title: dto.title,
         content: dto.content,
+        content_format: dto
+            .content_format
+            .map(ContentFormat::from)
+            .unwrap_or_default(),
+        status: dto.status.map_or(
+            if dto.publish_at.is_some() {
+                PostStatus::Draft
+            } else {
+                PostStatus::Published
+            },
+            PostStatus::from,
+        ),
+        tags: dto.tags,
+        publish_at: dto.publish_at,
     };
 
-    let result = state.blog_service.create_post(auth.user_id, req).await?;
+    let result = state.blog_service.create_post(auth.actor(), req).await?;
     Ok((StatusCode::CREATED, Json(PostDto::from(result))))
 }
 
@@ -172,15 +537,18 @@ pub(crate) async fn create_post(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i64, Path, description = "Post id")
+        ("id" = i64, Path, description = "Post id"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the edited version")
     ),
     request_body = UpdatePostDto,
     responses(
-        (status = 200, description = "Post updated", body = PostDto),
+        (status = 200, description = "Post updated", body = PostDto,
+            headers(("ETag" = String, description = "New post version"))),
         (status = 400, description = "Validation error"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
         (status = 404, description = "Post not found"),
+        (status = 412, description = "Post was changed since the given ETag"),
         (status = 500, description = "Internal error")
     )
 )]
@@ -188,19 +556,27 @@ pub(crate) async fn update_post(
     State(state): State<AppState>,
     auth: AuthenticatedUser,
     Path(id): Path<i64>,
+    headers: HeaderMap,
     Json(dto): Json<UpdatePostDto>,
-) -> AppResult<(StatusCode, Json<PostDto>)> {
+) -> AppResult<(StatusCode, EtagHeader, Json<PostDto>)> {
     dto.validate()?;
     let req = UpdatePostRequest {
         title: dto.title,
         content: dto.content,
+        content_format: dto.content_format.map(ContentFormat::from),
+        tags: dto.tags,
+        expected_version: if_match_version(&headers)?,
     };
 
     let result = state
         .blog_service
-        .update_post(auth.user_id, id, req)
+        .update_post(auth.actor(), id, req)
         .await?;
-    Ok((StatusCode::OK, Json(PostDto::from(result))))
+    Ok((
+        StatusCode::OK,
+        post_etag(&result),
+        Json(PostDto::from(result)),
+    ))
 }
 
 #[utoipa::path(
@@ -211,13 +587,16 @@ pub(crate) async fn update_post(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i64, Path, description = "Post id")
+        ("id" = i64, Path, description = "Post id"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the deleted version")
     ),
     responses(
-        (status = 204, description = "Post deleted"),
+        (status = 204, description = "Post moved to trash"),
+        (status = 400, description = "Malformed If-Match"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
         (status = 404, description = "Post not found"),
+        (status = 412, description = "Post was changed since the given ETag"),
         (status = 500, description = "Internal error")
     )
 )]
@@ -225,7 +604,214 @@ pub(crate) async fn delete_post(
     State(state): State<AppState>,
     auth: AuthenticatedUser,
     Path(id): Path<i64>,
+    headers: HeaderMap,
+) -> AppResult<StatusCode> {
+    let expected_version = if_match_version(&headers)?;
+    state
+        .blog_service
+        .delete_post(auth.actor(), id, expected_version)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/publish",
+    tag = "posts",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id")
+    ),
+    responses(
+        (status = 200, description = "Post published", body = PostDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn publish_post(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(id): Path<i64>,
+) -> AppResult<(StatusCode, Json<PostDto>)> {
+    let result = state.blog_service.publish_post(auth.actor(), id).await?;
+    Ok((StatusCode::OK, Json(PostDto::from(result))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/unpublish",
+    tag = "posts",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id")
+    ),
+    responses(
+        (status = 200, description = "Post moved back to drafts", body = PostDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn unpublish_post(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(id): Path<i64>,
+) -> AppResult<(StatusCode, Json<PostDto>)> {
+    let result = state.blog_service.unpublish_post(auth.actor(), id).await?;
+    Ok((StatusCode::OK, Json(PostDto::from(result))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/archive",
+    tag = "posts",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id")
+    ),
+    responses(
+        (status = 200, description = "Post archived", body = PostDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn archive_post(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(id): Path<i64>,
+) -> AppResult<(StatusCode, Json<PostDto>)> {
+    let result = state.blog_service.archive_post(auth.actor(), id).await?;
+    Ok((StatusCode::OK, Json(PostDto::from(result))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/schedule",
+    tag = "posts",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id")
+    ),
+    request_body = SchedulePostDto,
+    responses(
+        (status = 200, description = "Publication scheduled or cancelled", body = PostDto),
+        (status = 400, description = "Post is not a draft or time is in the past"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn schedule_post(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(id): Path<i64>,
+    Json(dto): Json<SchedulePostDto>,
+) -> AppResult<(StatusCode, Json<PostDto>)> {
+    let result = state
+        .blog_service
+        .schedule_post(auth.actor(), id, dto.publish_at)
+        .await?;
+    Ok((StatusCode::OK, Json(PostDto::from(result))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/trash",
+    tag = "posts",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("limit" = Option<u32>, Query, description = "Items per page (1..=100)"),
+        ("offset" = Option<u32>, Query, description = "Offset from the beginning (>= 0)")
+    ),
+    responses(
+        (status = 200, description = "Own deleted posts, most recently deleted first", body = ListPostsResponseDto),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn list_trash(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Query(query): Query<TrashQuery>,
+) -> AppResult<(StatusCode, Json<ListPostsResponseDto>)> {
+    query.validate()?;
+    let limit = query.limit.unwrap_or(20);
+    let page = query.offset.unwrap_or(0) / limit + 1;
+
+    let result = state
+        .blog_service
+        .list_trash(auth.actor(), page, limit)
+        .await?;
+    Ok((StatusCode::OK, Json(ListPostsResponseDto::from(result))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/trash/{id}/restore",
+    tag = "posts",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id")
+    ),
+    responses(
+        (status = 200, description = "Post restored from trash", body = PostDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post is not in trash"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn restore_post(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(id): Path<i64>,
+) -> AppResult<(StatusCode, Json<PostDto>)> {
+    let result = state.blog_service.restore_post(auth.actor(), id).await?;
+    Ok((StatusCode::OK, Json(PostDto::from(result))))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/posts/trash/{id}",
+    tag = "posts",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id")
+    ),
+    responses(
+        (status = 204, description = "Post permanently deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post is not in trash"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn purge_post(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(id): Path<i64>,
 ) -> AppResult<StatusCode> {
-    state.blog_service.delete_post(auth.user_id, id).await?;
+    state.blog_service.purge_post(auth.actor(), id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
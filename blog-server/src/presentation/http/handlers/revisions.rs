@@ -0,0 +1,250 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::revision::{DiffLine, DiffOp, PostRevision, RevisionDiff};
+use crate::presentation::AppState;
+use crate::presentation::http::app_error::AppResult;
+use crate::presentation::http::handlers::posts::PostDto;
+use crate::presentation::http::middleware::auth::AuthenticatedUser;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct RevisionSummaryDto {
+    pub(crate) revision: i32,
+    pub(crate) title: String,
+    pub(crate) editor_id: i64,
+    /// Когда версия была заменена правкой.
+    pub(crate) created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ListRevisionsResponseDto {
+    /// От новых ревизий к старым.
+    pub(crate) revisions: Vec<RevisionSummaryDto>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct RevisionDto {
+    pub(crate) post_id: i64,
+    pub(crate) revision: i32,
+    pub(crate) title: String,
+    pub(crate) content: String,
+    pub(crate) editor_id: i64,
+    pub(crate) created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DiffOpDto {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct DiffLineDto {
+    pub(crate) op: DiffOpDto,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct RevisionDiffDto {
+    pub(crate) from_revision: i32,
+    /// `null` — сравнение с текущей версией поста.
+    pub(crate) to_revision: Option<i32>,
+    pub(crate) from_title: String,
+    pub(crate) to_title: String,
+    pub(crate) lines: Vec<DiffLineDto>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct RevisionDiffQuery {
+    pub(crate) from: i32,
+    /// Без параметра сравнивается с текущей версией.
+    pub(crate) to: Option<i32>,
+}
+
+impl From<PostRevision> for RevisionSummaryDto {
+    fn from(revision: PostRevision) -> Self {
+        Self {
+            revision: revision.revision,
+            title: revision.title,
+            editor_id: revision.editor_id,
+            created_at: revision.created_at,
+        }
+    }
+}
+
+impl From<PostRevision> for RevisionDto {
+    fn from(revision: PostRevision) -> Self {
+        Self {
+            post_id: revision.post_id,
+            revision: revision.revision,
+            title: revision.title,
+            content: revision.content,
+            editor_id: revision.editor_id,
+            created_at: revision.created_at,
+        }
+    }
+}
+
+impl From<DiffLine> for DiffLineDto {
+    fn from(line: DiffLine) -> Self {
+        let op = match line.op {
+            DiffOp::Equal => DiffOpDto::Equal,
+            DiffOp::Insert => DiffOpDto::Insert,
+            DiffOp::Delete => DiffOpDto::Delete,
+        };
+        Self {
+            op,
+            text: line.text,
+        }
+    }
+}
+
+impl From<RevisionDiff> for RevisionDiffDto {
+    fn from(diff: RevisionDiff) -> Self {
+        Self {
+            from_revision: diff.from_revision,
+            to_revision: diff.to_revision,
+            from_title: diff.from_title,
+            to_title: diff.to_title,
+            lines: diff.lines.into_iter().map(DiffLineDto::from).collect(),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/revisions",
+    tag = "revisions",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id")
+    ),
+    responses(
+        (status = 200, description = "Revisions listed", body = ListRevisionsResponseDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn list_revisions(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(id): Path<i64>,
+) -> AppResult<(StatusCode, Json<ListRevisionsResponseDto>)> {
+    let revisions = state.blog_service.list_revisions(auth.actor(), id).await?;
+
+    let revisions = revisions
+        .into_iter()
+        .map(RevisionSummaryDto::from)
+        .collect();
+    Ok((StatusCode::OK, Json(ListRevisionsResponseDto { revisions })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/revisions/{revision}",
+    tag = "revisions",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id"),
+        ("revision" = i32, Path, description = "Revision number")
+    ),
+    responses(
+        (status = 200, description = "Revision found", body = RevisionDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post or revision not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn get_revision(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path((id, revision)): Path<(i64, i32)>,
+) -> AppResult<(StatusCode, Json<RevisionDto>)> {
+    let revision = state
+        .blog_service
+        .get_revision(auth.actor(), id, revision)
+        .await?;
+
+    Ok((StatusCode::OK, Json(RevisionDto::from(revision))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/revisions/diff",
+    tag = "revisions",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id"),
+        ("from" = i32, Query, description = "Base revision"),
+        ("to" = Option<i32>, Query, description = "Target revision; current version when omitted")
+    ),
+    responses(
+        (status = 200, description = "Line diff of post content", body = RevisionDiffDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post or revision not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn diff_revisions(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path(id): Path<i64>,
+    Query(query): Query<RevisionDiffQuery>,
+) -> AppResult<(StatusCode, Json<RevisionDiffDto>)> {
+    let diff = state
+        .blog_service
+        .diff_revisions(auth.actor(), id, query.from, query.to)
+        .await?;
+
+    Ok((StatusCode::OK, Json(RevisionDiffDto::from(diff))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/revisions/{revision}/restore",
+    tag = "revisions",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Post id"),
+        ("revision" = i32, Path, description = "Revision number")
+    ),
+    responses(
+        (status = 200, description = "Revision restored as a new update", body = PostDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Post or revision not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn restore_revision(
+    State(state): State<AppState>,
+    auth: AuthenticatedUser,
+    Path((id, revision)): Path<(i64, i32)>,
+) -> AppResult<(StatusCode, Json<PostDto>)> {
+    let post = state
+        .blog_service
+        .restore_revision(auth.actor(), id, revision)
+        .await?;
+
+    Ok((StatusCode::OK, Json(PostDto::from(post))))
+}
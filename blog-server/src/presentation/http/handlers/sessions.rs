@@ -0,0 +1,92 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::session::Session;
+use crate::presentation::AppState;
+use crate::presentation::http::app_error::AppResult;
+use crate::presentation::http::middleware::auth::AuthenticatedUser;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SessionDto {
+    pub(crate) id: i64,
+    pub(crate) user_agent: Option<String>,
+    pub(crate) ip_address: Option<String>,
+    pub(crate) created_at: DateTime<Utc>,
+    pub(crate) last_seen_at: DateTime<Utc>,
+    /// Сессия, которой выдан токен этого запроса.
+    pub(crate) current: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ListSessionsResponseDto {
+    pub(crate) sessions: Vec<SessionDto>,
+}
+
+impl SessionDto {
+    fn new(session: Session, current_session: Option<i64>) -> Self {
+        Self {
+            id: session.id,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            current: current_session == Some(session.id),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/sessions",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Active sessions, most recently used first", body = ListSessionsResponseDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Personal access tokens cannot manage sessions"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn list_sessions(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> AppResult<Json<ListSessionsResponseDto>> {
+    let sessions = state.sessions.list_sessions(user.user_id).await?;
+    Ok(Json(ListSessionsResponseDto {
+        sessions: sessions
+            .into_iter()
+            .map(|session| SessionDto::new(session, user.session_id))
+            .collect(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/auth/sessions/{id}",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = i64, Path, description = "Session id")
+    ),
+    responses(
+        (status = 204, description = "Session revoked, its tokens are no longer accepted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Personal access tokens cannot manage sessions"),
+        (status = 404, description = "Session not found or already ended"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn revoke_session(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<i64>,
+) -> AppResult<StatusCode> {
+    state.sessions.revoke_session(user.user_id, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,42 @@
+use axum::{Json, extract::State, http::StatusCode};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::tag::TagWithCount;
+use crate::presentation::AppState;
+use crate::presentation::http::app_error::AppResult;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct TagDto {
+    pub(crate) name: String,
+    /// Количество опубликованных постов с этим тегом.
+    pub(crate) post_count: i64,
+}
+
+impl From<TagWithCount> for TagDto {
+    fn from(tag: TagWithCount) -> Self {
+        Self {
+            name: tag.name,
+            post_count: tag.post_count,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tags",
+    tag = "tags",
+    responses(
+        (status = 200, description = "Tags listed", body = [TagDto]),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn list_tags(
+    State(state): State<AppState>,
+) -> AppResult<(StatusCode, Json<Vec<TagDto>>)> {
+    let tags = state.blog_service.list_tags().await?;
+    Ok((
+        StatusCode::OK,
+        Json(tags.into_iter().map(TagDto::from).collect()),
+    ))
+}
@@ -0,0 +1,155 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::application::personal_token_service::CreatedPersonalToken;
+use crate::domain::personal_token::{CreatePersonalTokenRequest, PersonalToken, TokenScopes};
+use crate::presentation::AppState;
+use crate::presentation::http::app_error::AppResult;
+use crate::presentation::http::middleware::auth::AuthenticatedUser;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub(crate) struct CreatePersonalTokenDto {
+    #[validate(length(min = 1, max = 64))]
+    pub(crate) name: String,
+    /// Права токена: `posts:read`, `posts:write`, `comments:write`.
+    #[validate(length(min = 1, max = 8))]
+    pub(crate) scopes: Vec<String>,
+    /// Срок жизни в днях (1–365); без поля — бессрочный токен.
+    pub(crate) expires_in_days: Option<u32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct PersonalTokenDto {
+    pub(crate) id: i64,
+    pub(crate) name: String,
+    pub(crate) scopes: Vec<String>,
+    pub(crate) expires_at: Option<DateTime<Utc>>,
+    pub(crate) last_used_at: Option<DateTime<Utc>>,
+    pub(crate) created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct CreatedPersonalTokenDto {
+    pub(crate) token: PersonalTokenDto,
+    /// Сам токен для заголовка `Authorization: Bearer`; показывается только сейчас.
+    pub(crate) secret: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ListPersonalTokensResponseDto {
+    pub(crate) tokens: Vec<PersonalTokenDto>,
+}
+
+impl From<PersonalToken> for PersonalTokenDto {
+    fn from(token: PersonalToken) -> Self {
+        Self {
+            id: token.id,
+            name: token.name,
+            scopes: token
+                .scopes
+                .iter()
+                .map(|scope| scope.as_str().to_string())
+                .collect(),
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+            created_at: token.created_at,
+        }
+    }
+}
+
+impl From<CreatedPersonalToken> for CreatedPersonalTokenDto {
+    fn from(created: CreatedPersonalToken) -> Self {
+        Self {
+            token: created.token.into(),
+            secret: created.secret,
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/tokens",
+    tag = "auth",
+    request_body = CreatePersonalTokenDto,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Token created, secret is shown only once", body = CreatedPersonalTokenDto),
+        (status = 400, description = "Validation error or too many active tokens"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Personal access tokens cannot manage tokens"),
+        (status = 409, description = "Active token with this name already exists"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn create_token(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(dto): Json<CreatePersonalTokenDto>,
+) -> AppResult<(StatusCode, Json<CreatedPersonalTokenDto>)> {
+    dto.validate()?;
+    let req = CreatePersonalTokenRequest {
+        name: dto.name,
+        scopes: TokenScopes::parse(&dto.scopes)?,
+        expires_in_days: dto.expires_in_days,
+    };
+
+    let created = state
+        .personal_tokens
+        .create_token(user.user_id, req)
+        .await?;
+    Ok((StatusCode::CREATED, Json(created.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/auth/tokens",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Active (not revoked) tokens, newest first", body = ListPersonalTokensResponseDto),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Personal access tokens cannot manage tokens"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn list_tokens(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> AppResult<Json<ListPersonalTokensResponseDto>> {
+    let tokens = state.personal_tokens.list_tokens(user.user_id).await?;
+    Ok(Json(ListPersonalTokensResponseDto {
+        tokens: tokens.into_iter().map(PersonalTokenDto::from).collect(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/auth/tokens/{id}",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = i64, Path, description = "Token id")
+    ),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Personal access tokens cannot manage tokens"),
+        (status = 404, description = "Token not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn revoke_token(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<i64>,
+) -> AppResult<StatusCode> {
+    state.personal_tokens.revoke_token(user.user_id, id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
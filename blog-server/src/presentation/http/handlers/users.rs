@@ -0,0 +1,102 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::profile::{UpdateProfileRequest, UserProfile};
+use crate::presentation::AppState;
+use crate::presentation::http::app_error::AppResult;
+use crate::presentation::http::middleware::auth::AuthenticatedUser;
+
+/// Публичный профиль: email и роль не раскрываются.
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ProfileDto {
+    pub(crate) id: i64,
+    pub(crate) username: String,
+    pub(crate) display_name: Option<String>,
+    pub(crate) bio: Option<String>,
+    pub(crate) website: Option<String>,
+    pub(crate) avatar_url: Option<String>,
+    pub(crate) created_at: DateTime<Utc>,
+}
+
+/// Поле без значения не меняется, пустая строка очищает его.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct UpdateProfileDto {
+    /// До 64 символов.
+    pub(crate) display_name: Option<String>,
+    /// До 500 символов.
+    pub(crate) bio: Option<String>,
+    /// Ссылка http(s).
+    pub(crate) website: Option<String>,
+    /// Ссылка http(s) на изображение.
+    pub(crate) avatar_url: Option<String>,
+}
+
+impl From<UserProfile> for ProfileDto {
+    fn from(user: UserProfile) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            display_name: user.profile.display_name,
+            bio: user.profile.bio,
+            website: user.profile.website,
+            avatar_url: user.profile.avatar_url,
+            created_at: user.created_at,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{username}",
+    tag = "users",
+    params(
+        ("username" = String, Path, description = "Username")
+    ),
+    responses(
+        (status = 200, description = "Public profile", body = ProfileDto),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn get_profile(
+    State(state): State<AppState>,
+    Path(username): Path<String>,
+) -> AppResult<Json<ProfileDto>> {
+    let profile = state.profiles.get_profile(&username).await?;
+    Ok(Json(profile.into()))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/users/me",
+    tag = "users",
+    request_body = UpdateProfileDto,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Profile updated", body = ProfileDto),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Personal access tokens cannot manage the account"),
+        (status = 500, description = "Internal error")
+    )
+)]
+pub(crate) async fn update_profile(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(dto): Json<UpdateProfileDto>,
+) -> AppResult<Json<ProfileDto>> {
+    let req = UpdateProfileRequest {
+        display_name: dto.display_name,
+        bio: dto.bio,
+        website: dto.website,
+        avatar_url: dto.avatar_url,
+    };
+
+    let profile = state.profiles.update_profile(user.user_id, req).await?;
+    Ok(Json(profile.into()))
+}
@@ -1,10 +1,16 @@
 use axum::{
-    extract::{FromRequestParts, Request, State},
-    http::{header, request::Parts},
+    extract::{FromRequestParts, OptionalFromRequestParts, Request, State},
+    http::{HeaderMap, header, request::Parts},
     middleware::Next,
     response::Response,
 };
 
+use crate::application::personal_token_service::PersonalTokenAuth;
+use crate::application::policy::Actor;
+use crate::domain::error::DomainError;
+use crate::domain::personal_token::{PERSONAL_TOKEN_PREFIX, TokenScope, TokenScopes};
+use crate::domain::user::Role;
+use crate::infrastructure::jwt::Claims;
 use crate::presentation::AppState;
 use crate::presentation::http::app_error::AppError;
 
@@ -12,6 +18,52 @@ use crate::presentation::http::app_error::AppError;
 pub(crate) struct AuthenticatedUser {
     pub(crate) user_id: i64,
     // pub(crate) username: String,
+    pub(crate) role: Role,
+    pub(crate) email_verified: bool,
+    /// Для токена доступа — `pat-<id>`: такой запрос не доходит до выхода из сессии.
+    pub(crate) jti: String,
+    pub(crate) token_expires_at: i64,
+    /// Права токена доступа; `None` — обычная сессия.
+    pub(crate) scopes: Option<TokenScopes>,
+    /// Сессия access-токена; `None` для токена доступа и токенов, выданных до появления сессий.
+    pub(crate) session_id: Option<i64>,
+}
+
+impl AuthenticatedUser {
+    pub(crate) fn actor(&self) -> Actor {
+        let actor = Actor::new(self.user_id, self.role).with_email_verified(self.email_verified);
+        match self.scopes {
+            Some(scopes) => actor.with_scopes(scopes),
+            None => actor,
+        }
+    }
+
+    fn from_claims(claims: Claims) -> Self {
+        Self {
+            user_id: claims.user_id,
+            // username: claims.username,
+            role: claims.role,
+            email_verified: claims.email_verified,
+            jti: claims.jti,
+            token_expires_at: claims.exp,
+            scopes: None,
+            session_id: claims.sid,
+        }
+    }
+
+    fn from_personal_token(auth: PersonalTokenAuth) -> Self {
+        Self {
+            user_id: auth.actor.user_id,
+            role: auth.actor.role,
+            email_verified: auth.actor.email_verified,
+            jti: format!("pat-{}", auth.token_id),
+            token_expires_at: auth
+                .expires_at
+                .map_or(i64::MAX, |expires_at| expires_at.timestamp()),
+            scopes: auth.actor.scopes,
+            session_id: None,
+        }
+    }
 }
 
 impl<S> FromRequestParts<S> for AuthenticatedUser
@@ -29,13 +81,103 @@ where
     }
 }
 
+impl<S> OptionalFromRequestParts<S> for AuthenticatedUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        Ok(parts.extensions.get::<AuthenticatedUser>().cloned())
+    }
+}
+
+/// Пропускает access-токены (JWT) и персональные токены доступа.
 pub(crate) async fn jwt_auth_middleware(
     State(state): State<AppState>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
-    let auth_header = request
-        .headers()
+    let user = authenticate(&state, request.headers()).await?;
+    request.extensions_mut().insert(user);
+
+    Ok(next.run(request).await)
+}
+
+/// Для управления аккаунтом и токенами: только сессия, токен доступа получает `403`.
+pub(crate) async fn session_auth_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let user = authenticate(&state, request.headers()).await?;
+    if user.scopes.is_some() {
+        return Err(DomainError::Forbidden.into());
+    }
+    request.extensions_mut().insert(user);
+
+    Ok(next.run(request).await)
+}
+
+/// Для публичных маршрутов: валидный токен добавляет `AuthenticatedUser`,
+/// отсутствующий, невалидный или отозванный — запрос обрабатывается как анонимный.
+pub(crate) async fn optional_jwt_auth_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    // черновики токену доступа видны только с правом на чтение постов
+    let user = authenticate(&state, request.headers())
+        .await
+        .ok()
+        .filter(|user| {
+            user.scopes
+                .is_none_or(|scopes| scopes.contains(TokenScope::PostsRead))
+        });
+    if let Some(user) = user {
+        request.extensions_mut().insert(user);
+    }
+
+    next.run(request).await
+}
+
+async fn authenticate(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<AuthenticatedUser, AppError> {
+    let token = parse_bearer_token(headers)?;
+
+    if token.starts_with(PERSONAL_TOKEN_PREFIX) {
+        return state
+            .personal_tokens
+            .authenticate(token)
+            .await?
+            .map(AuthenticatedUser::from_personal_token)
+            .ok_or(AppError::Unauthorized);
+    }
+
+    let claims = state
+        .jwt
+        .verify_token(token)
+        .map_err(|_| AppError::Unauthorized)?;
+
+    if !state.revocations.is_active(&claims).await? {
+        return Err(AppError::Unauthorized);
+    }
+    if let Some(session_id) = claims.sid
+        && !state.sessions.is_active(session_id).await?
+    {
+        return Err(AppError::Unauthorized);
+    }
+
+    Ok(AuthenticatedUser::from_claims(claims))
+}
+
+pub(crate) fn parse_bearer_token(headers: &HeaderMap) -> Result<&str, AppError> {
+    let auth_header = headers
         .get(header::AUTHORIZATION)
         .and_then(|value| value.to_str().ok())
         .ok_or(AppError::Unauthorized)?;
@@ -53,15 +195,5 @@ pub(crate) async fn jwt_auth_middleware(
         return Err(AppError::Unauthorized);
     }
 
-    let claims = state
-        .jwt
-        .verify_token(token.trim())
-        .map_err(|_| AppError::Unauthorized)?;
-
-    request.extensions_mut().insert(AuthenticatedUser {
-        user_id: claims.user_id,
-        // username: claims.username,
-    });
-
-    Ok(next.run(request).await)
+    Ok(token.trim())
 }
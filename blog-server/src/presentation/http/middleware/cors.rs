@@ -28,7 +28,15 @@ pub(crate) fn build_cors_layer(settings: &Settings) -> Result<CorsLayer> {
             Method::DELETE,
             Method::OPTIONS,
         ])
-        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, header::ACCEPT]))
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            header::ACCEPT,
+            header::IF_MATCH,
+            header::IF_NONE_MATCH,
+            header::IF_MODIFIED_SINCE,
+        ])
+        .expose_headers([header::ETAG, header::LAST_MODIFIED]))
 }
 
 pub(crate) fn apply_cors(router: Router, settings: &Settings) -> Result<Router> {
@@ -1,38 +1,143 @@
 use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
-use crate::presentation::http::handlers::auth::{AuthResponseDto, LoginDto, RegisterDto, UserDto};
+use crate::presentation::http::handlers::auth::{
+    AuthResponseDto, ChangePasswordDto, LoginDto, LogoutDto, MfaChallengeDto, MfaLoginDto,
+    PasswordResetConfirmDto, PasswordResetRequestDto, RecoveryCodesDto, RefreshDto, RegisterDto,
+    RoleDto, TotpCodeDto, TotpEnrollmentDto, UserDto, VerifyEmailDto,
+};
+use crate::presentation::http::handlers::comments::{
+    CommentDto, CommentThreadDto, CreateCommentDto, ListCommentsResponseDto,
+};
 use crate::presentation::http::handlers::posts::{
-    CreatePostDto, ListPostsResponseDto, PaginationQuery, PostDto, UpdatePostDto,
+    ContentFormatDto, CreatePostDto, ListPostsResponseDto, PaginationQuery, PostAuthorDto, PostDto,
+    PostStatusDto, SchedulePostDto, SearchHitDto, SearchPostsResponseDto, SearchQuery, TrashQuery,
+    UpdatePostDto,
+};
+use crate::presentation::http::handlers::revisions::{
+    DiffLineDto, DiffOpDto, ListRevisionsResponseDto, RevisionDiffDto, RevisionDiffQuery,
+    RevisionDto, RevisionSummaryDto,
+};
+use crate::presentation::http::handlers::sessions::{ListSessionsResponseDto, SessionDto};
+use crate::presentation::http::handlers::tags::TagDto;
+use crate::presentation::http::handlers::tokens::{
+    CreatePersonalTokenDto, CreatedPersonalTokenDto, ListPersonalTokensResponseDto,
+    PersonalTokenDto,
 };
+use crate::presentation::http::handlers::users::{ProfileDto, UpdateProfileDto};
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         crate::presentation::http::handlers::auth::register,
         crate::presentation::http::handlers::auth::login,
+        crate::presentation::http::handlers::auth::complete_mfa_login,
+        crate::presentation::http::handlers::auth::refresh,
+        crate::presentation::http::handlers::auth::logout,
+        crate::presentation::http::handlers::auth::logout_all,
+        crate::presentation::http::handlers::auth::change_password,
+        crate::presentation::http::handlers::auth::request_password_reset,
+        crate::presentation::http::handlers::auth::reset_password,
+        crate::presentation::http::handlers::auth::verify_email,
+        crate::presentation::http::handlers::auth::resend_verification_email,
+        crate::presentation::http::handlers::auth::start_totp_enrollment,
+        crate::presentation::http::handlers::auth::confirm_totp_enrollment,
+        crate::presentation::http::handlers::auth::disable_totp,
+        crate::presentation::http::handlers::auth::jwks,
+        crate::presentation::http::handlers::sessions::list_sessions,
+        crate::presentation::http::handlers::sessions::revoke_session,
+        crate::presentation::http::handlers::tokens::create_token,
+        crate::presentation::http::handlers::tokens::list_tokens,
+        crate::presentation::http::handlers::tokens::revoke_token,
+        crate::presentation::http::handlers::users::get_profile,
+        crate::presentation::http::handlers::users::update_profile,
+        crate::presentation::http::handlers::feeds::site_feed,
+        crate::presentation::http::handlers::feeds::author_feed,
+        crate::presentation::http::handlers::feeds::tag_feed,
         crate::presentation::http::handlers::posts::list_posts,
+        crate::presentation::http::handlers::posts::search_posts,
         crate::presentation::http::handlers::posts::get_post,
+        crate::presentation::http::handlers::posts::get_post_by_slug,
         crate::presentation::http::handlers::posts::create_post,
         crate::presentation::http::handlers::posts::update_post,
-        crate::presentation::http::handlers::posts::delete_post
+        crate::presentation::http::handlers::posts::delete_post,
+        crate::presentation::http::handlers::posts::publish_post,
+        crate::presentation::http::handlers::posts::unpublish_post,
+        crate::presentation::http::handlers::posts::archive_post,
+        crate::presentation::http::handlers::posts::schedule_post,
+        crate::presentation::http::handlers::posts::list_trash,
+        crate::presentation::http::handlers::posts::restore_post,
+        crate::presentation::http::handlers::posts::purge_post,
+        crate::presentation::http::handlers::revisions::list_revisions,
+        crate::presentation::http::handlers::revisions::get_revision,
+        crate::presentation::http::handlers::revisions::diff_revisions,
+        crate::presentation::http::handlers::revisions::restore_revision,
+        crate::presentation::http::handlers::comments::list_comments,
+        crate::presentation::http::handlers::comments::create_comment,
+        crate::presentation::http::handlers::comments::delete_comment,
+        crate::presentation::http::handlers::tags::list_tags
     ),
     components(
         schemas(
             RegisterDto,
             LoginDto,
+            RefreshDto,
+            LogoutDto,
+            ChangePasswordDto,
+            PasswordResetRequestDto,
+            PasswordResetConfirmDto,
+            VerifyEmailDto,
+            MfaLoginDto,
+            TotpCodeDto,
+            MfaChallengeDto,
+            TotpEnrollmentDto,
+            RecoveryCodesDto,
+            SessionDto,
+            ListSessionsResponseDto,
+            CreatePersonalTokenDto,
+            PersonalTokenDto,
+            CreatedPersonalTokenDto,
+            ListPersonalTokensResponseDto,
             AuthResponseDto,
             UserDto,
+            RoleDto,
+            ProfileDto,
+            UpdateProfileDto,
+            PostAuthorDto,
             CreatePostDto,
             UpdatePostDto,
+            SchedulePostDto,
             PaginationQuery,
             PostDto,
-            ListPostsResponseDto
+            PostStatusDto,
+            ContentFormatDto,
+            ListPostsResponseDto,
+            SearchQuery,
+            TrashQuery,
+            SearchHitDto,
+            SearchPostsResponseDto,
+            RevisionSummaryDto,
+            ListRevisionsResponseDto,
+            RevisionDto,
+            DiffOpDto,
+            DiffLineDto,
+            RevisionDiffDto,
+            RevisionDiffQuery,
+            CreateCommentDto,
+            CommentDto,
+            CommentThreadDto,
+            ListCommentsResponseDto,
+            TagDto
         )
     ),
     tags(
         (name = "auth", description = "Authentication endpoints"),
-        (name = "posts", description = "Post endpoints")
+        (name = "posts", description = "Post endpoints"),
+        (name = "revisions", description = "Post revision history endpoints"),
+        (name = "comments", description = "Comment endpoints"),
+        (name = "tags", description = "Tag endpoints"),
+        (name = "users", description = "User profile endpoints"),
+        (name = "feeds", description = "RSS, Atom and JSON Feed endpoints")
     ),
     modifiers(&SecurityAddon)
 )]
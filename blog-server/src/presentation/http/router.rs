@@ -2,12 +2,14 @@ use axum::{Json, Router, routing::get};
 use serde::Serialize;
 
 use crate::presentation::AppState;
+use crate::presentation::http::handlers::auth::jwks;
 
 use super::routes;
 
 pub(crate) fn routes(state: AppState) -> Router {
     Router::new()
         .route("/healthz", get(health_handler))
+        .route("/.well-known/jwks.json", get(jwks))
         .merge(routes::router(state.clone()))
         .with_state(state)
 }
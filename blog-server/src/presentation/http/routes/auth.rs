@@ -1,10 +1,44 @@
-use axum::{Router, routing::post};
+use axum::{
+    Router, middleware,
+    routing::{delete, get, post},
+};
 
 use crate::presentation::AppState;
-use crate::presentation::http::handlers::auth::{login, register};
+use crate::presentation::http::handlers::auth::{
+    change_password, complete_mfa_login, confirm_totp_enrollment, disable_totp, login, logout,
+    logout_all, refresh, register, request_password_reset, resend_verification_email,
+    reset_password, start_totp_enrollment, verify_email,
+};
+use crate::presentation::http::handlers::sessions::{list_sessions, revoke_session};
+use crate::presentation::http::handlers::tokens::{create_token, list_tokens, revoke_token};
+use crate::presentation::http::middleware::auth::session_auth_middleware;
 
-pub(crate) fn router() -> Router<AppState> {
-    Router::new()
+pub(crate) fn router(state: AppState) -> Router<AppState> {
+    let public = Router::new()
         .route("/register", post(register))
         .route("/login", post(login))
+        .route("/login/mfa", post(complete_mfa_login))
+        .route("/refresh", post(refresh))
+        .route("/password-reset/request", post(request_password_reset))
+        .route("/password-reset/confirm", post(reset_password))
+        .route("/verify-email", post(verify_email));
+
+    let protected = Router::new()
+        .route("/logout", post(logout))
+        .route("/logout-all", post(logout_all))
+        .route("/password", post(change_password))
+        .route("/verify-email/resend", post(resend_verification_email))
+        .route("/totp/setup", post(start_totp_enrollment))
+        .route("/totp/confirm", post(confirm_totp_enrollment))
+        .route("/totp/disable", post(disable_totp))
+        .route("/sessions", get(list_sessions))
+        .route("/sessions/{id}", delete(revoke_session))
+        .route("/tokens", post(create_token).get(list_tokens))
+        .route("/tokens/{id}", delete(revoke_token))
+        .layer(middleware::from_fn_with_state(
+            state,
+            session_auth_middleware,
+        ));
+
+    public.merge(protected)
 }
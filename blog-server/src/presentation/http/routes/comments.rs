@@ -0,0 +1,31 @@
+use axum::Router;
+use axum::middleware;
+use axum::routing::{delete, get, post};
+
+use crate::presentation::AppState;
+use crate::presentation::http::handlers::comments::{
+    create_comment, delete_comment, list_comments,
+};
+use crate::presentation::http::middleware::auth::{
+    jwt_auth_middleware, optional_jwt_auth_middleware,
+};
+
+/// Маршруты относительно `/api/posts`.
+pub(crate) fn router(state: AppState) -> Router<AppState> {
+    let public = Router::new()
+        .route("/{id}/comments", get(list_comments))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            optional_jwt_auth_middleware,
+        ));
+
+    let protected = Router::new()
+        .route("/{id}/comments", post(create_comment))
+        .route("/{id}/comments/{comment_id}", delete(delete_comment))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            jwt_auth_middleware,
+        ));
+
+    public.merge(protected)
+}
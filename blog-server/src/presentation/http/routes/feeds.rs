@@ -0,0 +1,20 @@
+use axum::Router;
+use axum::routing::get;
+
+use crate::presentation::AppState;
+use crate::presentation::http::handlers::feeds::{author_feed, site_feed, tag_feed};
+
+/// Формат ленты выбирается расширением, поэтому каждый адрес регистрируется трижды.
+pub(crate) fn router() -> Router<AppState> {
+    ["rss", "atom", "json"]
+        .into_iter()
+        .fold(Router::new(), |router, extension| {
+            router
+                .route(&format!("/feed.{extension}"), get(site_feed))
+                .route(
+                    &format!("/users/{{username}}/feed.{extension}"),
+                    get(author_feed),
+                )
+                .route(&format!("/tags/{{tag}}/feed.{extension}"), get(tag_feed))
+        })
+}
@@ -3,10 +3,23 @@ use axum::Router;
 use crate::presentation::AppState;
 
 pub(crate) mod auth;
+pub(crate) mod comments;
+pub(crate) mod feeds;
 pub(crate) mod posts;
+pub(crate) mod revisions;
+pub(crate) mod tags;
+pub(crate) mod users;
 
 pub(crate) fn router(state: AppState) -> Router<AppState> {
     Router::new()
-        .nest("/api/auth", auth::router())
-        .nest("/api/posts", posts::router(state))
+        .nest("/api/auth", auth::router(state.clone()))
+        .nest(
+            "/api/posts",
+            posts::router(state.clone())
+                .merge(comments::router(state.clone()))
+                .merge(revisions::router(state.clone())),
+        )
+        .nest("/api/tags", tags::router())
+        .nest("/api/users", users::router(state))
+        .merge(feeds::router())
 }
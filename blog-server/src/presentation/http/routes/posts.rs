@@ -1,21 +1,38 @@
 use axum::Router;
 use axum::middleware;
-use axum::routing::{get, post, put};
+use axum::routing::{delete, get, post, put};
 
 use crate::presentation::AppState;
 use crate::presentation::http::handlers::posts::{
-    create_post, delete_post, get_post, list_posts, update_post,
+    archive_post, create_post, delete_post, get_post, get_post_by_slug, list_posts, list_trash,
+    publish_post, purge_post, restore_post, schedule_post, search_posts, unpublish_post,
+    update_post,
+};
+use crate::presentation::http::middleware::auth::{
+    jwt_auth_middleware, optional_jwt_auth_middleware,
 };
-use crate::presentation::http::middleware::auth::jwt_auth_middleware;
 
 pub(crate) fn router(state: AppState) -> Router<AppState> {
     let public = Router::new()
         .route("/", get(list_posts))
-        .route("/{id}", get(get_post));
+        .route("/search", get(search_posts))
+        .route("/by-slug/{slug}", get(get_post_by_slug))
+        .route("/{id}", get(get_post))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            optional_jwt_auth_middleware,
+        ));
 
     let protected = Router::new()
         .route("/", post(create_post))
         .route("/{id}", put(update_post).delete(delete_post))
+        .route("/{id}/publish", post(publish_post))
+        .route("/{id}/unpublish", post(unpublish_post))
+        .route("/{id}/archive", post(archive_post))
+        .route("/{id}/schedule", post(schedule_post))
+        .route("/trash", get(list_trash))
+        .route("/trash/{id}", delete(purge_post))
+        .route("/trash/{id}/restore", post(restore_post))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             jwt_auth_middleware,
@@ -0,0 +1,19 @@
+use axum::Router;
+use axum::middleware;
+use axum::routing::{get, post};
+
+use crate::presentation::AppState;
+use crate::presentation::http::handlers::revisions::{
+    diff_revisions, get_revision, list_revisions, restore_revision,
+};
+use crate::presentation::http::middleware::auth::jwt_auth_middleware;
+
+/// Маршруты относительно `/api/posts`; история правок доступна только автору.
+pub(crate) fn router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/{id}/revisions", get(list_revisions))
+        .route("/{id}/revisions/diff", get(diff_revisions))
+        .route("/{id}/revisions/{revision}", get(get_revision))
+        .route("/{id}/revisions/{revision}/restore", post(restore_revision))
+        .layer(middleware::from_fn_with_state(state, jwt_auth_middleware))
+}
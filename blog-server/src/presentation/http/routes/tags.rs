@@ -0,0 +1,9 @@
+use axum::Router;
+use axum::routing::get;
+
+use crate::presentation::AppState;
+use crate::presentation::http::handlers::tags::list_tags;
+
+pub(crate) fn router() -> Router<AppState> {
+    Router::new().route("/", get(list_tags))
+}
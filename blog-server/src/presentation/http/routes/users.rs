@@ -0,0 +1,22 @@
+use axum::{
+    Router, middleware,
+    routing::{get, patch},
+};
+
+use crate::presentation::AppState;
+use crate::presentation::http::handlers::users::{get_profile, update_profile};
+use crate::presentation::http::middleware::auth::session_auth_middleware;
+
+pub(crate) fn router(state: AppState) -> Router<AppState> {
+    let public = Router::new().route("/{username}", get(get_profile));
+
+    let protected =
+        Router::new()
+            .route("/me", patch(update_profile))
+            .layer(middleware::from_fn_with_state(
+                state,
+                session_auth_middleware,
+            ));
+
+    public.merge(protected)
+}
@@ -2,30 +2,95 @@ use std::sync::Arc;
 
 use crate::application::auth_service::AuthService;
 use crate::application::blog_service::BlogService;
+use crate::application::comment_service::CommentService;
+use crate::application::personal_token_service::PersonalTokenService;
+use crate::application::profile_service::ProfileService;
+use crate::application::session_service::SessionService;
+use crate::application::token_revocation_service::TokenRevocationService;
+use crate::data::repositories::postgres::comment_repository::PostgresCommentRepository;
+use crate::data::repositories::postgres::email_verification_repository::PostgresEmailVerificationRepository;
+use crate::data::repositories::postgres::mfa_repository::PostgresMfaRepository;
+use crate::data::repositories::postgres::password_reset_repository::PostgresPasswordResetRepository;
+use crate::data::repositories::postgres::personal_token_repository::PostgresPersonalTokenRepository;
 use crate::data::repositories::postgres::post_repository::PostgresPostRepository;
+use crate::data::repositories::postgres::profile_repository::PostgresProfileRepository;
+use crate::data::repositories::postgres::refresh_token_repository::PostgresRefreshTokenRepository;
+use crate::data::repositories::postgres::session_repository::PostgresSessionRepository;
+use crate::data::repositories::postgres::tag_repository::PostgresTagRepository;
+use crate::data::repositories::postgres::token_revocation_repository::PostgresTokenRevocationRepository;
 use crate::data::repositories::postgres::user_repository::PostgresUserRepository;
+use crate::infrastructure::feed::FeedConfig;
 use crate::infrastructure::jwt::JwtService;
+use crate::infrastructure::mailer::LocalMailer;
+use crate::infrastructure::rate_limiter::RateLimiter;
 
 pub(crate) mod grpc;
 pub(crate) mod http;
+pub(crate) mod rate_limit;
+
+pub(crate) type AppAuthService = AuthService<
+    PostgresUserRepository,
+    PostgresRefreshTokenRepository,
+    PostgresTokenRevocationRepository,
+    PostgresSessionRepository,
+    PostgresPasswordResetRepository,
+    PostgresEmailVerificationRepository,
+    PostgresMfaRepository,
+    LocalMailer,
+>;
+pub(crate) type AppBlogService = BlogService<PostgresPostRepository, PostgresTagRepository>;
+pub(crate) type AppCommentService =
+    CommentService<PostgresCommentRepository, PostgresPostRepository>;
+pub(crate) type AppPersonalTokenService = PersonalTokenService<PostgresPersonalTokenRepository>;
+pub(crate) type AppProfileService = ProfileService<PostgresProfileRepository>;
+pub(crate) type AppSessionService = SessionService<PostgresSessionRepository>;
+pub(crate) type AppTokenRevocationService =
+    TokenRevocationService<PostgresTokenRevocationRepository>;
 
 #[derive(Clone)]
 pub(crate) struct AppState {
-    pub(crate) auth_service: Arc<AuthService<PostgresUserRepository>>,
-    pub(crate) blog_service: Arc<BlogService<PostgresPostRepository>>,
+    pub(crate) auth_service: Arc<AppAuthService>,
+    pub(crate) blog_service: Arc<AppBlogService>,
+    pub(crate) comment_service: Arc<AppCommentService>,
+    pub(crate) revocations: Arc<AppTokenRevocationService>,
+    pub(crate) sessions: Arc<AppSessionService>,
+    pub(crate) personal_tokens: Arc<AppPersonalTokenService>,
+    pub(crate) profiles: Arc<AppProfileService>,
     pub(crate) jwt: Arc<JwtService>,
+    /// Общий для HTTP и gRPC: квота клиента не удваивается сменой протокола.
+    pub(crate) rate_limiter: Arc<RateLimiter>,
+    pub(crate) feed: Arc<FeedConfig>,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
-        auth_service: Arc<AuthService<PostgresUserRepository>>,
-        blog_service: Arc<BlogService<PostgresPostRepository>>,
+        auth_service: Arc<AppAuthService>,
+        blog_service: Arc<AppBlogService>,
+        comment_service: Arc<AppCommentService>,
+        revocations: Arc<AppTokenRevocationService>,
+        sessions: Arc<AppSessionService>,
+        personal_tokens: Arc<AppPersonalTokenService>,
+        profiles: Arc<AppProfileService>,
         jwt: Arc<JwtService>,
+        rate_limiter: Arc<RateLimiter>,
     ) -> Self {
         Self {
             auth_service,
             blog_service,
+            comment_service,
+            revocations,
+            sessions,
+            personal_tokens,
+            profiles,
             jwt,
+            rate_limiter,
+            feed: Arc::new(FeedConfig::default()),
         }
     }
+
+    pub(crate) fn with_feed(mut self, feed: FeedConfig) -> Self {
+        self.feed = Arc::new(feed);
+        self
+    }
 }
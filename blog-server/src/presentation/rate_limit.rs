@@ -0,0 +1,373 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::extract::ConnectInfo;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Request, Response, StatusCode, header};
+use serde_json::json;
+use tonic::transport::server::TcpConnectInfo;
+use tower::{Layer, Service};
+
+use crate::application::personal_token_service::PersonalTokenService;
+use crate::data::personal_token_repository::PersonalTokenRepository;
+use crate::domain::personal_token::PERSONAL_TOKEN_PREFIX;
+use crate::infrastructure::jwt::JwtService;
+use crate::infrastructure::rate_limiter::{RateLimitDecision, RateLimiter};
+use crate::presentation::AppPersonalTokenService;
+use crate::presentation::http::middleware::auth::parse_bearer_token;
+
+const RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// Tower-слой, ограничивающий частоту запросов одного клиента.
+///
+/// Клиент — пользователь из валидного JWT, действующий токен доступа,
+/// иначе IP-адрес соединения.
+/// Подходит и для axum, и для tonic: отличается только ответ на превышение квоты.
+pub(crate) struct RateLimitLayer<B> {
+    limiter: Arc<RateLimiter>,
+    jwt: Arc<JwtService>,
+    personal_tokens: Arc<AppPersonalTokenService>,
+    reject: fn() -> Response<B>,
+}
+
+impl<B> Clone for RateLimitLayer<B> {
+    fn clone(&self) -> Self {
+        Self {
+            limiter: self.limiter.clone(),
+            jwt: self.jwt.clone(),
+            personal_tokens: self.personal_tokens.clone(),
+            reject: self.reject,
+        }
+    }
+}
+
+impl RateLimitLayer<axum::body::Body> {
+    /// Отказ для HTTP: `429` с JSON-ошибкой, как у остальных ответов API.
+    pub(crate) fn http(
+        limiter: Arc<RateLimiter>,
+        jwt: Arc<JwtService>,
+        personal_tokens: Arc<AppPersonalTokenService>,
+    ) -> Self {
+        Self {
+            limiter,
+            jwt,
+            personal_tokens,
+            reject: || {
+                let body = json!({ "error": "rate limit exceeded" }).to_string();
+                let mut response = Response::new(axum::body::Body::from(body));
+                *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+                response.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/json"),
+                );
+                response
+            },
+        }
+    }
+}
+
+impl RateLimitLayer<tonic::body::Body> {
+    /// Отказ для gRPC: статус `RESOURCE_EXHAUSTED`.
+    pub(crate) fn grpc(
+        limiter: Arc<RateLimiter>,
+        jwt: Arc<JwtService>,
+        personal_tokens: Arc<AppPersonalTokenService>,
+    ) -> Self {
+        Self {
+            limiter,
+            jwt,
+            personal_tokens,
+            reject: || tonic::Status::resource_exhausted("rate limit exceeded").into_http(),
+        }
+    }
+}
+
+impl<S, B> Layer<S> for RateLimitLayer<B> {
+    type Service = RateLimitService<S, B>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+pub(crate) struct RateLimitService<S, B> {
+    inner: S,
+    layer: RateLimitLayer<B>,
+}
+
+impl<S: Clone, B> Clone for RateLimitService<S, B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            layer: self.layer.clone(),
+        }
+    }
+}
+
+impl<S, ReqBody, B> Service<Request<ReqBody>> for RateLimitService<S, B>
+where
+    S: Service<Request<ReqBody>, Response = Response<B>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response<B>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<B>, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // токен доступа проверяется в БД, поэтому готовый внутренний сервис уходит в future
+        let ready = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, ready);
+        let layer = self.layer.clone();
+        let token = parse_bearer_token(request.headers())
+            .ok()
+            .map(str::to_string);
+        let peer = peer_addr(&request);
+
+        Box::pin(async move {
+            let client_key =
+                client_key(token.as_deref(), peer, &layer.jwt, &layer.personal_tokens).await;
+            let decision = layer.limiter.check(request.uri().path(), &client_key);
+
+            if !decision.allowed {
+                let mut response = (layer.reject)();
+                set_rate_limit_headers(response.headers_mut(), &decision);
+                return Ok(response);
+            }
+
+            let mut response = inner.call(request).await?;
+            set_rate_limit_headers(response.headers_mut(), &decision);
+            Ok(response)
+        })
+    }
+}
+
+/// JWT здесь только различает клиентов: отзыв проверяет авторизация дальше по цепочке.
+/// Токен доступа получает свою квоту, только если он есть в хранилище и действует:
+/// иначе случайные `blog_pat_…` давали бы новый ключ на каждый запрос.
+async fn client_key<T: PersonalTokenRepository>(
+    token: Option<&str>,
+    peer: Option<SocketAddr>,
+    jwt: &JwtService,
+    personal_tokens: &PersonalTokenService<T>,
+) -> String {
+    if let Some(token) = token {
+        if token.starts_with(PERSONAL_TOKEN_PREFIX) {
+            if let Ok(Some(auth)) = personal_tokens.authenticate(token).await {
+                return format!("pat:{}", auth.token_id);
+            }
+        } else if let Ok(claims) = jwt.verify_token(token) {
+            return format!("user:{}", claims.user_id);
+        }
+    }
+
+    match peer {
+        Some(addr) => format!("ip:{}", addr.ip()),
+        None => "ip:unknown".to_string(),
+    }
+}
+
+fn peer_addr<B>(request: &Request<B>) -> Option<SocketAddr> {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr)
+        .or_else(|| {
+            request
+                .extensions()
+                .get::<TcpConnectInfo>()
+                .and_then(TcpConnectInfo::remote_addr)
+        })
+}
+
+fn set_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from(decision.limit));
+    headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from(decision.remaining));
+    headers.insert(
+        RATE_LIMIT_RESET,
+        HeaderValue::from(decision.reset_after.as_secs_f64().ceil() as u64),
+    );
+    if let Some(retry_after) = decision.retry_after {
+        // не меньше секунды: `Retry-After: 0` клиенты понимают как "сразу"
+        let secs = (retry_after.as_secs_f64().ceil() as u64).max(1);
+        headers.insert(header::RETRY_AFTER, HeaderValue::from(secs));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use axum::extract::ConnectInfo;
+    use axum::http::{Request, header};
+    use chrono::{DateTime, Utc};
+
+    use super::{client_key, parse_bearer_token, peer_addr};
+    use crate::application::personal_token_service::PersonalTokenService;
+    use crate::data::personal_token_repository::{
+        NewPersonalToken, PersonalTokenOwner, PersonalTokenRepository,
+    };
+    use crate::domain::error::DomainError;
+    use crate::domain::personal_token::{
+        CreatePersonalTokenRequest, PersonalToken, TokenScope, TokenScopes,
+    };
+    use crate::domain::user::{Role, User};
+    use crate::infrastructure::jwt::JwtService;
+
+    #[derive(Clone, Default)]
+    struct FakePersonalTokenRepo {
+        tokens: Arc<Mutex<Vec<(String, PersonalToken)>>>,
+    }
+
+    #[async_trait]
+    impl PersonalTokenRepository for FakePersonalTokenRepo {
+        async fn create_token(
+            &self,
+            input: NewPersonalToken,
+        ) -> Result<PersonalToken, DomainError> {
+            let mut tokens = self.tokens.lock().expect("tokens mutex poisoned");
+            let token = PersonalToken {
+                id: tokens.len() as i64 + 1,
+                user_id: input.user_id,
+                name: input.name,
+                scopes: input.scopes,
+                expires_at: input.expires_at,
+                last_used_at: None,
+                created_at: Utc::now(),
+            };
+            tokens.push((input.token_hash, token.clone()));
+            Ok(token)
+        }
+
+        async fn list_tokens(&self, _user_id: i64) -> Result<Vec<PersonalToken>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_active_by_hash(
+            &self,
+            token_hash: &str,
+        ) -> Result<Option<PersonalTokenOwner>, DomainError> {
+            Ok(self
+                .tokens
+                .lock()
+                .expect("tokens mutex poisoned")
+                .iter()
+                .find(|(hash, _)| hash == token_hash)
+                .map(|(_, token)| PersonalTokenOwner {
+                    token: token.clone(),
+                    role: Role::User,
+                    email_verified: true,
+                }))
+        }
+
+        async fn touch_last_used(
+            &self,
+            _token_id: i64,
+            _at: DateTime<Utc>,
+        ) -> Result<(), DomainError> {
+            Ok(())
+        }
+
+        async fn revoke_token(&self, _user_id: i64, _token_id: i64) -> Result<bool, DomainError> {
+            Ok(false)
+        }
+    }
+
+    fn jwt() -> JwtService {
+        JwtService::new("0123456789abcdef0123456789abcdef", 900)
+    }
+
+    fn personal_tokens() -> PersonalTokenService<FakePersonalTokenRepo> {
+        PersonalTokenService::new(FakePersonalTokenRepo::default())
+    }
+
+    fn request(authorization: Option<&str>, ip: [u8; 4]) -> Request<()> {
+        let mut builder = Request::builder().uri("/api/posts");
+        if let Some(value) = authorization {
+            builder = builder.header(header::AUTHORIZATION, value);
+        }
+        let mut request = builder.body(()).expect("valid request");
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((ip, 40000))));
+        request
+    }
+
+    async fn key(
+        authorization: Option<&str>,
+        ip: [u8; 4],
+        jwt: &JwtService,
+        personal_tokens: &PersonalTokenService<FakePersonalTokenRepo>,
+    ) -> String {
+        let request = request(authorization, ip);
+        let token = parse_bearer_token(request.headers()).ok();
+        client_key(token, peer_addr(&request), jwt, personal_tokens).await
+    }
+
+    #[tokio::test]
+    async fn bearer_scheme_is_case_insensitive() {
+        let (jwt, tokens) = (jwt(), personal_tokens());
+        let user = User::new(7, "alice", "alice@example.com", Utc::now()).expect("user");
+        let token = jwt.generate_token(&user, None).expect("token");
+        let ip = [203, 0, 113, 7];
+
+        for scheme in ["Bearer", "bearer", "BEARER"] {
+            let header = format!("{scheme} {token}");
+            assert_eq!(key(Some(&header), ip, &jwt, &tokens).await, "user:7");
+        }
+        assert_eq!(key(None, ip, &jwt, &tokens).await, "ip:203.0.113.7");
+        assert_eq!(
+            key(Some("Bearer not-a-jwt"), ip, &jwt, &tokens).await,
+            "ip:203.0.113.7"
+        );
+    }
+
+    #[tokio::test]
+    async fn only_verified_personal_token_has_own_bucket() {
+        let (jwt, tokens) = (jwt(), personal_tokens());
+        let created = tokens
+            .create_token(
+                7,
+                CreatePersonalTokenRequest {
+                    name: "ci".to_string(),
+                    scopes: [TokenScope::PostsRead].into_iter().collect::<TokenScopes>(),
+                    expires_in_days: None,
+                },
+            )
+            .await
+            .expect("token must be created");
+        let header = format!("bearer {}", created.secret);
+
+        let expected = format!("pat:{}", created.token.id);
+        assert_eq!(
+            key(Some(&header), [203, 0, 113, 7], &jwt, &tokens).await,
+            expected
+        );
+        assert_eq!(
+            key(Some(&header), [198, 51, 100, 1], &jwt, &tokens).await,
+            expected
+        );
+
+        // выдуманный токен не даёт новой квоты и считается по адресу
+        for fake in ["Bearer blog_pat_0123456789abcdef", "Bearer blog_pat_fedcba"] {
+            assert_eq!(
+                key(Some(fake), [203, 0, 113, 7], &jwt, &tokens).await,
+                "ip:203.0.113.7"
+            );
+        }
+    }
+}
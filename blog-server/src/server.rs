@@ -2,14 +2,16 @@ use axum::BoxError;
 use axum::Router;
 use axum::error_handling::HandleErrorLayer;
 use axum::http::StatusCode;
+use std::net::SocketAddr;
 use std::time::Duration;
 use tokio::net::TcpListener;
+use tokio::time::MissedTickBehavior;
 use tower::ServiceBuilder;
 use tower::limit::ConcurrencyLimitLayer;
 use tower::timeout::TimeoutLayer;
 use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::trace::TraceLayer;
-use tracing::info;
+use tracing::{info, warn};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -20,11 +22,17 @@ use crate::presentation::http::middleware::cors::apply_cors;
 use crate::presentation::http::middleware::trace::apply_trace;
 use crate::presentation::http::openapi::ApiDoc;
 use crate::presentation::http::router as http_router;
+use crate::presentation::rate_limit::RateLimitLayer;
 
 use tonic::transport::Server;
 
 pub(crate) async fn run_http(settings: &Settings, state: AppState) -> anyhow::Result<()> {
-    let app = build_router(state);
+    let rate_limit = RateLimitLayer::http(
+        state.rate_limiter.clone(),
+        state.jwt.clone(),
+        state.personal_tokens.clone(),
+    );
+    let app = build_router(state).layer(rate_limit);
     let app = apply_trace(app);
     let app = apply_cors(app, settings)?;
     let app = app.layer(
@@ -42,7 +50,12 @@ pub(crate) async fn run_http(settings: &Settings, state: AppState) -> anyhow::Re
     let listener = TcpListener::bind(&settings.http_addr).await?;
 
     info!("HTTP server listening on {}", settings.http_addr);
-    axum::serve(listener, app).await?;
+    // адрес клиента нужен для учёта неудачных входов
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
 
@@ -52,6 +65,11 @@ pub(crate) fn build_router(state: AppState) -> Router {
 }
 
 pub(crate) async fn run_grpc(settings: &Settings, state: AppState) -> anyhow::Result<()> {
+    let rate_limit = RateLimitLayer::grpc(
+        state.rate_limiter.clone(),
+        state.jwt.clone(),
+        state.personal_tokens.clone(),
+    );
     let grpc = GrpcBlogService::new(state)
         .into_server()
         .max_decoding_message_size(settings.grpc_max_decoding_message_size_bytes)
@@ -67,6 +85,8 @@ pub(crate) async fn run_grpc(settings: &Settings, state: AppState) -> anyhow::Re
             settings.grpc_request_timeout_secs,
         )))
         .layer(TraceLayer::new_for_grpc())
+        // внутренним слоем: отказ собирается в тип ответа самого сервиса
+        .layer(rate_limit)
         .add_service(grpc)
         .serve(addr)
         .await?;
@@ -74,6 +94,63 @@ pub(crate) async fn run_grpc(settings: &Settings, state: AppState) -> anyhow::Re
     Ok(())
 }
 
+/// Публикует черновики, у которых наступило `publish_at`.
+///
+/// Посты выбираются через `FOR UPDATE SKIP LOCKED`, поэтому задачу можно
+/// запускать на каждой реплике сервера: один пост опубликует только одна из них.
+pub(crate) async fn run_publish_scheduler(
+    settings: &Settings,
+    state: AppState,
+) -> anyhow::Result<()> {
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        settings.publish_scheduler_interval_secs,
+    ));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    info!(
+        "Publish scheduler started, interval {}s",
+        settings.publish_scheduler_interval_secs
+    );
+    loop {
+        interval.tick().await;
+        // ошибка базы не должна останавливать сервер: следующий тик повторит попытку
+        match state
+            .blog_service
+            .publish_due_posts(settings.publish_scheduler_batch_size)
+            .await
+        {
+            Ok(0) => {}
+            Ok(published) => info!(published, "Scheduled posts published"),
+            Err(err) => warn!(error = %err, "Failed to publish scheduled posts"),
+        }
+    }
+}
+
+/// Окончательно удаляет посты, пролежавшие в корзине дольше `TRASH_RETENTION_DAYS`.
+pub(crate) async fn run_trash_purger(settings: &Settings, state: AppState) -> anyhow::Result<()> {
+    let retention = chrono::Duration::days(i64::from(settings.trash_retention_days));
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(settings.trash_purge_interval_secs));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    info!(
+        "Trash purger started, retention {} days",
+        settings.trash_retention_days
+    );
+    loop {
+        interval.tick().await;
+        match state
+            .blog_service
+            .purge_expired_posts(retention, settings.trash_purge_batch_size)
+            .await
+        {
+            Ok(0) => {}
+            Ok(purged) => info!(purged, "Expired posts purged from trash"),
+            Err(err) => warn!(error = %err, "Failed to purge trash"),
+        }
+    }
+}
+
 async fn handle_http_middleware_error(err: BoxError) -> StatusCode {
     if err.is::<tower::timeout::error::Elapsed>() {
         StatusCode::REQUEST_TIMEOUT
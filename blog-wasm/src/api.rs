@@ -116,6 +116,20 @@ pub(crate) async fn login(username: &str, password: &str) -> Result<AuthResponse
     parse_json(response).await
 }
 
+pub(crate) async fn logout(token: &str) -> Result<(), ApiError> {
+    let response = Request::post(&endpoint("/api/auth/logout"))
+        .header("Authorization", &format!("Bearer {token}"))
+        .send()
+        .await
+        .map_err(|err| ApiError::Network(err.to_string()))?;
+
+    if !response.ok() {
+        return Err(parse_error_body(response).await);
+    }
+
+    Ok(())
+}
+
 pub(crate) async fn list_posts(limit: u32, offset: u32) -> Result<ListPostsResponse, ApiError> {
     let url = endpoint(&format!("/api/posts?limit={limit}&offset={offset}"));
 
@@ -131,10 +145,18 @@ pub(crate) async fn list_posts(limit: u32, offset: u32) -> Result<ListPostsRespo
     parse_json(response).await
 }
 
-pub(crate) async fn create_post(token: &str, title: &str, content: &str) -> Result<Post, ApiError> {
+pub(crate) async fn create_post(
+    token: &str,
+    title: &str,
+    content: &str,
+    markdown: bool,
+    publish_at: Option<String>,
+) -> Result<Post, ApiError> {
     let payload = CreatePostRequest {
         title: title.to_string(),
         content: content.to_string(),
+        content_format: if markdown { "markdown" } else { "plain" },
+        publish_at,
     };
 
     let response = Request::post(&endpoint("/api/posts"))
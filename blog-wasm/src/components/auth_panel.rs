@@ -91,6 +91,12 @@ pub(crate) fn AuthPanel(state: AppState) -> impl IntoView {
     let on_logout = {
         let state = state.clone();
         move |_| {
+            // отзываем токен на сервере; локальный выход выполняем в любом случае
+            if let Some(token) = state.token.get_untracked() {
+                spawn_local(async move {
+                    let _ = api::logout(&token).await;
+                });
+            }
             if let Err(err) = storage::clear_token() {
                 state.set_error(err);
                 return;
@@ -1,6 +1,7 @@
 use leptos::ev::SubmitEvent;
 use leptos::prelude::*;
 use leptos::task::spawn_local;
+use wasm_bindgen::JsValue;
 
 use crate::api;
 use crate::models::Post;
@@ -24,10 +25,59 @@ fn find_post_for_edit(posts: &[Post], post_id: i64) -> Option<(String, String)>
         .map(|post| (post.title.clone(), post.content.clone()))
 }
 
+/// HTML тела поста. Сервер присылает его уже очищенным; если HTML нет,
+/// текст экранируется здесь.
+fn post_body_html(post: &Post) -> String {
+    if !post.content_html.is_empty() {
+        return post.content_html.clone();
+    }
+
+    let mut html = String::with_capacity(post.content.len());
+    for ch in post.content.chars() {
+        match ch {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            '\n' => html.push_str("<br>"),
+            _ => html.push(ch),
+        }
+    }
+    html
+}
+
+/// Значение `datetime-local` (локальное время браузера) в RFC 3339 UTC.
+/// Пустая строка — публикация без отложенного времени.
+fn publish_at_to_utc(raw: &str) -> Result<Option<String>, &'static str> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    let date = js_sys::Date::new(&JsValue::from_str(raw));
+    if date.get_time().is_nan() {
+        return Err("Некорректное время публикации");
+    }
+    Ok(Some(String::from(date.to_iso_string())))
+}
+
+/// Отображаемое имя автора, а если его нет — логин.
+fn author_label(post: &Post) -> String {
+    match &post.author {
+        Some(author) => author
+            .display_name
+            .clone()
+            .unwrap_or_else(|| author.username.clone()),
+        None => format!("#{}", post.author_id),
+    }
+}
+
 #[component]
 pub(crate) fn PostsPanel(state: AppState, on_refresh: Callback<()>) -> impl IntoView {
     let create_title = RwSignal::new(String::new());
     let create_content = RwSignal::new(String::new());
+    let create_markdown = RwSignal::new(false);
+    let create_publish_at = RwSignal::new(String::new());
 
     let editing_post_id = RwSignal::new(None::<i64>);
     let edit_title = RwSignal::new(String::new());
@@ -41,6 +91,7 @@ pub(crate) fn PostsPanel(state: AppState, on_refresh: Callback<()>) -> impl Into
 
             let title = create_title.get().trim().to_string();
             let content = create_content.get().trim().to_string();
+            let markdown = create_markdown.get();
 
             if let Err(message) =
                 validate_non_empty_fields(&title, &content, "Заполните title и content")
@@ -48,6 +99,13 @@ pub(crate) fn PostsPanel(state: AppState, on_refresh: Callback<()>) -> impl Into
                 state.set_error(message);
                 return;
             }
+            let publish_at = match publish_at_to_utc(&create_publish_at.get()) {
+                Ok(publish_at) => publish_at,
+                Err(message) => {
+                    state.set_error(message);
+                    return;
+                }
+            };
 
             let Some(token) = state.token.get() else {
                 state.set_error("Нужна авторизация для создания поста");
@@ -57,11 +115,12 @@ pub(crate) fn PostsPanel(state: AppState, on_refresh: Callback<()>) -> impl Into
             state.loading.set(true);
             let state2 = state.clone();
             leptos::task::spawn_local(async move {
-                match api::create_post(&token, &title, &content).await {
+                match api::create_post(&token, &title, &content, markdown, publish_at).await {
                     Ok(created) => {
                         state2.posts.update(|posts| posts.insert(0, created));
                         create_title.set(String::new());
                         create_content.set(String::new());
+                        create_publish_at.set(String::new());
                         state2.clear_error();
                     }
                     Err(err) => state2.set_error(err.to_string()),
@@ -191,6 +250,22 @@ pub(crate) fn PostsPanel(state: AppState, on_refresh: Callback<()>) -> impl Into
                     prop:value=move || create_content.get()
                     on:input=move |ev| create_content.set(event_target_value(&ev))
                 />
+                <label style="margin-left: 0.5rem;">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || create_markdown.get()
+                        on:change=move |ev| create_markdown.set(event_target_checked(&ev))
+                    />
+                    "Markdown"
+                </label>
+                <label style="margin-left: 0.5rem;">
+                    "Publish at "
+                    <input
+                        type="datetime-local"
+                        prop:value=move || create_publish_at.get()
+                        on:input=move |ev| create_publish_at.set(event_target_value(&ev))
+                    />
+                </label>
                 <button type="submit" disabled=move || state.loading.get()>
                     "Create"
                 </button>
@@ -206,7 +281,13 @@ pub(crate) fn PostsPanel(state: AppState, on_refresh: Callback<()>) -> impl Into
                     let post_id = post.id;
                     let post_author_id = post.author_id;
                     let post_title = post.title.clone();
-                    let post_content = post.content.clone();
+                    let post_body = post_body_html(&post);
+                    let post_author = author_label(&post);
+                    let post_schedule = post
+                        .publish_at
+                        .clone()
+                        .map(|at| format!(", публикация: {at}"))
+                        .unwrap_or_default();
 
                     let is_editing_this = {
                         let id = post_id;
@@ -215,8 +296,8 @@ pub(crate) fn PostsPanel(state: AppState, on_refresh: Callback<()>) -> impl Into
                     view! {
                         <li style="margin-bottom: 0.5rem;">
                             <strong>{post_title.clone()}</strong>
-                            <div>{post_content.clone()}</div>
-                            <small>{format!("id={}, author_id={}", post_id, post_author_id)}</small>
+                            <div inner_html=post_body></div>
+                            <small>{format!("id={}, автор: {}{}", post_id, post_author, post_schedule)}</small>
 
                             <Show when=move || {
                                 if !state_for_post_actions_show.is_authenticated() {
@@ -300,18 +381,49 @@ pub(crate) fn PostsPanel(state: AppState, on_refresh: Callback<()>) -> impl Into
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::PostAuthor;
 
     fn sample_post(id: i64, title: &str, content: &str) -> Post {
         Post {
             id,
             title: title.to_string(),
             content: content.to_string(),
+            content_html: String::new(),
             author_id: 1,
+            author: None,
+            publish_at: None,
             created_at: "2026-01-01T00:00:00Z".to_string(),
             updated_at: "2026-01-01T00:00:00Z".to_string(),
         }
     }
 
+    #[test]
+    fn author_label_prefers_display_name() {
+        let mut post = sample_post(1, "A", "X");
+        assert_eq!(author_label(&post), "#1");
+
+        post.author = Some(PostAuthor {
+            username: "alice".to_string(),
+            display_name: None,
+        });
+        assert_eq!(author_label(&post), "alice");
+
+        post.author = Some(PostAuthor {
+            username: "alice".to_string(),
+            display_name: Some("Алиса".to_string()),
+        });
+        assert_eq!(author_label(&post), "Алиса");
+    }
+
+    #[test]
+    fn post_body_html_prefers_server_html_and_escapes_fallback() {
+        let mut post = sample_post(1, "A", "<b>x</b>\ny");
+        assert_eq!(post_body_html(&post), "&lt;b&gt;x&lt;/b&gt;<br>y");
+
+        post.content_html = "<p><strong>x</strong></p>".to_string();
+        assert_eq!(post_body_html(&post), "<p><strong>x</strong></p>");
+    }
+
     #[test]
     fn validate_non_empty_fields_accepts_non_blank_values() {
         let result = validate_non_empty_fields("title", "content", "err");
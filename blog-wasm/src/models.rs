@@ -13,11 +13,25 @@ pub struct Post {
     pub id: i64,
     pub title: String,
     pub content: String,
+    /// HTML, очищенный сервером; старый сервер его не присылает.
+    #[serde(default)]
+    pub content_html: String,
     pub author_id: i64,
+    #[serde(default)]
+    pub author: Option<PostAuthor>,
+    /// Время отложенной публикации черновика (RFC 3339, UTC).
+    #[serde(default)]
+    pub publish_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostAuthor {
+    pub username: String,
+    pub display_name: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AuthResponse {
     pub access_token: String,
@@ -49,6 +63,10 @@ pub struct LoginRequest {
 pub struct CreatePostRequest {
     pub title: String,
     pub content: String,
+    pub content_format: &'static str,
+    /// С заданным временем сервер сохраняет пост черновиком и публикует его позже.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publish_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]